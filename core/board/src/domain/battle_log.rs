@@ -14,7 +14,7 @@
 //!
 //! 持有事件序列的 `BattleLog` Resource 定義在 `ecs_types::resources`。
 
-use crate::domain::alias::{SkillName, TypeName};
+use crate::domain::alias::{Coord, SkillName, TypeName};
 use crate::domain::core_types::{AccuracySource, DefenseType, HitCheckBreakdowns};
 
 /// log 事件的目標（自帶名稱快照，不依賴事後反查、不暴露座標給玩家）
@@ -54,6 +54,7 @@ pub struct LogCheckDetail {
     pub defense_type: DefenseType,
     pub breakdowns: HitCheckBreakdowns,
     pub roll: i32,
+    pub concealment_roll: Option<i32>,
 }
 
 /// log 的效果摘要
@@ -63,6 +64,11 @@ pub enum LogEffect {
     HpChange { amount: i32 },
     SpawnObject { object_type: TypeName },
     ApplyBuff { buff_name: String },
+    Dispel { beneficial: bool },
+    Teleport,
+    Pull { distance: Coord },
+    Summon { unit_type: TypeName },
+    Shield { amount: i32 },
 }
 
 /// 單筆戰鬥 log 事件（扁平結構，照發生順序 append）
@@ -89,4 +95,10 @@ pub enum LogEvent {
     },
     /// 單位死亡（只記身分名稱快照）
     Death { unit: TypeName },
+    /// 死者的死亡爆發波及相鄰單位
+    DeathNova {
+        unit: TypeName,
+        target: TypeName,
+        amount: i32,
+    },
 }
@@ -1,6 +1,7 @@
 //! 遊戲常數定義
 
-use crate::domain::alias::{ID, MovementCost};
+use crate::domain::alias::{Coord, ID, MovementCost};
+use crate::domain::core_types::{ExclusiveTagGroup, SkillTag};
 
 /// 玩家所屬同盟 ID（寫死，未來擴展時移除）
 pub const PLAYER_ALLIANCE_ID: ID = 0;
@@ -14,6 +15,11 @@ pub const BASIC_MOVEMENT_COST: MovementCost = 10;
 /// 無法通過的移動成本
 pub const IMPASSABLE_MOVEMENT_COST: MovementCost = BASIC_MOVEMENT_COST * 1000;
 
+/// 尋路時踩上危險地面的額外權重（只影響路線偏好，不消耗移動力）
+///
+/// 大於繞過單一危險格所需的兩格移動成本，因此能繞路時會繞開危險格
+pub const HAZARD_PATH_COST: MovementCost = BASIC_MOVEMENT_COST * 3;
+
 /// 即死傷害（負數表示傷害）
 pub const HP_MODIFY_DAMAGE: i32 = -10000;
 
@@ -25,6 +31,20 @@ pub const FORCED_SUCCESS_LOWER: i32 = 96;
 /// 傷害預覽強制命中時，CheckDetail.roll 的顯示隨機值（不參與判定）
 pub const FORCED_HIT_PREVIEW_ROLL: i32 = 100;
 
+/// FNV-1a 64-bit 初始值（內容雜湊用）
+pub const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// FNV-1a 64-bit 乘數（內容雜湊用）
+pub const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 百分比基數（value_percent 等欄位以 100 為 1 倍）
+pub const PERCENT_BASE: i32 = 100;
+
+/// 主動技能預設攻擊段數
+pub const DEFAULT_SKILL_HITS: u8 = 1;
+
+/// 直線範圍預設穿透（舊資料未指定時沿用穿過阻擋的行為）
+pub const DEFAULT_LINE_PIERCE: bool = true;
+
 /// 暴擊傷害倍率
 pub const CRIT_DAMAGE_MULTIPLIER: i32 = 2;
 
@@ -33,3 +53,49 @@ pub const FLANKING_REQUIRED_ALLIES: usize = 2;
 
 /// 施放者相鄰敵人時的命中懲罰（負值，直接加到命中值）
 pub const ACCURACY_PENALTY_WHEN_ENEMY_ADJACENT: i32 = -20;
+
+/// 遠程技能由高處攻擊低處時的命中加成
+pub const HIGH_GROUND_ACCURACY_BONUS: i32 = 10;
+
+/// 4 個正交相鄰格的座標位移
+pub const NEIGHBOR_OFFSETS_4: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// 尋路展開相鄰格的固定順序：上、左、右、下（不依賴 `Direction` 列舉的宣告順序）
+pub const PATH_NEIGHBOR_OFFSETS: [(i32, i32); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// 8 個相鄰格（含斜角）的座標位移
+pub const NEIGHBOR_OFFSETS_8: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// 方向吸附 8 方位的判定門檻：次軸 / 主軸 ≤ tan(22.5°) 時吸附到正交方向
+pub const FACING_SNAP_TAN: f64 = std::f64::consts::SQRT_2 - 1.0;
+
+/// 棋盤單邊長度上限
+pub const MAX_BOARD_SIDE: Coord = 200;
+
+/// 棋盤面積上限（超過則拒絕建立，避免配置巨大網格）
+pub const MAX_BOARD_AREA: usize = 10_000;
+
+/// 全點對距離場的格數上限（記憶體為格數平方，超過則拒絕建立）
+pub const MAX_DISTANCE_FIELD_TILES: usize = 1_024;
+
+/// 技能影響距離上限（最大棋盤對角兩端的曼哈頓距離，超過代表設定失當）
+pub const MAX_SKILL_AFFECTED_DISTANCE: Coord = 2 * MAX_BOARD_SIDE;
+
+/// 技能標籤互斥群組（編輯器驗證技能時使用）
+///
+/// 技能類別：近戰、遠程、法術至多擇一；不選視為通用技能
+pub const SKILL_TAG_EXCLUSIVE_GROUPS: &[ExclusiveTagGroup] = &[ExclusiveTagGroup {
+    name: "技能類別",
+    tags: &[SkillTag::Melee, SkillTag::Ranged, SkillTag::Magic],
+    min: 0,
+    max: 1,
+}];
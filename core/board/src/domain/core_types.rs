@@ -1,6 +1,7 @@
 //! 基本資料類型定義
 
 use crate::domain::alias::{Coord, ID, SkillName, TypeName};
+use crate::domain::constants::{DEFAULT_LINE_PIERCE, DEFAULT_SKILL_HITS};
 use crate::ecs_types::components::Occupant;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -51,13 +52,45 @@ define_attributes!(
 // ============================================================================
 
 /// 技能標籤
-#[derive(Debug, Clone, Default, Serialize, Deserialize, Display, EnumIter)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Display, EnumIter)]
 pub enum SkillTag {
     #[default]
     Flankable,
     AccuracyPenaltyWhenEnemyAdjacent,
     UsableAfterDoubleMove,
     AllowedDuringGrabbing,
+    /// 技能類別：近戰（單位可透過 allowed_categories 限制可學類別）
+    Melee,
+    /// 技能類別：遠程
+    Ranged,
+    /// 技能類別：法術
+    Magic,
+    /// 技能屬性：火焰（水域等地形可禁止施放）
+    Fire,
+    /// 關閉友軍傷害：範圍內與施放者同盟的單位（含施放者）不受影響
+    NoFriendlyFire,
+}
+
+/// 傷害屬性：HP 效果依此對照目標的抗性／弱點調整傷害
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumIter,
+)]
+pub enum DamageType {
+    /// 無屬性：不受任何抗性影響（舊資料未指定時的預設）
+    #[default]
+    Neutral,
+    Fire,
+    Ice,
+    Lightning,
+}
+
+/// 技能標籤互斥群組：同一技能在群組內選用的標籤數量須介於 min 與 max 之間
+#[derive(Debug, Clone, Copy)]
+pub struct ExclusiveTagGroup {
+    pub name: &'static str,
+    pub tags: &'static [SkillTag],
+    pub min: usize,
+    pub max: usize,
 }
 
 /// 檢定類型
@@ -107,6 +140,18 @@ pub enum MoveDirection {
     TowardTarget,
 }
 
+/// 距離度量：決定「半徑 r 以內」的格子形狀
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumIter)]
+pub enum RangeMetric {
+    /// |dx| + |dy|（菱形）
+    #[default]
+    Manhattan,
+    /// max(|dx|, |dy|)（正方形）
+    Chebyshev,
+    /// √(dx² + dy²) 無條件進位（近似圓形）
+    Euclidean,
+}
+
 /// 範圍形狀
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Display, EnumIter)]
 pub enum Area {
@@ -115,11 +160,29 @@ pub enum Area {
     Diamond {
         radius: Coord,
     },
+    /// 以目標格為中心、依 metric 計算距離在 radius 以內的格子
+    Circle {
+        radius: Coord,
+        #[serde(default)]
+        metric: RangeMetric,
+    },
     Cross {
         length: Coord,
     },
     Line {
         length: Coord,
+        /// 方向不在正交線上時，改為吸附到最接近的 8 方位（否則視為無效目標）
+        #[serde(default)]
+        snap_to_facing: bool,
+        /// 穿透阻擋；false 時在第一個單位或佔據格子的物件處停止（包含該格，如光束）
+        #[serde(default = "default_line_pierce")]
+        pierce: bool,
+    },
+    /// 以目標格為近端中心、沿施放者→目標方向延伸的矩形
+    /// （`width` 為垂直於方向的寬度，`depth` 為沿方向的深度）
+    Rectangle {
+        width: Coord,
+        depth: Coord,
     },
 }
 
@@ -137,6 +200,8 @@ pub enum ReactionTrigger {
     #[default]
     AttackOfOpportunity,
     TakesDamage,
+    /// 警戒：須先以 `set_overwatch` 架設，敵方移入 source_range 時觸發，觸發一次即解除
+    Overwatch,
 }
 
 /// 效果條件
@@ -148,9 +213,22 @@ pub struct EffectCondition {
     pub crit_bonus: i32,
 }
 
+/// 同名 Buff 重複施加時的疊加規則
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Display, EnumIter)]
+pub enum StackingPolicy {
+    /// 每次施加都是獨立實例，效果累加
+    #[default]
+    Stack,
+    /// 可同時存在多個實例，但只有數值最高的一個生效
+    HighestOnly,
+    /// 不新增實例，重置既有實例的剩餘回合
+    RefreshDuration,
+}
+
 /// Buff 結束條件（多個條件之間為 OR 關係）
 #[derive(Debug, Clone, Serialize, Deserialize, Display, EnumIter)]
 pub enum EndCondition {
+    /// 持續回合數，於換輪時遞減；0 表示永久（與未設定 Duration 相同），不遞減也不到期
     Duration(u32),
     TargetResistsPerTurn,
     CasterUsesSkillWithoutTag(SkillTag),
@@ -183,6 +261,9 @@ pub enum LightType {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Target {
     pub range: (Coord, Coord),
+    /// 射程的距離度量
+    #[serde(default)]
+    pub range_metric: RangeMetric,
     pub selection: TargetSelection,
     pub selectable_filter: TargetFilter,
     pub count: usize,
@@ -210,6 +291,20 @@ pub struct Scaling {
 // 技能系統 - 效果層
 // ============================================================================
 
+/// 主動技能頂層效果節點的套用順序
+///
+/// 頂層節點依序「解析 → 寫入」，後面的節點會看到前面位移效果造成的新站位。
+/// 選單位的技能鎖定施放時的目標單位，目標被位移後後續節點跟著作用在新位置
+/// （例如先拉近再打擊時，打擊命中拉動後的目標）。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Display, EnumIter)]
+pub enum EffectOrder {
+    /// 依 effects 列出的順序
+    #[default]
+    AsListed,
+    /// 含位移效果（拉動、強制移動、換位、瞬移、踐踏）的節點優先，其餘維持列出順序
+    MovementFirst,
+}
+
 /// 效果節點（遞迴巢狀結構）
 /// 禁止在非測試程式碼中 clone（效能考量，應使用引用）
 #[derive(Debug, Serialize, Deserialize, Display, EnumIter)]
@@ -238,19 +333,46 @@ pub enum EffectNode {
 pub enum Effect {
     HpEffect {
         scaling: Scaling,
+        #[serde(default)]
+        damage_type: DamageType,
     },
     MpEffect {
         value: i32,
     },
     ApplyBuff {
-        buff: BuffType,
+        buff: Arc<BuffType>,
+    },
+    /// 驅散目標身上的增益（beneficial = true）或減益（false）
+    Dispel {
+        beneficial: bool,
+    },
+    /// 連鎖：對目標造成 HP 效果後，跳到 jump_range 內最近、未被擊中且符合篩選的單位，
+    /// 每跳數值衰減 falloff_percent，最多跳 max_jumps 次。
+    /// 每一跳都依 damage_type 套用承受者的抗性
+    Chain {
+        scaling: Scaling,
+        #[serde(default)]
+        damage_type: DamageType,
+        filter: TargetFilter,
+        max_jumps: usize,
+        jump_range: Coord,
+        falloff_percent: i32,
     },
     ForcedMove {
         direction: MoveDirection,
         distance: Coord,
     },
+    /// 拉動：目標沿直線朝施放者移動最多 distance 格，遇到阻擋或佔據的格子即停止
+    Pull {
+        distance: Coord,
+    },
     AllowRemainingMovement,
     SwapPosition,
+    /// 瞬移：施放者移動到目標空格，不受路徑上的障礙影響，
+    /// 與施放者的曼哈頓距離不得超過 max_distance
+    Teleport {
+        max_distance: Coord,
+    },
     Trample {
         distance: Coord,
         scaling: Scaling,
@@ -260,6 +382,42 @@ pub enum Effect {
         duration: Option<u32>,
         contact_effects: Vec<EffectNode>,
     },
+    /// 召喚：在施放者相鄰的空格生成施放者陣營的單位，存在 duration 輪後移除
+    Summon {
+        unit_type: TypeName,
+        duration: u32,
+    },
+    /// 護盾：目標獲得可吸收 amount 點傷害的臨時 HP，持續 duration 輪（重複施加時覆蓋）
+    Shield {
+        amount: i32,
+        duration: u32,
+    },
+    /// 條件效果：施放當下目標符合 condition 時才套用 then（如斬殺技）
+    If {
+        condition: TargetCondition,
+        then: Box<Effect>,
+    },
+    /// 隨機效果：每次施放依權重抽出一個選項套用（如狂野魔法）
+    Random {
+        options: Vec<WeightedEffect>,
+    },
+}
+
+/// 隨機效果的候選項目，被抽中的機率為 weight / 權重總和
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "clone-skill"), derive(Clone))]
+pub struct WeightedEffect {
+    pub weight: u32,
+    pub effect: Effect,
+}
+
+/// 條件效果判斷的目標狀態
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, EnumIter)]
+pub enum TargetCondition {
+    /// 目標目前 HP 低於最大 HP 的 percent%
+    TargetBelowHp { percent: i32 },
+    /// 目標身上有指定名稱的 Buff
+    TargetHasBuff { buff: String },
 }
 
 /// 持續性效果（被動技能與 Buff 共用）
@@ -300,6 +458,26 @@ pub enum ContinuousEffect {
         range: Coord,
     },
     Blinded,
+    /// 死亡爆發：單位死亡時對相鄰單位造成 HP 變化（負值為傷害）
+    DeathNova {
+        value: i32,
+    },
+    /// 再生：Buff 持續期間，承受者每個回合開始時回復 amount HP（不超過 HP 上限）
+    Regen {
+        amount: i32,
+    },
+    /// 屬性抗性：承受 damage_type 傷害時減少 percent%（負值為弱點，傷害增加）
+    DamageResistance {
+        damage_type: DamageType,
+        percent: i32,
+    },
+    /// 光環：來源單位存活期間，曼哈頓距離 range 內的其他同盟單位持續獲得 effects
+    ///
+    /// 只在被動技能中生效；依當下站位即時計算，來源死亡或離開範圍即失效
+    Aura {
+        range: Coord,
+        effects: Vec<ContinuousEffect>,
+    },
 }
 
 // ============================================================================
@@ -315,6 +493,21 @@ pub enum SkillType {
         name: SkillName,
         tags: Vec<SkillTag>,
         cost: u32,
+        /// 前置技能：施放者須同時擁有這些技能才能使用（如連段終結技）
+        #[serde(default)]
+        requires: Vec<SkillName>,
+        /// 攻擊段數：每段對每個目標獨立解析效果樹（各自判定命中與爆擊）；位移效果只在第一段套用
+        #[serde(default = "default_skill_hits")]
+        hits: u8,
+        /// 反噬：主要效果結算後施放者失去的 HP（可能致死）
+        #[serde(default)]
+        recoil: u32,
+        /// 頂層效果節點的套用順序；每個節點結算後立即寫入，後續節點以新的站位解析
+        #[serde(default)]
+        effect_order: EffectOrder,
+        /// 引導回合數：大於 0 時施放後不立即結算，需維持引導至之後第 N 個自身回合開始
+        #[serde(default)]
+        channel_turns: u32,
         target: Target,
         effects: Arc<[EffectNode]>,
     },
@@ -337,7 +530,9 @@ pub enum SkillType {
 #[cfg_attr(any(test, feature = "clone-skill"), derive(Clone))]
 pub struct BuffType {
     pub name: String,
-    pub stackable: bool,
+    /// 增益（true）或減益（false），驅散時依此篩選
+    pub beneficial: bool,
+    pub stacking: StackingPolicy,
     pub while_active: Vec<ContinuousEffect>,
     pub per_turn_effects: Vec<EffectNode>,
     pub end_conditions: Vec<EndCondition>,
@@ -356,6 +551,15 @@ pub struct PendingReaction {
     pub available_skills: Vec<SkillName>,
 }
 
+/// 地格機關：設定在物件類型上，單位踏入或停留該格時作用
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Display, EnumIter)]
+pub enum TileTrigger {
+    /// 傷害陷阱：單位移動踏入此格時受到 amount 點傷害（護盾先吸收）
+    Damage { amount: i32 },
+    /// 目標點：單位停在此格即由其陣營佔領，供 CaptureObjective 結局條件判定
+    Objective,
+}
+
 // ============================================================================
 // 關卡結局判定
 // ============================================================================
@@ -365,6 +569,13 @@ pub struct PendingReaction {
 pub enum EndLevelCondition {
     /// 指定 faction 全滅則此條件成立
     EliminateFaction(ID),
+    /// 指定 faction 的單位站在任一目標點上則此條件成立
+    CaptureObjective(ID),
+    /// 指定 faction 的佔領進度累積達 rounds 輪則此條件成立
+    ///
+    /// 每輪結束時，只有單一同盟站在目標點上才累積（該同盟在目標點上的各 faction 各 +1），
+    /// 敵對同盟同時在場（爭奪中）或無人佔領時進度暫停，不歸零
+    HoldObjective { faction_id: ID, rounds: u32 },
 }
 
 /// 結局規則：多個結局分支（分支間 OR），每個分支為 (多語系 key, 該分支的達成條件（分支內 AND）)
@@ -379,6 +590,8 @@ pub enum LevelOutcome {
     Victory(String),
     /// 觸發分支的多語系 key
     Defeat(String),
+    /// 超過回合上限仍未分出勝負
+    Draw { max_rounds: u32 },
 }
 
 // ============================================================================
@@ -387,7 +600,7 @@ pub enum LevelOutcome {
 
 /// 命中值的組成明細（供 UI 逐項解釋命中率來源）
 ///
-/// `total` = `base` + `skill_bonus` + `flanking_bonus` + `adjacent_penalty`，
+/// `total` = `base` + `skill_bonus` + `flanking_bonus` + `adjacent_penalty` + `high_ground_bonus`，
 /// 即最終代入命中判定的攻擊命中值。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AccuracyBreakdown {
@@ -399,7 +612,9 @@ pub struct AccuracyBreakdown {
     pub flanking_bonus: i32,
     /// 相鄰敵人懲罰（未觸發為 0，觸發為負值）
     pub adjacent_penalty: i32,
-    /// 四項相加後的最終命中值
+    /// 高處遠程加成（未觸發為 0）
+    pub high_ground_bonus: i32,
+    /// 各項相加後的最終命中值
     pub total: i32,
 }
 
@@ -447,10 +662,17 @@ impl Default for Effect {
     fn default() -> Self {
         Self::HpEffect {
             scaling: Scaling::default(),
+            damage_type: DamageType::default(),
         }
     }
 }
 
+impl Default for TargetCondition {
+    fn default() -> Self {
+        Self::TargetBelowHp { percent: 0 }
+    }
+}
+
 impl Default for ContinuousEffect {
     fn default() -> Self {
         Self::AttributeFlat {
@@ -472,12 +694,25 @@ impl Default for SkillType {
             name: SkillName::default(),
             tags: Vec::default(),
             cost: 0,
+            requires: Vec::default(),
+            hits: DEFAULT_SKILL_HITS,
+            recoil: 0,
+            effect_order: EffectOrder::default(),
+            channel_turns: 0,
             target: Target::default(),
             effects: Arc::from([]),
         }
     }
 }
 
+fn default_skill_hits() -> u8 {
+    DEFAULT_SKILL_HITS
+}
+
+fn default_line_pierce() -> bool {
+    DEFAULT_LINE_PIERCE
+}
+
 impl SkillType {
     /// 獲取技能名稱
     pub fn name(&self) -> &SkillName {
@@ -1,9 +1,10 @@
 //! 回合系統資料型別定義
 
 use crate::ecs_types::components::Occupant;
+use serde::{Deserialize, Serialize};
 
 /// 單位在回合表中的資訊
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TurnEntry {
     pub occupant: Occupant,
     pub initiative: i32, // 原始 INI
@@ -25,7 +25,8 @@ use bevy_ecs::prelude::{With, World};
 ///
 /// 施放者名稱快照由本函數從 entry 的 caster ID 查 World 取得，呼叫端無需預先查。
 ///
-/// 由呼叫端（editor）在 `execute_skill` 之後明確呼叫，core 不自動 append。
+/// 由呼叫端（editor）在 `execute_skill` 之後明確呼叫，core 不自動 append；
+/// 唯一例外是引導技能於回合開始時結算，由 core 在結算後自行呼叫。
 pub fn append_skill_log(world: &mut World, entries: &[EffectEntry]) -> Result<()> {
     let events = entries
         .iter()
@@ -172,6 +173,7 @@ fn to_log_check_detail(detail: &CheckDetail) -> LogCheckDetail {
         defense_type: detail.defense_type,
         breakdowns: detail.breakdowns.clone(),
         roll: detail.roll,
+        concealment_roll: detail.concealment_roll,
     }
 }
 
@@ -188,5 +190,16 @@ fn to_log_effect(effect: &ResolvedEffect) -> LogEffect {
         ResolvedEffect::ApplyBuff(buff_name) => LogEffect::ApplyBuff {
             buff_name: buff_name.clone(),
         },
+        ResolvedEffect::Dispel { beneficial } => LogEffect::Dispel {
+            beneficial: *beneficial,
+        },
+        ResolvedEffect::Teleport { .. } => LogEffect::Teleport,
+        ResolvedEffect::Pull { distance } => LogEffect::Pull {
+            distance: *distance,
+        },
+        ResolvedEffect::Summon { unit_type, .. } => LogEffect::Summon {
+            unit_type: unit_type.clone(),
+        },
+        ResolvedEffect::Shield { amount, .. } => LogEffect::Shield { amount: *amount },
     }
 }
@@ -0,0 +1,220 @@
+//! Buff ECS 操作函數
+
+use super::{get_component, get_component_mut};
+use crate::domain::core_types::{
+    BuffType, ContinuousEffect, EndCondition, SkillType, StackingPolicy,
+};
+use crate::ecs_logic::query::{build_aura_effects_map, find_entity_by_occupant, get_resource};
+use crate::ecs_types::components::{
+    AppliedBuff, CurrentHp, CurrentMp, MaxHp, MaxMp, Occupant, Skills, Unit,
+};
+use crate::ecs_types::resources::GameData;
+use crate::error::Result;
+use crate::logic::skill::unit_attributes::{
+    adjust_current_for_max_change, calculate_attributes, filter_continuous_effect,
+};
+use bevy_ecs::prelude::{Entity, With, World};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 對目標施加 buff，依疊加規則決定新增實例或重置既有實例的剩餘回合
+///
+/// RefreshDuration 且目標已有同名 buff 時只重置剩餘回合，其餘情況皆新增實例；
+/// HighestOnly 的「只取最高」在屬性計算時處理
+pub fn apply_buff(
+    world: &mut World,
+    def: impl Into<Arc<BuffType>>,
+    caster: Occupant,
+    target: Occupant,
+) -> Result<()> {
+    let def = def.into();
+    // 讀取階段
+    find_entity_by_occupant(world, target)?;
+    let existing = match def.stacking {
+        StackingPolicy::RefreshDuration => find_buff_entity(world, target, &def.name),
+        StackingPolicy::Stack | StackingPolicy::HighestOnly => None,
+    };
+
+    // 邏輯階段
+    let remaining_duration = buff_duration(&def);
+
+    // 寫入階段
+    match existing {
+        Some(entity) => {
+            let mut entity_mut = world.entity_mut(entity);
+            let mut buff = get_component_mut!(entity_mut, AppliedBuff)?;
+            buff.remaining_duration = remaining_duration;
+        }
+        None => {
+            world.spawn(AppliedBuff {
+                def,
+                caster,
+                target,
+                remaining_duration,
+                inherited_defense: None,
+            });
+        }
+    }
+    refresh_max_hp_mp(world, target)
+}
+
+/// 移除目標身上所有增益（beneficial = true）或減益（false）buff，回傳移除數量
+pub fn dispel_buffs(world: &mut World, target: Occupant, beneficial: bool) -> Result<usize> {
+    let dispelled: Vec<Entity> = world
+        .query::<(Entity, &AppliedBuff)>()
+        .iter(world)
+        .filter(|(_, buff)| buff.target == target && buff.def.beneficial == beneficial)
+        .map(|(entity, _)| entity)
+        .collect();
+
+    for &entity in &dispelled {
+        world.despawn(entity);
+    }
+    if !dispelled.is_empty() {
+        refresh_max_hp_mp(world, target)?;
+    }
+    Ok(dispelled.len())
+}
+
+/// 依被動技能、身上 buff 與光環重算 HP/MP 上限，並依規則調整當前值
+///
+/// 上限提高時當前值同步增加差值；上限降低時當前值壓到新上限。
+/// 於 buff 施加、驅散與到期移除後呼叫，使上限類 buff 的得失對稱。
+pub(crate) fn refresh_max_hp_mp(world: &mut World, occupant: Occupant) -> Result<()> {
+    // 讀取階段
+    let entity = find_entity_by_occupant(world, occupant)?;
+    let (old_max_hp, current_hp, old_max_mp, current_mp) = {
+        let entity_ref = world.entity(entity);
+        (
+            get_component!(entity_ref, MaxHp)?.0,
+            get_component!(entity_ref, CurrentHp)?.0,
+            get_component!(entity_ref, MaxMp)?.0,
+            get_component!(entity_ref, CurrentMp)?.0,
+        )
+    };
+    let mut buff_query = world.query::<&AppliedBuff>();
+    let world_ref: &World = world;
+    let buffs: Vec<&BuffType> = buff_query
+        .iter(world_ref)
+        .filter(|buff| buff.target == occupant)
+        .map(|buff| buff.def.as_ref())
+        .collect();
+    let skills = get_component!(world_ref.entity(entity), Skills)?;
+    let game_data = get_resource::<GameData>(world_ref, "請先呼叫 parse_and_insert_game_data")?;
+    let auras = build_aura_effects_map(world_ref)?
+        .remove(&occupant)
+        .unwrap_or_default();
+
+    // 邏輯階段
+    let effects = filter_continuous_effect(&skills.0, buffs, &game_data.skill_map)?.chain(auras);
+    let attributes = calculate_attributes(effects);
+    let new_max_hp = attributes.max_hp.0;
+    let new_max_mp = attributes.max_mp.0;
+    let new_current_hp = adjust_current_for_max_change(current_hp, old_max_hp, new_max_hp);
+    let new_current_mp = adjust_current_for_max_change(current_mp, old_max_mp, new_max_mp);
+
+    // 寫入階段
+    let mut entity_mut = world.entity_mut(entity);
+    get_component_mut!(entity_mut, MaxHp)?.0 = new_max_hp;
+    get_component_mut!(entity_mut, CurrentHp)?.0 = new_current_hp;
+    get_component_mut!(entity_mut, MaxMp)?.0 = new_max_mp;
+    get_component_mut!(entity_mut, CurrentMp)?.0 = new_current_mp;
+    Ok(())
+}
+
+/// 光環依站位即時生效，HP/MP 上限須另行同步：重算場上單位的上限
+///
+/// 於單位位置改變（移動、強制位移、召喚）與死亡後呼叫，使進出光環範圍或來源死亡時上限立即得失。
+/// 光環對應表與各單位 buff 只建構一次，上限未變的單位不寫入；技能資料中沒有任何光環時直接略過。
+pub(crate) fn refresh_aura_max_hp_mp(world: &mut World) -> Result<()> {
+    let has_aura = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?
+        .skill_map
+        .values()
+        .any(|skill| match skill {
+            SkillType::Passive { effects, .. } => effects
+                .iter()
+                .any(|effect| matches!(effect, ContinuousEffect::Aura { .. })),
+            SkillType::Active { .. } | SkillType::Reaction { .. } => false,
+        });
+    if !has_aura {
+        return Ok(());
+    }
+
+    // 讀取階段
+    let mut buff_query = world.query::<&AppliedBuff>();
+    let mut unit_query = world.query_filtered::<(
+        Entity,
+        &Occupant,
+        &Skills,
+        &MaxHp,
+        &CurrentHp,
+        &MaxMp,
+        &CurrentMp,
+    ), With<Unit>>();
+    let world_ref: &World = world;
+    let game_data = get_resource::<GameData>(world_ref, "請先呼叫 parse_and_insert_game_data")?;
+    let mut buffs: HashMap<Occupant, Vec<&BuffType>> = HashMap::new();
+    for applied in buff_query.iter(world_ref) {
+        buffs
+            .entry(applied.target)
+            .or_default()
+            .push(applied.def.as_ref());
+    }
+    let mut auras = build_aura_effects_map(world_ref)?;
+
+    // 邏輯階段
+    let mut updates = Vec::new();
+    for (entity, occupant, skills, max_hp, current_hp, max_mp, current_mp) in
+        unit_query.iter(world_ref)
+    {
+        let effects = filter_continuous_effect(
+            &skills.0,
+            buffs.remove(occupant).unwrap_or_default(),
+            &game_data.skill_map,
+        )?
+        .chain(auras.remove(occupant).unwrap_or_default());
+        let attributes = calculate_attributes(effects);
+        let new_max_hp = attributes.max_hp.0;
+        let new_max_mp = attributes.max_mp.0;
+        if new_max_hp == max_hp.0 && new_max_mp == max_mp.0 {
+            continue;
+        }
+        updates.push((
+            entity,
+            new_max_hp,
+            adjust_current_for_max_change(current_hp.0, max_hp.0, new_max_hp),
+            new_max_mp,
+            adjust_current_for_max_change(current_mp.0, max_mp.0, new_max_mp),
+        ));
+    }
+
+    // 寫入階段
+    for (entity, new_max_hp, new_current_hp, new_max_mp, new_current_mp) in updates {
+        let mut entity_mut = world.entity_mut(entity);
+        get_component_mut!(entity_mut, MaxHp)?.0 = new_max_hp;
+        get_component_mut!(entity_mut, CurrentHp)?.0 = new_current_hp;
+        get_component_mut!(entity_mut, MaxMp)?.0 = new_max_mp;
+        get_component_mut!(entity_mut, CurrentMp)?.0 = new_current_mp;
+    }
+    Ok(())
+}
+
+/// 找出目標身上第一個同名 buff 的 entity
+fn find_buff_entity(world: &mut World, target: Occupant, name: &str) -> Option<Entity> {
+    world
+        .query::<(Entity, &AppliedBuff)>()
+        .iter(world)
+        .find(|(_, buff)| buff.target == target && buff.def.name == name)
+        .map(|(entity, _)| entity)
+}
+
+/// 由結束條件推得初始剩餘回合：取最短的 Duration，Duration(0) 或沒有 Duration 視為無限期
+fn buff_duration(def: &BuffType) -> Option<u32> {
+    def.end_conditions
+        .iter()
+        .filter_map(|condition| match condition {
+            EndCondition::Duration(turns) if *turns > 0 => Some(*turns),
+            _ => None,
+        })
+        .min()
+}
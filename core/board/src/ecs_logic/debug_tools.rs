@@ -0,0 +1,75 @@
+//! 壓力測試用的調試工具（需開啟 `debug-tools` feature）
+
+use crate::ecs_logic::query::get_resource;
+use crate::ecs_types::components::{CurrentHp, MaxHp, Position, Unit};
+use crate::ecs_types::resources::DeploymentConfig;
+use crate::error::Result;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::prelude::{With, World};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngExt};
+use std::collections::HashSet;
+
+/// 以給定的 RNG 打亂單位的起始 HP 與位置，用於壓力測試 AI 與平衡
+///
+/// - HP：每個單位的目前 HP 隨機落在 [MaxHp × (100 − hp_pct)%, MaxHp]，至少為 1；hp_pct 超過 100 視為 100
+/// - reposition：站在部署點上的單位在「未被其他單位佔據的部署點」之間重新隨機分配，其他單位不動
+///
+/// 單位與部署點依座標排序後再抽樣，同一個種子的 RNG 在同一關卡上結果相同。
+pub fn jitter_units<R: Rng + ?Sized>(
+    world: &mut World,
+    rng: &mut R,
+    hp_pct: u32,
+    reposition: bool,
+) -> Result<()> {
+    let deployment_positions = get_resource::<DeploymentConfig>(world, "請先呼叫 spawn_level")?
+        .deployment_positions
+        .clone();
+    let mut units: Vec<(Entity, Position, i32)> = world
+        .query_filtered::<(Entity, &Position, &MaxHp), With<Unit>>()
+        .iter(world)
+        .map(|(entity, pos, max_hp)| (entity, *pos, max_hp.0))
+        .collect();
+    units.sort_by_key(|(_, pos, _)| (pos.y, pos.x));
+
+    let hp_pct = hp_pct.min(100) as i32;
+    let new_hps: Vec<(Entity, i32)> = units
+        .iter()
+        .map(|(entity, _, max_hp)| {
+            let min_hp = (max_hp * (100 - hp_pct) / 100).max(1);
+            let hp = if min_hp >= *max_hp {
+                *max_hp
+            } else {
+                rng.random_range(min_hp..=*max_hp)
+            };
+            (*entity, hp)
+        })
+        .collect();
+
+    let mut new_positions: Vec<(Entity, Position)> = Vec::new();
+    if reposition {
+        let (moving, staying): (Vec<_>, Vec<_>) = units
+            .iter()
+            .partition(|(_, pos, _)| deployment_positions.contains(pos));
+        let blocked: HashSet<Position> = staying.iter().map(|(_, pos, _)| *pos).collect();
+        let mut candidates: Vec<Position> = deployment_positions
+            .into_iter()
+            .filter(|pos| !blocked.contains(pos))
+            .collect();
+        candidates.sort_by_key(|pos| (pos.y, pos.x));
+        candidates.shuffle(rng);
+        new_positions = moving
+            .iter()
+            .zip(candidates)
+            .map(|((entity, _, _), pos)| (*entity, pos))
+            .collect();
+    }
+
+    for (entity, hp) in new_hps {
+        world.entity_mut(entity).insert(CurrentHp(hp));
+    }
+    for (entity, pos) in new_positions {
+        world.entity_mut(entity).insert(pos);
+    }
+    Ok(())
+}
@@ -1,15 +1,19 @@
 use crate::domain::alias::ID;
 use crate::domain::core_types::{EndLevelCondition, LevelOutcome, OutcomeBranches};
-use crate::ecs_logic::query::get_resource;
+use crate::ecs_logic::query::{
+    build_faction_alliance_map, get_objective_holders, get_resource, get_resource_mut,
+    resolve_alliance,
+};
 use crate::ecs_types::components::UnitFaction;
-use crate::ecs_types::resources::EndConditionConfig;
+use crate::ecs_types::resources::{CaptureProgress, EndConditionConfig, TurnOrder};
 use crate::error::Result;
 use bevy_ecs::prelude::World;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-/// 判定關卡結局（defeat 優先於 victory）並回傳結果
+/// 判定關卡結局（defeat 優先於 victory，兩者皆未達成時才檢查回合上限）並回傳結果
 ///
-/// 存活 faction 集合只撈一次，victory、defeat 兩份 `OutcomeBranches` 共用。
+/// 存活 faction 與佔領目標點的 faction 集合只撈一次，victory、defeat 兩份 `OutcomeBranches` 共用。
+/// 設有回合上限時，進入第 max_rounds + 1 輪即判定平手，避免僵局的模擬無限循環。
 pub fn resolve_level_outcome(world: &mut World) -> Result<LevelOutcome> {
     // === 讀取階段 ===
     let alive_factions: HashSet<ID> = world
@@ -17,14 +21,34 @@ pub fn resolve_level_outcome(world: &mut World) -> Result<LevelOutcome> {
         .iter(world)
         .map(|faction| faction.0)
         .collect();
+    let capturing_factions: HashSet<ID> = get_objective_holders(world).into_values().collect();
+    let capture_progress = &get_resource::<CaptureProgress>(world, "請先呼叫 spawn_level")?.0;
     let end_condition_config = get_resource::<EndConditionConfig>(world, "請先呼叫 spawn_level")?;
+    // 戰鬥開始前沒有 TurnOrder，視為第 0 輪
+    let round = world
+        .get_resource::<TurnOrder>()
+        .map(|turn_order| turn_order.round)
+        .unwrap_or(0);
 
     // === 純邏輯階段 ===
-    let outcome = match find_triggered_branch(&end_condition_config.defeat, &alive_factions) {
+    let outcome = match find_triggered_branch(
+        &end_condition_config.defeat,
+        &alive_factions,
+        &capturing_factions,
+        capture_progress,
+    ) {
         Some(key) => LevelOutcome::Defeat(key),
-        None => match find_triggered_branch(&end_condition_config.victory, &alive_factions) {
+        None => match find_triggered_branch(
+            &end_condition_config.victory,
+            &alive_factions,
+            &capturing_factions,
+            capture_progress,
+        ) {
             Some(key) => LevelOutcome::Victory(key),
-            None => LevelOutcome::Undetermined,
+            None => match end_condition_config.max_rounds {
+                Some(max_rounds) if round > max_rounds => LevelOutcome::Draw { max_rounds },
+                _ => LevelOutcome::Undetermined,
+            },
         },
     };
 
@@ -32,9 +56,18 @@ pub fn resolve_level_outcome(world: &mut World) -> Result<LevelOutcome> {
 }
 
 /// TODO match arms 夠多後重構
-fn is_end_level_condition_met(condition: &EndLevelCondition, alive_factions: &HashSet<ID>) -> bool {
+fn is_end_level_condition_met(
+    condition: &EndLevelCondition,
+    alive_factions: &HashSet<ID>,
+    capturing_factions: &HashSet<ID>,
+    capture_progress: &HashMap<ID, u32>,
+) -> bool {
     match condition {
         EndLevelCondition::EliminateFaction(faction_id) => !alive_factions.contains(faction_id),
+        EndLevelCondition::CaptureObjective(faction_id) => capturing_factions.contains(faction_id),
+        EndLevelCondition::HoldObjective { faction_id, rounds } => {
+            capture_progress.get(faction_id).copied().unwrap_or(0) >= *rounds
+        }
     }
 }
 
@@ -42,13 +75,49 @@ fn is_end_level_condition_met(condition: &EndLevelCondition, alive_factions: &Ha
 fn find_triggered_branch(
     branches: &OutcomeBranches,
     alive_factions: &HashSet<ID>,
+    capturing_factions: &HashSet<ID>,
+    capture_progress: &HashMap<ID, u32>,
 ) -> Option<String> {
     branches
         .iter()
         .find(|(_, conditions)| {
-            conditions
-                .iter()
-                .all(|condition| is_end_level_condition_met(condition, alive_factions))
+            conditions.iter().all(|condition| {
+                is_end_level_condition_met(
+                    condition,
+                    alive_factions,
+                    capturing_factions,
+                    capture_progress,
+                )
+            })
         })
         .map(|(key, _)| key.clone())
 }
+
+/// 查詢各 faction 目前的目標點佔領進度（輪數）
+pub fn get_capture_progress(world: &World) -> Result<&HashMap<ID, u32>> {
+    Ok(&get_resource::<CaptureProgress>(world, "請先呼叫 spawn_level")?.0)
+}
+
+/// 整輪結束時累積目標點佔領進度
+///
+/// 目標點上只有單一同盟時，該同盟在目標點上的各 faction 進度 +1；
+/// 有兩個以上同盟（爭奪中）或無人佔領時不變
+pub(crate) fn accrue_capture_progress(world: &mut World) -> Result<()> {
+    // 讀取階段
+    let holding_factions: HashSet<ID> = get_objective_holders(world).into_values().collect();
+    let faction_to_alliance = build_faction_alliance_map(world)?;
+    let holding_alliances = holding_factions
+        .iter()
+        .map(|faction_id| resolve_alliance(&faction_to_alliance, *faction_id))
+        .collect::<Result<HashSet<ID>>>()?;
+    if holding_alliances.len() != 1 {
+        return Ok(());
+    }
+
+    // 寫入階段
+    let mut progress = get_resource_mut::<CaptureProgress>(world, "請先呼叫 spawn_level")?;
+    for faction_id in holding_factions {
+        *progress.0.entry(faction_id).or_insert(0) += 1;
+    }
+    Ok(())
+}
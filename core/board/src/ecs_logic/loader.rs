@@ -1,21 +1,43 @@
+use crate::domain::alias::{SkillName, TypeName};
+use crate::domain::core_types::{Effect, SkillType};
+use crate::ecs_logic::buff::refresh_max_hp_mp;
+use crate::ecs_logic::get_component;
+use crate::ecs_logic::query::{get_resource_mut, read_attribute_bundle};
+use crate::ecs_logic::spawner::spawn_level;
+use crate::ecs_types::components::{AttributeBundle, Occupant, ReactionPoint, Skills, Unit};
 use crate::ecs_types::resources::GameData;
 use crate::error::{LoadError, Result};
-use crate::loader_schema::{ObjectsToml, SkillsToml, UnitsToml};
-use bevy_ecs::prelude::World;
-use std::collections::HashMap;
+use crate::loader_schema::{
+    ObjectType, ObjectsToml, ScenarioBundle, SkillsToml, UnitType, UnitsToml,
+};
+use crate::logic::scenario::build_scenario_bundle;
+use crate::logic::skill::unit_attributes::{
+    adjust_current_for_max_change, calculate_attributes, filter_continuous_effect,
+};
+use bevy_ecs::prelude::{Entity, With, World};
+use std::collections::{HashMap, HashSet};
+use strum::IntoEnumIterator;
+use toml::Value;
+
+/// 寬鬆載入技能時被略過的未知效果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedEffect {
+    /// 含有未知效果的技能
+    pub skill: SkillName,
+    /// 無法辨識的效果類型名稱
+    pub effect_type: String,
+}
 
 /// 反序列化 TOML 並將遊戲資料存入 World Resource
+///
+/// 技能以 `parse_skills_toml` 寬鬆載入，回傳被略過的未知效果供呼叫端提示
 pub fn parse_and_insert_game_data(
     world: &mut World,
     units_toml: &str,
     skills_toml: &str,
     objects_toml: &str,
-) -> Result<()> {
-    let parsed_skills: SkillsToml =
-        toml::from_str(skills_toml).map_err(|e| LoadError::DeserializeError {
-            format: "skills.toml".to_string(),
-            reason: e.to_string(),
-        })?;
+) -> Result<Vec<SkippedEffect>> {
+    let (skills, skipped) = parse_skills_toml(skills_toml)?;
 
     let parsed_units: UnitsToml =
         toml::from_str(units_toml).map_err(|e| LoadError::DeserializeError {
@@ -29,20 +51,193 @@ pub fn parse_and_insert_game_data(
             reason: e.to_string(),
         })?;
 
-    let skill_map = parsed_skills
-        .skills
+    insert_game_data(world, parsed_units.units, skills, parsed_objects.objects);
+    Ok(skipped)
+}
+
+/// 寬鬆反序列化技能 TOML：含未知效果類型的效果葉節點會被略過並回報，其餘照常載入
+///
+/// 讓較舊的程式能讀取含有新效果類型的資料檔。未知效果位於巢狀結構
+/// （If、Random、Buff 每回合效果、接觸效果）時，依所在位置略過最近的葉節點或隨機選項。
+/// TOML 語法錯誤或其他欄位錯誤仍回傳錯誤。
+pub fn parse_skills_toml(skills_toml: &str) -> Result<(Vec<SkillType>, Vec<SkippedEffect>)> {
+    let deserialize_error = |reason: String| LoadError::DeserializeError {
+        format: "skills.toml".to_string(),
+        reason,
+    };
+    let mut raw: toml::Table =
+        toml::from_str(skills_toml).map_err(|e| deserialize_error(e.to_string()))?;
+
+    let known: HashSet<String> = Effect::iter().map(|effect| effect.to_string()).collect();
+    let mut skipped = Vec::new();
+    if let Some(skills) = raw.get_mut("skills").and_then(Value::as_array_mut) {
+        for skill in skills {
+            strip_unknown_skill_effects(skill, &known, &mut skipped);
+        }
+    }
+
+    let parsed: SkillsToml = Value::Table(raw)
+        .try_into()
+        .map_err(|e: toml::de::Error| deserialize_error(e.to_string()))?;
+    Ok((parsed.skills, skipped))
+}
+
+/// 技能為外部標記（`{ Active = { ... } }`），只有 Active/Reaction 帶有效果節點
+fn strip_unknown_skill_effects(
+    skill: &mut Value,
+    known: &HashSet<String>,
+    skipped: &mut Vec<SkippedEffect>,
+) {
+    let (name, effects) = match tagged_body(skill) {
+        Some((_, body)) => (
+            body.get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            body.get_mut("effects"),
+        ),
+        None => return,
+    };
+    if let Some(nodes) = effects.and_then(Value::as_array_mut) {
+        let mut unknown = Vec::new();
+        strip_unknown_nodes(nodes, known, &mut unknown);
+        skipped.extend(unknown.into_iter().map(|effect_type| SkippedEffect {
+            skill: name.clone(),
+            effect_type,
+        }));
+    }
+}
+
+/// Area/Branch 遞迴處理子節點；Leaf 的效果含未知類型時移除整個葉節點
+fn strip_unknown_nodes(nodes: &mut Vec<Value>, known: &HashSet<String>, unknown: &mut Vec<String>) {
+    nodes.retain_mut(|node| match tagged_body(node) {
+        Some(("Area", body)) => {
+            strip_child_nodes(body, "nodes", known, unknown);
+            true
+        }
+        Some(("Branch", body)) => {
+            strip_child_nodes(body, "on_success", known, unknown);
+            strip_child_nodes(body, "on_failure", known, unknown);
+            true
+        }
+        Some(("Leaf", body)) => body
+            .get_mut("effect")
+            .is_none_or(|effect| keep_effect(effect, known, unknown)),
+        _ => true,
+    });
+}
+
+fn strip_child_nodes(
+    body: &mut toml::Table,
+    key: &str,
+    known: &HashSet<String>,
+    unknown: &mut Vec<String>,
+) {
+    if let Some(nodes) = body.get_mut(key).and_then(Value::as_array_mut) {
+        strip_unknown_nodes(nodes, known, unknown);
+    }
+}
+
+/// 效果本身或不可分割的巢狀效果（If 的 then、全部隨機選項）未知時回傳 false
+///
+/// 巢狀的效果節點列表與個別隨機選項則就地移除，保留效果其餘部分
+fn keep_effect(effect: &mut Value, known: &HashSet<String>, unknown: &mut Vec<String>) -> bool {
+    // 無欄位的變體序列化為字串，其餘為單一鍵的表格
+    let tag = match effect {
+        Value::String(tag) => Some(tag.clone()),
+        Value::Table(table) if table.len() == 1 => table.keys().next().cloned(),
+        _ => None,
+    };
+    match tag {
+        Some(tag) if !known.contains(&tag) => {
+            unknown.push(tag);
+            return false;
+        }
+        _ => {}
+    }
+
+    match tagged_body(effect) {
+        Some(("ApplyBuff", body)) => {
+            if let Some(buff) = body.get_mut("buff").and_then(Value::as_table_mut) {
+                strip_child_nodes(buff, "per_turn_effects", known, unknown);
+            }
+            true
+        }
+        Some(("SpawnObject", body)) => {
+            strip_child_nodes(body, "contact_effects", known, unknown);
+            true
+        }
+        Some(("If", body)) => body
+            .get_mut("then")
+            .is_none_or(|then| keep_effect(then, known, unknown)),
+        Some(("Random", body)) => match body.get_mut("options").and_then(Value::as_array_mut) {
+            Some(options) if !options.is_empty() => {
+                options.retain_mut(|option| {
+                    option
+                        .get_mut("effect")
+                        .is_none_or(|effect| keep_effect(effect, known, unknown))
+                });
+                !options.is_empty()
+            }
+            _ => true,
+        },
+        _ => true,
+    }
+}
+
+/// 外部標記的 enum 為單一鍵的表格，回傳（變體名稱, 內容表格）
+fn tagged_body(value: &mut Value) -> Option<(&str, &mut toml::Table)> {
+    let table = value.as_table_mut()?;
+    if table.len() != 1 {
+        return None;
+    }
+    let (tag, body) = table.iter_mut().next()?;
+    Some((tag.as_str(), body.as_table_mut()?))
+}
+
+/// 反序列化情境包，存入遊戲資料並生成關卡，回傳玩家名冊
+///
+/// 寫入前會重新檢查引用完整性：關卡、名冊或技能引用到的定義缺漏時回傳錯誤，World 保持不變。
+pub fn load_scenario_bundle(world: &mut World, bundle_toml: &str) -> Result<Vec<TypeName>> {
+    let parsed: ScenarioBundle =
+        toml::from_str(bundle_toml).map_err(|e| LoadError::DeserializeError {
+            format: "scenario bundle".to_string(),
+            reason: e.to_string(),
+        })?;
+    let bundle = build_scenario_bundle(
+        parsed.level,
+        parsed.roster,
+        parsed.units,
+        parsed.skills,
+        parsed.objects,
+    )?;
+    let level_toml = toml::to_string(&bundle.level).map_err(|e| LoadError::SerializeError {
+        format: bundle.level.name.clone(),
+        reason: e.to_string(),
+    })?;
+
+    insert_game_data(world, bundle.units, bundle.skills, bundle.objects);
+    spawn_level(world, &level_toml, &bundle.level.name)?;
+    Ok(bundle.roster)
+}
+
+fn insert_game_data(
+    world: &mut World,
+    units: Vec<UnitType>,
+    skills: Vec<SkillType>,
+    objects: Vec<ObjectType>,
+) {
+    let skill_map = skills
         .into_iter()
         .map(|skill| (skill.name().clone(), skill))
         .collect::<HashMap<_, _>>();
 
-    let unit_type_map = parsed_units
-        .units
+    let unit_type_map = units
         .into_iter()
         .map(|unit| (unit.name.clone(), unit))
         .collect::<HashMap<_, _>>();
 
-    let object_type_map = parsed_objects
-        .objects
+    let object_type_map = objects
         .into_iter()
         .map(|object| (object.name.clone(), object))
         .collect::<HashMap<_, _>>();
@@ -52,6 +247,56 @@ pub fn parse_and_insert_game_data(
         unit_type_map,
         object_type_map,
     });
+}
+
+/// 以新的技能定義取代遊戲資料中的技能，並重算場上所有單位的屬性
+///
+/// 供編輯器在模擬中修改技能後套用：屬性依新技能重算，
+/// HP/MP 上限另計入身上 buff，當前 HP/MP 與反應點依上限變化調整。
+/// 任一單位的技能在新定義中不存在時回傳錯誤，World 保持不變。
+pub fn reload_skills(world: &mut World, skills: Vec<SkillType>) -> Result<()> {
+    let skill_map = skills
+        .into_iter()
+        .map(|skill| (skill.name().clone(), skill))
+        .collect::<HashMap<_, _>>();
+
+    // 讀取階段
+    let units: Vec<(Entity, Occupant)> = world
+        .query_filtered::<(Entity, &Occupant), With<Unit>>()
+        .iter(world)
+        .map(|(entity, occupant)| (entity, *occupant))
+        .collect();
+    let mut recalculated: Vec<(Entity, Occupant, AttributeBundle)> = Vec::new();
+    for (entity, occupant) in units {
+        let entity_ref = world.entity(entity);
+        let skills = get_component!(entity_ref, Skills)?;
+        let current = read_attribute_bundle(&entity_ref)?;
+        let attributes =
+            calculate_attributes(filter_continuous_effect(&skills.0, &[], &skill_map)?);
+
+        // 邏輯階段：HP/MP 留待 refresh_max_hp_mp 計入 buff 後調整
+        let reaction_point = adjust_current_for_max_change(
+            current.reaction_point.0,
+            current.max_reaction_point.0,
+            attributes.max_reaction_point.0,
+        );
+        let bundle = AttributeBundle {
+            max_hp: current.max_hp,
+            current_hp: current.current_hp,
+            max_mp: current.max_mp,
+            current_mp: current.current_mp,
+            reaction_point: ReactionPoint(reaction_point),
+            ..attributes
+        };
+        recalculated.push((entity, occupant, bundle));
+    }
 
+    // 寫入階段
+    get_resource_mut::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?.skill_map =
+        skill_map;
+    for (entity, occupant, bundle) in recalculated {
+        world.entity_mut(entity).insert(bundle);
+        refresh_max_hp_mp(world, occupant)?;
+    }
     Ok(())
 }
@@ -1,10 +1,14 @@
 pub mod battle_log;
+pub mod buff;
+#[cfg(feature = "debug-tools")]
+pub mod debug_tools;
 pub mod deployment;
 pub mod level_outcome;
 pub mod loader;
 pub mod movement;
 pub mod query;
 pub mod reaction;
+pub mod save;
 pub mod skill;
 pub mod spawner;
 pub mod turn;
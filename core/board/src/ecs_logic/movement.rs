@@ -1,26 +1,37 @@
 //! ECS 移動操作函數
 
 use super::{get_component, get_component_mut};
-use crate::domain::alias::{ID, MovementCost, SkillName};
-use crate::domain::constants::BASIC_MOVEMENT_COST;
-use crate::domain::core_types::{PendingReaction, ReactionTrigger, SkillType};
+use crate::domain::alias::{Coord, ID, MovementCost, SkillName};
+use crate::domain::constants::{
+    BASIC_MOVEMENT_COST, HAZARD_PATH_COST, IMPASSABLE_MOVEMENT_COST, PLAYER_ALLIANCE_ID,
+};
+use crate::domain::core_types::{PendingReaction, ReactionTrigger, SkillType, TileTrigger};
+use crate::ecs_logic::buff::refresh_aura_max_hp_mp;
 use crate::ecs_logic::query::{
-    build_faction_alliance_map, find_entity_by_occupant, get_resource, get_resource_mut,
-    resolve_alliance,
+    build_blocks_sight, build_faction_alliance_map, find_entity_by_occupant, get_resource,
+    get_resource_mut, resolve_alliance,
 };
+use crate::ecs_logic::skill::apply_hp_change;
 use crate::ecs_logic::turn::get_current_unit;
 use crate::ecs_types::components::{
-    ActionState, BlocksSight, Hazardous, MovementPoint, Object, ObjectMovementCost, Occupant,
-    Position, ReactionPoint, Skills, Unit, UnitFaction,
+    ActionState, CliffFacing, CurrentMp, Hazardous, MovementPoint, Object, ObjectMovementCost,
+    ObjectTrigger, Occupant, Overwatch, Position, ReactionPoint, Skills, Unit, UnitFaction,
+};
+use crate::ecs_types::resources::{
+    Board, DistanceFieldCache, GameData, MovementPlan, ReactionState, TurnOrder,
+};
+use crate::error::{BoardError, Result};
+use crate::logic::board::chebyshev_distance;
+use crate::logic::cliff::cliff_blocks;
+use crate::logic::distance_field::DistanceField;
+use crate::logic::movement::{
+    Direction, Mover, ReachableInfo, find_path, nearest_reachable, reachable_positions,
 };
-use crate::ecs_types::resources::{Board, GameData, MovementPlan, ReactionState, TurnOrder};
-use crate::error::{BoardError, DataError, Result};
-use crate::logic::movement::{Mover, ReachableInfo, reachable_positions, reconstruct_path};
-use crate::logic::skill::UnitInfo;
 use crate::logic::skill::skill_reaction::{
     CollectMoveReactionsResult, MoveReaction, ReactionUnitInfo, collect_move_reactions,
 };
-use bevy_ecs::prelude::{With, World};
+use crate::logic::skill::{UnitInfo, manhattan_distance};
+use bevy_ecs::prelude::{Entity, With, World};
 use std::collections::{HashMap, HashSet};
 
 /// advance_move 的回傳值
@@ -45,9 +56,39 @@ pub fn get_reachable_positions(
     world: &mut World,
     occupant: Occupant,
 ) -> Result<HashMap<Position, ReachableInfo>> {
+    context_reachable(&load_move_context(world, occupant)?)
+}
+
+/// 找出單位本回合移動力內、路徑成本最低的目標格
+///
+/// 返回 (目標, 成本, 含起點與目標的路徑)；沒有可停留的目標時返回 None。
+/// 路徑為最低成本路徑，不考慮危險地面偏好；實際移動以 plan_move 規劃為準。
+pub fn get_nearest_reachable(
+    world: &mut World,
+    occupant: Occupant,
+    goals: &[Position],
+) -> Result<Option<(Position, MovementCost, Vec<Position>)>> {
+    context_nearest(&load_move_context(world, occupant)?, goals)
+}
+
+/// 單位尋路所需的棋盤資料（讀取階段一次收集）
+struct MoveContext {
+    board: Board,
+    mover_pos: Position,
+    mover_alliance: ID,
+    /// 可用預算（2 倍移動力 - 已使用的）
+    budget: MovementCost,
+    units_faction: HashMap<Position, ID>,
+    faction_to_alliance: HashMap<ID, ID>,
+    objects_movement_cost: HashMap<Position, MovementCost>,
+    cliffs: HashMap<Position, Direction>,
+    hazard_cells: HashSet<Position>,
+}
+
+fn load_move_context(world: &mut World, occupant: Occupant) -> Result<MoveContext> {
     let entity = find_entity_by_occupant(world, occupant)?;
     let entity_ref = world.entity(entity);
-    let unit_pos = *get_component!(entity_ref, Position)?;
+    let mover_pos = *get_component!(entity_ref, Position)?;
     let faction = get_component!(entity_ref, UnitFaction)?.0;
     let movement_point = get_component!(entity_ref, MovementPoint)?.0 as MovementCost;
     let movement_used = match get_component!(entity_ref, ActionState)? {
@@ -56,40 +97,180 @@ pub fn get_reachable_positions(
     };
 
     let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
-    let units_faction = get_units_faction_map(world)?;
-    let objects_movement_cost = get_objects_movement_cost_map(world)?;
     let faction_to_alliance = build_faction_alliance_map(world)?;
+    let mover_alliance = resolve_alliance(&faction_to_alliance, faction)?;
+    let hazard_cells = world
+        .query_filtered::<&Position, With<Hazardous>>()
+        .iter(world)
+        .copied()
+        .collect();
 
-    // 計算可用預算（2 倍移動力 - 已使用的）
-    let budget = movement_point * 2 - movement_used;
+    Ok(MoveContext {
+        board,
+        mover_pos,
+        mover_alliance,
+        budget: movement_point * 2 - movement_used,
+        units_faction: get_units_faction_map(world)?,
+        faction_to_alliance,
+        objects_movement_cost: get_objects_movement_cost_map(world)?,
+        cliffs: get_cliff_map(world),
+        hazard_cells,
+    })
+}
 
-    let get_occupant_alliance = |pos: Position| -> Option<ID> {
-        units_faction.get(&pos).and_then(|faction_id| {
-            faction_to_alliance
-                .get(faction_id)
-                .copied()
-                .or_else(|| unreachable!("faction_id {} 不存在於 faction_to_alliance", faction_id))
-        })
-    };
+fn context_mover(context: &MoveContext) -> Mover {
+    Mover {
+        pos: context.mover_pos,
+        faction_alliance: context.mover_alliance,
+    }
+}
 
+fn context_occupant_alliance(context: &MoveContext, pos: Position) -> Option<ID> {
+    context.units_faction.get(&pos).and_then(|faction_id| {
+        context
+            .faction_to_alliance
+            .get(faction_id)
+            .copied()
+            .or_else(|| unreachable!("faction_id {} 不存在於 faction_to_alliance", faction_id))
+    })
+}
+
+fn context_terrain_cost(context: &MoveContext, pos: Position) -> MovementCost {
+    BASIC_MOVEMENT_COST
+        + context
+            .objects_movement_cost
+            .get(&pos)
+            .copied()
+            .unwrap_or(0)
+}
+
+fn context_reachable(context: &MoveContext) -> Result<HashMap<Position, ReachableInfo>> {
+    reachable_positions(
+        context.board,
+        context_mover(context),
+        context.budget,
+        |pos| context_occupant_alliance(context, pos),
+        |pos| context_terrain_cost(context, pos),
+        |from, to| is_cliff_climb(context.board, &context.cliffs, from, to),
+    )
+}
+
+fn context_nearest(
+    context: &MoveContext,
+    goals: &[Position],
+) -> Result<Option<(Position, MovementCost, Vec<Position>)>> {
+    nearest_reachable(
+        context.board,
+        context_mover(context),
+        context.budget,
+        goals,
+        |pos| context_occupant_alliance(context, pos),
+        |pos| context_terrain_cost(context, pos),
+        |from, to| is_cliff_climb(context.board, &context.cliffs, from, to),
+    )
+}
+
+/// 到 target 的路徑（含起點），預算內能繞開危險地面時繞路；不可達時為空
+fn context_path_to(context: &MoveContext, target: Position) -> Result<Vec<Position>> {
+    find_path(
+        context.board,
+        context_mover(context),
+        context.budget,
+        target,
+        |pos| context_occupant_alliance(context, pos),
+        |pos| {
+            let hazard_cost = match context.hazard_cells.contains(&pos) {
+                true => HAZARD_PATH_COST,
+                false => 0,
+            };
+            (context_terrain_cost(context, pos), hazard_cost)
+        },
+        |from, to| is_cliff_climb(context.board, &context.cliffs, from, to),
+    )
+}
+
+/// 兩格之間的距離與地形移動成本（測距工具用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileMeasurement {
+    pub chebyshev: Coord,
+    pub manhattan: Coord,
+    /// 繞過不可通行地形的最低移動成本；無法抵達時為 None
+    pub movement_cost: Option<MovementCost>,
+}
+
+/// 量測兩格之間的距離與實際尋路的移動成本
+///
+/// 只考慮地形，不考慮單位阻擋；成本達到不可通行門檻的路徑視為無法抵達
+pub fn measure_between(world: &mut World, from: Position, to: Position) -> Result<TileMeasurement> {
+    // 讀取階段
+    let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
+    let objects_movement_cost = get_objects_movement_cost_map(world)?;
+    let cliffs = get_cliff_map(world);
+
+    // 邏輯階段
     let get_terrain_cost = |pos: Position| -> MovementCost {
         BASIC_MOVEMENT_COST + objects_movement_cost.get(&pos).copied().unwrap_or(0)
     };
-
-    let mover_alliance = resolve_alliance(&faction_to_alliance, faction)?;
-
     let mover = Mover {
-        pos: unit_pos,
-        faction_alliance: mover_alliance,
+        pos: from,
+        faction_alliance: PLAYER_ALLIANCE_ID,
     };
-
-    reachable_positions(
+    let reachable = reachable_positions(
         board,
         mover,
-        budget,
-        get_occupant_alliance,
+        IMPASSABLE_MOVEMENT_COST - 1,
+        |_| None,
         get_terrain_cost,
-    )
+        |from, to| is_cliff_climb(board, &cliffs, from, to),
+    )?;
+    let movement_cost = match from == to {
+        true => Some(0),
+        false => reachable.get(&to).map(|info| info.cost),
+    };
+
+    Ok(TileMeasurement {
+        chebyshev: chebyshev_distance(from, to),
+        manhattan: manhattan_distance(from, to),
+        movement_cost,
+    })
+}
+
+/// 取得目前地形的全點對距離場（只考慮地形，不考慮單位阻擋）
+///
+/// 結果快取在 DistanceFieldCache，地形（棋盤尺寸、物件移動成本、懸崖）未變時直接重用，
+/// 改變時自動重建。建立成本為格數平方，只適合小棋盤的重複查詢。
+pub fn get_distance_field(world: &mut World) -> Result<&DistanceField> {
+    // 讀取階段
+    let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
+    let objects_movement_cost = get_objects_movement_cost_map(world)?;
+    let cliffs = get_cliff_map(world);
+    let is_fresh = match world.get_resource::<DistanceFieldCache>() {
+        Some(cache) => {
+            cache.board.width == board.width
+                && cache.board.height == board.height
+                && cache.objects_movement_cost == objects_movement_cost
+                && cache.cliffs == cliffs
+        }
+        None => false,
+    };
+
+    // 寫入階段
+    if !is_fresh {
+        let field = DistanceField::build(
+            board,
+            |pos| BASIC_MOVEMENT_COST + objects_movement_cost.get(&pos).copied().unwrap_or(0),
+            |from, to| is_cliff_climb(board, &cliffs, from, to),
+        )?;
+        world.insert_resource(DistanceFieldCache {
+            board,
+            objects_movement_cost,
+            cliffs,
+            field,
+        });
+    }
+
+    let cache = get_resource::<DistanceFieldCache>(world, "DistanceFieldCache 應已建立")?;
+    Ok(&cache.field)
 }
 
 /// 預覽當前行動單位移動到目標格會觸發的藉機攻擊
@@ -105,10 +286,9 @@ pub fn preview_move_reactions(
     let occupant = get_current_unit(turn_order)?;
 
     let entity = find_entity_by_occupant(world, occupant)?;
-    let start_pos = *get_component!(world.entity(entity), Position)?;
     let mover_faction = get_component!(world.entity(entity), UnitFaction)?.0;
 
-    let reachable = get_reachable_positions(world, occupant)?;
+    let move_context = load_move_context(world, occupant)?;
 
     let faction_to_alliance = build_faction_alliance_map(world)?;
     let mover_alliance = resolve_alliance(&faction_to_alliance, mover_faction)?;
@@ -118,16 +298,12 @@ pub fn preview_move_reactions(
         alliance_id: mover_alliance,
     };
 
-    let blocks_sight: HashSet<Position> = world
-        .query_filtered::<&Position, With<BlocksSight>>()
-        .iter(world)
-        .copied()
-        .collect();
+    let blocks_sight = build_blocks_sight(world);
 
     let reaction_unit_map = build_reaction_unit_map(world, &faction_to_alliance)?;
 
     // path 含起點：[start, step1, ..., target]；目標不可達時為空
-    let path = reconstruct_path(&reachable, start_pos, target);
+    let path = context_path_to(&move_context, target)?;
 
     collect_move_reactions(&mover_info, &path, &reaction_unit_map, &blocks_sight)
 }
@@ -138,6 +314,8 @@ pub fn preview_move_reactions(
 /// 唯讀，不改變 World、不產生 pending 反應。
 #[derive(Debug)]
 pub struct MovePathPreview {
+    /// 實際會走的路徑（含起點），與 plan_move 規劃的路徑相同
+    pub path: Vec<Position>,
     /// 整條路徑上所有會觸發藉機攻擊的反應者（沿途每處脫離都提示）
     pub reactions: Vec<MoveReaction>,
     /// 路徑上經過的危險地面格
@@ -156,10 +334,9 @@ pub fn preview_move_path(world: &mut World, target: Position) -> Result<MovePath
     let occupant = get_current_unit(turn_order)?;
 
     let entity = find_entity_by_occupant(world, occupant)?;
-    let start_pos = *get_component!(world.entity(entity), Position)?;
     let mover_faction = get_component!(world.entity(entity), UnitFaction)?.0;
 
-    let reachable = get_reachable_positions(world, occupant)?;
+    let move_context = load_move_context(world, occupant)?;
 
     let faction_to_alliance = build_faction_alliance_map(world)?;
     let mover_alliance = resolve_alliance(&faction_to_alliance, mover_faction)?;
@@ -169,22 +346,12 @@ pub fn preview_move_path(world: &mut World, target: Position) -> Result<MovePath
         alliance_id: mover_alliance,
     };
 
-    let blocks_sight: HashSet<Position> = world
-        .query_filtered::<&Position, With<BlocksSight>>()
-        .iter(world)
-        .copied()
-        .collect();
-
-    let hazard_cells: HashSet<Position> = world
-        .query_filtered::<&Position, With<Hazardous>>()
-        .iter(world)
-        .copied()
-        .collect();
+    let blocks_sight = build_blocks_sight(world);
 
     let reaction_unit_map = build_reaction_unit_map(world, &faction_to_alliance)?;
 
     // path 含起點：[start, step1, ..., target]；目標不可達時為空
-    let path = reconstruct_path(&reachable, start_pos, target);
+    let path = context_path_to(&move_context, target)?;
 
     if path.is_empty() {
         return Err(BoardError::Unreachable {
@@ -201,10 +368,11 @@ pub fn preview_move_path(world: &mut World, target: Position) -> Result<MovePath
     let hazard_positions: Vec<Position> = path[1..]
         .iter()
         .copied()
-        .filter(|pos| hazard_cells.contains(pos))
+        .filter(|pos| move_context.hazard_cells.contains(pos))
         .collect();
 
     Ok(MovePathPreview {
+        path,
         reactions: reaction_result.reactions,
         hazard_positions,
     })
@@ -213,16 +381,15 @@ pub fn preview_move_path(world: &mut World, target: Position) -> Result<MovePath
 /// 規劃移動路徑並存入 MovementPlan resource
 ///
 /// 驗證目標位置可到達後，將完整路徑存入 resource。
+/// 預算內能繞開危險地面時選擇繞路，移動消耗仍只計地形成本。
 /// 後續呼叫 advance_move 或 force_advance_move 才真正移動單位。
 pub fn plan_move(world: &mut World, target: Position) -> Result<()> {
     // 從 TurnOrder 取得當前行動單位
     let turn_order = get_resource::<TurnOrder>(world, "請先呼叫 start_new_round")?;
     let occupant = get_current_unit(turn_order)?;
 
-    let entity = find_entity_by_occupant(world, occupant)?;
-    let start_pos = *get_component!(world.entity(entity), Position)?;
-
-    let reachable = get_reachable_positions(world, occupant)?;
+    let move_context = load_move_context(world, occupant)?;
+    let reachable = context_reachable(&move_context)?;
 
     // 驗證目標位置是否可到達且不是僅穿越位置
     let reach_info = reachable
@@ -241,27 +408,17 @@ pub fn plan_move(world: &mut World, target: Position) -> Result<()> {
         .into());
     }
 
-    // path 含起點：[start, step1, ..., target]
-    let path = reconstruct_path(&reachable, start_pos, target);
+    // path 含起點：[start, step1, ..., target]；預算內能繞開危險地面時繞路
+    let path = context_path_to(&move_context, target)?;
 
     // step_costs[0] = 0（起點），step_costs[i] = 走到 path[i] 的移動消耗
-    let mut step_costs = Vec::with_capacity(path.len());
-    step_costs.push(0);
-    for i in 1..path.len() {
-        let prev_cumulative = if i == 1 {
-            0
-        } else {
-            reachable
-                .get(&path[i - 1])
-                .expect("path 中的位置必定在 reachable 內")
-                .cost
-        };
-        let curr_cumulative = reachable
-            .get(&path[i])
-            .expect("path 中的位置必定在 reachable 內")
-            .cost;
-        step_costs.push(curr_cumulative - prev_cumulative);
-    }
+    let step_costs = std::iter::once(0)
+        .chain(
+            path[1..]
+                .iter()
+                .map(|pos| context_terrain_cost(&move_context, *pos)),
+        )
+        .collect();
 
     world.insert_resource(MovementPlan {
         occupant,
@@ -301,11 +458,7 @@ pub fn advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
         alliance_id: mover_alliance,
     };
 
-    let blocks_sight: HashSet<Position> = world
-        .query_filtered::<&Position, With<BlocksSight>>()
-        .iter(world)
-        .copied()
-        .collect();
+    let blocks_sight = build_blocks_sight(world);
 
     let reaction_unit_map = build_reaction_unit_map(world, &faction_to_alliance)?;
 
@@ -317,7 +470,6 @@ pub fn advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
         &reaction_unit_map,
         &blocks_sight,
     )?;
-    let stop_pos = reaction_result.stop_position;
     // 只取最早觸發步驟的那批反應者（實際移動走到第一個觸發就停）
     let earliest_from_index = reaction_result.earliest_from_index;
     let earliest_reactions: Vec<_> = reaction_result
@@ -327,23 +479,11 @@ pub fn advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
         .collect();
     let has_reactions = !earliest_reactions.is_empty();
 
-    // steps_to_stop：在 path[next_step_index..] 中的 index，即走了幾步
-    let steps_to_stop = path[next_step_index..]
-        .iter()
-        .position(|p| *p == stop_pos)
-        .ok_or_else(|| DataError::InternalError {
-            message: format!(
-                "collect_move_reactions 回傳的 stop_position {:?} 不在 path[{}..] 中",
-                stop_pos, next_step_index
-            ),
-        })?;
-
-    // 有反應時停在觸發格的前一格（stop_index - 1），無反應時走到 stop_index
-    let stop_index = next_step_index + steps_to_stop;
+    // 有反應時停在觸發格（藉機攻擊為離開前、警戒為踏入後），無反應時走到終點
     let stop_index = if has_reactions {
-        stop_index - 1
+        next_step_index + earliest_from_index
     } else {
-        stop_index
+        path.len() - 1
     };
     let actual_stop_pos = path[stop_index];
     let walked_path: Vec<Position> = path[next_step_index..=stop_index].to_vec();
@@ -371,7 +511,13 @@ pub fn advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
         }
     }
 
-    if reached_end {
+    // 踏入的格子（不含出發格）上的傷害陷阱
+    apply_trap_damage(world, entity, &walked_path[1..])?;
+    // 進出光環範圍
+    refresh_aura_max_hp_mp(world)?;
+
+    // 警戒觸發於終點時保留計畫，讓反應結束後的 force_advance_move 收尾
+    if reached_end && !has_reactions {
         world.remove_resource::<MovementPlan>();
     } else {
         let mut plan_mut = get_resource_mut::<MovementPlan>(world, "請先呼叫 plan_move")?;
@@ -384,7 +530,7 @@ pub fn advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
             .map(|r| PendingReaction {
                 reactor: r.occupant,
                 trigger: occupant,
-                trigger_event: ReactionTrigger::AttackOfOpportunity,
+                trigger_event: r.trigger,
                 available_skills: r.skill_names,
             })
             .collect();
@@ -412,6 +558,8 @@ pub fn advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
 /// 若原移動者已在反應鏈中死亡（當前單位已遞補為他人），此計畫作廢：
 /// 移除 MovementPlan、不移動任何單位，回傳走了 0 步的 Completed。
 /// 避免把死者遺留的計畫誤套用到遞補的當前單位身上。
+///
+/// 計畫已走到終點（警戒於終點觸發）時同樣移除計畫，回傳走了 0 步的 Completed。
 pub fn force_advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
     let turn_order = get_resource::<TurnOrder>(world, "請先呼叫 start_new_round")?;
     let occupant = get_current_unit(turn_order)?;
@@ -432,6 +580,15 @@ pub fn force_advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
         });
     }
 
+    // 已在終點（警戒於終點觸發）：沒有下一格可走，直接結束計畫
+    if next_step_index + 1 >= path.len() {
+        world.remove_resource::<MovementPlan>();
+        return Ok(AdvanceMoveResult::Completed {
+            path_walked: vec![],
+            cost: 0,
+        });
+    }
+
     let new_next_step_index = next_step_index + 1;
     let next_pos = path[new_next_step_index];
     let this_step_cost = step_costs[new_next_step_index];
@@ -456,6 +613,9 @@ pub fn force_advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
         }
     }
 
+    apply_trap_damage(world, entity, &[next_pos])?;
+    refresh_aura_max_hp_mp(world)?;
+
     world.remove_resource::<MovementPlan>();
     return Ok(AdvanceMoveResult::Completed {
         path_walked: vec![next_pos],
@@ -467,6 +627,23 @@ pub fn force_advance_move(world: &mut World) -> Result<AdvanceMoveResult> {
 // 私有輔助函數
 // ============================================================================
 
+/// 對移動中踏入傷害陷阱格的單位結算陷阱傷害（護盾先吸收）
+fn apply_trap_damage(world: &mut World, entity: Entity, entered: &[Position]) -> Result<()> {
+    let damage: i32 = world
+        .query::<(&Position, &ObjectTrigger)>()
+        .iter(world)
+        .filter(|(pos, _)| entered.contains(pos))
+        .map(|(_, trigger)| match trigger.0 {
+            TileTrigger::Damage { amount } => amount,
+            TileTrigger::Objective => 0,
+        })
+        .sum();
+    if damage == 0 {
+        return Ok(());
+    }
+    apply_hp_change(world, entity, -damage)
+}
+
 fn build_reaction_unit_map<'a>(
     world: &'a mut World,
     faction_to_alliance: &'a HashMap<ID, ID>,
@@ -476,34 +653,62 @@ fn build_reaction_unit_map<'a>(
         &Occupant,
         &UnitFaction,
         &ReactionPoint,
+        &CurrentMp,
         &Skills,
+        Option<&Overwatch>,
     ), With<Unit>>();
 
     // 先快照原始資料釋放 world borrow，再借用 game_data 查詢 SkillType
-    let snapshots: Vec<(Position, Occupant, ID, i32, Vec<SkillName>)> = query
+    type Snapshot = (
+        Position,
+        Occupant,
+        ID,
+        i32,
+        i32,
+        Vec<SkillName>,
+        Option<SkillName>,
+    );
+    let snapshots: Vec<Snapshot> = query
         .iter(world)
-        .filter(|(_, _, _, reaction_point, _)| reaction_point.0 > 0)
-        .map(|(pos, occupant, faction, reaction_point, skills)| {
-            (
-                *pos,
-                *occupant,
-                faction.0,
-                reaction_point.0,
-                skills.0.clone(),
-            )
-        })
+        .filter(|(_, _, _, reaction_point, _, _, _)| reaction_point.0 > 0)
+        .map(
+            |(pos, occupant, faction, reaction_point, mp, skills, overwatch)| {
+                (
+                    *pos,
+                    *occupant,
+                    faction.0,
+                    reaction_point.0,
+                    mp.0,
+                    skills.0.clone(),
+                    overwatch.map(|overwatch| overwatch.0.clone()),
+                )
+            },
+        )
         .collect();
 
     let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
 
     let mut result = HashMap::new();
-    for (pos, occupant, faction_id, remaining_reactions, skill_names) in snapshots {
+    for (pos, occupant, faction_id, remaining_reactions, current_mp, skill_names, overwatch) in
+        snapshots
+    {
         let alliance_id = resolve_alliance(faction_to_alliance, faction_id)?;
 
+        // 警戒技能只有架設中的那一個列入
         let skills: Vec<&'a SkillType> = skill_names
             .iter()
             .filter_map(|name| game_data.skill_map.get(name))
-            .filter(|s| matches!(s, SkillType::Reaction { .. }))
+            .filter(|s| match s {
+                SkillType::Reaction {
+                    name,
+                    triggering_unit,
+                    ..
+                } => {
+                    !matches!(triggering_unit.trigger, ReactionTrigger::Overwatch)
+                        || overwatch.as_ref() == Some(name)
+                }
+                SkillType::Active { .. } | SkillType::Passive { .. } => false,
+            })
             .collect();
 
         result.insert(
@@ -515,6 +720,7 @@ fn build_reaction_unit_map<'a>(
                     alliance_id,
                 },
                 remaining_reactions,
+                current_mp,
                 skills,
             },
         );
@@ -534,6 +740,28 @@ fn get_units_faction_map(world: &mut World) -> Result<HashMap<Position, ID>> {
     Ok(result)
 }
 
+/// 取得懸崖位置與朝向對應表
+fn get_cliff_map(world: &mut World) -> HashMap<Position, Direction> {
+    let mut query = world.query_filtered::<(&Position, &CliffFacing), With<Object>>();
+    query
+        .iter(world)
+        .map(|(position, facing)| (*position, facing.0))
+        .collect()
+}
+
+/// 判斷 from → to 是否為從崖下攀上懸崖（跳下與其他方向不受影響）
+fn is_cliff_climb(
+    board: Board,
+    cliffs: &HashMap<Position, Direction>,
+    from: Position,
+    to: Position,
+) -> bool {
+    match cliffs.get(&to) {
+        Some(facing) => cliff_blocks(board, from, to, to, *facing),
+        None => false,
+    }
+}
+
 /// 取得物件地形消耗對應表
 fn get_objects_movement_cost_map(world: &mut World) -> Result<HashMap<Position, MovementCost>> {
     let mut result = HashMap::new();
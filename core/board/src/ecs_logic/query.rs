@@ -1,28 +1,38 @@
-use crate::domain::alias::{ID, SkillName};
+use crate::domain::alias::{Coord, ID, MovementCost, SkillName};
 use crate::domain::battle_log::LogEvent;
 use crate::domain::constants::IMPASSABLE_MOVEMENT_COST;
-use crate::domain::core_types::{EffectNode, SkillTag, SkillType, Target, TriggeringSource};
+use crate::domain::core_types::{
+    BuffType, ContinuousEffect, DamageType, EffectNode, EffectOrder, SkillTag, SkillType, Target,
+    TileTrigger, TriggeringSource,
+};
 use crate::ecs_logic::get_component;
 use crate::ecs_types::components::{
-    ActionState, Agility, AttributeBundle, Block, BlockProtection, BlocksSight, BlocksSound,
-    ContactEffects, CurrentHp, CurrentMp, FlankingAccuracyBonus, Fortitude, Hazardous, Initiative,
-    MagicalAccuracy, MagicalAttack, MaxHp, MaxMp, MaxReactionPoint, MovementPoint, Object,
-    ObjectBundle, ObjectMovementCost, Occupant, OccupantTypeName, PhysicalAccuracy, PhysicalAttack,
-    Position, ReactionPoint, Skills, Unit, UnitBundle, UnitFaction, Will,
+    ActionState, Agility, AppliedBuff, AttributeBundle, Block, BlockProtection, BlocksSight,
+    BlocksSound, Concealment, ContactEffects, CurrentHp, CurrentMp, Elevation,
+    FlankingAccuracyBonus, ForbiddenSkillTags, Fortitude, Hazardous, Initiative, MagicalAccuracy,
+    MagicalAttack, MaxHp, MaxMp, MaxReactionPoint, MovementPoint, Object, ObjectBundle,
+    ObjectMovementCost, ObjectTrigger, Occupant, OccupantTypeName, PhysicalAccuracy,
+    PhysicalAttack, Position, ReactionPoint, Skills, Unit, UnitBundle, UnitFaction, Will,
 };
 use crate::ecs_types::resources::{
-    BattleLog, GameData, LevelConfig, OccupantIndex, SkillTargeting,
+    BattleLog, Board, GameData, LevelConfig, OccupantIndex, SkillTargeting,
 };
 use crate::error::{BoardError, DataError, Result, UnitError};
+use crate::logic::content_hash::stable_hash;
 use crate::logic::debug::short_type_name;
 use crate::logic::skill::UnitInfo;
+use crate::logic::skill::line_of_sight::{compute_visible_positions, tile_elevation};
 use crate::logic::skill::skill_execution::{CombatStats, ObjectOnBoard};
+use crate::logic::skill::unit_attributes::{
+    AuraUnit, apply_attribute_modifiers, calculate_attributes, calculate_resistances,
+    collect_aura_effects, filter_continuous_effect,
+};
 use bevy_ecs::change_detection::Mut;
 use bevy_ecs::event::EntityEvent;
 use bevy_ecs::lifecycle::{Add, Remove};
 use bevy_ecs::prelude::{Entity, On, Query, ResMut, Resource, With, World};
 use bevy_ecs::world::EntityRef;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// 查詢所有單位，以位置為 key
@@ -78,6 +88,7 @@ pub fn get_all_objects(world: &mut World) -> Result<HashMap<Position, ObjectQuer
             occupant_type_name: get_component!(entity_ref, OccupantTypeName)?.clone(),
             terrain_movement_cost: get_component!(entity_ref, ObjectMovementCost)?.clone(),
             contact_effects: get_component!(entity_ref, ContactEffects)?.clone(),
+            elevation: *get_component!(entity_ref, Elevation)?,
         };
         let blocks_sight = entity_ref.get::<BlocksSight>().is_some();
         let blocks_sound = entity_ref.get::<BlocksSound>().is_some();
@@ -189,30 +200,102 @@ pub(crate) fn resolve_alliance(map: &HashMap<ID, ID>, faction_id: ID) -> Result<
     })
 }
 
-/// 取得指定技能名稱對應的 Active 技能欄位；若非 Active 則視為 SkillNotFound
-pub(crate) fn get_active_skill_data(
-    game_data: &GameData,
+/// 主動技能定義的各欄位（借用自 GameData）
+pub(crate) struct ActiveSkill<'a> {
+    pub tags: &'a [SkillTag],
+    pub cost: u32,
+    pub requires: &'a [SkillName],
+    pub hits: u8,
+    pub recoil: u32,
+    pub effect_order: EffectOrder,
+    pub channel_turns: u32,
+    pub target: &'a Target,
+    pub effects: &'a Arc<[EffectNode]>,
+}
+
+/// 取得指定技能名稱對應的 Active 技能定義；若非 Active 則視為 SkillNotFound
+pub(crate) fn get_active_skill<'a>(
+    game_data: &'a GameData,
     skill_name: &SkillName,
-) -> Result<(Target, Arc<[EffectNode]>, u32, Vec<SkillTag>)> {
-    let skill_type =
-        game_data
-            .skill_map
-            .get(skill_name)
-            .ok_or_else(|| UnitError::SkillNotFound {
-                skill_name: skill_name.clone(),
-            })?;
-    match skill_type {
-        SkillType::Active {
+) -> Result<ActiveSkill<'a>> {
+    match game_data.skill_map.get(skill_name) {
+        Some(SkillType::Active {
+            name: _,
+            tags,
+            cost,
+            requires,
+            hits,
+            recoil,
+            effect_order,
+            channel_turns,
             target,
             effects,
-            cost,
+        }) => Ok(ActiveSkill {
             tags,
-            name: _,
-        } => Ok((target.clone(), effects.clone(), *cost, tags.clone())),
-        SkillType::Reaction { .. } | SkillType::Passive { .. } => Err(UnitError::SkillNotFound {
+            cost: *cost,
+            requires,
+            hits: *hits,
+            recoil: *recoil,
+            effect_order: *effect_order,
+            channel_turns: *channel_turns,
+            target,
+            effects,
+        }),
+        Some(SkillType::Reaction { .. } | SkillType::Passive { .. }) | None => {
+            Err(UnitError::SkillNotFound {
+                skill_name: skill_name.clone(),
+            }
+            .into())
+        }
+    }
+}
+
+/// 檢查施放者是否擁有主動技能要求的所有前置技能
+pub(crate) fn check_skill_requirements(
+    game_data: &GameData,
+    skill_name: &SkillName,
+    owned_skills: &[SkillName],
+) -> Result<()> {
+    let requires = get_active_skill(game_data, skill_name)?.requires;
+    let missing: Vec<SkillName> = requires
+        .iter()
+        .filter(|required| !owned_skills.contains(required))
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(UnitError::MissingPrerequisite {
+            skill_name: skill_name.clone(),
+            missing,
+        }
+        .into())
+    }
+}
+
+/// 取得指定格上所有物件禁止施放的技能標籤（無限制時為空）
+pub(crate) fn forbidden_skill_tags_at(world: &mut World, pos: Position) -> Vec<SkillTag> {
+    world
+        .query_filtered::<(&Position, &ForbiddenSkillTags), With<Object>>()
+        .iter(world)
+        .filter(|(object_pos, _)| **object_pos == pos)
+        .flat_map(|(_, tags)| tags.0.iter().cloned())
+        .collect()
+}
+
+/// 檢查技能標籤是否被所在地形禁止，命中第一個被禁止的標籤即回錯誤
+pub(crate) fn check_terrain_skill_restriction(
+    skill_name: &SkillName,
+    skill_tags: &[SkillTag],
+    forbidden_tags: &[SkillTag],
+) -> Result<()> {
+    match skill_tags.iter().find(|tag| forbidden_tags.contains(tag)) {
+        Some(tag) => Err(UnitError::SkillForbiddenByTerrain {
             skill_name: skill_name.clone(),
+            tag: tag.clone(),
         }
         .into()),
+        None => Ok(()),
     }
 }
 
@@ -286,6 +369,165 @@ pub fn get_battle_log(world: &World) -> Result<&[LogEvent]> {
         .map(|battle_log| battle_log.0.as_slice())
 }
 
+/// 查詢單位可見的格子（視野範圍內且有視線），供戰爭迷霧渲染
+pub fn get_visible_positions(
+    world: &mut World,
+    occupant: Occupant,
+    sight_range: Coord,
+) -> Result<HashSet<Position>> {
+    let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
+    let entity = find_entity_by_occupant(world, occupant)?;
+    let viewer = *get_component!(world.entity(entity), Position)?;
+    let blocks_sight = build_blocks_sight(world);
+    let elevations = build_elevation_map(world);
+
+    Ok(compute_visible_positions(
+        viewer,
+        sight_range,
+        board,
+        &blocks_sight,
+        &elevations,
+    ))
+}
+
+/// 查詢陣營所有單位可見格子的聯集，供戰爭迷霧渲染
+pub fn get_faction_visibility(
+    world: &mut World,
+    faction_id: ID,
+    sight_range: Coord,
+) -> Result<HashSet<Position>> {
+    let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
+    let viewers: Vec<Position> = world
+        .query_filtered::<(&Position, &UnitFaction), With<Unit>>()
+        .iter(world)
+        .filter(|(_, faction)| faction.0 == faction_id)
+        .map(|(pos, _)| *pos)
+        .collect();
+    let blocks_sight = build_blocks_sight(world);
+    let elevations = build_elevation_map(world);
+
+    Ok(viewers
+        .into_iter()
+        .flat_map(|viewer| {
+            compute_visible_positions(viewer, sight_range, board, &blocks_sight, &elevations)
+        })
+        .collect())
+}
+
+/// 查詢各目標點目前由哪個陣營佔領：有單位停在目標點上即由該單位的陣營佔領，
+/// 無人站立的目標點不列入
+pub fn get_objective_holders(world: &mut World) -> HashMap<Position, ID> {
+    let objectives: HashSet<Position> = world
+        .query::<(&Position, &ObjectTrigger)>()
+        .iter(world)
+        .filter(|(_, trigger)| matches!(trigger.0, TileTrigger::Objective))
+        .map(|(pos, _)| *pos)
+        .collect();
+    world
+        .query_filtered::<(&Position, &UnitFaction), With<Unit>>()
+        .iter(world)
+        .filter(|(pos, _)| objectives.contains(pos))
+        .map(|(pos, faction)| (*pos, faction.0))
+        .collect()
+}
+
+/// 計算單位含 buff 與光環的實際屬性（供 UI 顯示）
+///
+/// 屬性 component 只在 buff 或光環變動時同步 HP/MP 上限，其餘屬性須由技能、身上 buff 與光環重算；
+/// 當前 HP/MP 與反應點為戰鬥中的狀態，直接取自單位現況。
+pub fn get_calculated_attributes(world: &mut World, occupant: Occupant) -> Result<AttributeBundle> {
+    // 讀取階段
+    let entity = find_entity_by_occupant(world, occupant)?;
+    let mut buff_query = world.query::<&AppliedBuff>();
+    let world_ref: &World = world;
+    let buffs: Vec<&BuffType> = buff_query
+        .iter(world_ref)
+        .filter(|buff| buff.target == occupant)
+        .map(|buff| buff.def.as_ref())
+        .collect();
+    let entity_ref = world_ref.entity(entity);
+    let skills = get_component!(entity_ref, Skills)?;
+    let current = read_attribute_bundle(&entity_ref)?;
+    let game_data = get_resource::<GameData>(world_ref, "請先呼叫 parse_and_insert_game_data")?;
+    let auras = build_aura_effects_map(world_ref)?
+        .remove(&occupant)
+        .unwrap_or_default();
+
+    // 邏輯階段
+    let effects = filter_continuous_effect(&skills.0, buffs, &game_data.skill_map)?.chain(auras);
+    Ok(AttributeBundle {
+        current_hp: current.current_hp,
+        current_mp: current.current_mp,
+        reaction_point: current.reaction_point,
+        ..calculate_attributes(effects)
+    })
+}
+
+/// 計算棋盤內容的穩定雜湊，供快取 key 與 golden test 使用
+///
+/// 涵蓋棋盤尺寸、陣營、物件與單位狀態，各集合依固定順序排序後雜湊，
+/// 與 `HashMap` 迭代順序及 entity 生成順序無關。
+/// 執行期隨機產生的 Occupant ID 不納入，單位與物件以位置識別。
+pub fn get_content_hash(world: &mut World) -> Result<u64> {
+    let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
+    let level_config = get_resource::<LevelConfig>(world, "請先呼叫 spawn_level")?;
+    let mut factions: Vec<(ID, ID, String)> = level_config
+        .factions
+        .values()
+        .map(|faction| (faction.id, faction.alliance, faction.name.clone()))
+        .collect();
+    factions.sort();
+
+    let mut objects: Vec<(Position, String, MovementCost, i32)> = world
+        .query_filtered::<(
+            &Position,
+            &OccupantTypeName,
+            &ObjectMovementCost,
+            &Elevation,
+        ), With<Object>>()
+        .iter(world)
+        .map(|(pos, type_name, movement_cost, elevation)| {
+            (*pos, type_name.0.clone(), movement_cost.0, elevation.0)
+        })
+        .collect();
+    objects.sort();
+
+    let unit_entities: Vec<Entity> = world
+        .query_filtered::<Entity, With<Unit>>()
+        .iter(world)
+        .collect();
+    let mut units = Vec::new();
+    for entity in unit_entities {
+        let entity_ref = world.entity(entity);
+        units.push((
+            *get_component!(entity_ref, Position)?,
+            get_component!(entity_ref, OccupantTypeName)?.0.clone(),
+            get_component!(entity_ref, UnitFaction)?.0,
+            get_component!(entity_ref, CurrentHp)?.0,
+            get_component!(entity_ref, MaxHp)?.0,
+            get_component!(entity_ref, CurrentMp)?.0,
+            get_component!(entity_ref, MaxMp)?.0,
+        ));
+    }
+    units.sort();
+
+    Ok(stable_hash(&(
+        (board.width, board.height),
+        factions,
+        objects,
+        units,
+    )))
+}
+
+/// 建構阻擋視線的格子集合
+pub(crate) fn build_blocks_sight(world: &mut World) -> HashSet<Position> {
+    world
+        .query_filtered::<&Position, With<BlocksSight>>()
+        .iter(world)
+        .copied()
+        .collect()
+}
+
 /// 建構棋盤上所有物件的位置對應表
 pub(crate) fn build_objects_on_board(world: &mut World) -> HashMap<Position, ObjectOnBoard> {
     world
@@ -303,6 +545,147 @@ pub(crate) fn build_objects_on_board(world: &mut World) -> HashMap<Position, Obj
         .collect()
 }
 
+/// 建構各格地形高度對應表（同格多個物件取最高者，無物件的格子不列出）
+pub(crate) fn build_elevation_map(world: &mut World) -> HashMap<Position, i32> {
+    let mut elevations: HashMap<Position, i32> = HashMap::new();
+    for (pos, elevation) in world
+        .query_filtered::<(&Position, &Elevation), With<Object>>()
+        .iter(world)
+    {
+        let entry = elevations.entry(*pos).or_insert(elevation.0);
+        *entry = (*entry).max(elevation.0);
+    }
+    elevations
+}
+
+/// 建構各格隱蔽機率對應表（同格多個物件取最高者，無隱蔽的格子不列出）
+pub(crate) fn build_concealment_map(world: &mut World) -> HashMap<Position, i32> {
+    let mut concealments: HashMap<Position, i32> = HashMap::new();
+    for (pos, concealment) in world
+        .query_filtered::<(&Position, &Concealment), With<Object>>()
+        .iter(world)
+    {
+        let entry = concealments.entry(*pos).or_insert(concealment.0);
+        *entry = (*entry).max(concealment.0);
+    }
+    concealments
+}
+
+/// 建構各單位身上 Buff 名稱的對應表（無 Buff 的單位不列出）
+pub(crate) fn build_buff_names_map(world: &mut World) -> HashMap<Occupant, Vec<String>> {
+    let mut buff_names: HashMap<Occupant, Vec<String>> = HashMap::new();
+    for applied in world.query::<&AppliedBuff>().iter(world) {
+        buff_names
+            .entry(applied.target)
+            .or_default()
+            .push(applied.def.name.clone());
+    }
+    buff_names
+}
+
+/// 建構各單位所受的光環效果對應表（來源與受影響者皆為存活單位，未受影響的單位不列出）
+///
+/// 光環依當下站位計算，單位移動或來源死亡後重新呼叫即反映新的範圍。
+pub(crate) fn build_aura_effects_map(
+    world: &World,
+) -> Result<HashMap<Occupant, Vec<&ContinuousEffect>>> {
+    let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
+    let faction_to_alliance = build_faction_alliance_map(world)?;
+    let mut unit_query = match world.try_query_filtered::<(
+        &Occupant,
+        &Position,
+        &UnitFaction,
+        &Skills,
+        &CurrentHp,
+    ), With<Unit>>() {
+        Some(query) => query,
+        None => return Ok(HashMap::new()),
+    };
+    let units = unit_query
+        .iter(world)
+        .filter(|(_, _, _, _, hp)| hp.0 > 0)
+        .map(|(occupant, pos, faction, skills, _)| {
+            Ok(AuraUnit {
+                position: *pos,
+                unit_info: UnitInfo {
+                    occupant: *occupant,
+                    faction_id: faction.0,
+                    alliance_id: resolve_alliance(&faction_to_alliance, faction.0)?,
+                },
+                skills: &skills.0,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    collect_aura_effects(&units, &game_data.skill_map)
+}
+
+/// 建構各單位的傷害屬性抗性對應表（被動技能、身上 buff 與光環的抗性加總，無抗性的單位不列出）
+pub(crate) fn build_resistance_map(
+    world: &mut World,
+) -> Result<HashMap<Occupant, HashMap<DamageType, i32>>> {
+    let mut buff_query = world.query::<&AppliedBuff>();
+    let mut unit_query = world.query_filtered::<(&Occupant, &Skills), With<Unit>>();
+    let world_ref: &World = world;
+    let game_data = get_resource::<GameData>(world_ref, "請先呼叫 parse_and_insert_game_data")?;
+    let mut buffs: HashMap<Occupant, Vec<&BuffType>> = HashMap::new();
+    for applied in buff_query.iter(world_ref) {
+        buffs
+            .entry(applied.target)
+            .or_default()
+            .push(applied.def.as_ref());
+    }
+
+    let mut auras = build_aura_effects_map(world_ref)?;
+
+    let mut result = HashMap::new();
+    for (occupant, skills) in unit_query.iter(world_ref) {
+        let effects = filter_continuous_effect(
+            &skills.0,
+            buffs.remove(occupant).unwrap_or_default(),
+            &game_data.skill_map,
+        )?
+        .chain(auras.remove(occupant).unwrap_or_default());
+        let resistances = calculate_resistances(effects);
+        if !resistances.is_empty() {
+            result.insert(*occupant, resistances);
+        }
+    }
+    Ok(result)
+}
+
+/// 建構各單位戰鬥結算用的屬性對應表（屬性 component 疊加身上 buff 與光環的修正）
+pub(crate) fn build_combat_attributes_map(
+    world: &mut World,
+) -> Result<HashMap<Occupant, AttributeBundle>> {
+    let mut buff_query = world.query::<&AppliedBuff>();
+    let mut unit_query = world.query_filtered::<(Entity, &Occupant, &Skills), With<Unit>>();
+    let world_ref: &World = world;
+    let game_data = get_resource::<GameData>(world_ref, "請先呼叫 parse_and_insert_game_data")?;
+    let mut buffs: HashMap<Occupant, Vec<&BuffType>> = HashMap::new();
+    for applied in buff_query.iter(world_ref) {
+        buffs
+            .entry(applied.target)
+            .or_default()
+            .push(applied.def.as_ref());
+    }
+
+    let mut auras = build_aura_effects_map(world_ref)?;
+
+    let mut result = HashMap::new();
+    for (entity, occupant, skills) in unit_query.iter(world_ref) {
+        let current = read_attribute_bundle(&world_ref.entity(entity))?;
+        let attributes = apply_attribute_modifiers(
+            current,
+            &skills.0,
+            buffs.remove(occupant).unwrap_or_default(),
+            auras.remove(occupant).unwrap_or_default(),
+            &game_data.skill_map,
+        )?;
+        result.insert(*occupant, attributes);
+    }
+    Ok(result)
+}
+
 /// 建構棋盤上所有單位的戰鬥屬性位置對應表
 pub(crate) fn build_unit_stats_on_board(
     world: &mut World,
@@ -312,13 +695,20 @@ pub(crate) fn build_unit_stats_on_board(
         .query_filtered::<Entity, With<Unit>>()
         .iter(world)
         .collect();
+    let elevations = build_elevation_map(world);
+    let concealments = build_concealment_map(world);
+    let mut buff_names = build_buff_names_map(world);
+    let mut resistances = build_resistance_map(world)?;
+    let mut combat_attributes = build_combat_attributes_map(world)?;
     let mut result = HashMap::new();
     for unit_entity in unit_entities {
         let entity_ref = world.entity(unit_entity);
         let pos = *get_component!(entity_ref, Position)?;
         let occupant = *get_component!(entity_ref, Occupant)?;
         let faction_id = get_component!(entity_ref, UnitFaction)?.0;
-        let attributes = read_attribute_bundle(&entity_ref)?;
+        let attributes = combat_attributes
+            .remove(&occupant)
+            .ok_or(BoardError::OccupantNotFound { occupant })?;
         let alliance_id = resolve_alliance(faction_to_alliance, faction_id)?;
         result.insert(
             pos,
@@ -329,6 +719,10 @@ pub(crate) fn build_unit_stats_on_board(
                     alliance_id,
                 },
                 attribute: attributes,
+                elevation: tile_elevation(&elevations, pos),
+                concealment: concealments.get(&pos).copied().unwrap_or(0),
+                buffs: buff_names.remove(&occupant).unwrap_or_default(),
+                resistances: resistances.remove(&occupant).unwrap_or_default(),
             },
         );
     }
@@ -2,19 +2,23 @@
 
 use super::{get_component, get_component_mut};
 use crate::domain::alias::{ID, SkillName};
-use crate::domain::core_types::PendingReaction;
+use crate::domain::core_types::{PendingReaction, ReactionTrigger};
 use crate::ecs_logic::query::{
-    build_faction_alliance_map, build_objects_on_board, build_unit_stats_on_board,
+    build_buff_names_map, build_concealment_map, build_elevation_map, build_faction_alliance_map,
+    build_objects_on_board, build_resistance_map, build_unit_stats_on_board,
     find_entity_by_occupant, get_reaction_skill_data, get_resource, get_resource_mut,
-    read_attribute_bundle, resolve_alliance,
+    resolve_alliance,
 };
-use crate::ecs_logic::skill::apply_effect_entries;
+use crate::ecs_logic::skill::{apply_effect_entries, check_action_point};
+use crate::ecs_logic::turn::get_current_unit;
 use crate::ecs_types::components::{
-    CurrentMp, Occupant, Position, ReactionPoint, Skills, Unit, UnitFaction,
+    ActionState, CurrentMp, MovementPoint, Occupant, Overwatch, Position, ReactionPoint, Skills,
+    Unit, UnitFaction,
 };
-use crate::ecs_types::resources::{Board, GameData, ReactionState};
+use crate::ecs_types::resources::{Board, GameData, ReactionState, TurnOrder};
 use crate::error::{DataError, ReactionError, Result, UnitError};
 use crate::logic::skill::UnitInfo;
+use crate::logic::skill::line_of_sight::tile_elevation;
 use crate::logic::skill::skill_execution::{CombatStats, EffectEntry, resolve_effect_tree};
 use crate::logic::skill::skill_reaction::{TakesDamageUnitInfo, collect_takes_damage_reactions};
 use bevy_ecs::prelude::{Entity, With, World};
@@ -89,6 +93,50 @@ pub fn set_reactions(world: &mut World, decisions: Vec<(Occupant, SkillName)>) -
     Ok(())
 }
 
+/// 當前單位架設警戒：指定一個 Overwatch 反應技能並結束本回合的行動
+///
+/// 警戒持續到觸發一次，或該單位下一個回合開始；重複架設會取代先前的技能。
+/// - 行動點不足時回傳 Err
+/// - 技能不在單位技能列表中，或不是 Overwatch 觸發的反應技能時回傳 Err
+pub fn set_overwatch(world: &mut World, skill_name: &SkillName) -> Result<()> {
+    // === 讀取階段 ===
+    let turn_order = get_resource::<TurnOrder>(world, "請先呼叫 start_new_round")?;
+    let occupant = get_current_unit(turn_order)?;
+    let entity = find_entity_by_occupant(world, occupant)?;
+    let (action_state, movement_point, skills) = {
+        let entity_ref = world.entity(entity);
+        let action_state = get_component!(entity_ref, ActionState)?.clone();
+        let movement_point = get_component!(entity_ref, MovementPoint)?.0;
+        let skills = get_component!(entity_ref, Skills)?.0.clone();
+        (action_state, movement_point, skills)
+    };
+
+    check_action_point(&action_state, movement_point)?;
+    if !skills.contains(skill_name) {
+        return Err(UnitError::SkillNotFound {
+            skill_name: skill_name.clone(),
+        }
+        .into());
+    }
+    let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
+    let (triggering, _, _, _) = get_reaction_skill_data(game_data, skill_name)?;
+    if !matches!(triggering.trigger, ReactionTrigger::Overwatch) {
+        return Err(UnitError::SkillNotFound {
+            skill_name: skill_name.clone(),
+        }
+        .into());
+    }
+
+    // === 寫入階段 ===
+    let mut entity_mut = world.entity_mut(entity);
+    {
+        let mut action_state = get_component_mut!(entity_mut, ActionState)?;
+        *action_state = ActionState::Done;
+    }
+    entity_mut.insert(Overwatch(skill_name.clone()));
+    Ok(())
+}
+
 /// 執行 queue 中的下一個反應
 pub fn process_reactions(world: &mut World) -> Result<ProcessReactionResult> {
     // === 讀取階段 ===
@@ -114,14 +162,13 @@ pub fn process_reactions(world: &mut World) -> Result<ProcessReactionResult> {
     let reactor_entity = find_entity_by_occupant(world, reactor)?;
     let trigger_entity = find_entity_by_occupant(world, trigger)?;
 
-    let (reactor_pos, reactor_faction, reactor_mp, reactor_reaction_point, reactor_attributes) = {
+    let (reactor_pos, reactor_faction, reactor_mp, reactor_reaction_point) = {
         let entity_ref = world.entity(reactor_entity);
         let pos = *get_component!(entity_ref, Position)?;
         let faction = get_component!(entity_ref, UnitFaction)?.0;
         let mp = get_component!(entity_ref, CurrentMp)?.0;
         let reaction_point = get_component!(entity_ref, ReactionPoint)?.0;
-        let attributes = read_attribute_bundle(&entity_ref)?;
-        (pos, faction, mp, reaction_point, attributes)
+        (pos, faction, mp, reaction_point)
     };
 
     let trigger_pos = *get_component!(world.entity(trigger_entity), Position)?;
@@ -130,11 +177,20 @@ pub fn process_reactions(world: &mut World) -> Result<ProcessReactionResult> {
     let reactor_alliance = resolve_alliance(&faction_to_alliance, reactor_faction)?;
 
     let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
-    let (_triggering, effects, cost, skill_tags) = get_reaction_skill_data(game_data, &skill_name)?;
+    let (triggering, effects, cost, skill_tags) = get_reaction_skill_data(game_data, &skill_name)?;
 
     let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
 
     let unit_stats_on_board = build_unit_stats_on_board(world, &faction_to_alliance)?;
+    // 反應者屬性與棋盤上的戰鬥屬性一致（含 buff 與光環修正）
+    let reactor_attributes = unit_stats_on_board
+        .get(&reactor_pos)
+        .map(|stats| stats.attribute.clone())
+        .ok_or(ReactionError::ReactorNotFound { occupant: reactor })?;
+    let elevations = build_elevation_map(world);
+    let concealments = build_concealment_map(world);
+    let buff_names = build_buff_names_map(world);
+    let resistances = build_resistance_map(world)?;
     let objects_on_board = build_objects_on_board(world);
 
     let unit_reaction_info: HashMap<Occupant, TakesDamageUnitInfo> = {
@@ -148,12 +204,14 @@ pub fn process_reactions(world: &mut World) -> Result<ProcessReactionResult> {
             let occupant = *get_component!(entity_ref, Occupant)?;
             let pos = *get_component!(entity_ref, Position)?;
             let remaining_reaction_point = get_component!(entity_ref, ReactionPoint)?.0;
+            let current_mp = get_component!(entity_ref, CurrentMp)?.0;
             let skill_names = get_component!(entity_ref, Skills)?.0.clone();
             map.insert(
                 occupant,
                 TakesDamageUnitInfo {
                     pos,
                     remaining_reaction_point,
+                    current_mp,
                     skill_names,
                 },
             );
@@ -192,6 +250,10 @@ pub fn process_reactions(world: &mut World) -> Result<ProcessReactionResult> {
             alliance_id: reactor_alliance,
         },
         attribute: reactor_attributes,
+        elevation: tile_elevation(&elevations, reactor_pos),
+        concealment: concealments.get(&reactor_pos).copied().unwrap_or(0),
+        buffs: buff_names.get(&reactor).cloned().unwrap_or_default(),
+        resistances: resistances.get(&reactor).cloned().unwrap_or_default(),
     };
 
     let mut rng = rand::rng();
@@ -238,6 +300,10 @@ pub fn process_reactions(world: &mut World) -> Result<ProcessReactionResult> {
             let mut reaction_point = get_component_mut!(entity_mut, ReactionPoint)?;
             reaction_point.0 -= 1;
         }
+        // 警戒觸發一次即解除
+        if matches!(triggering.trigger, ReactionTrigger::Overwatch) {
+            entity_mut.remove::<Overwatch>();
+        }
     }
 
     apply_effect_entries(world, &entries, &mut used_ids)?;
@@ -0,0 +1,241 @@
+//! 戰鬥存檔 ECS 操作函數
+//!
+//! 存檔只涵蓋戰鬥中會變動的執行期狀態（單位、buff、回合順序、目標點佔領進度），
+//! 棋盤尺寸、陣營與關卡物件等靜態資料由 `spawn_level` 重建，
+//! 因此讀檔前須先以同一份關卡 TOML 呼叫 `spawn_level`。
+//! 技能生成的物件（SpawnObject）目前不在存檔範圍內。
+
+use super::{get_component, get_component_mut};
+use crate::domain::alias::{ID, TypeName};
+use crate::domain::core_types::{BuffType, DefenseType};
+use crate::domain::turn::TurnEntry;
+use crate::ecs_logic::buff::refresh_max_hp_mp;
+use crate::ecs_logic::query::{find_entity_by_occupant, get_resource};
+use crate::ecs_logic::spawner::build_unit_bundle;
+use crate::ecs_types::components::{
+    ActionState, AppliedBuff, Channeling, CurrentHp, CurrentMp, Occupant, OccupantTypeName,
+    Overwatch, Position, ReactionPoint, Shield, Summoned, Unit, UnitFaction,
+};
+use crate::ecs_types::resources::{
+    CaptureProgress, GameData, MovementPlan, ReactionState, SkillTargeting, TurnOrder,
+};
+use crate::error::{DataError, LoadError, Result};
+use bevy_ecs::prelude::{Entity, With, World};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// 存檔格式名稱（用於錯誤訊息）
+const SAVE_FORMAT: &str = "battle save";
+
+/// 戰鬥存檔
+///
+/// `B` 為 buff 定義的持有方式：存檔時借用 `&BuffType`，讀檔時擁有 `BuffType`，
+/// 避免為了序列化複製技能資料。
+#[derive(Debug, Serialize, Deserialize)]
+struct BattleSave<B> {
+    round: u32,
+    current_index: usize,
+    turn_entries: Vec<TurnEntry>,
+    units: Vec<UnitSave>,
+    buffs: Vec<BuffSave<B>>,
+    /// 目標點佔領進度 (faction, 輪數)，依 faction 排序
+    #[serde(default)]
+    capture_progress: Vec<(ID, u32)>,
+}
+
+/// 單位的執行期狀態（屬性上限由單位模板與 buff 重算，不存檔）
+#[derive(Debug, Serialize, Deserialize)]
+struct UnitSave {
+    id: ID,
+    unit_type_name: TypeName,
+    faction_id: ID,
+    position: Position,
+    current_hp: i32,
+    current_mp: i32,
+    reaction_point: i32,
+    action_state: ActionState,
+    summoned_rounds: Option<u32>,
+    #[serde(default)]
+    shield: Option<Shield>,
+    #[serde(default)]
+    overwatch: Option<Overwatch>,
+    #[serde(default)]
+    channeling: Option<Channeling>,
+}
+
+/// 施加中 buff 的執行期狀態
+#[derive(Debug, Serialize, Deserialize)]
+struct BuffSave<B> {
+    def: B,
+    caster: Occupant,
+    target: Occupant,
+    remaining_duration: Option<u32>,
+    inherited_defense: Option<DefenseType>,
+}
+
+/// 將戰鬥中途狀態序列化為 TOML 字串
+///
+/// 應於行動之間呼叫：移動計畫、技能選目標、待決反應等進行中的暫存狀態不存檔。
+pub fn save_battle(world: &mut World) -> Result<String> {
+    // 讀取階段
+    let turn_order = get_resource::<TurnOrder>(world, "請先呼叫 start_new_round")?.clone();
+    let mut capture_progress: Vec<(ID, u32)> =
+        get_resource::<CaptureProgress>(world, "請先呼叫 spawn_level")?
+            .0
+            .iter()
+            .map(|(faction_id, rounds)| (*faction_id, *rounds))
+            .collect();
+    capture_progress.sort();
+
+    let unit_entities: Vec<Entity> = world
+        .query_filtered::<Entity, With<Unit>>()
+        .iter(world)
+        .collect();
+    let mut units = Vec::new();
+    for entity in unit_entities {
+        let entity_ref = world.entity(entity);
+        let id = match get_component!(entity_ref, Occupant)? {
+            Occupant::Unit(id) => *id,
+            Occupant::Object(id) => {
+                return Err(DataError::InternalError {
+                    message: format!("單位 entity 的 Occupant 為物件 {id}"),
+                }
+                .into());
+            }
+        };
+        units.push(UnitSave {
+            id,
+            unit_type_name: get_component!(entity_ref, OccupantTypeName)?.0.clone(),
+            faction_id: get_component!(entity_ref, UnitFaction)?.0,
+            position: *get_component!(entity_ref, Position)?,
+            current_hp: get_component!(entity_ref, CurrentHp)?.0,
+            current_mp: get_component!(entity_ref, CurrentMp)?.0,
+            reaction_point: get_component!(entity_ref, ReactionPoint)?.0,
+            action_state: get_component!(entity_ref, ActionState)?.clone(),
+            summoned_rounds: entity_ref
+                .get::<Summoned>()
+                .map(|summoned| summoned.remaining_rounds),
+            shield: entity_ref.get::<Shield>().copied(),
+            overwatch: entity_ref.get::<Overwatch>().cloned(),
+            channeling: entity_ref.get::<Channeling>().cloned(),
+        });
+    }
+    units.sort_by_key(|unit| unit.id);
+
+    let mut buff_query = world.query::<&AppliedBuff>();
+    let world_ref: &World = world;
+    let buffs: Vec<BuffSave<&BuffType>> = buff_query
+        .iter(world_ref)
+        .map(|buff| BuffSave {
+            def: buff.def.as_ref(),
+            caster: buff.caster,
+            target: buff.target,
+            remaining_duration: buff.remaining_duration,
+            inherited_defense: buff.inherited_defense,
+        })
+        .collect();
+
+    // 邏輯階段
+    let save = BattleSave {
+        round: turn_order.round,
+        current_index: turn_order.current_index,
+        turn_entries: turn_order.entries,
+        units,
+        buffs,
+        capture_progress,
+    };
+    toml::to_string_pretty(&save).map_err(|e| {
+        LoadError::SerializeError {
+            format: SAVE_FORMAT.to_string(),
+            reason: e.to_string(),
+        }
+        .into()
+    })
+}
+
+/// 由 TOML 存檔還原戰鬥中途狀態
+///
+/// 移除現有單位與 buff 後依存檔重建，並取代回合順序；進行中的暫存狀態一併清除。
+pub fn load_battle(world: &mut World, save_toml: &str) -> Result<()> {
+    // 讀取階段
+    let save: BattleSave<BuffType> =
+        toml::from_str(save_toml).map_err(|e| LoadError::DeserializeError {
+            format: SAVE_FORMAT.to_string(),
+            reason: e.to_string(),
+        })?;
+    let unit_bundles = {
+        let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
+        save.units
+            .iter()
+            .map(|unit| {
+                build_unit_bundle(
+                    game_data,
+                    &unit.unit_type_name,
+                    unit.id,
+                    unit.position,
+                    unit.faction_id,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    let mut stale_entities: Vec<Entity> = world
+        .query_filtered::<Entity, With<Unit>>()
+        .iter(world)
+        .collect();
+    stale_entities.extend(
+        world
+            .query_filtered::<Entity, With<AppliedBuff>>()
+            .iter(world),
+    );
+
+    // 寫入階段
+    for entity in stale_entities {
+        world.despawn(entity);
+    }
+    world.remove_resource::<MovementPlan>();
+    world.remove_resource::<SkillTargeting>();
+    world.remove_resource::<ReactionState>();
+
+    for (bundle, unit) in unit_bundles.into_iter().zip(&save.units) {
+        let mut entity_mut = world.spawn(bundle);
+        if let Some(remaining_rounds) = unit.summoned_rounds {
+            entity_mut.insert(Summoned { remaining_rounds });
+        }
+        if let Some(shield) = unit.shield {
+            entity_mut.insert(shield);
+        }
+        if let Some(overwatch) = &unit.overwatch {
+            entity_mut.insert(overwatch.clone());
+        }
+        if let Some(channeling) = &unit.channeling {
+            entity_mut.insert(channeling.clone());
+        }
+    }
+    for buff in save.buffs {
+        world.spawn(AppliedBuff {
+            def: Arc::new(buff.def),
+            caster: buff.caster,
+            target: buff.target,
+            remaining_duration: buff.remaining_duration,
+            inherited_defense: buff.inherited_defense,
+        });
+    }
+    // 先依 buff 重算上限，再覆寫當前值，避免重算時的差值調整影響存檔數值
+    for unit in save.units {
+        let occupant = Occupant::Unit(unit.id);
+        refresh_max_hp_mp(world, occupant)?;
+        let entity = find_entity_by_occupant(world, occupant)?;
+        let mut entity_mut = world.entity_mut(entity);
+        get_component_mut!(entity_mut, CurrentHp)?.0 = unit.current_hp;
+        get_component_mut!(entity_mut, CurrentMp)?.0 = unit.current_mp;
+        get_component_mut!(entity_mut, ReactionPoint)?.0 = unit.reaction_point;
+        *get_component_mut!(entity_mut, ActionState)? = unit.action_state;
+    }
+    world.insert_resource(TurnOrder {
+        round: save.round,
+        entries: save.turn_entries,
+        current_index: save.current_index,
+    });
+    world.insert_resource(CaptureProgress(save.capture_progress.into_iter().collect()));
+    Ok(())
+}
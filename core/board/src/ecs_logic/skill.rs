@@ -2,31 +2,39 @@
 
 use super::{get_component, get_component_mut};
 use crate::domain::alias::{ID, SkillName};
-use crate::domain::core_types::{HitCheckBreakdowns, SkillType, TargetSelection};
+use crate::domain::core_types::{
+    HitCheckBreakdowns, SkillTag, SkillType, TargetFilter, TargetSelection,
+};
+use crate::ecs_logic::buff::{apply_buff, dispel_buffs, refresh_aura_max_hp_mp};
 use crate::ecs_logic::query::{
-    build_faction_alliance_map, build_objects_on_board, build_unit_stats_on_board,
-    find_entity_by_occupant, get_active_skill_data, get_resource, get_resource_mut,
-    read_attribute_bundle, resolve_alliance,
+    build_blocks_sight, build_buff_names_map, build_concealment_map, build_elevation_map,
+    build_faction_alliance_map, build_objects_on_board, build_resistance_map,
+    build_unit_stats_on_board, check_skill_requirements, check_terrain_skill_restriction,
+    find_entity_by_occupant, forbidden_skill_tags_at, get_active_skill, get_resource,
+    get_resource_mut, resolve_alliance,
 };
+use crate::ecs_logic::spawner::build_unit_bundle;
 use crate::ecs_logic::turn::get_current_unit;
 use crate::ecs_types::components::{
-    ActionState, BlocksSight, ContactEffects, CurrentHp, CurrentMp, MaxHp, MovementPoint, Object,
-    ObjectBundle, ObjectMovementCost, Occupant, OccupantTypeName, Position, Skills, Unit,
-    UnitFaction,
+    ActionState, Channeling, ContactEffects, CurrentHp, CurrentMp, Elevation, MaxHp, MovementPoint,
+    Object, ObjectBundle, ObjectMovementCost, Occupant, OccupantTypeName, Position, Shield, Skills,
+    Summoned, Unit, UnitFaction,
 };
 use crate::ecs_types::resources::{Board, GameData, SkillTargeting, TurnOrder};
-use crate::error::{BoardError, Result, UnitError};
+use crate::error::{BoardError, DataError, Result, UnitError};
 use crate::logic::id_generator::generate_unique_id;
-use crate::logic::skill::line_of_sight::has_line_of_sight;
+use crate::logic::movement::{find_free_adjacent, pull_destination};
+use crate::logic::skill::line_of_sight::{has_elevated_line_of_sight, tile_elevation};
 use crate::logic::skill::skill_check::{HitProbabilities, hit_probabilities};
 use crate::logic::skill::skill_execution::{
-    CheckTarget, CombatStats, EffectEntry, ResolvedEffect, preview_first_branch_accuracy,
-    resolve_effect_tree,
+    CheckTarget, CombatStats, EffectEntry, ResolvedEffect, absorb_with_shield,
+    drop_movement_entries, find_buff_def, order_effect_nodes, preview_first_branch_accuracy,
+    recoil_entry, resolve_effect_tree, resolve_hits,
 };
 use crate::logic::skill::skill_range::{compute_affected_positions, compute_range_positions};
 use crate::logic::skill::skill_target::{validate_filter, validate_skill_targets};
-use crate::logic::skill::{CasterInfo, UnitInfo, is_in_filter, manhattan_distance};
-use bevy_ecs::prelude::{With, World};
+use crate::logic::skill::{CasterInfo, UnitInfo, is_in_filter, metric_distance};
+use bevy_ecs::prelude::{Entity, With, World};
 use rand::RngExt;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -57,8 +65,12 @@ pub fn get_available_skills(world: &mut World) -> Result<Vec<AvailableSkill>> {
     let turn_order = get_resource::<TurnOrder>(world, "請先呼叫 start_new_round")?;
     let active_occupant = get_current_unit(turn_order)?;
 
-    // 讀取：當前單位的 Skills、CurrentMp、ActionState、MovementPoint
+    // 讀取：當前單位所在格的地形技能限制
     let entity = find_entity_by_occupant(world, active_occupant)?;
+    let position = *get_component!(world.entity(entity), Position)?;
+    let forbidden_tags = forbidden_skill_tags_at(world, position);
+
+    // 讀取：當前單位的 Skills、CurrentMp、ActionState、MovementPoint
     let entity_ref = world.entity(entity);
     let skills = get_component!(entity_ref, Skills)?;
     let current_mp = get_component!(entity_ref, CurrentMp)?.0;
@@ -84,8 +96,13 @@ pub fn get_available_skills(world: &mut World) -> Result<Vec<AvailableSkill>> {
                     skill_name: skill_name.clone(),
                 })?;
         match skill_type {
-            SkillType::Active { name, cost, .. } => {
-                let usable = can_act && current_mp >= *cost as i32;
+            SkillType::Active {
+                name, cost, tags, ..
+            } => {
+                let usable = can_act
+                    && current_mp >= *cost as i32
+                    && check_skill_requirements(game_data, name, &skills.0).is_ok()
+                    && check_terrain_skill_restriction(name, tags, &forbidden_tags).is_ok();
                 result.push(AvailableSkill {
                     name: name.clone(),
                     cost: *cost,
@@ -123,21 +140,19 @@ pub fn get_skill_targetable_positions(
     let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
 
     // 讀取：技能 range
-    let (target, _, _, _) = get_active_skill_data(game_data, skill_name)?;
+    let target = get_active_skill(game_data, skill_name)?.target;
     let range = target.range;
+    let range_metric = target.range_metric;
 
     // 讀取：視線阻擋格子集合
-    let blocks_sight: HashSet<Position> = world
-        .query_filtered::<&Position, With<BlocksSight>>()
-        .iter(world)
-        .copied()
-        .collect();
+    let blocks_sight = build_blocks_sight(world);
+    let elevations = build_elevation_map(world);
 
     // 純邏輯：計算射程內格子，並過濾無視線的格子
-    let range_positions = compute_range_positions(caster_pos, range, board);
+    let range_positions = compute_range_positions(caster_pos, range, range_metric, board);
     Ok(range_positions
         .into_iter()
-        .filter(|pos| has_line_of_sight(caster_pos, *pos, &blocks_sight))
+        .filter(|pos| has_elevated_line_of_sight(caster_pos, *pos, &blocks_sight, &elevations))
         .collect::<Vec<_>>())
 }
 
@@ -162,10 +177,13 @@ pub fn get_skill_affected_positions(
     // 讀取
     let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
 
-    let target = {
+    let (target, spares_allies) = {
         let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
-        let (target, _, _, _) = get_active_skill_data(game_data, skill_name)?;
-        target.clone()
+        let skill = get_active_skill(game_data, skill_name)?;
+        (
+            skill.target.clone(),
+            skill.tags.contains(&SkillTag::NoFriendlyFire),
+        )
     };
 
     let faction_to_alliance = build_faction_alliance_map(world)?;
@@ -208,21 +226,30 @@ pub fn get_skill_affected_positions(
         }
         units_on_board
     };
+    let objects_on_board = build_objects_on_board(world);
 
     // ============================================================================
 
     // 計算 AOE 範圍
     let (min_range, max_range) = target.range;
-    let distance = manhattan_distance(caster_pos, target_pos);
+    let distance = metric_distance(target.range_metric, caster_pos, target_pos);
     if distance < min_range || distance > max_range {
         return Err(BoardError::OutOfRange {
+            x: target_pos.x,
+            y: target_pos.y,
             distance,
             min_range,
             max_range,
         }
         .into());
     }
-    let all_positions = compute_affected_positions(&target.area, caster_pos, target_pos, board)?;
+    let all_positions =
+        compute_affected_positions(&target.area, caster_pos, target_pos, board, |pos| {
+            units_on_board.contains_key(&pos)
+                || objects_on_board
+                    .get(&pos)
+                    .is_some_and(|object| object.occupies_tile)
+        })?;
 
     let can_target_ground = matches!(target.selection, TargetSelection::Ground);
     let filter = target.selectable_filter;
@@ -230,7 +257,10 @@ pub fn get_skill_affected_positions(
         .iter()
         .filter(|pos| match units_on_board.get(pos) {
             None => can_target_ground,
-            Some(unit) => is_in_filter(&caster_info, unit, filter),
+            Some(unit) => {
+                is_in_filter(&caster_info, unit, filter)
+                    && !(spares_allies && is_in_filter(&caster_info, unit, TargetFilter::Ally))
+            }
         })
         .copied()
         .collect();
@@ -247,10 +277,15 @@ pub fn start_skill_targeting(world: &mut World, skill_name: &SkillName) -> Resul
     let turn_order = get_resource::<TurnOrder>(world, "請先呼叫 start_new_round")?;
     let active_occupant = get_current_unit(turn_order)?;
 
-    // 讀取：當前單位的 Skills、CurrentMp、ActionState、MovementPoint
+    // 讀取：當前單位所在格的地形技能限制
     let entity = find_entity_by_occupant(world, active_occupant)?;
+    let position = *get_component!(world.entity(entity), Position)?;
+    let forbidden_tags = forbidden_skill_tags_at(world, position);
+
+    // 讀取：當前單位的 Skills、CurrentMp、ActionState、MovementPoint
     let entity_ref = world.entity(entity);
-    let has_skill = get_component!(entity_ref, Skills)?.0.contains(skill_name);
+    let owned_skills = &get_component!(entity_ref, Skills)?.0;
+    let has_skill = owned_skills.contains(skill_name);
     let current_mp = get_component!(entity_ref, CurrentMp)?.0;
     let action_state = get_component!(entity_ref, ActionState)?.clone();
     let movement_point = get_component!(entity_ref, MovementPoint)?.0;
@@ -264,8 +299,10 @@ pub fn start_skill_targeting(world: &mut World, skill_name: &SkillName) -> Resul
             }
             .into());
         }
-        let (target, _, cost, _) = get_active_skill_data(game_data, skill_name)?;
-        (cost, target.count)
+        let skill = get_active_skill(game_data, skill_name)?;
+        check_skill_requirements(game_data, skill_name, owned_skills)?;
+        check_terrain_skill_restriction(skill_name, skill.tags, &forbidden_tags)?;
+        (skill.cost, skill.target.count)
     };
 
     // 檢查行動點與 MP
@@ -299,14 +336,15 @@ pub fn add_skill_target(world: &mut World, pos: Position) -> Result<()> {
 
     let targetable = get_skill_targetable_positions(world, &skill_name)?;
 
-    let (count, allow_same_target, min_range, max_range, selection, filter) = {
+    let (count, allow_same_target, min_range, max_range, range_metric, selection, filter) = {
         let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
-        let (target, _, _, _) = get_active_skill_data(game_data, &skill_name)?;
+        let target = get_active_skill(game_data, &skill_name)?.target;
         (
             target.count,
             target.allow_same_target,
             target.range.0,
             target.range.1,
+            target.range_metric,
             target.selection.clone(),
             target.selectable_filter,
         )
@@ -349,8 +387,10 @@ pub fn add_skill_target(world: &mut World, pos: Position) -> Result<()> {
     // ========================================================================
 
     if !targetable.contains(&pos) {
-        let distance = manhattan_distance(caster_pos, pos);
+        let distance = metric_distance(range_metric, caster_pos, pos);
         return Err(BoardError::OutOfRange {
+            x: pos.x,
+            y: pos.y,
             distance,
             min_range,
             max_range,
@@ -413,13 +453,12 @@ pub fn preview_skill_effect(
     let faction_to_alliance = build_faction_alliance_map(world)?;
 
     let caster_entity = find_entity_by_occupant(world, active_occupant)?;
-    let (caster_pos, caster_occupant, caster_faction, caster_attributes) = {
+    let (caster_pos, caster_occupant, caster_faction) = {
         let entity_ref = world.entity(caster_entity);
         let pos = *get_component!(entity_ref, Position)?;
         let occupant = *get_component!(entity_ref, Occupant)?;
         let faction = get_component!(entity_ref, UnitFaction)?.0;
-        let attributes = read_attribute_bundle(&entity_ref)?;
-        (pos, occupant, faction, attributes)
+        (pos, occupant, faction)
     };
 
     let caster_alliance = resolve_alliance(&faction_to_alliance, caster_faction)?;
@@ -429,12 +468,30 @@ pub fn preview_skill_effect(
         alliance_id: caster_alliance,
     };
 
-    let (_, effects, _, skill_tags) = {
+    let (effects, skill_tags, hits, recoil, effect_order) = {
         let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
-        get_active_skill_data(game_data, skill_name)?
+        let skill = get_active_skill(game_data, skill_name)?;
+        (
+            skill.effects.clone(),
+            skill.tags.to_vec(),
+            skill.hits,
+            skill.recoil,
+            skill.effect_order,
+        )
     };
 
     let unit_stats_on_board = build_unit_stats_on_board(world, &faction_to_alliance)?;
+    // 施放者屬性與棋盤上的戰鬥屬性一致（含 buff 與光環修正）
+    let caster_attributes = unit_stats_on_board
+        .get(&caster_pos)
+        .map(|stats| stats.attribute.clone())
+        .ok_or(BoardError::OccupantNotFound {
+            occupant: caster_occupant,
+        })?;
+    let elevations = build_elevation_map(world);
+    let concealments = build_concealment_map(world);
+    let buff_names = build_buff_names_map(world);
+    let resistances = build_resistance_map(world)?;
     let objects_on_board = build_objects_on_board(world);
 
     // ========================================================================
@@ -444,6 +501,16 @@ pub fn preview_skill_effect(
     let caster_stats = CombatStats {
         unit_info: caster_info,
         attribute: caster_attributes,
+        elevation: tile_elevation(&elevations, caster_pos),
+        concealment: concealments.get(&caster_pos).copied().unwrap_or(0),
+        buffs: buff_names
+            .get(&caster_occupant)
+            .cloned()
+            .unwrap_or_default(),
+        resistances: resistances
+            .get(&caster_occupant)
+            .cloned()
+            .unwrap_or_default(),
     };
 
     let caster_id = match caster_occupant {
@@ -451,25 +518,36 @@ pub fn preview_skill_effect(
         Occupant::Object(_) => return Err(BoardError::NoActiveUnit.into()),
     };
 
+    // 條目順序與 execute_skill 一致（依 effect_order 排列頂層節點）；
+    // 預覽不寫入 World，各節點共用同一快照
+    let ordered_nodes = order_effect_nodes(&effects, effect_order);
     let mut rng = rand::rng();
     let mut all_entries = Vec::new();
     for target_pos in target_positions {
-        let entries = resolve_effect_tree(
-            caster_id,
-            skill_name,
-            &skill_tags,
-            &effects,
-            &caster_stats,
-            caster_pos,
-            *target_pos,
-            &unit_stats_on_board,
-            &objects_on_board,
-            board,
-            &mut || rng.random_range(1..=100),
-            true,
-        )?;
+        let entries = resolve_hits(hits, || {
+            let mut hit_entries = Vec::new();
+            for node in &ordered_nodes {
+                hit_entries.extend(resolve_effect_tree(
+                    caster_id,
+                    skill_name,
+                    &skill_tags,
+                    std::slice::from_ref(*node),
+                    &caster_stats,
+                    caster_pos,
+                    *target_pos,
+                    &unit_stats_on_board,
+                    &objects_on_board,
+                    board,
+                    &mut || rng.random_range(1..=100),
+                    true,
+                )?);
+            }
+            Ok(hit_entries)
+        })?;
         all_entries.extend(entries);
     }
+    // 反噬排在主要效果之後套用
+    all_entries.extend(recoil_entry(caster_id, skill_name, recoil));
 
     Ok(all_entries)
 }
@@ -502,13 +580,12 @@ pub fn preview_hit_probabilities(
     let faction_to_alliance = build_faction_alliance_map(world)?;
 
     let caster_entity = find_entity_by_occupant(world, active_occupant)?;
-    let (caster_pos, caster_occupant, caster_faction, caster_attributes) = {
+    let (caster_pos, caster_occupant, caster_faction) = {
         let entity_ref = world.entity(caster_entity);
         let pos = *get_component!(entity_ref, Position)?;
         let occupant = *get_component!(entity_ref, Occupant)?;
         let faction = get_component!(entity_ref, UnitFaction)?.0;
-        let attributes = read_attribute_bundle(&entity_ref)?;
-        (pos, occupant, faction, attributes)
+        (pos, occupant, faction)
     };
 
     let caster_alliance = resolve_alliance(&faction_to_alliance, caster_faction)?;
@@ -518,12 +595,24 @@ pub fn preview_hit_probabilities(
         alliance_id: caster_alliance,
     };
 
-    let (_, effects, _, skill_tags) = {
+    let (effects, skill_tags) = {
         let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
-        get_active_skill_data(game_data, skill_name)?
+        let skill = get_active_skill(game_data, skill_name)?;
+        (skill.effects.clone(), skill.tags.to_vec())
     };
 
     let unit_stats_on_board = build_unit_stats_on_board(world, &faction_to_alliance)?;
+    // 施放者屬性與棋盤上的戰鬥屬性一致（含 buff 與光環修正）
+    let caster_attributes = unit_stats_on_board
+        .get(&caster_pos)
+        .map(|stats| stats.attribute.clone())
+        .ok_or(BoardError::OccupantNotFound {
+            occupant: caster_occupant,
+        })?;
+    let elevations = build_elevation_map(world);
+    let concealments = build_concealment_map(world);
+    let buff_names = build_buff_names_map(world);
+    let resistances = build_resistance_map(world)?;
 
     // ========================================================================
     // 純邏輯階段（不寫入 World）
@@ -532,6 +621,16 @@ pub fn preview_hit_probabilities(
     let caster_stats = CombatStats {
         unit_info: caster_info,
         attribute: caster_attributes,
+        elevation: tile_elevation(&elevations, caster_pos),
+        concealment: concealments.get(&caster_pos).copied().unwrap_or(0),
+        buffs: buff_names
+            .get(&caster_occupant)
+            .cloned()
+            .unwrap_or_default(),
+        resistances: resistances
+            .get(&caster_occupant)
+            .cloned()
+            .unwrap_or_default(),
     };
 
     let breakdowns = preview_first_branch_accuracy(
@@ -576,7 +675,7 @@ pub fn execute_skill(
         caster_mp,
         caster_action_state,
         caster_movement_point,
-        caster_attributes,
+        caster_skills,
     ) = {
         let entity_ref = world.entity(caster_entity);
         let pos = *get_component!(entity_ref, Position)?;
@@ -585,7 +684,7 @@ pub fn execute_skill(
         let mp = get_component!(entity_ref, CurrentMp)?.0;
         let action_state = get_component!(entity_ref, ActionState)?.clone();
         let movement_point = get_component!(entity_ref, MovementPoint)?.0;
-        let attributes = read_attribute_bundle(&entity_ref)?;
+        let skills = get_component!(entity_ref, Skills)?.0.clone();
         (
             pos,
             occupant,
@@ -593,11 +692,12 @@ pub fn execute_skill(
             mp,
             action_state,
             movement_point,
-            attributes,
+            skills,
         )
     };
 
     check_action_point(&caster_action_state, caster_movement_point)?;
+    let forbidden_tags = forbidden_skill_tags_at(world, caster_pos);
 
     let caster_alliance = resolve_alliance(&faction_to_alliance, caster_faction)?;
     let caster_info = UnitInfo {
@@ -606,10 +706,18 @@ pub fn execute_skill(
         alliance_id: caster_alliance,
     };
 
-    let (target, effects, cost, skill_tags) = {
+    let (target, cost, skill_tags, channel_turns) = {
         let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
-        get_active_skill_data(game_data, skill_name)?
+        check_skill_requirements(game_data, skill_name, &caster_skills)?;
+        let skill = get_active_skill(game_data, skill_name)?;
+        (
+            skill.target.clone(),
+            skill.cost,
+            skill.tags.to_vec(),
+            skill.channel_turns,
+        )
     };
+    check_terrain_skill_restriction(skill_name, &skill_tags, &forbidden_tags)?;
 
     if caster_mp < cost as i32 {
         return Err(UnitError::InsufficientMp {
@@ -620,21 +728,9 @@ pub fn execute_skill(
     }
 
     let unit_stats_on_board = build_unit_stats_on_board(world, &faction_to_alliance)?;
-    let objects_on_board = build_objects_on_board(world);
-
-    let blocks_sight: HashSet<Position> = world
-        .query_filtered::<&Position, With<BlocksSight>>()
-        .iter(world)
-        .copied()
-        .collect();
+    let elevations = build_elevation_map(world);
 
-    let mut used_ids: HashSet<ID> = world
-        .query::<&Occupant>()
-        .iter(world)
-        .map(|occ| match occ {
-            Occupant::Unit(id) | Occupant::Object(id) => *id,
-        })
-        .collect();
+    let blocks_sight = build_blocks_sight(world);
 
     // ========================================================================
     // 純邏輯階段
@@ -656,7 +752,7 @@ pub fn execute_skill(
     )?;
 
     for target_pos in target_positions {
-        if !has_line_of_sight(caster_pos, *target_pos, &blocks_sight) {
+        if !has_elevated_line_of_sight(caster_pos, *target_pos, &blocks_sight, &elevations) {
             return Err(BoardError::NoLineOfSight {
                 x: target_pos.x,
                 y: target_pos.y,
@@ -665,34 +761,8 @@ pub fn execute_skill(
         }
     }
 
-    let caster_stats = CombatStats {
-        unit_info: caster_info,
-        attribute: caster_attributes,
-    };
-
-    let caster_id = match caster_occupant {
-        Occupant::Unit(id) => id,
-        Occupant::Object(_) => return Err(BoardError::NoActiveUnit.into()),
-    };
-
-    let mut rng = rand::rng();
-    let mut all_entries = Vec::new();
-    for target_pos in target_positions {
-        let entries = resolve_effect_tree(
-            caster_id,
-            skill_name,
-            &skill_tags,
-            &effects,
-            &caster_stats,
-            caster_pos,
-            *target_pos,
-            &unit_stats_on_board,
-            &objects_on_board,
-            board,
-            &mut || rng.random_range(1..=100),
-            false,
-        )?;
-        all_entries.extend(entries);
+    if let Occupant::Object(_) = caster_occupant {
+        return Err(BoardError::NoActiveUnit.into());
     }
 
     // ========================================================================
@@ -711,7 +781,123 @@ pub fn execute_skill(
         }
     }
 
-    apply_effect_entries(world, &all_entries, &mut used_ids)?;
+    // 引導技能：鎖定目標後等待之後的回合開始才結算
+    if channel_turns > 0 {
+        world.entity_mut(caster_entity).insert(Channeling {
+            skill_name: skill_name.clone(),
+            targets: target_positions.to_vec(),
+            remaining_turns: channel_turns,
+        });
+        return Ok(Vec::new());
+    }
+
+    resolve_skill_effects(world, caster_entity, skill_name, target_positions)
+}
+
+/// 結算主動技能效果並寫入 World（不檢查、不扣除施放成本），回傳效果條目
+///
+/// 由 `execute_skill` 立即結算，或由引導完成時（回合開始）延後結算共用。
+pub(crate) fn resolve_skill_effects(
+    world: &mut World,
+    caster_entity: Entity,
+    skill_name: &SkillName,
+    target_positions: &[Position],
+) -> Result<Vec<EffectEntry>> {
+    let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
+    let faction_to_alliance = build_faction_alliance_map(world)?;
+    let caster_id = match *get_component!(world.entity(caster_entity), Occupant)? {
+        Occupant::Unit(id) => id,
+        Occupant::Object(_) => return Err(BoardError::NoActiveUnit.into()),
+    };
+    let (effects, skill_tags, hits, recoil, effect_order, targets_units) = {
+        let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
+        let skill = get_active_skill(game_data, skill_name)?;
+        (
+            skill.effects.clone(),
+            skill.tags.to_vec(),
+            skill.hits,
+            skill.recoil,
+            skill.effect_order,
+            matches!(skill.target.selection, TargetSelection::Unit),
+        )
+    };
+    let mut used_ids: HashSet<ID> = world
+        .query::<&Occupant>()
+        .iter(world)
+        .map(|occ| match occ {
+            Occupant::Unit(id) | Occupant::Object(id) => *id,
+        })
+        .collect();
+
+    // 選單位的技能鎖定施放時站在目標格上的單位，目標被位移後後續節點跟著打到新位置
+    let mut target_positions = target_positions.to_vec();
+    let locked_targets: Vec<Option<Entity>> = {
+        let mut unit_query = world.query_filtered::<(Entity, &Position), With<Unit>>();
+        target_positions
+            .iter()
+            .map(|target_pos| {
+                unit_query
+                    .iter(world)
+                    .find(|(_, pos)| targets_units && *pos == target_pos)
+                    .map(|(entity, _)| entity)
+            })
+            .collect()
+    };
+
+    // 頂層節點逐一「解析 → 寫入」：每個節點以當下站位重新讀取快照，
+    // 後面的節點因此看到前面位移效果造成的新位置
+    let ordered_nodes = order_effect_nodes(&effects, effect_order);
+    let mut rng = rand::rng();
+    let mut all_entries = Vec::new();
+    for hit in 0..hits {
+        for node in &ordered_nodes {
+            for (target_pos, locked) in target_positions.iter_mut().zip(&locked_targets) {
+                let current = locked
+                    .and_then(|entity| world.get_entity(entity).ok())
+                    .and_then(|entity_ref| entity_ref.get::<Position>().copied());
+                if let Some(pos) = current {
+                    *target_pos = pos;
+                }
+            }
+            let unit_stats_on_board = build_unit_stats_on_board(world, &faction_to_alliance)?;
+            let objects_on_board = build_objects_on_board(world);
+            let caster_pos = *get_component!(world.entity(caster_entity), Position)?;
+            let caster_stats = unit_stats_on_board
+                .get(&caster_pos)
+                .cloned()
+                .ok_or(BoardError::NoActiveUnit)?;
+
+            let mut stage_entries = Vec::new();
+            for target_pos in &target_positions {
+                stage_entries.extend(resolve_effect_tree(
+                    caster_id,
+                    skill_name,
+                    &skill_tags,
+                    std::slice::from_ref(*node),
+                    &caster_stats,
+                    caster_pos,
+                    *target_pos,
+                    &unit_stats_on_board,
+                    &objects_on_board,
+                    board,
+                    &mut || rng.random_range(1..=100),
+                    false,
+                )?);
+            }
+            if hit > 0 {
+                drop_movement_entries(&mut stage_entries);
+            }
+            apply_effect_entries(world, &stage_entries, &mut used_ids)?;
+            all_entries.extend(stage_entries);
+        }
+    }
+
+    // 反噬排在主要效果之後套用
+    let recoil_entries: Vec<EffectEntry> = recoil_entry(caster_id, skill_name, recoil)
+        .into_iter()
+        .collect();
+    apply_effect_entries(world, &recoil_entries, &mut used_ids)?;
+    all_entries.extend(recoil_entries);
 
     Ok(all_entries)
 }
@@ -729,10 +915,7 @@ pub(crate) fn apply_effect_entries(
                     CheckTarget::Unit(id) => find_entity_by_occupant(world, Occupant::Unit(id))?,
                     CheckTarget::Position(_) => unreachable!("HpChange 不應該有 Position 目標"),
                 };
-                let mut entity_mut = world.entity_mut(entity);
-                let max_hp = get_component!(entity_mut, MaxHp)?.0;
-                let mut hp = get_component_mut!(entity_mut, CurrentHp)?;
-                hp.0 = (hp.0 + final_amount).min(max_hp);
+                apply_hp_change(world, entity, *final_amount)?;
             }
             ResolvedEffect::SpawnObject { object_type } => {
                 let pos = match entry.target {
@@ -748,17 +931,177 @@ pub(crate) fn apply_effect_entries(
                     occupant_type_name: OccupantTypeName(object_type.clone()),
                     terrain_movement_cost: ObjectMovementCost(0),
                     contact_effects: ContactEffects(Arc::from([])),
+                    elevation: Elevation(0),
                 });
             }
-            // TODO 其他效果類型的寫入邏輯
-            ResolvedEffect::ApplyBuff(_) | ResolvedEffect::NoEffect => {}
+            ResolvedEffect::Dispel { beneficial } => {
+                let occupant = match entry.target {
+                    CheckTarget::Unit(id) => Occupant::Unit(id),
+                    CheckTarget::Position(_) => unreachable!("Dispel 不應該有 Position 目標"),
+                };
+                dispel_buffs(world, occupant, *beneficial)?;
+            }
+            ResolvedEffect::Teleport { to } => {
+                let entity = match entry.target {
+                    CheckTarget::Unit(id) => find_entity_by_occupant(world, Occupant::Unit(id))?,
+                    CheckTarget::Position(_) => unreachable!("Teleport 不應該有 Position 目標"),
+                };
+                let mut entity_mut = world.entity_mut(entity);
+                let mut pos = get_component_mut!(entity_mut, Position)?;
+                *pos = *to;
+            }
+            ResolvedEffect::Pull { distance } => {
+                let target_entity = match entry.target {
+                    CheckTarget::Unit(id) => find_entity_by_occupant(world, Occupant::Unit(id))?,
+                    CheckTarget::Position(_) => unreachable!("Pull 不應該有 Position 目標"),
+                };
+                let caster_entity = find_entity_by_occupant(world, Occupant::Unit(entry.caster))?;
+                let caster_pos = *get_component!(world.entity(caster_entity), Position)?;
+                let target_pos = *get_component!(world.entity(target_entity), Position)?;
+                let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
+                let occupied = collect_occupied_positions(world);
+                let destination =
+                    pull_destination(board, target_pos, caster_pos, *distance, |pos| {
+                        occupied.contains(&pos)
+                    });
+                let mut entity_mut = world.entity_mut(target_entity);
+                let mut pos = get_component_mut!(entity_mut, Position)?;
+                *pos = destination;
+            }
+            ResolvedEffect::Summon {
+                unit_type,
+                duration,
+            } => {
+                let caster_entity = match entry.target {
+                    CheckTarget::Unit(id) => find_entity_by_occupant(world, Occupant::Unit(id))?,
+                    CheckTarget::Position(_) => unreachable!("Summon 不應該有 Position 目標"),
+                };
+                let (caster_pos, faction_id) = {
+                    let entity_ref = world.entity(caster_entity);
+                    (
+                        *get_component!(entity_ref, Position)?,
+                        get_component!(entity_ref, UnitFaction)?.0,
+                    )
+                };
+                let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
+                let occupied = collect_occupied_positions(world);
+                // 四周皆無空格時召喚失敗，不視為錯誤
+                let pos = match find_free_adjacent(board, caster_pos, &occupied) {
+                    Some(pos) => pos,
+                    None => continue,
+                };
+                let id = generate_unique_id(used_ids)?;
+                let bundle = {
+                    let game_data =
+                        get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
+                    build_unit_bundle(game_data, unit_type, id, pos, faction_id)?
+                };
+                world.spawn((
+                    bundle,
+                    Summoned {
+                        remaining_rounds: *duration,
+                    },
+                ));
+            }
+            ResolvedEffect::Shield { amount, duration } => {
+                let entity = match entry.target {
+                    CheckTarget::Unit(id) => find_entity_by_occupant(world, Occupant::Unit(id))?,
+                    CheckTarget::Position(_) => unreachable!("Shield 不應該有 Position 目標"),
+                };
+                world.entity_mut(entity).insert(Shield {
+                    amount: *amount,
+                    remaining_rounds: *duration,
+                });
+            }
+            ResolvedEffect::ApplyBuff(buff_name) => {
+                let target = match entry.target {
+                    CheckTarget::Unit(id) => Occupant::Unit(id),
+                    CheckTarget::Position(_) => unreachable!("ApplyBuff 不應該有 Position 目標"),
+                };
+                let def = {
+                    let game_data =
+                        get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
+                    let nodes = match game_data.skill_map.get(&entry.skill_name) {
+                        Some(SkillType::Active { effects, .. })
+                        | Some(SkillType::Reaction { effects, .. }) => effects,
+                        Some(SkillType::Passive { .. }) | None => {
+                            return Err(UnitError::SkillNotFound {
+                                skill_name: entry.skill_name.clone(),
+                            }
+                            .into());
+                        }
+                    };
+                    find_buff_def(nodes, buff_name).cloned().ok_or_else(|| {
+                        DataError::InternalError {
+                            message: format!(
+                                "技能 {} 的效果樹中找不到 buff {buff_name}",
+                                entry.skill_name
+                            ),
+                        }
+                    })?
+                };
+                // 依疊加規則新增實例或重置既有實例的剩餘回合
+                apply_buff(world, def, Occupant::Unit(entry.caster), target)?;
+            }
+            ResolvedEffect::NoEffect => {}
         }
     }
+    // 位移與召喚改變了站位，進出光環範圍的單位同步 HP/MP 上限
+    let moves_units = entries.iter().any(|entry| {
+        matches!(
+            entry.effect,
+            ResolvedEffect::Teleport { .. }
+                | ResolvedEffect::Pull { .. }
+                | ResolvedEffect::Summon { .. }
+        )
+    });
+    if moves_units {
+        refresh_aura_max_hp_mp(world)?;
+    }
+    Ok(())
+}
+
+/// 對單位套用 HP 變化：傷害先由護盾吸收，治療不超過 HP 上限；護盾耗盡即移除
+///
+/// 受到傷害（即使被護盾完全吸收）會中斷引導中的技能
+pub(crate) fn apply_hp_change(world: &mut World, entity: Entity, amount: i32) -> Result<()> {
+    let mut entity_mut = world.entity_mut(entity);
+    if amount < 0 {
+        entity_mut.remove::<Channeling>();
+    }
+    let shield = entity_mut.get::<Shield>().map_or(0, |shield| shield.amount);
+    let (remaining_shield, hp_change) = absorb_with_shield(shield, amount);
+    match entity_mut.get_mut::<Shield>() {
+        Some(mut shield) if remaining_shield > 0 => shield.amount = remaining_shield,
+        Some(_) => {
+            entity_mut.remove::<Shield>();
+        }
+        None => {}
+    }
+    let max_hp = get_component!(entity_mut, MaxHp)?.0;
+    let mut hp = get_component_mut!(entity_mut, CurrentHp)?;
+    hp.0 = (hp.0 + hp_change).min(max_hp);
     Ok(())
 }
 
+/// 收集被單位或不可通過物件佔據的位置
+pub(crate) fn collect_occupied_positions(world: &mut World) -> HashSet<Position> {
+    let mut occupied: HashSet<Position> = world
+        .query_filtered::<&Position, With<Unit>>()
+        .iter(world)
+        .copied()
+        .collect();
+    occupied.extend(
+        build_objects_on_board(world)
+            .into_iter()
+            .filter(|(_, object)| object.occupies_tile)
+            .map(|(pos, _)| pos),
+    );
+    occupied
+}
+
 /// 檢查施放者的行動點是否足夠發動技能
-fn check_action_point(action_state: &ActionState, movement_point: i32) -> Result<()> {
+pub(crate) fn check_action_point(action_state: &ActionState, movement_point: i32) -> Result<()> {
     let action_point_max = movement_point * 2;
     match action_state {
         ActionState::Done => Err(UnitError::InsufficientActionPoint {
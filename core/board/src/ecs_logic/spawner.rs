@@ -1,21 +1,105 @@
-use crate::domain::alias::ID;
+use crate::domain::alias::{ID, TypeName};
+use crate::domain::constants::IMPASSABLE_MOVEMENT_COST;
 use crate::ecs_logic::query::{get_resource, setup_occupant_index};
 use crate::ecs_types::components::{
-    ActionState, BlocksSight, BlocksSound, ContactEffects, Hazardous, Object, ObjectBundle,
-    ObjectMovementCost, Occupant, OccupantTypeName, Skills, Unit, UnitBundle, UnitFaction,
+    ActionState, BlocksSight, BlocksSound, CliffFacing, Concealment, ContactEffects, Elevation,
+    ForbiddenSkillTags, Hazardous, Object, ObjectBundle, ObjectMovementCost, ObjectTrigger,
+    Occupant, OccupantTypeName, Position, Skills, Unit, UnitBundle, UnitFaction,
 };
 use crate::ecs_types::resources::{
-    BattleLog, Board, DeploymentConfig, EndConditionConfig, GameData, LevelConfig,
+    BattleLog, Board, CaptureProgress, DeploymentConfig, EndConditionConfig, GameData, LevelConfig,
+    ReinforcementSchedule,
 };
-use crate::error::{DataError, LoadError, Result};
+use crate::error::{BoardError, DataError, LoadError, Result};
 use crate::loader_schema::LevelType;
+use crate::logic::board::{new_board, require_position_in_board};
 use crate::logic::id_generator::generate_unique_id;
 use crate::logic::skill::unit_attributes;
 use bevy_ecs::prelude::World;
 use std::collections::HashSet;
 use std::sync::Arc;
 
+/// 依單位模板建立 UnitBundle（關卡生成與召喚共用）
+pub(crate) fn build_unit_bundle(
+    game_data: &GameData,
+    unit_type_name: &TypeName,
+    id: ID,
+    position: Position,
+    faction_id: ID,
+) -> Result<UnitBundle> {
+    let unit_type =
+        game_data
+            .unit_type_map
+            .get(unit_type_name)
+            .ok_or_else(|| DataError::UnitTypeNotFound {
+                type_name: unit_type_name.clone(),
+            })?;
+    let no_buffs = &vec![];
+    let effects = unit_attributes::filter_continuous_effect(
+        &unit_type.skills,
+        no_buffs,
+        &game_data.skill_map,
+    )?;
+    let attributes = unit_attributes::calculate_attributes(effects);
+
+    Ok(UnitBundle {
+        unit: Unit,
+        position,
+        occupant: Occupant::Unit(id),
+        occupant_type_name: OccupantTypeName(unit_type.name.clone()),
+        unit_faction: UnitFaction(faction_id),
+        skills: Skills(unit_type.skills.clone()),
+        attributes,
+        action_state: ActionState::Moved { cost: 0 },
+    })
+}
+
+/// 驗證關卡預設單位的出生位置：須在棋盤內，且不可與其他單位或不可通行的物件重疊
+fn validate_unit_placements(level: &LevelType, board: Board, game_data: &GameData) -> Result<()> {
+    let mut occupied: HashSet<Position> = level
+        .object_placements
+        .iter()
+        .filter(|placement| {
+            game_data
+                .object_type_map
+                .get(&placement.object_type_name)
+                .is_some_and(|object_type| object_type.movement_cost >= IMPASSABLE_MOVEMENT_COST)
+        })
+        .map(|placement| placement.position)
+        .collect();
+    for placement in &level.unit_placements {
+        let position = require_position_in_board(board, placement.position)?;
+        if !occupied.insert(position) {
+            return Err(BoardError::PositionOccupied {
+                x: position.x,
+                y: position.y,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// 驗證增援波次：生成點須在棋盤內，單位類型須存在（生成點是否被佔據於生成當下判斷）
+fn validate_reinforcements(level: &LevelType, board: Board, game_data: &GameData) -> Result<()> {
+    for placement in level.reinforcements.iter().flat_map(|wave| &wave.units) {
+        require_position_in_board(board, placement.position)?;
+        if !game_data
+            .unit_type_map
+            .contains_key(&placement.unit_type_name)
+        {
+            return Err(DataError::UnitTypeNotFound {
+                type_name: placement.unit_type_name.clone(),
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
 /// 反序列化並生成關卡的所有 Entity（棋盤、單位、物件）
+///
+/// 預設單位依 `unit_placements` 的陣營與位置原樣生成；位置超出棋盤或已被佔據時回傳 Err。
 pub fn spawn_level(world: &mut World, level_toml: &str, level_name: &str) -> Result<()> {
     // 初始化 OccupantIndex（必須在 spawn 之前，observer 才能自動追蹤）
     setup_occupant_index(world);
@@ -25,38 +109,25 @@ pub fn spawn_level(world: &mut World, level_toml: &str, level_name: &str) -> Res
         reason: e.to_string(),
     })?;
 
+    let board = new_board(level.board_width, level.board_height)?;
+
     // 第一階段：借用 GameData，預先收集所有需要 spawn 的資料
     let (unit_bundles, object_spawn_data) = {
         let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
+        validate_unit_placements(&level, board, game_data)?;
+        validate_reinforcements(&level, board, game_data)?;
 
         let mut used_ids: HashSet<ID> = HashSet::new();
         let mut unit_bundles: Vec<UnitBundle> = Vec::new();
         for placement in &level.unit_placements {
             let id = generate_unique_id(&mut used_ids)?;
-            let unit_type = game_data
-                .unit_type_map
-                .get(&placement.unit_type_name)
-                .ok_or_else(|| DataError::UnitTypeNotFound {
-                    type_name: placement.unit_type_name.clone(),
-                })?;
-            let no_buffs = &vec![];
-            let effects = unit_attributes::filter_continuous_effect(
-                &unit_type.skills,
-                no_buffs,
-                &game_data.skill_map,
-            )?;
-            let attributes = unit_attributes::calculate_attributes(effects);
-
-            unit_bundles.push(UnitBundle {
-                unit: Unit,
-                position: placement.position,
-                occupant: Occupant::Unit(id),
-                occupant_type_name: OccupantTypeName(unit_type.name.clone()),
-                unit_faction: UnitFaction(placement.faction_id),
-                skills: Skills(unit_type.skills.clone()),
-                attributes,
-                action_state: ActionState::Moved { cost: 0 },
-            });
+            unit_bundles.push(build_unit_bundle(
+                game_data,
+                &placement.unit_type_name,
+                id,
+                placement.position,
+                placement.faction_id,
+            )?);
         }
 
         let mut object_spawn_data: Vec<(
@@ -64,6 +135,10 @@ pub fn spawn_level(world: &mut World, level_toml: &str, level_name: &str) -> Res
             Option<BlocksSight>,
             Option<BlocksSound>,
             Option<Hazardous>,
+            Option<CliffFacing>,
+            Option<Concealment>,
+            Option<ForbiddenSkillTags>,
+            Option<ObjectTrigger>,
         )> = Vec::new();
         for placement in &level.object_placements {
             let id = generate_unique_id(&mut used_ids)?;
@@ -82,10 +157,16 @@ pub fn spawn_level(world: &mut World, level_toml: &str, level_name: &str) -> Res
                     occupant_type_name: OccupantTypeName(object_type.name.clone()),
                     terrain_movement_cost: ObjectMovementCost(object_type.movement_cost),
                     contact_effects: ContactEffects(Arc::from([])),
+                    elevation: Elevation(object_type.elevation),
                 },
                 object_type.blocks_sight.then_some(BlocksSight),
                 object_type.blocks_sound.then_some(BlocksSound),
                 object_type.hazardous.then_some(Hazardous),
+                object_type.cliff_facing.map(CliffFacing),
+                (object_type.concealment > 0).then_some(Concealment(object_type.concealment)),
+                (!object_type.forbidden_skill_tags.is_empty())
+                    .then(|| ForbiddenSkillTags(object_type.forbidden_skill_tags.clone())),
+                object_type.trigger.map(ObjectTrigger),
             ));
         }
 
@@ -95,10 +176,7 @@ pub fn spawn_level(world: &mut World, level_toml: &str, level_name: &str) -> Res
     // 第二階段：GameData 借用已結束，可以可變借用 world 進行 spawn
 
     // 插入 Board resource
-    world.insert_resource(Board {
-        width: level.board_width,
-        height: level.board_height,
-    });
+    world.insert_resource(board);
 
     // 插入 LevelConfig resource
     world.insert_resource(LevelConfig {
@@ -116,8 +194,15 @@ pub fn spawn_level(world: &mut World, level_toml: &str, level_name: &str) -> Res
     world.insert_resource(EndConditionConfig {
         victory: level.victory_conditions,
         defeat: level.defeat_conditions,
+        max_rounds: level.max_rounds,
     });
 
+    // 初始化目標點佔領進度
+    world.insert_resource(CaptureProgress::default());
+
+    // 插入定時增援 resource
+    world.insert_resource(ReinforcementSchedule(level.reinforcements));
+
     // 初始化戰鬥 log（關卡生成時建立，整場戰鬥持有同一份）
     world.insert_resource(BattleLog::default());
 
@@ -127,7 +212,17 @@ pub fn spawn_level(world: &mut World, level_toml: &str, level_name: &str) -> Res
     }
 
     // Spawn Object entities
-    for (bundle, blocks_sight, blocks_sound, hazardous) in object_spawn_data {
+    for (
+        bundle,
+        blocks_sight,
+        blocks_sound,
+        hazardous,
+        cliff_facing,
+        concealment,
+        forbidden_skill_tags,
+        trigger,
+    ) in object_spawn_data
+    {
         let mut entity = world.spawn(bundle);
 
         if let Some(tag) = blocks_sight {
@@ -141,6 +236,22 @@ pub fn spawn_level(world: &mut World, level_toml: &str, level_name: &str) -> Res
         if let Some(tag) = hazardous {
             entity.insert(tag);
         }
+
+        if let Some(facing) = cliff_facing {
+            entity.insert(facing);
+        }
+
+        if let Some(concealment) = concealment {
+            entity.insert(concealment);
+        }
+
+        if let Some(tags) = forbidden_skill_tags {
+            entity.insert(tags);
+        }
+
+        if let Some(trigger) = trigger {
+            entity.insert(trigger);
+        }
     }
 
     Ok(())
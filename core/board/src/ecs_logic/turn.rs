@@ -1,20 +1,35 @@
 //! 回合順序 ECS 操作函數
 
 use super::{get_component, get_component_mut};
-use crate::domain::alias::TypeName;
+use crate::domain::alias::{ID, TypeName};
 use crate::domain::battle_log::LogEvent;
 use crate::domain::constants::PLAYER_FACTION_ID;
+use crate::domain::core_types::ContinuousEffect;
+use crate::ecs_logic::battle_log::append_skill_log;
+use crate::ecs_logic::buff::{refresh_aura_max_hp_mp, refresh_max_hp_mp};
+use crate::ecs_logic::level_outcome::accrue_capture_progress;
 use crate::ecs_logic::query::{find_entity_by_occupant, get_resource, get_resource_mut};
+use crate::ecs_logic::skill::{apply_hp_change, collect_occupied_positions, resolve_skill_effects};
+use crate::ecs_logic::spawner::build_unit_bundle;
 use crate::ecs_types::components::{
-    ActionState, AppliedBuff, CurrentHp, Initiative, MaxReactionPoint, Occupant, OccupantTypeName,
-    ReactionPoint, Unit, UnitFaction,
+    ActionState, AppliedBuff, Channeling, CurrentHp, Initiative, MaxHp, MaxReactionPoint, Occupant,
+    OccupantTypeName, Overwatch, Position, ReactionPoint, Shield, Skills, Summoned, Unit,
+    UnitFaction,
+};
+use crate::ecs_types::resources::{
+    BattleLog, Board, GameData, ReactionState, ReinforcementSchedule, TurnOrder,
 };
-use crate::ecs_types::resources::{BattleLog, ReactionState, TurnOrder};
 use crate::error::{BoardError, DataError, Result};
+use crate::loader_schema::UnitPlacement;
 use crate::logic::debug::short_type_name;
+use crate::logic::id_generator::generate_unique_id;
+use crate::logic::movement::find_free_adjacent;
+use crate::logic::skill::manhattan_distance;
+use crate::logic::skill::unit_attributes::filter_continuous_effect;
 use crate::logic::turn_order::{self, TurnOrderInput};
-use bevy_ecs::prelude::{With, World};
+use bevy_ecs::prelude::{Entity, With, World};
 use rand::RngExt;
+use std::collections::{HashMap, HashSet};
 
 /// 查詢單位、擲骰、計算順序、插入 TurnOrder
 fn insert_turn_order(world: &mut World, round: u32) {
@@ -58,28 +73,131 @@ fn tick_buff_durations(world: &mut World) {
     }
 }
 
+/// 整輪輪替時呼叫：護盾剩餘輪數減 1，歸 0 即移除
+fn expire_shields(world: &mut World) {
+    let expired: Vec<Entity> = world
+        .query::<(Entity, &mut Shield)>()
+        .iter_mut(world)
+        .filter_map(|(entity, mut shield)| {
+            shield.remaining_rounds = shield.remaining_rounds.saturating_sub(1);
+            (shield.remaining_rounds == 0).then_some(entity)
+        })
+        .collect();
+
+    for entity in expired {
+        world.entity_mut(entity).remove::<Shield>();
+    }
+}
+
+/// 整輪輪替時呼叫：召喚物剩餘輪數減 1，移除歸 0 的召喚物（連同其身上的 buff）
+fn expire_summons(world: &mut World) {
+    let expired: Vec<(Entity, Occupant)> = world
+        .query::<(Entity, &Occupant, &mut Summoned)>()
+        .iter_mut(world)
+        .filter_map(|(entity, occupant, mut summoned)| {
+            summoned.remaining_rounds = summoned.remaining_rounds.saturating_sub(1);
+            (summoned.remaining_rounds == 0).then_some((entity, *occupant))
+        })
+        .collect();
+
+    let occupants: Vec<Occupant> = expired.iter().map(|(_, occupant)| *occupant).collect();
+    for (entity, _) in expired {
+        world.despawn(entity);
+    }
+    remove_buffs_targeting(world, &occupants);
+}
+
 /// 開新一輪的單一入口：所有 buff 剩餘回合 -1，再重新擲骰排序為下一輪。
 ///
 /// 由 `end_current_turn`（全員行動完畢換輪）與 `resolve_deaths`
 /// （批次死光剩餘單位換輪）共同呼叫，確保兩條換輪路徑的副作用一致——
 /// 避免某一條漏 tick buff 造成存活單位 buff 剩餘回合不遞減。
-fn advance_to_new_round(world: &mut World, prev_round: u32) {
+fn advance_to_new_round(world: &mut World, prev_round: u32) -> Result<()> {
+    accrue_capture_progress(world)?;
     tick_buff_durations(world);
+    expire_shields(world);
+    expire_summons(world);
+    spawn_reinforcements(world, prev_round + 1)?;
+    // 召喚物到期與增援改變了場上單位
+    refresh_aura_max_hp_mp(world)?;
     insert_turn_order(world, prev_round + 1);
+    Ok(())
+}
+
+/// 生成第 round 輪的增援：須在排定該輪回合順序之前呼叫，增援才會加入本輪先攻
+///
+/// 生成點被佔據時改放四周第一個空格；四周也無空格時該單位不生成（不視為錯誤）
+fn spawn_reinforcements(world: &mut World, round: u32) -> Result<()> {
+    // 讀取階段
+    let placements: Vec<UnitPlacement> =
+        get_resource::<ReinforcementSchedule>(world, "請先呼叫 spawn_level")?
+            .0
+            .iter()
+            .filter(|wave| wave.round == round)
+            .flat_map(|wave| wave.units.iter().cloned())
+            .collect();
+    if placements.is_empty() {
+        return Ok(());
+    }
+    let board = *get_resource::<Board>(world, "請先呼叫 spawn_level")?;
+    let mut occupied = collect_occupied_positions(world);
+    let mut used_ids: HashSet<ID> = world
+        .query::<&Occupant>()
+        .iter(world)
+        .map(|occupant| match occupant {
+            Occupant::Unit(id) => *id,
+            Occupant::Object(id) => *id,
+        })
+        .collect();
+
+    let mut bundles = Vec::new();
+    {
+        let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
+        for placement in placements {
+            let position = match occupied.contains(&placement.position) {
+                false => Some(placement.position),
+                true => find_free_adjacent(board, placement.position, &occupied),
+            };
+            let position = match position {
+                Some(position) => position,
+                None => continue,
+            };
+            occupied.insert(position);
+            let id = generate_unique_id(&mut used_ids)?;
+            bundles.push(build_unit_bundle(
+                game_data,
+                &placement.unit_type_name,
+                id,
+                position,
+                placement.faction_id,
+            )?);
+        }
+    }
+
+    // 寫入階段
+    for bundle in bundles {
+        world.spawn(bundle);
+    }
+    Ok(())
 }
 
-/// 單位回合開始時呼叫:移除該單位身上已過期(remaining_duration == Some(0))的 buff
-fn remove_expired_buffs_for(world: &mut World, occupant: Occupant) {
-    let expired: Vec<bevy_ecs::entity::Entity> = world
-        .query::<(bevy_ecs::entity::Entity, &AppliedBuff)>()
+/// 單位回合開始時呼叫:移除該單位身上已過期(remaining_duration == Some(0))的 buff,
+/// 有移除時重算 HP/MP 上限
+fn remove_expired_buffs_for(world: &mut World, occupant: Occupant) -> Result<()> {
+    let expired: Vec<Entity> = world
+        .query::<(Entity, &AppliedBuff)>()
         .iter(world)
         .filter(|(_, buff)| buff.target == occupant && buff.remaining_duration == Some(0))
         .map(|(entity, _)| entity)
         .collect();
 
+    if expired.is_empty() {
+        return Ok(());
+    }
     for entity in expired {
         world.despawn(entity);
     }
+    refresh_max_hp_mp(world, occupant)
 }
 
 /// 清除掛在指定 occupants 身上（target 在集合內）的所有 buff entity。
@@ -87,8 +205,8 @@ fn remove_expired_buffs_for(world: &mut World, occupant: Occupant) {
 /// 由 `resolve_deaths` 在死者 despawn 後呼叫：buff 是獨立 entity，不會隨死者
 /// entity 一併移除，須主動清除避免留下孤兒 buff。
 fn remove_buffs_targeting(world: &mut World, targets: &[Occupant]) {
-    let orphaned: Vec<bevy_ecs::entity::Entity> = world
-        .query::<(bevy_ecs::entity::Entity, &AppliedBuff)>()
+    let orphaned: Vec<Entity> = world
+        .query::<(Entity, &AppliedBuff)>()
         .iter(world)
         .filter(|(_, buff)| targets.contains(&buff.target))
         .map(|(entity, _)| entity)
@@ -99,12 +217,84 @@ fn remove_buffs_targeting(world: &mut World, targets: &[Occupant]) {
     }
 }
 
-/// 單位回合開始流程：移除該單位身上已過期的 buff。
+/// 單位回合開始時呼叫：解除該單位上一輪架設、尚未觸發的警戒
+fn clear_overwatch_for(world: &mut World, occupant: Occupant) {
+    let armed: Vec<Entity> = world
+        .query_filtered::<(Entity, &Occupant), With<Overwatch>>()
+        .iter(world)
+        .filter(|(_, occ)| **occ == occupant)
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in armed {
+        world.entity_mut(entity).remove::<Overwatch>();
+    }
+}
+
+/// 單位回合開始時呼叫：依該單位身上仍有效的 buff 中的 `Regen` 回復 HP，
+/// 不超過 HP 上限
+fn apply_regen_for(world: &mut World, occupant: Occupant) -> Result<()> {
+    let regen: i32 = world
+        .query::<&AppliedBuff>()
+        .iter(world)
+        .filter(|buff| buff.target == occupant)
+        .flat_map(|buff| buff.def.while_active.iter())
+        .map(|effect| match effect {
+            ContinuousEffect::Regen { amount } => *amount,
+            _ => 0,
+        })
+        .sum();
+    if regen == 0 {
+        return Ok(());
+    }
+
+    let entity = find_entity_by_occupant(world, occupant)?;
+    let mut entity_mut = world.entity_mut(entity);
+    let max_hp = get_component!(entity_mut, MaxHp)?.0;
+    let mut hp = get_component_mut!(entity_mut, CurrentHp)?;
+    hp.0 = (hp.0 + regen).min(max_hp);
+    Ok(())
+}
+
+/// 推進單位的引導：剩餘回合遞減，歸 0 即移除引導、結算技能並 append 技能 log
+///
+/// 引導尚未完成的單位須專心維持（不可移動或施放），本回合直接視為行動完畢。
+/// 結算可能致死，呼叫端須在回合推進後呼叫 `resolve_deaths`。
+fn advance_channel_for(world: &mut World, occupant: Occupant) -> Result<()> {
+    let channel = world
+        .query::<(Entity, &Occupant, &Channeling)>()
+        .iter(world)
+        .find(|(_, owner, _)| **owner == occupant)
+        .map(|(entity, _, channeling)| (entity, channeling.clone()));
+    let (entity, channeling) = match channel {
+        Some(channel) => channel,
+        None => return Ok(()),
+    };
+
+    let remaining_turns = channeling.remaining_turns.saturating_sub(1);
+    let mut entity_mut = world.entity_mut(entity);
+    if remaining_turns > 0 {
+        get_component_mut!(entity_mut, Channeling)?.remaining_turns = remaining_turns;
+        let mut action_state = get_component_mut!(entity_mut, ActionState)?;
+        *action_state = ActionState::Done;
+        return Ok(());
+    }
+    entity_mut.remove::<Channeling>();
+
+    let entries =
+        resolve_skill_effects(world, entity, &channeling.skill_name, &channeling.targets)?;
+    append_skill_log(world, &entries)
+}
+
+/// 單位回合開始流程：解除該單位上一輪架設的警戒，移除已過期的 buff，
+/// 套用剩餘 buff 的再生，再推進引導中的技能。
 ///
 /// 由 `end_current_turn`（推進到下一個單位）與 `resolve_deaths`
 /// （死當前單位使下一個單位遞補為當前）共同呼叫，作為「回合開始」的單一入口。
-fn begin_unit_turn(world: &mut World, occupant: Occupant) {
-    remove_expired_buffs_for(world, occupant);
+fn begin_unit_turn(world: &mut World, occupant: Occupant) -> Result<()> {
+    clear_overwatch_for(world, occupant);
+    remove_expired_buffs_for(world, occupant)?;
+    apply_regen_for(world, occupant)?;
+    advance_channel_for(world, occupant)
 }
 
 /// 單位回合結束流程：重置該單位的行動狀態與反應點數，為其下一輪預備。
@@ -147,6 +337,9 @@ pub fn start_new_round(world: &mut World) -> Result<&TurnOrder> {
         .into());
     }
 
+    spawn_reinforcements(world, 1)?;
+    // 開戰時的站位即生效
+    refresh_aura_max_hp_mp(world)?;
     insert_turn_order(world, 1);
 
     require_turn_order(world)
@@ -170,7 +363,7 @@ pub fn end_current_turn(world: &mut World) -> Result<()> {
         None => {
             // 所有單位都已行動，開始新一輪
             let prev_round = inner.round;
-            advance_to_new_round(world, prev_round);
+            advance_to_new_round(world, prev_round)?;
         }
     }
 
@@ -183,7 +376,7 @@ pub fn end_current_turn(world: &mut World) -> Result<()> {
     // 剛結束回合的單位：重置行動狀態與反應點數
     end_unit_turn(world, current_occupant)?;
     // 下一個單位的回合開始
-    begin_unit_turn(world, next_occupant);
+    begin_unit_turn(world, next_occupant)?;
 
     Ok(())
 }
@@ -205,9 +398,8 @@ pub fn delay_current_unit(world: &mut World, target_index: usize) -> Result<()>
     if !can_delay_current_unit(world)? {
         let turn_order = require_turn_order(world)?;
         let current_occupant = get_current_unit(turn_order)?;
-        return Err(BoardError::InvalidDelay {
+        return Err(BoardError::DelayAfterMove {
             occupant: current_occupant,
-            reason: "已移動的單位無法延遲".to_string(),
         }
         .into());
     }
@@ -221,26 +413,115 @@ pub fn delay_current_unit(world: &mut World, target_index: usize) -> Result<()>
     Ok(())
 }
 
+/// 單位 INI 於戰鬥中變動後，重排本輪尚未輪到的單位
+///
+/// 變動於本輪即生效：當前行動單位之後的單位依新 INI 重新排序，
+/// 已行動者與當前行動單位不受影響，下一輪則照常以新 INI 擲骰。
+pub fn refresh_turn_order(world: &mut World) -> Result<()> {
+    // 讀取：各單位當前 INI
+    let initiatives: HashMap<Occupant, i32> = world
+        .query_filtered::<(&Occupant, &Initiative), With<Unit>>()
+        .iter(world)
+        .map(|(occupant, initiative)| (*occupant, initiative.0))
+        .collect();
+
+    // 寫入：重排回合表（當前行動單位不動，current_index 不變）
+    let inner = get_resource_mut::<TurnOrder>(world, "請先呼叫 start_new_round")?.into_inner();
+    turn_order::resort_pending_entries(&mut inner.entries, &initiatives);
+
+    Ok(())
+}
+
+/// 收集全場 HP≤0 的單位（Entity、Occupant、名稱快照）
+fn collect_dead_units(world: &mut World) -> Vec<(Entity, Occupant, TypeName)> {
+    world
+        .query_filtered::<(Entity, &Occupant, &CurrentHp, &OccupantTypeName), With<Unit>>()
+        .iter(world)
+        .filter(|(_, _, hp, _)| hp.0 <= 0)
+        .map(|(entity, occupant, _, type_name)| (entity, *occupant, type_name.0.clone()))
+        .collect()
+}
+
+/// 死亡爆發：死者被動技能中的 `DeathNova` 對其相鄰的存活單位造成 HP 變化，回傳爆發 log
+///
+/// 只有傳入的死者會爆發，每個爆發對每個相鄰單位只結算一次
+fn resolve_death_novas(
+    world: &mut World,
+    dead_units: &[(Entity, Occupant, TypeName)],
+) -> Result<Vec<LogEvent>> {
+    // 讀取：死者位置與爆發數值
+    let novas: Vec<(TypeName, Position, i32)> = {
+        let game_data = get_resource::<GameData>(world, "請先呼叫 parse_and_insert_game_data")?;
+        let mut novas = Vec::new();
+        for (entity, _, type_name) in dead_units {
+            let entity_ref = world.entity(*entity);
+            let skills = get_component!(entity_ref, Skills)?;
+            let value: i32 = filter_continuous_effect(&skills.0, &[], &game_data.skill_map)?
+                .map(|effect| match effect {
+                    ContinuousEffect::DeathNova { value } => *value,
+                    _ => 0,
+                })
+                .sum();
+            if value != 0 {
+                let pos = *get_component!(entity_ref, Position)?;
+                novas.push((type_name.clone(), pos, value));
+            }
+        }
+        novas
+    };
+    if novas.is_empty() {
+        return Ok(Vec::new());
+    }
+    let living: Vec<(Entity, Position, TypeName)> = world
+        .query_filtered::<(Entity, &Position, &CurrentHp, &OccupantTypeName), With<Unit>>()
+        .iter(world)
+        .filter(|(_, _, hp, _)| hp.0 > 0)
+        .map(|(entity, pos, _, type_name)| (entity, *pos, type_name.0.clone()))
+        .collect();
+
+    // 邏輯：每個爆發波及曼哈頓距離 1 的存活單位
+    let hits: Vec<(Entity, i32, LogEvent)> = novas
+        .iter()
+        .flat_map(|(unit, nova_pos, value)| {
+            living
+                .iter()
+                .filter(move |(_, pos, _)| manhattan_distance(*nova_pos, *pos) == 1)
+                .map(move |(entity, _, target)| {
+                    (
+                        *entity,
+                        *value,
+                        LogEvent::DeathNova {
+                            unit: unit.clone(),
+                            target: target.clone(),
+                            amount: *value,
+                        },
+                    )
+                })
+        })
+        .collect();
+
+    // 寫入
+    let mut events = Vec::new();
+    for (entity, amount, event) in hits {
+        apply_hp_change(world, entity, amount)?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
 /// 掃描全場 HP≤0 的單位、批次移出 TurnOrder 並 despawn，產生死亡 log
 ///
 /// 批次語意：先收集所有死者，全部移除後**只判一次**是否全員行動完畢、
 /// 要不要開新一輪。逐個移除各判一次會在 AOE 多死時提前誤開新一輪。
 ///
+/// 死者若有死亡爆發，會在移除前結算；被爆發波及致死的單位併入同一批移除，
+/// 但不再觸發自己的爆發，避免連鎖無限延伸。
+///
 /// 對「無 `ReactionState`」（如 `execute_skill` 後）安全處理：沒有 pending
 /// 可剔除就只做移除；有則一併把死者剔出 pending，避免死者出現在反應面板。
 pub fn resolve_deaths(world: &mut World) -> Result<()> {
     // === 讀取階段：收集死者（Entity、Occupant、名稱快照）===
-    let dead_units: Vec<(bevy_ecs::entity::Entity, Occupant, TypeName)> = world
-        .query_filtered::<(
-            bevy_ecs::entity::Entity,
-            &Occupant,
-            &CurrentHp,
-            &OccupantTypeName,
-        ), With<Unit>>()
-        .iter(world)
-        .filter(|(_, _, hp, _)| hp.0 <= 0)
-        .map(|(entity, occupant, _, type_name)| (entity, *occupant, type_name.0.clone()))
-        .collect();
+    let mut dead_units = collect_dead_units(world);
 
     if dead_units.is_empty() {
         return Ok(());
@@ -249,12 +530,18 @@ pub fn resolve_deaths(world: &mut World) -> Result<()> {
     // 移除前的當前單位，用於判斷遞補後當前單位是否改變（改變才跑回合開始）
     let prev_current = get_current_unit(require_turn_order(world)?)?;
 
+    // 死亡爆發結算後重新收集，將被波及致死的單位併入本批
+    let nova_events = resolve_death_novas(world, &dead_units)?;
+    if !nova_events.is_empty() {
+        dead_units = collect_dead_units(world);
+    }
+
     // === 純邏輯階段：產生死亡 log 事件（只記身分名稱快照）===
-    let death_events: Vec<LogEvent> = dead_units
-        .iter()
-        .map(|(_, _, type_name)| LogEvent::Death {
+    let log_events: Vec<LogEvent> = nova_events
+        .into_iter()
+        .chain(dead_units.iter().map(|(_, _, type_name)| LogEvent::Death {
             unit: type_name.clone(),
-        })
+        }))
         .collect();
 
     let dead_occupants: Vec<Occupant> = dead_units
@@ -267,7 +554,7 @@ pub fn resolve_deaths(world: &mut World) -> Result<()> {
     get_resource_mut::<BattleLog>(world, "請先呼叫 spawn_level")?
         .into_inner()
         .0
-        .extend(death_events);
+        .extend(log_events);
 
     // 從 pending 與 decided 剔除死者（無 ReactionState 則跳過）
     // decided 也須剔除：否則 process_reactions 之後會 pop 出已死的 reactor，
@@ -288,6 +575,8 @@ pub fn resolve_deaths(world: &mut World) -> Result<()> {
     }
     // 清除掛在死者身上的孤兒 buff entity（buff 是獨立 entity，不隨死者一併移除）
     remove_buffs_targeting(world, &dead_occupants);
+    // 死者的光環隨之消失
+    refresh_aura_max_hp_mp(world)?;
     let is_new_round = {
         let inner = get_resource_mut::<TurnOrder>(world, "請先呼叫 start_new_round")?.into_inner();
         for (_, occupant, _) in &dead_units {
@@ -300,7 +589,7 @@ pub fn resolve_deaths(world: &mut World) -> Result<()> {
             }
             None => {
                 let prev_round = inner.round;
-                advance_to_new_round(world, prev_round);
+                advance_to_new_round(world, prev_round)?;
                 true
             }
         }
@@ -311,7 +600,9 @@ pub fn resolve_deaths(world: &mut World) -> Result<()> {
     // 換輪時即使新當前與原當前是同一 occupant，仍屬新一輪的回合開始，須跑。
     let new_current = get_current_unit(require_turn_order(world)?)?;
     if is_new_round || new_current != prev_current {
-        begin_unit_turn(world, new_current);
+        begin_unit_turn(world, new_current)?;
+        // 回合開始時引導完成的技能可能致死（含施放者的反噬），再結算一批
+        resolve_deaths(world)?;
     }
 
     Ok(())
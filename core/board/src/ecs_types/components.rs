@@ -1,7 +1,8 @@
 //! ECS Component 定義
 
 use crate::domain::alias::{Coord, ID, MovementCost, SkillName, TypeName};
-use crate::domain::core_types::{BuffType, DefenseType, EffectNode};
+use crate::domain::core_types::{BuffType, DefenseType, EffectNode, SkillTag, TileTrigger};
+use crate::logic::movement::Direction;
 use bevy_ecs::bundle::Bundle;
 use bevy_ecs::component::Component;
 use serde::{Deserialize, Serialize};
@@ -57,7 +58,7 @@ pub struct OccupantTypeName(pub TypeName);
 define_tag_components!(Unit, Object);
 
 /// 位置上的佔據者（單位或物件）
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component, Serialize, Deserialize)]
 pub enum Occupant {
     Unit(ID),
     Object(ID),
@@ -116,7 +117,7 @@ define_attribute_components!(
 /// 單位的行動狀態
 ///
 /// 初始值為 `Moved { cost: 0 }`，使用技能後變為 `Done`
-#[derive(Debug, Clone, Component)]
+#[derive(Debug, Clone, Component, Serialize, Deserialize)]
 pub enum ActionState {
     /// 尚未使用技能，記錄已消耗的移動力
     Moved { cost: MovementCost },
@@ -134,6 +135,22 @@ pub struct ObjectMovementCost(pub MovementCost);
 
 define_tag_components!(BlocksSight, BlocksSound, Hazardous);
 
+/// 懸崖朝向（僅懸崖物件持有）：可往該方向跳下，不可從下方攀上
+#[derive(Debug, Clone, Copy, Component)]
+pub struct CliffFacing(pub Direction);
+
+/// 隱蔽機率（百分比，僅具隱蔽效果的物件持有）
+#[derive(Debug, Clone, Copy, Component)]
+pub struct Concealment(pub i32);
+
+/// 站在此物件格上時禁止施放的技能標籤（僅有限制的物件持有）
+#[derive(Debug, Clone, Component)]
+pub struct ForbiddenSkillTags(pub Vec<SkillTag>);
+
+/// 地形高度（0 為平地；站在其上的單位視為同高）
+#[derive(Debug, Clone, Copy, Component)]
+pub struct Elevation(pub i32);
+
 /// 接觸效果（單位接觸物件時觸發的效果鏈）
 // by claude
 // - 每幀/熱迴圈：即使幾百 bytes 也值得避免
@@ -142,17 +159,46 @@ define_tag_components!(BlocksSight, BlocksSound, Hazardous);
 #[derive(Debug, Clone, Component)]
 pub struct ContactEffects(pub Arc<[EffectNode]>);
 
+/// 物件所在格的機關（傷害陷阱、目標點）
+#[derive(Debug, Clone, Copy, Component)]
+pub struct ObjectTrigger(pub TileTrigger);
+
 /// 施加在單位上的 Buff
 /// Buff 施加後的運行時狀態
 #[derive(Debug, Component)]
 pub struct AppliedBuff {
-    pub def: BuffType,
+    pub def: Arc<BuffType>,
     pub caster: Occupant,
     pub target: Occupant,
     pub remaining_duration: Option<u32>,
     pub inherited_defense: Option<DefenseType>,
 }
 
+/// 護盾：承受傷害時先由 amount 吸收，剩餘輪數於換輪時遞減，歸 0 即移除
+#[derive(Debug, Clone, Copy, PartialEq, Component, Serialize, Deserialize)]
+pub struct Shield {
+    pub amount: i32,
+    pub remaining_rounds: u32,
+}
+
+/// 警戒中的反應技能：由 set_overwatch 架設，觸發一次或輪到自己的回合開始時解除
+#[derive(Debug, Clone, PartialEq, Component, Serialize, Deserialize)]
+pub struct Overwatch(pub SkillName);
+
+/// 引導中的技能：施放時鎖定目標，於施放者之後的回合開始時遞減，歸 0 即結算；受到傷害即中斷
+#[derive(Debug, Clone, PartialEq, Component, Serialize, Deserialize)]
+pub struct Channeling {
+    pub skill_name: SkillName,
+    pub targets: Vec<Position>,
+    pub remaining_turns: u32,
+}
+
+/// 召喚物剩餘存在的輪數，換輪時遞減，歸 0 即移除
+#[derive(Debug, Clone, Copy, Component)]
+pub struct Summoned {
+    pub remaining_rounds: u32,
+}
+
 // ============================================================================
 // Bundles
 // ============================================================================
@@ -179,6 +225,7 @@ pub struct ObjectBundle {
     pub occupant_type_name: OccupantTypeName,
     pub terrain_movement_cost: ObjectMovementCost,
     pub contact_effects: ContactEffects,
+    pub elevation: Elevation,
     // block sight
     // block sound
 }
@@ -5,7 +5,9 @@ use crate::domain::battle_log::LogEvent;
 use crate::domain::core_types::{OutcomeBranches, PendingReaction, SkillType};
 use crate::domain::turn::TurnEntry;
 use crate::ecs_types::components::{Occupant, Position};
-use crate::loader_schema::{Faction, ObjectType, UnitType};
+use crate::loader_schema::{Faction, ObjectType, Reinforcement, UnitType};
+use crate::logic::distance_field::DistanceField;
+use crate::logic::movement::Direction;
 use bevy_ecs::entity::Entity;
 use bevy_ecs::prelude::Resource;
 use std::collections::{HashMap, HashSet};
@@ -70,6 +72,17 @@ pub struct MovementPlan {
     pub next_step_index: usize,
 }
 
+/// 全點對距離場快取（get_distance_field 建立）
+///
+/// 記錄建立時的棋盤尺寸、物件移動成本與懸崖；任一項改變即視為過期並重建
+#[derive(Debug, Clone, Resource)]
+pub struct DistanceFieldCache {
+    pub board: Board,
+    pub objects_movement_cost: HashMap<Position, MovementCost>,
+    pub cliffs: HashMap<Position, Direction>,
+    pub field: DistanceField,
+}
+
 /// 技能目標選取狀態（玩家逐次累積選目標時的暫存）
 #[derive(Debug, Clone, Resource)]
 pub struct SkillTargeting {
@@ -94,9 +107,18 @@ pub struct ReactionState {
 #[derive(Debug, Default, Resource)]
 pub struct BattleLog(pub Vec<LogEvent>);
 
+/// 關卡定時增援 Resource（spawn_level 時存入，新一輪開始時生成對應輪數的波次）
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ReinforcementSchedule(pub Vec<Reinforcement>);
+
+/// 各 faction 的目標點佔領進度（輪數），供 HoldObjective 結局條件判定
+#[derive(Debug, Clone, Default, Resource)]
+pub struct CaptureProgress(pub HashMap<ID, u32>);
+
 /// 勝利與失敗規則 Resource
 #[derive(Debug, Resource)]
 pub struct EndConditionConfig {
     pub victory: OutcomeBranches,
     pub defeat: OutcomeBranches,
+    pub max_rounds: Option<u32>,
 }
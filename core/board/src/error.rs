@@ -7,6 +7,7 @@
 //! - 維護成本低
 
 use crate::domain::alias::{Coord, SkillName, TypeName};
+use crate::domain::core_types::SkillTag;
 use crate::ecs_types::components::Occupant;
 use std::backtrace::Backtrace;
 use std::fmt::{Display, Formatter};
@@ -80,15 +81,45 @@ pub enum BoardError {
         width: Coord,
         height: Coord,
     },
+    #[error("棋盤尺寸無效: {width}x{height}（單邊 1~{max_side}，面積上限 {max_area}）")]
+    InvalidBoardSize {
+        width: Coord,
+        height: Coord,
+        max_side: Coord,
+        max_area: usize,
+    },
+    #[error("棋盤格數 {tile_count} 超過距離場上限 {max_tiles}")]
+    DistanceFieldTooLarge { tile_count: usize, max_tiles: usize },
     #[error("位置 ({x}, {y}) 不可到達")]
     Unreachable { x: Coord, y: Coord },
+    #[error("位置 ({x}, {y}) 已被佔據")]
+    PositionOccupied { x: Coord, y: Coord },
+    #[error("位置 ({x}, {y}) 平移 ({dx}, {dy}) 後超出棋盤")]
+    OffsetOutOfBoard {
+        x: Coord,
+        y: Coord,
+        dx: i32,
+        dy: i32,
+    },
     // turn
     #[error("沒有未行動的單位")]
     NoActiveUnit,
     #[error("佔據者不存在於回合表中: {occupant:?}")]
     OccupantNotFound { occupant: Occupant },
-    #[error("單位 {occupant:?} 無法延後: {reason}")]
-    InvalidDelay { occupant: Occupant, reason: String },
+    #[error("單位 {occupant:?} 已移動，無法延後")]
+    DelayAfterMove { occupant: Occupant },
+    #[error("單位 {occupant:?} 無法向前延後到位置 {target_index}: 當前位置 {current_index}")]
+    DelayNotBackward {
+        occupant: Occupant,
+        target_index: usize,
+        current_index: usize,
+    },
+    #[error("單位 {occupant:?} 的延後位置 {target_index} 超出回合表範圍（{len}）")]
+    DelayOutOfTurnOrder {
+        occupant: Occupant,
+        target_index: usize,
+        len: usize,
+    },
     // skill
     #[error(
         "非法的技能施放位置:\n\t範圍 {shape}\n\t施放者 ({caster_x}, {caster_y})\n\t目標 ({target_x}, {target_y})"
@@ -102,8 +133,10 @@ pub enum BoardError {
     },
     #[error("目標數量錯誤: 預期 {expected} 個，實際 {actual} 個")]
     WrongTargetCount { expected: usize, actual: usize },
-    #[error("目標超出射程: 距離 {distance}，射程 {min_range}-{max_range}")]
+    #[error("目標 ({x}, {y}) 超出射程: 距離 {distance}，射程 {min_range}-{max_range}")]
     OutOfRange {
+        x: Coord,
+        y: Coord,
         distance: usize,
         min_range: Coord,
         max_range: Coord,
@@ -153,6 +186,16 @@ pub enum UnitError {
     InsufficientReactionPoint { current: i32 },
     #[error("技能 '{skill_name}' 必須至少有一個 effect")]
     EmptySkillEffects { skill_name: SkillName },
+    #[error("技能 '{skill_name}' 缺少前置技能: {missing:?}")]
+    MissingPrerequisite {
+        skill_name: SkillName,
+        missing: Vec<SkillName>,
+    },
+    #[error("所在地形禁止施放 {tag} 技能 '{skill_name}'")]
+    SkillForbiddenByTerrain {
+        skill_name: SkillName,
+        tag: SkillTag,
+    },
 }
 
 impl Error {
@@ -1,8 +1,9 @@
 //! Loader 相關的資料結構定義
 
 use crate::domain::alias::{Coord, ID, MovementCost, SkillName, TypeName};
-use crate::domain::core_types::{OutcomeBranches, SkillType};
+use crate::domain::core_types::{OutcomeBranches, SkillTag, SkillType, TileTrigger};
 use crate::ecs_types::components::Position;
+use crate::logic::movement::Direction;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -14,6 +15,12 @@ use serde::{Deserialize, Serialize};
 pub struct UnitType {
     pub name: String,
     pub skills: Vec<SkillName>,
+    /// 可學習的技能類別（Melee / Ranged / Magic），空表示不限制
+    #[serde(default)]
+    pub allowed_categories: Vec<SkillTag>,
+    /// 技能欄位上限，None 表示不限制
+    #[serde(default)]
+    pub max_skills: Option<usize>,
 }
 
 // ============================================================================
@@ -28,6 +35,21 @@ pub struct ObjectType {
     pub blocks_sight: bool,
     pub blocks_sound: bool,
     pub hazardous: bool,
+    /// 地形高度（預設 0 為平地）：高處可越過較低的視線阻擋物，遠程攻擊低處有命中加成
+    #[serde(default)]
+    pub elevation: i32,
+    /// 懸崖朝向：此格朝該方向的邊為斷崖，可往該方向跳下，不可從下方攀上
+    #[serde(default)]
+    pub cliff_facing: Option<Direction>,
+    /// 隱蔽機率（百分比，預設 0）：站在此格的單位被命中後，仍有此機率使攻擊落空
+    #[serde(default)]
+    pub concealment: i32,
+    /// 站在此格的單位不可施放帶有任一這些標籤的技能（例如深水禁火）
+    #[serde(default)]
+    pub forbidden_skill_tags: Vec<SkillTag>,
+    /// 地格機關（傷害陷阱、目標點），預設無
+    #[serde(default)]
+    pub trigger: Option<TileTrigger>,
 }
 
 // ============================================================================
@@ -59,6 +81,20 @@ pub struct ObjectPlacement {
     pub position: Position,
 }
 
+/// 增援波次：第 round 輪開始時，依 units 的位置（生成點）生成單位
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Reinforcement {
+    pub round: u32,
+    pub units: Vec<UnitPlacement>,
+}
+
+/// 格子註記：僅供編輯器標示用途（例如「首領生成點」），遊戲邏輯不讀取
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TileLabel {
+    pub position: Position,
+    pub text: String,
+}
+
 /// 關卡類型定義
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LevelType {
@@ -72,6 +108,15 @@ pub struct LevelType {
     pub object_placements: Vec<ObjectPlacement>,
     pub victory_conditions: OutcomeBranches,
     pub defeat_conditions: OutcomeBranches,
+    /// 回合上限：進入第 max_rounds + 1 輪仍未分出勝負即判定平手（None 為不限制）
+    #[serde(default)]
+    pub max_rounds: Option<u32>,
+    /// 編輯器格子註記（舊關卡檔沒有此欄位時為空）
+    #[serde(default)]
+    pub tile_labels: Vec<TileLabel>,
+    /// 定時增援（舊關卡檔沒有此欄位時為空）
+    #[serde(default)]
+    pub reinforcements: Vec<Reinforcement>,
 }
 
 // ============================================================================
@@ -95,3 +140,15 @@ pub struct UnitsToml {
 pub struct ObjectsToml {
     pub objects: Vec<ObjectType>,
 }
+
+/// 情境包頂層結構：關卡與其引用到的所有定義，可單檔分享
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScenarioBundle {
+    pub level: LevelType,
+    /// 玩家可部署的單位類型
+    #[serde(default)]
+    pub roster: Vec<TypeName>,
+    pub units: Vec<UnitType>,
+    pub skills: Vec<SkillType>,
+    pub objects: Vec<ObjectType>,
+}
@@ -1,13 +1,50 @@
 //! 棋盤邏輯
 
+use crate::domain::alias::Coord;
+use crate::domain::constants::{
+    MAX_BOARD_AREA, MAX_BOARD_SIDE, NEIGHBOR_OFFSETS_4, NEIGHBOR_OFFSETS_8,
+};
 use crate::ecs_types::components::Position;
 use crate::ecs_types::resources::Board;
+use crate::error::{BoardError, Result};
+
+/// 建立棋盤，尺寸為 0、單邊超過上限或面積超過上限時回傳錯誤
+pub fn new_board(width: Coord, height: Coord) -> Result<Board> {
+    let side_ok = (1..=MAX_BOARD_SIDE).contains(&width) && (1..=MAX_BOARD_SIDE).contains(&height);
+    let area_ok = width
+        .checked_mul(height)
+        .is_some_and(|area| area <= MAX_BOARD_AREA);
+    if !side_ok || !area_ok {
+        return Err(BoardError::InvalidBoardSize {
+            width,
+            height,
+            max_side: MAX_BOARD_SIDE,
+            max_area: MAX_BOARD_AREA,
+        }
+        .into());
+    }
+    Ok(Board { width, height })
+}
 
 /// 驗證位置是否在棋盤邊界內
 pub fn is_valid_position(board: Board, pos: Position) -> bool {
     pos.x < board.width && pos.y < board.height
 }
 
+/// 驗證位置在棋盤內並原樣回傳，超出邊界時回傳帶座標與邊界的 `OutOfBoard`
+pub fn require_position_in_board(board: Board, pos: Position) -> Result<Position> {
+    if !is_valid_position(board, pos) {
+        return Err(BoardError::OutOfBoard {
+            x: pos.x,
+            y: pos.y,
+            width: board.width,
+            height: board.height,
+        }
+        .into());
+    }
+    Ok(pos)
+}
+
 pub(crate) fn try_position(board: Board, x: i32, y: i32) -> Option<Position> {
     if x < 0 || y < 0 {
         return None;
@@ -22,3 +59,36 @@ pub(crate) fn try_position(board: Board, x: i32, y: i32) -> Option<Position> {
         None
     }
 }
+
+/// 座標位移，任一軸結果為負時回傳 `None`（不檢查棋盤邊界）
+pub fn checked_offset(pos: Position, dx: i32, dy: i32) -> Option<Position> {
+    let x = pos.x.checked_add_signed(dx as isize)?;
+    let y = pos.y.checked_add_signed(dy as isize)?;
+    Some(Position { x, y })
+}
+
+/// 棋盤內的 4 個正交相鄰格
+pub fn neighbors4(board: Board, pos: Position) -> impl Iterator<Item = Position> {
+    neighbors_with(board, pos, &NEIGHBOR_OFFSETS_4)
+}
+
+/// 棋盤內的 8 個相鄰格（含斜角）
+pub fn neighbors8(board: Board, pos: Position) -> impl Iterator<Item = Position> {
+    neighbors_with(board, pos, &NEIGHBOR_OFFSETS_8)
+}
+
+fn neighbors_with(
+    board: Board,
+    pos: Position,
+    offsets: &'static [(i32, i32)],
+) -> impl Iterator<Item = Position> {
+    offsets
+        .iter()
+        .filter_map(move |(dx, dy)| checked_offset(pos, *dx, *dy))
+        .filter(move |neighbor| is_valid_position(board, *neighbor))
+}
+
+/// 切比雪夫距離（允許斜向移動時的步數）
+pub fn chebyshev_distance(a: Position, b: Position) -> Coord {
+    a.x.abs_diff(b.x).max(a.y.abs_diff(b.y))
+}
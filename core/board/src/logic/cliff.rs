@@ -0,0 +1,83 @@
+//! 懸崖邏輯（方向性地形阻擋）
+
+use crate::domain::alias::TypeName;
+use crate::domain::constants::IMPASSABLE_MOVEMENT_COST;
+use crate::ecs_types::components::Position;
+use crate::ecs_types::resources::Board;
+use crate::error::{DataError, Result};
+use crate::loader_schema::{LevelType, ObjectType};
+use crate::logic::movement::{Direction, step_in_direction};
+use std::collections::HashMap;
+
+/// 懸崖擺放問題（僅警告，不阻止存檔）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliffWarning {
+    /// 斷崖朝向棋盤外，無處可跳下
+    FacingOffBoard { position: Position },
+    /// 斷崖下方是不可通行的物件，跳下後無處落腳
+    FacingImpassable {
+        position: Position,
+        blocked_by: TypeName,
+    },
+}
+
+/// 懸崖下方（斷崖邊另一側）的格子，超出棋盤時回傳 `None`
+pub fn cliff_bottom(board: Board, cliff_pos: Position, facing: Direction) -> Option<Position> {
+    step_in_direction(board, cliff_pos, facing)
+}
+
+/// 判斷 from → to 的一步是否被懸崖阻擋：只有從崖下攀上崖頂會被阻擋，往下跳與平行移動不受影響
+pub fn cliff_blocks(
+    board: Board,
+    from: Position,
+    to: Position,
+    cliff_pos: Position,
+    facing: Direction,
+) -> bool {
+    to == cliff_pos && cliff_bottom(board, cliff_pos, facing) == Some(from)
+}
+
+/// 檢查關卡中所有懸崖的朝向：朝向棋盤外或朝向不可通行物件時產生警告
+pub fn cliff_placement_warnings(
+    level: &LevelType,
+    object_types: &HashMap<TypeName, ObjectType>,
+) -> Result<Vec<CliffWarning>> {
+    let board = Board {
+        width: level.board_width,
+        height: level.board_height,
+    };
+
+    let mut impassable: HashMap<Position, &TypeName> = HashMap::new();
+    let mut cliffs = Vec::new();
+    for placement in &level.object_placements {
+        let object_type = object_types
+            .get(&placement.object_type_name)
+            .ok_or_else(|| DataError::ObjectTypeNotFound {
+                type_name: placement.object_type_name.clone(),
+            })?;
+        if object_type.movement_cost >= IMPASSABLE_MOVEMENT_COST {
+            impassable.insert(placement.position, &object_type.name);
+        }
+        if let Some(facing) = object_type.cliff_facing {
+            cliffs.push((placement.position, facing));
+        }
+    }
+
+    let warnings = cliffs
+        .into_iter()
+        .filter_map(
+            |(position, facing)| match cliff_bottom(board, position, facing) {
+                None => Some(CliffWarning::FacingOffBoard { position }),
+                Some(bottom) => {
+                    impassable
+                        .get(&bottom)
+                        .map(|blocked_by| CliffWarning::FacingImpassable {
+                            position,
+                            blocked_by: (*blocked_by).clone(),
+                        })
+                }
+            },
+        )
+        .collect();
+    Ok(warnings)
+}
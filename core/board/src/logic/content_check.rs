@@ -0,0 +1,134 @@
+//! 內容檔案引用完整性檢查（關卡、單位、技能、物件、名冊之間的名稱引用）
+
+use crate::domain::alias::{SkillName, TypeName};
+use crate::domain::core_types::SkillType;
+use crate::loader_schema::{LevelType, ObjectType, UnitType};
+use crate::logic::scenario::skill_references;
+use std::collections::HashSet;
+
+/// 懸空引用：引用方存在，但被引用的定義不存在
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DanglingReference {
+    /// 關卡放置（含增援）了不存在的單位類型
+    LevelUnitType { level: String, unit_type: TypeName },
+    /// 關卡放置了不存在的物件類型
+    LevelObjectType {
+        level: String,
+        object_type: TypeName,
+    },
+    /// 單位類型的技能列表引用了不存在的技能
+    UnitSkill {
+        unit_type: TypeName,
+        skill: SkillName,
+    },
+    /// 技能的前置技能不存在
+    SkillRequires {
+        skill: SkillName,
+        required: SkillName,
+    },
+    /// 技能召喚不存在的單位類型
+    SkillUnitType {
+        skill: SkillName,
+        unit_type: TypeName,
+    },
+    /// 技能生成不存在的物件類型
+    SkillObjectType {
+        skill: SkillName,
+        object_type: TypeName,
+    },
+    /// 名冊引用了不存在的單位類型
+    RosterUnitType { unit_type: TypeName },
+}
+
+/// 檢查各內容檔案之間的名稱引用，回傳所有懸空引用（同一引用方對同一目標只回報一次）
+///
+/// 依關卡、單位、技能、名冊的順序檢查，回傳順序與資料順序一致，方便對照來源檔案。
+pub fn check_references(
+    levels: &[LevelType],
+    units: &[UnitType],
+    skills: &[SkillType],
+    objects: &[ObjectType],
+    roster: &[TypeName],
+) -> Vec<DanglingReference> {
+    let unit_names: HashSet<&TypeName> = units.iter().map(|unit| &unit.name).collect();
+    let skill_names: HashSet<&SkillName> = skills.iter().map(|skill| skill.name()).collect();
+    let object_names: HashSet<&TypeName> = objects.iter().map(|object| &object.name).collect();
+
+    let mut report = Vec::new();
+    let mut push = |dangling: DanglingReference| {
+        if !report.contains(&dangling) {
+            report.push(dangling);
+        }
+    };
+
+    for level in levels {
+        let reinforcement_units = level.reinforcements.iter().flat_map(|wave| &wave.units);
+        for placement in level.unit_placements.iter().chain(reinforcement_units) {
+            if !unit_names.contains(&placement.unit_type_name) {
+                push(DanglingReference::LevelUnitType {
+                    level: level.name.clone(),
+                    unit_type: placement.unit_type_name.clone(),
+                });
+            }
+        }
+        for placement in &level.object_placements {
+            if !object_names.contains(&placement.object_type_name) {
+                push(DanglingReference::LevelObjectType {
+                    level: level.name.clone(),
+                    object_type: placement.object_type_name.clone(),
+                });
+            }
+        }
+    }
+
+    for unit in units {
+        for skill in &unit.skills {
+            if !skill_names.contains(skill) {
+                push(DanglingReference::UnitSkill {
+                    unit_type: unit.name.clone(),
+                    skill: skill.clone(),
+                });
+            }
+        }
+    }
+
+    for skill in skills {
+        if let SkillType::Active { requires, .. } = skill {
+            for required in requires {
+                if !skill_names.contains(required) {
+                    push(DanglingReference::SkillRequires {
+                        skill: skill.name().clone(),
+                        required: required.clone(),
+                    });
+                }
+            }
+        }
+        let references = skill_references(skill);
+        for unit_type in references.unit_types {
+            if !unit_names.contains(&unit_type) {
+                push(DanglingReference::SkillUnitType {
+                    skill: skill.name().clone(),
+                    unit_type,
+                });
+            }
+        }
+        for object_type in references.object_types {
+            if !object_names.contains(&object_type) {
+                push(DanglingReference::SkillObjectType {
+                    skill: skill.name().clone(),
+                    object_type,
+                });
+            }
+        }
+    }
+
+    for unit_type in roster {
+        if !unit_names.contains(unit_type) {
+            push(DanglingReference::RosterUnitType {
+                unit_type: unit_type.clone(),
+            });
+        }
+    }
+
+    report
+}
@@ -0,0 +1,38 @@
+//! 穩定內容雜湊邏輯
+
+use crate::domain::constants::{FNV_OFFSET_BASIS, FNV_PRIME};
+use std::hash::{Hash, Hasher};
+
+/// FNV-1a 64-bit Hasher
+///
+/// 標準庫的 `DefaultHasher` 不保證跨 Rust 版本輸出一致，
+/// 快取 key 與 golden test 需要固定演算法，故自行實作。
+pub(crate) struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// 以 `StableHasher` 計算雜湊
+///
+/// 呼叫端須自行將集合排序成固定順序，此函數不處理 `HashMap` 迭代順序。
+pub(crate) fn stable_hash(value: &impl Hash) -> u64 {
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
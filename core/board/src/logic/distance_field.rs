@@ -0,0 +1,85 @@
+//! 全點對距離場：小棋盤上預先計算所有格子兩兩之間的地形移動成本
+
+use crate::domain::alias::MovementCost;
+use crate::domain::constants::{
+    IMPASSABLE_MOVEMENT_COST, MAX_DISTANCE_FIELD_TILES, PLAYER_ALLIANCE_ID,
+};
+use crate::ecs_types::components::Position;
+use crate::ecs_types::resources::Board;
+use crate::error::{BoardError, Result};
+use crate::logic::board::is_valid_position;
+use crate::logic::movement::{Mover, reachable_positions};
+
+/// 預先計算的全點對移動成本（只考慮地形，不考慮單位阻擋）
+///
+/// 以空間換時間：從每一格各跑一次 Dijkstra，記憶體為格數平方，只適合小棋盤
+/// （上限 `MAX_DISTANCE_FIELD_TILES` 格）。
+/// 地形改變後內容即失效，需重新 `build`。
+#[derive(Debug, Clone)]
+pub struct DistanceField {
+    board: Board,
+    /// `distances[from_index * tile_count + to_index]`，無法抵達為 None
+    distances: Vec<Option<MovementCost>>,
+}
+
+impl DistanceField {
+    /// 從每一格出發計算到其他格的最低移動成本
+    ///
+    /// 成本達到不可通行門檻的路徑視為無法抵達，與 `measure_between` 相同；
+    /// 格數超過 `MAX_DISTANCE_FIELD_TILES` 時回傳錯誤
+    pub(crate) fn build<G, H>(board: Board, get_terrain_cost: G, is_step_blocked: H) -> Result<Self>
+    where
+        G: Fn(Position) -> MovementCost + Copy,
+        H: Fn(Position, Position) -> bool + Copy,
+    {
+        let tile_count = board.width * board.height;
+        if tile_count > MAX_DISTANCE_FIELD_TILES {
+            return Err(BoardError::DistanceFieldTooLarge {
+                tile_count,
+                max_tiles: MAX_DISTANCE_FIELD_TILES,
+            }
+            .into());
+        }
+        let mut distances = vec![None; tile_count * tile_count];
+        for from_index in 0..tile_count {
+            let from = tile_position(board, from_index);
+            distances[from_index * tile_count + from_index] = Some(0);
+            let mover = Mover {
+                pos: from,
+                faction_alliance: PLAYER_ALLIANCE_ID,
+            };
+            let reachable = reachable_positions(
+                board,
+                mover,
+                IMPASSABLE_MOVEMENT_COST - 1,
+                |_| None,
+                get_terrain_cost,
+                is_step_blocked,
+            )?;
+            for (to, info) in reachable {
+                distances[from_index * tile_count + tile_index(board, to)] = Some(info.cost);
+            }
+        }
+        Ok(Self { board, distances })
+    }
+
+    /// 查詢 a 到 b 的最低移動成本；任一格在棋盤外或無法抵達時為 None
+    pub fn distance(&self, a: Position, b: Position) -> Option<MovementCost> {
+        if !is_valid_position(self.board, a) || !is_valid_position(self.board, b) {
+            return None;
+        }
+        let tile_count = self.board.width * self.board.height;
+        self.distances[tile_index(self.board, a) * tile_count + tile_index(self.board, b)]
+    }
+}
+
+fn tile_index(board: Board, pos: Position) -> usize {
+    pos.y * board.width + pos.x
+}
+
+fn tile_position(board: Board, index: usize) -> Position {
+    Position {
+        x: index % board.width,
+        y: index / board.width,
+    }
+}
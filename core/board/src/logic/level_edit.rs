@@ -0,0 +1,145 @@
+//! 關卡編輯輔助邏輯（批次放置／取代物件、群組平移單位）
+
+use crate::domain::alias::Coord;
+use crate::ecs_types::components::Position;
+use crate::ecs_types::resources::Board;
+use crate::error::{BoardError, Result};
+use crate::loader_schema::{LevelType, ObjectPlacement};
+use crate::logic::board::{checked_offset, is_valid_position};
+use std::collections::HashSet;
+
+/// 棋盤外圈所有格子（依列優先順序，不重複）
+pub fn perimeter_positions(width: Coord, height: Coord) -> Vec<Position> {
+    let mut positions = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let is_edge = x == 0 || y == 0 || x + 1 == width || y + 1 == height;
+            if is_edge {
+                positions.push(Position { x, y });
+            }
+        }
+    }
+    positions
+}
+
+/// 在棋盤外圈每格放置指定物件（已有同類型物件的格子略過，其他物件保留）
+pub fn apply_border(level: &mut LevelType, object_type_name: &str) {
+    let existing: HashSet<Position> = level
+        .object_placements
+        .iter()
+        .filter(|placement| placement.object_type_name == object_type_name)
+        .map(|placement| placement.position)
+        .collect();
+
+    for position in perimeter_positions(level.board_width, level.board_height) {
+        if existing.contains(&position) {
+            continue;
+        }
+        level.object_placements.push(ObjectPlacement {
+            object_type_name: object_type_name.to_string(),
+            position,
+        });
+    }
+}
+
+/// 移除棋盤外圈上指定類型的物件（`apply_border` 的反向操作，內部格子不受影響）
+pub fn clear_border(level: &mut LevelType, object_type_name: &str) {
+    let perimeter: HashSet<Position> = perimeter_positions(level.board_width, level.board_height)
+        .into_iter()
+        .collect();
+    level.object_placements.retain(|placement| {
+        placement.object_type_name != object_type_name || !perimeter.contains(&placement.position)
+    });
+}
+
+/// 將整張棋盤上 from 類型的物件全部換成 to 類型，回傳被取代的格子數
+///
+/// 地形（沼澤、深水等）也是物件，換主題時同樣適用。
+/// 格子上已有 to 類型物件時只移除 from，避免同格重複放置；from 與 to 相同時不做任何事。
+pub fn replace_object_type(level: &mut LevelType, from: &str, to: &str) -> usize {
+    if from == to {
+        return 0;
+    }
+    let occupied_by_to: HashSet<Position> = level
+        .object_placements
+        .iter()
+        .filter(|placement| placement.object_type_name == to)
+        .map(|placement| placement.position)
+        .collect();
+
+    let before = level.object_placements.len();
+    level.object_placements.retain(|placement| {
+        placement.object_type_name != from || !occupied_by_to.contains(&placement.position)
+    });
+    let mut replaced = before - level.object_placements.len();
+    for placement in &mut level.object_placements {
+        if placement.object_type_name == from {
+            placement.object_type_name = to.to_string();
+            replaced += 1;
+        }
+    }
+    replaced
+}
+
+/// 以兩角（含）圍成的矩形內所有格子，兩角順序不拘（框選用）
+pub fn rect_positions(a: Position, b: Position) -> Vec<Position> {
+    let mut positions = Vec::new();
+    for y in a.y.min(b.y)..=a.y.max(b.y) {
+        for x in a.x.min(b.x)..=a.x.max(b.x) {
+            positions.push(Position { x, y });
+        }
+    }
+    positions
+}
+
+/// 將位於 selected 的單位整組平移 (dx, dy)，回傳被移動單位的新位置
+///
+/// 全有或全無：任一目的地超出棋盤，或被組外單位、物件、部署點佔據時回傳錯誤，關卡保持不變。
+/// selected 中沒有單位的格子略過；組內單位互相讓位不視為衝突。
+pub fn translate_units(
+    level: &mut LevelType,
+    selected: &[Position],
+    (dx, dy): (i32, i32),
+) -> Result<Vec<Position>> {
+    let board = Board {
+        width: level.board_width,
+        height: level.board_height,
+    };
+    let selected_set: HashSet<Position> = selected.iter().copied().collect();
+    let moving: Vec<(usize, Position)> = level
+        .unit_placements
+        .iter()
+        .enumerate()
+        .filter(|(_, unit)| selected_set.contains(&unit.position))
+        .map(|(index, unit)| (index, unit.position))
+        .collect();
+    let blocked: HashSet<Position> = level
+        .unit_placements
+        .iter()
+        .map(|unit| unit.position)
+        .filter(|pos| !selected_set.contains(pos))
+        .chain(level.object_placements.iter().map(|obj| obj.position))
+        .chain(level.deployment_positions.iter().copied())
+        .collect();
+
+    let mut destinations = Vec::with_capacity(moving.len());
+    for (index, from) in &moving {
+        let to = checked_offset(*from, dx, dy)
+            .filter(|to| is_valid_position(board, *to))
+            .ok_or(BoardError::OffsetOutOfBoard {
+                x: from.x,
+                y: from.y,
+                dx,
+                dy,
+            })?;
+        if blocked.contains(&to) {
+            return Err(BoardError::PositionOccupied { x: to.x, y: to.y }.into());
+        }
+        destinations.push((*index, to));
+    }
+
+    for (index, to) in &destinations {
+        level.unit_placements[*index].position = *to;
+    }
+    Ok(destinations.into_iter().map(|(_, to)| to).collect())
+}
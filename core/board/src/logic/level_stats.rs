@@ -0,0 +1,119 @@
+//! 關卡統計邏輯（平衡用）
+
+use crate::domain::alias::{ID, TypeName};
+use crate::domain::constants::{IMPASSABLE_MOVEMENT_COST, PLAYER_FACTION_ID};
+use crate::ecs_types::components::Position;
+use crate::ecs_types::resources::Board;
+use crate::error::{DataError, Result};
+use crate::loader_schema::{LevelType, ObjectType};
+use crate::logic::movement::{Direction, step_in_direction};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use strum::IntoEnumIterator;
+
+/// 關卡統計結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LevelStats {
+    pub total_tiles: usize,
+    /// 沒有單位也沒有物件的格子數
+    pub empty_tiles: usize,
+    pub deployable_tiles: usize,
+    /// 物件類型 → 數量
+    pub object_counts: BTreeMap<TypeName, usize>,
+    /// 單位類型 → 數量
+    pub unit_type_counts: BTreeMap<TypeName, usize>,
+    /// 陣營 ID → 單位數量
+    pub unit_counts_by_faction: BTreeMap<ID, usize>,
+}
+
+/// 統計關卡的物件、部署點與各陣營單位數量
+pub fn level_stats(level: &LevelType) -> LevelStats {
+    let mut stats = LevelStats {
+        total_tiles: level.board_width * level.board_height,
+        deployable_tiles: level.deployment_positions.len(),
+        ..Default::default()
+    };
+
+    let mut occupied: HashSet<Position> = HashSet::new();
+    for object in &level.object_placements {
+        *stats
+            .object_counts
+            .entry(object.object_type_name.clone())
+            .or_default() += 1;
+        occupied.insert(object.position);
+    }
+    for unit in &level.unit_placements {
+        *stats
+            .unit_type_counts
+            .entry(unit.unit_type_name.clone())
+            .or_default() += 1;
+        *stats
+            .unit_counts_by_faction
+            .entry(unit.faction_id)
+            .or_default() += 1;
+        occupied.insert(unit.position);
+    }
+
+    stats.empty_tiles = stats.total_tiles.saturating_sub(occupied.len());
+    stats
+}
+
+/// 計算開放空間比例：從玩家起始區（部署點 + 預放玩家單位）出發，
+/// 可走到的格子佔全部可走格子的比例，用來抓出被大片封死的地圖
+///
+/// 不可通行或有危險的物件所在格視為不可走；單位會移動，不視為阻擋
+/// 沒有任何可走格子時回傳 0.0
+pub fn open_space_ratio(
+    level: &LevelType,
+    object_types: &HashMap<TypeName, ObjectType>,
+) -> Result<f32> {
+    let board = Board {
+        width: level.board_width,
+        height: level.board_height,
+    };
+
+    let mut blocked: HashSet<Position> = HashSet::new();
+    for placement in &level.object_placements {
+        let object_type = object_types
+            .get(&placement.object_type_name)
+            .ok_or_else(|| DataError::ObjectTypeNotFound {
+                type_name: placement.object_type_name.clone(),
+            })?;
+        if object_type.movement_cost >= IMPASSABLE_MOVEMENT_COST || object_type.hazardous {
+            blocked.insert(placement.position);
+        }
+    }
+
+    let walkable_count = (level.board_width * level.board_height).saturating_sub(blocked.len());
+    if walkable_count == 0 {
+        return Ok(0.0);
+    }
+
+    let starts = level.deployment_positions.iter().copied().chain(
+        level
+            .unit_placements
+            .iter()
+            .filter(|u| u.faction_id == PLAYER_FACTION_ID)
+            .map(|u| u.position),
+    );
+    let mut visited: HashSet<Position> = HashSet::new();
+    let mut queue: VecDeque<Position> = VecDeque::new();
+    for start in starts {
+        if !blocked.contains(&start) && visited.insert(start) {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(pos) = queue.pop_front() {
+        for direction in Direction::iter() {
+            let next = match step_in_direction(board, pos, direction) {
+                Some(next) => next,
+                None => continue,
+            };
+            if !blocked.contains(&next) && visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    Ok(visited.len() as f32 / walkable_count as f32)
+}
@@ -1,8 +1,15 @@
 //! 核心業務邏輯（不是 ECS System）
 
 pub mod board;
+pub mod cliff;
+pub mod content_check;
+pub mod content_hash;
 pub mod debug;
+pub mod distance_field;
 pub mod id_generator;
+pub mod level_edit;
+pub mod level_stats;
 pub mod movement;
+pub mod scenario;
 pub mod skill;
 pub mod turn_order;
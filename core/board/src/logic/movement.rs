@@ -1,18 +1,23 @@
 //! 移動邏輯
 
-use crate::domain::alias::{ID, MovementCost};
+use crate::domain::alias::{Coord, ID, MovementCost};
+use crate::domain::constants::PATH_NEIGHBOR_OFFSETS;
 use crate::ecs_types::components::Position;
 use crate::ecs_types::resources::Board;
-use crate::error::{BoardError, Result};
-use crate::logic::board::{is_valid_position, try_position};
+use crate::error::Result;
+use crate::logic::board::{require_position_in_board, try_position};
+use serde::{Deserialize, Serialize};
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
+use strum_macros::{Display, EnumIter};
 
 /// 移動方向（四方向）
-#[derive(Debug, Clone, Copy, EnumIter)]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumIter,
+)]
 pub enum Direction {
+    #[default]
     Up,
     Down,
     Left,
@@ -36,6 +41,48 @@ pub(crate) fn step_in_direction(
     }
 }
 
+/// 拉動的落點：目標沿 from → toward 的主軸（|dx| ≥ |dy| 取水平）逐格移動最多 distance 格，
+/// 遇到棋盤邊界或 `is_blocked` 的格子即停在前一格
+///
+/// toward 本身通常是施放者所在格，呼叫端應讓 `is_blocked` 對其回傳 true
+pub(crate) fn pull_destination(
+    board: Board,
+    from: Position,
+    toward: Position,
+    distance: Coord,
+    is_blocked: impl Fn(Position) -> bool,
+) -> Position {
+    let dx = toward.x as i32 - from.x as i32;
+    let dy = toward.y as i32 - from.y as i32;
+    let direction = match (dx, dy) {
+        (0, 0) => return from,
+        (dx, dy) if dx.abs() >= dy.abs() && dx > 0 => Direction::Right,
+        (dx, dy) if dx.abs() >= dy.abs() => Direction::Left,
+        (_, dy) if dy > 0 => Direction::Down,
+        _ => Direction::Up,
+    };
+
+    let mut current = from;
+    for _ in 0..distance {
+        match step_in_direction(board, current, direction) {
+            Some(next) if !is_blocked(next) => current = next,
+            Some(_) | None => break,
+        }
+    }
+    current
+}
+
+/// 依 `Direction` 順序找出 center 四周第一個在棋盤內且未被佔據的位置
+pub(crate) fn find_free_adjacent(
+    board: Board,
+    center: Position,
+    occupied: &HashSet<Position>,
+) -> Option<Position> {
+    Direction::iter()
+        .filter_map(|direction| step_in_direction(board, center, direction))
+        .find(|pos| !occupied.contains(pos))
+}
+
 /// 移動者（位置 + 陣營）
 #[derive(Debug)]
 pub struct Mover {
@@ -43,6 +90,9 @@ pub struct Mover {
     pub faction_alliance: ID,
 }
 
+/// 尋路狀態：(位置, 已用移動成本)
+type PathState = (Position, MovementCost);
+
 /// 可到達位置的資訊（含成本與前驅節點）
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ReachableInfo {
@@ -70,30 +120,33 @@ pub struct ReachableInfo {
 ///
 /// # 地形消耗：
 /// - `get_terrain_cost` 返回該位置的移動成本
-pub(crate) fn reachable_positions<F, G>(
+///
+/// # 方向性阻擋：
+/// - `is_step_blocked(from, to)` 為 true 時不可從 from 走到 to（如從崖下攀上懸崖），反向不受影響
+///
+/// # 等成本路徑：
+/// - 佇列以 (成本, 座標) 排序，同成本時座標較小（先比 x 再比 y）者先展開
+/// - 相鄰格依 `PATH_NEIGHBOR_OFFSETS`（上、左、右、下）展開，先展開者成為前驅，同成本不覆蓋
+/// - 因此同一盤面的 `prev` 與 `reconstruct_path` 結果固定，不受雜湊順序或平台影響；
+///   例如空曠棋盤上斜向移動時，會先沿 x 較小的一側走到底再轉向
+pub(crate) fn reachable_positions<F, G, H>(
     board: Board,
     mover: Mover,
     budget: MovementCost,
     get_occupant_alliance: F,
     get_terrain_cost: G,
+    is_step_blocked: H,
 ) -> Result<HashMap<Position, ReachableInfo>>
 where
     F: Fn(Position) -> Option<ID> + Copy,
     G: Fn(Position) -> MovementCost + Copy,
+    H: Fn(Position, Position) -> bool + Copy,
 {
     let from = mover.pos;
     let mover_alliance = mover.faction_alliance;
 
     // Fail fast：驗證起點在棋盤內
-    if !is_valid_position(board, from) {
-        return Err(BoardError::OutOfBoard {
-            x: from.x,
-            y: from.y,
-            width: board.width,
-            height: board.height,
-        }
-        .into());
-    }
+    require_position_in_board(board, from)?;
 
     let mut dist: HashMap<Position, MovementCost> = HashMap::new();
     let mut prev: HashMap<Position, Position> = HashMap::new();
@@ -109,9 +162,12 @@ where
             continue;
         }
 
-        // 探索相鄰位置
-        for direction in Direction::iter() {
-            if let Some(next_pos) = step_in_direction(board, pos, direction) {
+        // 依固定順序探索相鄰位置
+        for (dx, dy) in PATH_NEIGHBOR_OFFSETS {
+            if let Some(next_pos) = try_position(board, pos.x as i32 + dx, pos.y as i32 + dy) {
+                if is_step_blocked(pos, next_pos) {
+                    continue;
+                }
                 let terrain_cost = get_terrain_cost(next_pos);
                 let new_cost = cost + terrain_cost;
                 if new_cost > budget {
@@ -180,6 +236,170 @@ pub fn reconstruct_path(
     path
 }
 
+/// 在移動力預算內找出移動成本最低的目標格（多個目標只跑一次 Dijkstra）
+///
+/// 通行規則、展開順序與 `reachable_positions` 相同，第一個從佇列取出的可停留目標即為答案，
+/// 因此比較的是實際路徑成本而非直線距離（牆後的近目標可能比繞得到的遠目標更貴）。
+/// 友軍佔據的目標僅可穿越，不算抵達；起點本身在目標中時直接回傳成本 0。
+///
+/// 返回 (目標, 成本, 含起點與目標的路徑)；預算內沒有可停留的目標時返回 None
+pub(crate) fn nearest_reachable<F, G, H>(
+    board: Board,
+    mover: Mover,
+    budget: MovementCost,
+    goals: &[Position],
+    get_occupant_alliance: F,
+    get_terrain_cost: G,
+    is_step_blocked: H,
+) -> Result<Option<(Position, MovementCost, Vec<Position>)>>
+where
+    F: Fn(Position) -> Option<ID> + Copy,
+    G: Fn(Position) -> MovementCost + Copy,
+    H: Fn(Position, Position) -> bool + Copy,
+{
+    let from = mover.pos;
+    let mover_alliance = mover.faction_alliance;
+
+    // Fail fast：驗證起點在棋盤內
+    require_position_in_board(board, from)?;
+
+    let goals: HashSet<Position> = goals.iter().copied().collect();
+    let mut dist: HashMap<Position, MovementCost> = HashMap::new();
+    let mut prev: HashMap<Position, Position> = HashMap::new();
+    let mut queue: BinaryHeap<Reverse<(MovementCost, Position)>> = BinaryHeap::new();
+
+    dist.insert(from, 0);
+    queue.push(Reverse((0, from)));
+
+    while let Some(Reverse((cost, pos))) = queue.pop() {
+        if cost > dist.get(&pos).copied().unwrap_or(MovementCost::MAX) {
+            continue;
+        }
+        if goals.contains(&pos) && (pos == from || get_occupant_alliance(pos).is_none()) {
+            let mut path = vec![pos];
+            let mut current = pos;
+            while let Some(previous) = prev.get(&current) {
+                path.push(*previous);
+                current = *previous;
+            }
+            path.reverse();
+            return Ok(Some((pos, cost, path)));
+        }
+
+        for (dx, dy) in PATH_NEIGHBOR_OFFSETS {
+            if let Some(next_pos) = try_position(board, pos.x as i32 + dx, pos.y as i32 + dy) {
+                if is_step_blocked(pos, next_pos) {
+                    continue;
+                }
+                let new_cost = cost + get_terrain_cost(next_pos);
+                if new_cost > budget {
+                    continue;
+                }
+                if !is_passable(mover_alliance, get_occupant_alliance(next_pos)) {
+                    continue;
+                }
+
+                let best_cost = dist.get(&next_pos).copied().unwrap_or(MovementCost::MAX);
+                if new_cost < best_cost {
+                    dist.insert(next_pos, new_cost);
+                    prev.insert(next_pos, pos);
+                    queue.push(Reverse((new_cost, next_pos)));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// 在移動力預算內尋找到目標的路線，偏好避開危險地面
+///
+/// 通行規則與 `reachable_positions` 相同，移動力預算只計實際地形成本；
+/// `get_step_cost` 返回踏入該格的 (地形成本, 危險權重)，
+/// 路線以「地形成本 + 危險權重」總和最小者為準，
+/// 因此預算內有安全繞路時會繞開危險格，只有別無他路（或繞路超出預算）時才踩上去。
+/// 可到達的位置集合不受危險權重影響。
+///
+/// 同權重時以實際成本較低者優先，再依座標與 `PATH_NEIGHBOR_OFFSETS` 的固定順序決定；
+/// 沒有危險格時與 `reconstruct_path` 結果相同。
+///
+/// 返回含起點、含目標的完整位置序列；目標不可達時返回空序列
+pub(crate) fn find_path<F, G, H>(
+    board: Board,
+    mover: Mover,
+    budget: MovementCost,
+    target: Position,
+    get_occupant_alliance: F,
+    get_step_cost: G,
+    is_step_blocked: H,
+) -> Result<Vec<Position>>
+where
+    F: Fn(Position) -> Option<ID> + Copy,
+    G: Fn(Position) -> (MovementCost, MovementCost) + Copy,
+    H: Fn(Position, Position) -> bool + Copy,
+{
+    let from = mover.pos;
+    let mover_alliance = mover.faction_alliance;
+
+    // Fail fast：驗證起點在棋盤內
+    require_position_in_board(board, from)?;
+
+    // 狀態為 (位置, 已用移動成本)：同一格在不同預算剩餘下可能有不同的最佳延伸
+    let mut weight: HashMap<PathState, MovementCost> = HashMap::new();
+    let mut prev: HashMap<PathState, PathState> = HashMap::new();
+    let mut queue: BinaryHeap<Reverse<(MovementCost, MovementCost, Position)>> = BinaryHeap::new();
+
+    weight.insert((from, 0), 0);
+    queue.push(Reverse((0, 0, from)));
+
+    while let Some(Reverse((current_weight, used, pos))) = queue.pop() {
+        let state = (pos, used);
+        if current_weight > weight.get(&state).copied().unwrap_or(MovementCost::MAX) {
+            continue;
+        }
+        if pos == target {
+            let mut path = vec![pos];
+            let mut current = state;
+            while let Some(previous) = prev.get(&current) {
+                path.push(previous.0);
+                current = *previous;
+            }
+            path.reverse();
+            return Ok(path);
+        }
+
+        for (dx, dy) in PATH_NEIGHBOR_OFFSETS {
+            if let Some(next_pos) = try_position(board, pos.x as i32 + dx, pos.y as i32 + dy) {
+                if is_step_blocked(pos, next_pos) {
+                    continue;
+                }
+                let (terrain_cost, hazard_cost) = get_step_cost(next_pos);
+                let new_used = used + terrain_cost;
+                if new_used > budget {
+                    continue;
+                }
+                if !is_passable(mover_alliance, get_occupant_alliance(next_pos)) {
+                    continue;
+                }
+
+                let next_state = (next_pos, new_used);
+                let new_weight = current_weight + terrain_cost + hazard_cost;
+                let best_weight = weight
+                    .get(&next_state)
+                    .copied()
+                    .unwrap_or(MovementCost::MAX);
+                if new_weight < best_weight {
+                    weight.insert(next_state, new_weight);
+                    prev.insert(next_state, state);
+                    queue.push(Reverse((new_weight, new_used, next_pos)));
+                }
+            }
+        }
+    }
+
+    Ok(Vec::new())
+}
+
 /// 碰撞檢測：檢查位置是否可通行
 ///
 /// 規則：
@@ -0,0 +1,167 @@
+//! 情境包邏輯：從完整遊戲資料中挑出關卡實際引用到的單位、技能、物件
+
+use crate::domain::alias::{SkillName, TypeName};
+use crate::domain::core_types::{Effect, EffectNode, SkillType};
+use crate::error::{DataError, Result, UnitError};
+use crate::loader_schema::{LevelType, ObjectType, ScenarioBundle, UnitType};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// 技能效果中引用到的類型名稱
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillReferences {
+    /// 召喚（Summon）的單位類型
+    pub unit_types: Vec<TypeName>,
+    /// 生成（SpawnObject）的物件類型
+    pub object_types: Vec<TypeName>,
+}
+
+/// 收集技能效果樹中引用到的單位與物件類型（含巢狀條件、隨機、Buff 每回合效果與接觸效果）
+pub fn skill_references(skill: &SkillType) -> SkillReferences {
+    let mut references = SkillReferences::default();
+    match skill {
+        SkillType::Active { effects, .. } | SkillType::Reaction { effects, .. } => {
+            collect_node_references(effects, &mut references);
+        }
+        SkillType::Passive { .. } => {}
+    }
+    references
+}
+
+fn collect_node_references(nodes: &[EffectNode], references: &mut SkillReferences) {
+    for node in nodes {
+        match node {
+            EffectNode::Area { nodes, .. } => collect_node_references(nodes, references),
+            EffectNode::Branch {
+                on_success,
+                on_failure,
+                ..
+            } => {
+                collect_node_references(on_success, references);
+                collect_node_references(on_failure, references);
+            }
+            EffectNode::Leaf { effect, .. } => collect_effect_references(effect, references),
+        }
+    }
+}
+
+fn collect_effect_references(effect: &Effect, references: &mut SkillReferences) {
+    match effect {
+        Effect::Summon { unit_type, .. } => references.unit_types.push(unit_type.clone()),
+        Effect::SpawnObject {
+            object_type,
+            contact_effects,
+            ..
+        } => {
+            references.object_types.push(object_type.clone());
+            collect_node_references(contact_effects, references);
+        }
+        Effect::ApplyBuff { buff } => collect_node_references(&buff.per_turn_effects, references),
+        Effect::If { then, .. } => collect_effect_references(then, references),
+        Effect::Random { options } => {
+            for option in options {
+                collect_effect_references(&option.effect, references);
+            }
+        }
+        Effect::HpEffect { .. }
+        | Effect::MpEffect { .. }
+        | Effect::Dispel { .. }
+        | Effect::Chain { .. }
+        | Effect::ForcedMove { .. }
+        | Effect::Pull { .. }
+        | Effect::AllowRemainingMovement
+        | Effect::SwapPosition
+        | Effect::Teleport { .. }
+        | Effect::Trample { .. }
+        | Effect::Shield { .. } => {}
+    }
+}
+
+/// 打包情境：保留關卡放置、名冊與技能召喚／生成所引用到的定義
+///
+/// 單位類型來源為關卡預放單位、增援、名冊與技能召喚（遞迴展開）；
+/// 技能來源為上述單位的技能列表；物件類型來源為關卡物件放置與技能生成。
+/// 未被引用的定義不會打包，保留各清單原本的順序。
+/// 任一引用找不到定義時回傳錯誤。
+pub fn build_scenario_bundle(
+    level: LevelType,
+    roster: Vec<TypeName>,
+    units: Vec<UnitType>,
+    skills: Vec<SkillType>,
+    objects: Vec<ObjectType>,
+) -> Result<ScenarioBundle> {
+    let (used_units, used_skills, used_objects) = {
+        let unit_map: HashMap<&TypeName, &UnitType> =
+            units.iter().map(|unit| (&unit.name, unit)).collect();
+        let skill_map: HashMap<&SkillName, &SkillType> =
+            skills.iter().map(|skill| (skill.name(), skill)).collect();
+        let object_names: HashSet<&TypeName> = objects.iter().map(|object| &object.name).collect();
+
+        let mut used_units: HashSet<TypeName> = HashSet::new();
+        let mut used_skills: HashSet<SkillName> = HashSet::new();
+        let mut used_objects: HashSet<TypeName> = level
+            .object_placements
+            .iter()
+            .map(|placement| placement.object_type_name.clone())
+            .collect();
+        let mut pending: VecDeque<TypeName> = level
+            .unit_placements
+            .iter()
+            .chain(level.reinforcements.iter().flat_map(|wave| &wave.units))
+            .map(|placement| placement.unit_type_name.clone())
+            .chain(roster.iter().cloned())
+            .collect();
+
+        while let Some(type_name) = pending.pop_front() {
+            if used_units.contains(&type_name) {
+                continue;
+            }
+            let unit = unit_map
+                .get(&type_name)
+                .ok_or_else(|| DataError::UnitTypeNotFound {
+                    type_name: type_name.clone(),
+                })?;
+            for skill_name in &unit.skills {
+                if !used_skills.insert(skill_name.clone()) {
+                    continue;
+                }
+                let skill = skill_map
+                    .get(skill_name)
+                    .ok_or_else(|| UnitError::SkillNotFound {
+                        skill_name: skill_name.clone(),
+                    })?;
+                let references = skill_references(skill);
+                pending.extend(references.unit_types);
+                used_objects.extend(references.object_types);
+            }
+            used_units.insert(type_name);
+        }
+
+        if let Some(missing) = used_objects
+            .iter()
+            .find(|name| !object_names.contains(name))
+        {
+            return Err(DataError::ObjectTypeNotFound {
+                type_name: missing.clone(),
+            }
+            .into());
+        }
+        (used_units, used_skills, used_objects)
+    };
+
+    Ok(ScenarioBundle {
+        level,
+        roster,
+        units: units
+            .into_iter()
+            .filter(|unit| used_units.contains(&unit.name))
+            .collect(),
+        skills: skills
+            .into_iter()
+            .filter(|skill| used_skills.contains(skill.name()))
+            .collect(),
+        objects: objects
+            .into_iter()
+            .filter(|object| used_objects.contains(&object.name))
+            .collect(),
+    })
+}
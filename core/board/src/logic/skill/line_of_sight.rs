@@ -1,5 +1,9 @@
+use crate::domain::alias::Coord;
+use crate::domain::core_types::RangeMetric;
 use crate::ecs_types::components::Position;
-use std::collections::HashSet;
+use crate::ecs_types::resources::Board;
+use crate::logic::skill::skill_range::compute_range_positions;
+use std::collections::{HashMap, HashSet};
 
 /// 判斷從 `from` 到 `to` 是否有視線（Bresenham 直線算法）
 ///
@@ -12,10 +16,56 @@ pub(crate) fn has_line_of_sight(
     to: Position,
     blocks_sight: &HashSet<Position>,
 ) -> bool {
+    is_line_clear(from, to, |pos| blocks_sight.contains(&pos))
+}
+
+/// 考慮地形高度的視線判斷
+///
+/// 與 `has_line_of_sight` 相同，但阻擋物高度低於觀察者所在高度時不擋視線
+/// （高處可越過較低的障礙物）。`elevations` 未列出的格子視為高度 0，
+/// 全部為 0 時結果與 `has_line_of_sight` 相同。
+pub(crate) fn has_elevated_line_of_sight(
+    from: Position,
+    to: Position,
+    blocks_sight: &HashSet<Position>,
+    elevations: &HashMap<Position, i32>,
+) -> bool {
+    let viewer_elevation = tile_elevation(elevations, from);
+    is_line_clear(from, to, |pos| {
+        blocks_sight.contains(&pos) && tile_elevation(elevations, pos) >= viewer_elevation
+    })
+}
+
+/// 計算觀察者可見的格子（視野範圍內且有視線），含觀察者所在格
+///
+/// 視線規則同 `has_elevated_line_of_sight`，供戰爭迷霧渲染使用。
+pub(crate) fn compute_visible_positions(
+    viewer: Position,
+    sight_range: Coord,
+    board: Board,
+    blocks_sight: &HashSet<Position>,
+    elevations: &HashMap<Position, i32>,
+) -> HashSet<Position> {
+    compute_range_positions(viewer, (0, sight_range), RangeMetric::Manhattan, board)
+        .into_iter()
+        .filter(|pos| has_elevated_line_of_sight(viewer, *pos, blocks_sight, elevations))
+        .collect()
+}
+
+/// 取得格子高度，未列出的格子視為平地（0）
+pub(crate) fn tile_elevation(elevations: &HashMap<Position, i32>, pos: Position) -> i32 {
+    match elevations.get(&pos) {
+        Some(elevation) => *elevation,
+        None => 0,
+    }
+}
+
+/// Bresenham 直線走訪，`is_blocked` 判斷某格是否阻擋視線
+fn is_line_clear(from: Position, to: Position, is_blocked: impl Fn(Position) -> bool) -> bool {
     if from == to {
         return true;
     }
-    if blocks_sight.contains(&from) || blocks_sight.contains(&to) {
+    if is_blocked(from) || is_blocked(to) {
         return false;
     }
 
@@ -53,7 +103,7 @@ pub(crate) fn has_line_of_sight(
             x: x as usize,
             y: y as usize,
         };
-        if blocks_sight.contains(&mid) {
+        if is_blocked(mid) {
             return false;
         }
     }
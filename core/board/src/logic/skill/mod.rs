@@ -1,6 +1,7 @@
 //! 技能邏輯
 
 pub mod line_of_sight;
+pub mod skill_category;
 pub mod skill_check;
 pub mod skill_execution;
 pub mod skill_range;
@@ -9,7 +10,8 @@ pub mod skill_target;
 pub mod unit_attributes;
 
 use crate::domain::alias::{Coord, ID};
-use crate::domain::core_types::TargetFilter;
+use crate::domain::constants::FACING_SNAP_TAN;
+use crate::domain::core_types::{RangeMetric, TargetFilter};
 use crate::ecs_types::components::{Occupant, Position};
 use crate::error::{BoardError, Result};
 
@@ -35,6 +37,37 @@ pub(crate) fn manhattan_distance(a: Position, b: Position) -> Coord {
     dx + dy
 }
 
+/// 依度量計算兩格距離
+pub(crate) fn metric_distance(metric: RangeMetric, a: Position, b: Position) -> Coord {
+    let dx = (a.x as i32 - b.x as i32).unsigned_abs() as Coord;
+    let dy = (a.y as i32 - b.y as i32).unsigned_abs() as Coord;
+    metric_length(metric, dx, dy)
+}
+
+/// 依度量計算位移 (dx, dy) 的長度（取絕對值後傳入）
+///
+/// 歐幾里得距離無條件進位，故「長度 <= r」等價於 dx² + dy² <= r²。
+pub(crate) fn metric_length(metric: RangeMetric, dx: Coord, dy: Coord) -> Coord {
+    match metric {
+        RangeMetric::Manhattan => dx + dy,
+        RangeMetric::Chebyshev => dx.max(dy),
+        RangeMetric::Euclidean => ceil_sqrt(dx * dx + dy * dy),
+    }
+}
+
+/// 整數平方根（無條件進位）
+fn ceil_sqrt(value: Coord) -> Coord {
+    let mut root = (value as f64).sqrt() as Coord;
+    // 浮點誤差修正
+    while root * root > value {
+        root -= 1;
+    }
+    while root * root < value {
+        root += 1;
+    }
+    root
+}
+
 /// 檢查單位是否通過篩選條件
 pub(crate) fn is_in_filter(caster: &UnitInfo, target: &UnitInfo, filter: TargetFilter) -> bool {
     let is_caster = target.occupant == caster.occupant;
@@ -73,3 +106,43 @@ pub(crate) fn normalize_direction(caster: Position, target: Position) -> Result<
         .into()),
     }
 }
+
+/// 將方向吸附到最接近的 8 方位單位步進（正交或斜角）
+///
+/// 次軸與主軸比值不超過 tan(22.5°) 時取正交方向，否則取斜角方向。
+/// caster 與 target 同格時無方向可言，回傳錯誤。
+pub(crate) fn snap_direction(caster: Position, target: Position) -> Result<(i32, i32)> {
+    let dx = (target.x as i32) - (caster.x as i32);
+    let dy = (target.y as i32) - (caster.y as i32);
+    if dx == 0 && dy == 0 {
+        return Err(BoardError::InvalidSkillTarget {
+            shape: "Line".to_string(),
+            caster_x: caster.x,
+            caster_y: caster.y,
+            target_x: target.x,
+            target_y: target.y,
+        }
+        .into());
+    }
+
+    let (abs_x, abs_y) = (dx.abs() as f64, dy.abs() as f64);
+    if abs_y <= abs_x * FACING_SNAP_TAN {
+        Ok((dx.signum(), 0))
+    } else if abs_x <= abs_y * FACING_SNAP_TAN {
+        Ok((0, dy.signum()))
+    } else {
+        Ok((dx.signum(), dy.signum()))
+    }
+}
+
+/// 依 Line 的 `snap_to_facing` 選擇方向計算方式
+pub(crate) fn line_direction(
+    caster: Position,
+    target: Position,
+    snap_to_facing: bool,
+) -> Result<(i32, i32)> {
+    match snap_to_facing {
+        true => snap_direction(caster, target),
+        false => normalize_direction(caster, target),
+    }
+}
@@ -0,0 +1,104 @@
+//! 技能類別邏輯（單位可學技能限制）
+
+use crate::domain::core_types::{ExclusiveTagGroup, SkillTag, SkillType};
+use crate::loader_schema::UnitType;
+
+/// 判斷標籤是否為技能類別標籤
+pub fn is_category_tag(tag: &SkillTag) -> bool {
+    matches!(tag, SkillTag::Melee | SkillTag::Ranged | SkillTag::Magic)
+}
+
+/// 驗證技能標籤：不可重複，且每個互斥群組選用的數量須在 [min, max] 內
+pub fn validate_skill_tags(tags: &[SkillTag], groups: &[ExclusiveTagGroup]) -> Result<(), String> {
+    for (index, tag) in tags.iter().enumerate() {
+        if tags[..index].contains(tag) {
+            return Err(format!("標籤「{}」重複", tag));
+        }
+    }
+
+    for group in groups {
+        let selected: Vec<String> = tags
+            .iter()
+            .filter(|tag| group.tags.contains(tag))
+            .map(|tag| tag.to_string())
+            .collect();
+        if selected.len() > group.max {
+            return Err(format!(
+                "「{}」最多只能選 {} 個標籤，目前為 {}",
+                group.name,
+                group.max,
+                selected.join("、")
+            ));
+        }
+        if selected.len() < group.min {
+            let options: Vec<String> = group.tags.iter().map(|tag| tag.to_string()).collect();
+            return Err(format!(
+                "「{}」至少需選 {} 個標籤（{}）",
+                group.name,
+                group.min,
+                options.join("、")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 驗證單位的技能皆符合其欄位上限與可學類別
+///
+/// 未設定 max_skills / allowed_categories 的單位不受對應限制；沒有類別標籤的技能視為通用技能。
+/// 回傳所有違規訊息，方便編輯器一次顯示。
+pub fn validate_unit_skills(unit: &UnitType, skills: &[SkillType]) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    let max_skills = unit.max_skills.unwrap_or(usize::MAX);
+    if unit.skills.len() > max_skills {
+        errors.push(format!(
+            "技能數量（{}）超過欄位上限（{}）",
+            unit.skills.len(),
+            max_skills
+        ));
+    }
+
+    for skill_name in &unit.skills {
+        let skill = skills.iter().find(|skill| match skill {
+            SkillType::Active { name, .. }
+            | SkillType::Reaction { name, .. }
+            | SkillType::Passive { name, .. } => name == skill_name,
+        });
+        let tags = match skill {
+            Some(
+                SkillType::Active { tags, .. }
+                | SkillType::Reaction { tags, .. }
+                | SkillType::Passive { tags, .. },
+            ) => tags,
+            None => {
+                errors.push(format!("技能「{}」不存在", skill_name));
+                continue;
+            }
+        };
+
+        if unit.allowed_categories.is_empty() {
+            continue;
+        }
+
+        let categories: Vec<&SkillTag> = tags.iter().filter(|t| is_category_tag(t)).collect();
+        let allowed = categories.is_empty()
+            || categories
+                .iter()
+                .any(|category| unit.allowed_categories.contains(category));
+        if !allowed {
+            let names: Vec<String> = categories.iter().map(|c| c.to_string()).collect();
+            errors.push(format!(
+                "技能「{}」的類別（{}）不在單位可學類別內",
+                skill_name,
+                names.join("、")
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
@@ -73,6 +73,23 @@ fn compute_hit_result(
     HitCheckResult::Hit { crit }
 }
 
+/// 隱蔽平骰：原本命中（含格擋）且目標有隱蔽時再擲一次 d100，
+/// 骰 ≤ 隱蔽機率即改判為 Evade；回傳判定結果與平骰值（未擲骰為 None）
+pub(crate) fn apply_concealment(
+    check: HitCheckResult,
+    concealment: i32,
+    rng_int: &mut impl FnMut() -> i32,
+) -> (HitCheckResult, Option<i32>) {
+    if concealment <= 0 || check == HitCheckResult::Evade {
+        return (check, None);
+    }
+    let roll = rng_int();
+    match roll <= concealment {
+        true => (HitCheckResult::Evade, Some(roll)),
+        false => (check, Some(roll)),
+    }
+}
+
 fn is_crit(roll: i32, crit_rate: i32) -> bool {
     if crit_rate <= 0 {
         return false;
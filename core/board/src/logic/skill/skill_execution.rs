@@ -1,29 +1,38 @@
 //! 技能效果樹執行邏輯
 
-use crate::domain::alias::{ID, SkillName, TypeName};
+use crate::domain::alias::{Coord, ID, SkillName, TypeName};
 use crate::domain::constants::{
     ACCURACY_PENALTY_WHEN_ENEMY_ADJACENT, CRIT_DAMAGE_MULTIPLIER, FLANKING_REQUIRED_ALLIES,
-    FORCED_HIT_PREVIEW_ROLL,
+    FORCED_HIT_PREVIEW_ROLL, HIGH_GROUND_ACCURACY_BONUS, PERCENT_BASE,
 };
 use crate::domain::core_types::{
-    AccuracyBreakdown, AccuracySource, Attribute, BlockBreakdown, CasterOrTarget, DefenseType,
-    Effect, EffectCondition, EffectNode, EvasionBreakdown, HitCheckBreakdowns, Scaling, SkillTag,
-    TargetFilter,
+    AccuracyBreakdown, AccuracySource, Attribute, BlockBreakdown, BuffType, CasterOrTarget,
+    DamageType, DefenseType, Effect, EffectCondition, EffectNode, EffectOrder, EvasionBreakdown,
+    HitCheckBreakdowns, Scaling, SkillTag, TargetCondition, TargetFilter, WeightedEffect,
 };
 use crate::ecs_types::components::{AttributeBundle, Occupant, Position};
 use crate::ecs_types::resources::Board;
 use crate::error::Result;
-use crate::logic::board::try_position;
-use crate::logic::skill::skill_check::{HitCheckResult, resolve_hit};
+use crate::logic::board::neighbors4;
+use crate::logic::skill::skill_check::{HitCheckResult, apply_concealment, resolve_hit};
 use crate::logic::skill::skill_range::compute_affected_positions;
-use crate::logic::skill::{UnitInfo, is_in_filter};
-use std::collections::HashMap;
+use crate::logic::skill::{UnitInfo, is_in_filter, manhattan_distance};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 /// 戰鬥屬性（傳入 resolve_effect_tree 的單位資料）
 #[derive(Debug, Clone)]
 pub struct CombatStats {
     pub unit_info: UnitInfo,
     pub attribute: AttributeBundle,
+    /// 所在格的地形高度（平地為 0）
+    pub elevation: i32,
+    /// 所在格的隱蔽機率（百分比，無隱蔽為 0）
+    pub concealment: i32,
+    /// 身上的 Buff 名稱（供條件效果判斷）
+    pub buffs: Vec<String>,
+    /// 各傷害屬性的抗性百分比（負值為弱點，未列出視為 0）
+    pub resistances: HashMap<DamageType, i32>,
 }
 
 /// 棋盤上的物件資訊
@@ -60,33 +69,100 @@ pub enum CheckResult {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ResolvedEffect {
     NoEffect,
-    HpChange { raw_amount: i32, final_amount: i32 },
-    SpawnObject { object_type: TypeName },
+    HpChange {
+        raw_amount: i32,
+        final_amount: i32,
+    },
+    SpawnObject {
+        object_type: TypeName,
+    },
     ApplyBuff(String),
+    Dispel {
+        beneficial: bool,
+    },
+    /// 施放者瞬移到指定位置（條目目標為施放者）
+    Teleport {
+        to: Position,
+    },
+    /// 目標朝施放者拉動（落點於寫入階段依當下佔據狀況決定）
+    Pull {
+        distance: Coord,
+    },
+    /// 在施放者相鄰空格召喚單位（條目目標為施放者，落點於寫入階段決定）
+    Summon {
+        unit_type: TypeName,
+        duration: u32,
+    },
+    /// 目標獲得護盾
+    Shield {
+        amount: i32,
+        duration: u32,
+    },
+}
+
+/// 依站位決定的命中修正（夾擊、相鄰敵人、高低差）
+#[derive(Debug, Clone, Copy)]
+struct PositionalAccuracy {
+    flanking_bonus: i32,
+    adjacent_penalty: i32,
+    high_ground_bonus: i32,
 }
 
 /// 組裝攻擊命中值，回傳逐項來源明細
 fn compute_attacker_accuracy(
     caster: &CombatStats,
     condition: &EffectCondition,
-    flanking_bonus: i32,
-    adjacent_penalty: i32,
+    positional: PositionalAccuracy,
 ) -> AccuracyBreakdown {
     let base = match condition.accuracy_source {
         AccuracySource::Physical => caster.attribute.physical_accuracy.0,
         AccuracySource::Magical => caster.attribute.magical_accuracy.0,
     };
     let skill_bonus = condition.accuracy_bonus;
-    let total = base + skill_bonus + flanking_bonus + adjacent_penalty;
+    let total = base
+        + skill_bonus
+        + positional.flanking_bonus
+        + positional.adjacent_penalty
+        + positional.high_ground_bonus;
     AccuracyBreakdown {
         base,
         skill_bonus,
-        flanking_bonus,
-        adjacent_penalty,
+        flanking_bonus: positional.flanking_bonus,
+        adjacent_penalty: positional.adjacent_penalty,
+        high_ground_bonus: positional.high_ground_bonus,
         total,
     }
 }
 
+/// 計算 caster 對 target 的站位命中修正
+fn compute_positional_accuracy(
+    skill_tags: &[SkillTag],
+    caster: &CombatStats,
+    caster_pos: Position,
+    target: &CombatStats,
+    target_pos: Position,
+    units_on_board: &HashMap<Position, CombatStats>,
+    board: Board,
+) -> PositionalAccuracy {
+    PositionalAccuracy {
+        flanking_bonus: compute_flanking_bonus(
+            skill_tags,
+            caster,
+            target_pos,
+            units_on_board,
+            board,
+        ),
+        adjacent_penalty: compute_adjacent_enemy_penalty(
+            skill_tags,
+            caster,
+            caster_pos,
+            units_on_board,
+            board,
+        ),
+        high_ground_bonus: compute_high_ground_bonus(skill_tags, caster, target),
+    }
+}
+
 /// 預覽單體技能「第一個判定」的命中數值
 ///
 /// 只看頂層 `nodes[0]`：
@@ -113,16 +189,20 @@ pub(crate) fn preview_first_branch_accuracy(
         None => return None,
     };
 
-    // 注意：以下「算 flanking/adjacent → 組 accuracy → 取 defender 值組 breakdown」
+    // 注意：以下「算站位修正 → 組 accuracy → 取 defender 值組 breakdown」
     // 這條組裝鏈，與 `resolve_branch_check` 重複。
     // 不抽共用的原因是 `resolve_branch_check` 會需要太多參數：
     // 故刻意複製。修改此段時，請同步檢查 `resolve_branch_check`。
-    let flanking_bonus =
-        compute_flanking_bonus(skill_tags, caster, target_pos, units_on_board, board);
-    let adjacent_penalty =
-        compute_adjacent_enemy_penalty(skill_tags, caster, caster_pos, units_on_board, board);
-    let attacker_accuracy =
-        compute_attacker_accuracy(caster, condition, flanking_bonus, adjacent_penalty);
+    let positional = compute_positional_accuracy(
+        skill_tags,
+        caster,
+        caster_pos,
+        target_stats,
+        target_pos,
+        units_on_board,
+        board,
+    );
+    let attacker_accuracy = compute_attacker_accuracy(caster, condition, positional);
     let (defender_evasion, defender_block) =
         get_defense_values(&target_stats.attribute, condition.defense_type);
 
@@ -147,6 +227,8 @@ pub struct CheckDetail {
     pub defense_type: DefenseType,
     pub breakdowns: HitCheckBreakdowns,
     pub roll: i32,
+    /// 隱蔽平骰（目標無隱蔽、原本未命中或強制命中時為 None）
+    pub concealment_roll: Option<i32>,
 }
 
 /// 單筆效果條目
@@ -196,9 +278,17 @@ pub(crate) fn resolve_effect_tree(
                 nodes: inner_nodes,
             } => {
                 let affected_positions =
-                    compute_affected_positions(area, caster_pos, target_pos, board)?;
+                    compute_affected_positions(area, caster_pos, target_pos, board, |pos| {
+                        units_on_board.contains_key(&pos)
+                            || objects_on_board
+                                .get(&pos)
+                                .is_some_and(|object| object.occupies_tile)
+                    })?;
 
                 for target_pos in affected_positions {
+                    if is_spared_ally(skill_tags, &caster.unit_info, target_pos, units_on_board) {
+                        continue;
+                    }
                     resolve_at_position(
                         caster_id,
                         skill_name,
@@ -241,6 +331,154 @@ pub(crate) fn resolve_effect_tree(
     Ok(entries)
 }
 
+/// 多段攻擊：呼叫 `resolve_once` 共 `hits` 次並串接條目
+///
+/// 每段各自消耗 rng 判定命中與爆擊，未命中的段落只留下 NoEffect 條目，
+/// 套用時 HpChange 自然只加總命中的段落。位移只在第一段生效（見 [`drop_movement_entries`]）。
+pub(crate) fn resolve_hits(
+    hits: u8,
+    mut resolve_once: impl FnMut() -> Result<Vec<EffectEntry>>,
+) -> Result<Vec<EffectEntry>> {
+    let mut entries = Vec::new();
+    for hit in 0..hits {
+        let mut hit_entries = resolve_once()?;
+        if hit > 0 {
+            drop_movement_entries(&mut hit_entries);
+        }
+        entries.extend(hit_entries);
+    }
+    Ok(entries)
+}
+
+/// 移除會改變站位的條目：多段攻擊的第二段起只重複傷害等效果，不再重複拉動或瞬移
+pub(crate) fn drop_movement_entries(entries: &mut Vec<EffectEntry>) {
+    entries.retain(|entry| {
+        !matches!(
+            entry.effect,
+            ResolvedEffect::Teleport { .. } | ResolvedEffect::Pull { .. }
+        )
+    });
+}
+
+/// 依 `order` 排列頂層效果節點的套用順序
+///
+/// `MovementFirst` 以穩定排序將含位移效果的節點移到前面，同類節點維持列出順序。
+pub(crate) fn order_effect_nodes(nodes: &[EffectNode], order: EffectOrder) -> Vec<&EffectNode> {
+    let mut ordered: Vec<&EffectNode> = nodes.iter().collect();
+    match order {
+        EffectOrder::AsListed => {}
+        EffectOrder::MovementFirst => {
+            ordered.sort_by_key(|node| !node_has_movement(node));
+        }
+    }
+    ordered
+}
+
+/// 節點（含子節點）是否包含會改變站位的效果
+fn node_has_movement(node: &EffectNode) -> bool {
+    match node {
+        EffectNode::Area { nodes, .. } => nodes.iter().any(node_has_movement),
+        EffectNode::Branch {
+            on_success,
+            on_failure,
+            ..
+        } => on_success.iter().chain(on_failure).any(node_has_movement),
+        EffectNode::Leaf { effect, .. } => effect_has_movement(effect),
+    }
+}
+
+/// 效果（含條件效果的內層）是否會改變站位
+fn effect_has_movement(effect: &Effect) -> bool {
+    match effect {
+        Effect::ForcedMove { .. }
+        | Effect::Pull { .. }
+        | Effect::SwapPosition
+        | Effect::Teleport { .. }
+        | Effect::Trample { .. } => true,
+        Effect::If { then, .. } => effect_has_movement(then),
+        Effect::Random { options } => options
+            .iter()
+            .any(|option| effect_has_movement(&option.effect)),
+        _ => false,
+    }
+}
+
+/// 以護盾吸收 HP 變化，回傳（剩餘護盾, 實際 HP 變化）
+///
+/// 只有傷害（負值）會被吸收；治療（正值）不受護盾影響。
+pub(crate) fn absorb_with_shield(shield: i32, amount: i32) -> (i32, i32) {
+    if amount >= 0 {
+        return (shield, amount);
+    }
+    let absorbed = shield.min(-amount).max(0);
+    (shield - absorbed, amount + absorbed)
+}
+
+/// 在效果節點中找出指定名稱的 buff 定義（含巢狀條件、隨機、Buff 每回合效果與接觸效果）
+///
+/// 解析後的 `ResolvedEffect::ApplyBuff` 只記 buff 名稱，寫入階段以此取回共享的定義。
+pub(crate) fn find_buff_def<'a>(
+    nodes: &'a [EffectNode],
+    buff_name: &str,
+) -> Option<&'a Arc<BuffType>> {
+    nodes.iter().find_map(|node| match node {
+        EffectNode::Area { nodes, .. } => find_buff_def(nodes, buff_name),
+        EffectNode::Branch {
+            on_success,
+            on_failure,
+            ..
+        } => find_buff_def(on_success, buff_name).or_else(|| find_buff_def(on_failure, buff_name)),
+        EffectNode::Leaf { effect, .. } => find_effect_buff_def(effect, buff_name),
+    })
+}
+
+fn find_effect_buff_def<'a>(effect: &'a Effect, buff_name: &str) -> Option<&'a Arc<BuffType>> {
+    match effect {
+        Effect::ApplyBuff { buff } if buff.name == buff_name => Some(buff),
+        Effect::ApplyBuff { buff } => find_buff_def(&buff.per_turn_effects, buff_name),
+        Effect::SpawnObject {
+            contact_effects, ..
+        } => find_buff_def(contact_effects, buff_name),
+        Effect::If { then, .. } => find_effect_buff_def(then, buff_name),
+        Effect::Random { options } => options
+            .iter()
+            .find_map(|option| find_effect_buff_def(&option.effect, buff_name)),
+        Effect::HpEffect { .. }
+        | Effect::MpEffect { .. }
+        | Effect::Dispel { .. }
+        | Effect::Chain { .. }
+        | Effect::ForcedMove { .. }
+        | Effect::Pull { .. }
+        | Effect::AllowRemainingMovement
+        | Effect::SwapPosition
+        | Effect::Teleport { .. }
+        | Effect::Trample { .. }
+        | Effect::Summon { .. }
+        | Effect::Shield { .. } => None,
+    }
+}
+
+/// 反噬條目：施放者失去 `recoil` HP，無須判定；`recoil` 為 0 時不產生條目
+///
+/// 呼叫端應將此條目排在主要效果之後，使反噬於主要效果結算後才套用。
+pub(crate) fn recoil_entry(caster_id: ID, skill_name: &str, recoil: u32) -> Option<EffectEntry> {
+    if recoil == 0 {
+        return None;
+    }
+    let amount = -(recoil as i32);
+    Some(EffectEntry {
+        caster: caster_id,
+        skill_name: skill_name.to_string(),
+        target: CheckTarget::Unit(caster_id),
+        check: CheckResult::Auto,
+        check_detail: None,
+        effect: ResolvedEffect::HpChange {
+            raw_amount: amount,
+            final_amount: amount,
+        },
+    })
+}
+
 /// 在指定位置解析效果節點
 fn resolve_at_position(
     caster_id: ID,
@@ -263,12 +501,12 @@ fn resolve_at_position(
             if !is_in_filter(&caster.unit_info, &target_stats.unit_info, filter) {
                 return;
             }
-            let flanking_bonus =
-                compute_flanking_bonus(skill_tags, caster, target_pos, units_on_board, board);
-            let adjacent_enemy_penalty = compute_adjacent_enemy_penalty(
+            let positional = compute_positional_accuracy(
                 skill_tags,
                 caster,
                 caster_pos,
+                target_stats,
+                target_pos,
                 units_on_board,
                 board,
             );
@@ -278,8 +516,8 @@ fn resolve_at_position(
                 nodes,
                 caster,
                 target_stats,
-                flanking_bonus,
-                adjacent_enemy_penalty,
+                units_on_board,
+                positional,
                 CheckResult::Auto,
                 None,
                 rng,
@@ -292,9 +530,9 @@ fn resolve_at_position(
                 caster_id,
                 skill_name,
                 nodes,
+                caster_pos,
                 target_pos,
-                units_on_board,
-                objects_on_board,
+                is_tile_occupied(target_pos, units_on_board, objects_on_board),
                 entries,
             );
         }
@@ -334,6 +572,34 @@ fn compute_adjacent_enemy_penalty(
     ACCURACY_PENALTY_WHEN_ENEMY_ADJACENT
 }
 
+/// 遠程技能（Ranged tag）由高處攻擊低處時的命中加成
+fn compute_high_ground_bonus(
+    skill_tags: &[SkillTag],
+    caster: &CombatStats,
+    target: &CombatStats,
+) -> i32 {
+    let is_ranged = skill_tags.iter().any(|t| matches!(t, SkillTag::Ranged));
+    if !is_ranged || caster.elevation <= target.elevation {
+        return 0;
+    }
+    HIGH_GROUND_ACCURACY_BONUS
+}
+
+/// 技能帶 NoFriendlyFire tag 時，範圍內與施放者同盟的單位不受影響（不論節點的 filter）
+fn is_spared_ally(
+    skill_tags: &[SkillTag],
+    caster: &UnitInfo,
+    target_pos: Position,
+    units_on_board: &HashMap<Position, CombatStats>,
+) -> bool {
+    if !skill_tags.contains(&SkillTag::NoFriendlyFire) {
+        return false;
+    }
+    units_on_board
+        .get(&target_pos)
+        .is_some_and(|unit| is_in_filter(caster, &unit.unit_info, TargetFilter::Ally))
+}
+
 /// 帶判定結果的效果節點解析
 fn resolve_nodes_for_unit(
     caster_id: ID,
@@ -341,8 +607,8 @@ fn resolve_nodes_for_unit(
     nodes: &[EffectNode],
     caster: &CombatStats,
     target: &CombatStats,
-    flanking_bonus: i32,
-    adjacent_penalty: i32,
+    units_on_board: &HashMap<Position, CombatStats>,
+    positional: PositionalAccuracy,
     parent_check: CheckResult,
     parent_check_detail: Option<CheckDetail>,
     rng: &mut impl FnMut() -> i32,
@@ -352,32 +618,25 @@ fn resolve_nodes_for_unit(
     for node in nodes {
         match node {
             EffectNode::Leaf { who, effect } => {
+                // 條件 / 隨機效果：條件不成立或未抽中任何選項時整個 Leaf 不產生條目
+                let effect = match select_effect(effect, target, rng) {
+                    Some(effect) => effect,
+                    None => continue,
+                };
                 let resolved_target = match who {
                     CasterOrTarget::Caster => caster,
                     CasterOrTarget::Target => target,
                 };
                 let check_target = occupant_to_check_target(resolved_target.unit_info.occupant);
                 match effect {
-                    Effect::HpEffect { scaling } => {
-                        let raw_amount = compute_scaling(scaling, caster, target);
-                        let crit_multiplier = match parent_check {
-                            CheckResult::Hit { crit: true } | CheckResult::Block { crit: true } => {
-                                CRIT_DAMAGE_MULTIPLIER
-                            }
-                            _ => 1,
-                        };
-                        let final_amount = raw_amount * crit_multiplier;
-                        let final_amount = match parent_check {
-                            CheckResult::Block { .. } => apply_block_protection(
-                                final_amount,
-                                target.attribute.block_protection.0,
-                            ),
-                            CheckResult::Auto
-                            | CheckResult::Hit { .. }
-                            | CheckResult::Evade
-                            | CheckResult::Resisted
-                            | CheckResult::Affected => final_amount,
-                        };
+                    Effect::HpEffect {
+                        scaling,
+                        damage_type,
+                    } => {
+                        let (raw_amount, final_amount) =
+                            compute_hp_change(scaling, caster, target, parent_check);
+                        let final_amount =
+                            apply_damage_resistance(final_amount, *damage_type, resolved_target);
                         entries.push(EffectEntry {
                             caster: caster_id,
                             skill_name: skill_name.to_string(),
@@ -400,9 +659,111 @@ fn resolve_nodes_for_unit(
                             effect: ResolvedEffect::ApplyBuff(buff.name.clone()),
                         });
                     }
+                    Effect::Chain {
+                        scaling,
+                        damage_type,
+                        filter,
+                        max_jumps,
+                        jump_range,
+                        falloff_percent,
+                    } => {
+                        // 第一跳：與 HpEffect 相同，套用判定結果（爆擊 / 格擋）與抗性
+                        let (raw_amount, final_amount) =
+                            compute_hp_change(scaling, caster, resolved_target, parent_check);
+                        let final_amount =
+                            apply_damage_resistance(final_amount, *damage_type, resolved_target);
+                        entries.push(EffectEntry {
+                            caster: caster_id,
+                            skill_name: skill_name.to_string(),
+                            target: check_target,
+                            check: parent_check,
+                            check_detail: parent_check_detail.clone(),
+                            effect: ResolvedEffect::HpChange {
+                                raw_amount,
+                                final_amount,
+                            },
+                        });
+
+                        // 後續跳躍：不再判定，數值逐跳衰減
+                        let hops = chain_hops(
+                            &caster.unit_info,
+                            resolved_target.unit_info.occupant,
+                            *filter,
+                            *max_jumps,
+                            *jump_range,
+                            units_on_board,
+                        );
+                        let mut remaining_percent = PERCENT_BASE;
+                        for hop_stats in hops {
+                            remaining_percent =
+                                remaining_percent * (PERCENT_BASE - falloff_percent) / PERCENT_BASE;
+                            let raw_amount = compute_scaling(scaling, caster, hop_stats)
+                                * remaining_percent
+                                / PERCENT_BASE;
+                            let final_amount =
+                                apply_damage_resistance(raw_amount, *damage_type, hop_stats);
+                            entries.push(EffectEntry {
+                                caster: caster_id,
+                                skill_name: skill_name.to_string(),
+                                target: occupant_to_check_target(hop_stats.unit_info.occupant),
+                                check: CheckResult::Auto,
+                                check_detail: None,
+                                effect: ResolvedEffect::HpChange {
+                                    raw_amount,
+                                    final_amount,
+                                },
+                            });
+                        }
+                    }
+                    Effect::Dispel { beneficial } => {
+                        entries.push(EffectEntry {
+                            caster: caster_id,
+                            skill_name: skill_name.to_string(),
+                            target: check_target,
+                            check: parent_check,
+                            check_detail: parent_check_detail.clone(),
+                            effect: ResolvedEffect::Dispel {
+                                beneficial: *beneficial,
+                            },
+                        });
+                    }
                     Effect::SpawnObject { .. } => {
                         // TODO 新增格子著火的測試
                     }
+                    Effect::Teleport { .. } => {
+                        // 目標格已有單位，無法瞬移
+                    }
+                    Effect::Pull { distance } => {
+                        entries.push(EffectEntry {
+                            caster: caster_id,
+                            skill_name: skill_name.to_string(),
+                            target: check_target,
+                            check: parent_check,
+                            check_detail: parent_check_detail.clone(),
+                            effect: ResolvedEffect::Pull {
+                                distance: *distance,
+                            },
+                        });
+                    }
+                    Effect::Summon {
+                        unit_type,
+                        duration,
+                    } => {
+                        entries.push(summon_entry(caster_id, skill_name, unit_type, *duration));
+                    }
+                    Effect::Shield { amount, duration } => {
+                        entries.push(EffectEntry {
+                            caster: caster_id,
+                            skill_name: skill_name.to_string(),
+                            target: check_target,
+                            check: parent_check,
+                            check_detail: parent_check_detail.clone(),
+                            effect: ResolvedEffect::Shield {
+                                amount: *amount,
+                                duration: *duration,
+                            },
+                        });
+                    }
                     _ => unimplemented!("Effect type not supported yet: {:?}", effect),
                 }
             }
@@ -411,15 +772,8 @@ fn resolve_nodes_for_unit(
                 on_success,
                 on_failure,
             } => {
-                let (check, detail) = resolve_branch_check(
-                    caster,
-                    target,
-                    condition,
-                    flanking_bonus,
-                    adjacent_penalty,
-                    rng,
-                    force_hit,
-                );
+                let (check, detail) =
+                    resolve_branch_check(caster, target, condition, positional, rng, force_hit);
 
                 let branch_nodes = match check {
                     CheckResult::Auto
@@ -446,8 +800,8 @@ fn resolve_nodes_for_unit(
                         branch_nodes,
                         caster,
                         target,
-                        flanking_bonus,
-                        adjacent_penalty,
+                        units_on_board,
+                        positional,
                         check,
                         Some(detail),
                         rng,
@@ -463,21 +817,77 @@ fn resolve_nodes_for_unit(
     }
 }
 
+/// 展開條件與隨機效果，回傳最終要套用的效果
+///
+/// `If` 的條件不成立、或 `Random` 沒有可抽的選項時回傳 None。
+fn select_effect<'a>(
+    effect: &'a Effect,
+    target: &CombatStats,
+    rng: &mut impl FnMut() -> i32,
+) -> Option<&'a Effect> {
+    match effect {
+        Effect::If { condition, then } => {
+            if target_condition_holds(condition, target) {
+                select_effect(then, target, rng)
+            } else {
+                None
+            }
+        }
+        Effect::Random { options } => {
+            let picked = pick_weighted(options, rng())?;
+            select_effect(picked, target, rng)
+        }
+        _ => Some(effect),
+    }
+}
+
+/// 以一顆 d100 骰（1..=100）依權重抽選項
+///
+/// 骰值換算為權重總和上的落點 `(roll - 1) * total / 100`，
+/// 取第一個累計權重超過落點的選項；權重總和為 0 時回傳 None。
+pub(crate) fn pick_weighted(options: &[WeightedEffect], roll: i32) -> Option<&Effect> {
+    let total: u64 = options.iter().map(|option| u64::from(option.weight)).sum();
+    if total == 0 {
+        return None;
+    }
+    let roll = roll.clamp(1, PERCENT_BASE) as u64;
+    let point = (roll - 1) * total / PERCENT_BASE as u64;
+    let mut cumulative = 0;
+    options
+        .iter()
+        .find(|option| {
+            cumulative += u64::from(option.weight);
+            cumulative > point
+        })
+        .map(|option| &option.effect)
+}
+
+/// 以施放當下的目標狀態判斷條件是否成立
+fn target_condition_holds(condition: &TargetCondition, target: &CombatStats) -> bool {
+    match condition {
+        TargetCondition::TargetBelowHp { percent } => {
+            let max_hp = target.attribute.max_hp.0;
+            target.attribute.current_hp.0 * PERCENT_BASE < max_hp * percent
+        }
+        TargetCondition::TargetHasBuff { buff } => target.buffs.iter().any(|name| name == buff),
+    }
+}
+
 /// 對無單位位置解析效果節點（僅處理 SpawnObject 等位置效果）
 fn resolve_nodes_for_position(
     caster_id: ID,
     skill_name: &str,
     nodes: &[EffectNode],
+    caster_pos: Position,
     pos: Position,
-    units_on_board: &HashMap<Position, CombatStats>,
-    objects_on_board: &HashMap<Position, ObjectOnBoard>,
+    tile_occupied: bool,
     entries: &mut Vec<EffectEntry>,
 ) {
     for node in nodes {
         if let EffectNode::Leaf { effect, who: _who } = node {
             match effect {
                 Effect::SpawnObject { object_type, .. } => {
-                    if !is_tile_occupied(pos, units_on_board, objects_on_board) {
+                    if !tile_occupied {
                         entries.push(EffectEntry {
                             caster: caster_id,
                             skill_name: skill_name.to_string(),
@@ -490,7 +900,33 @@ fn resolve_nodes_for_position(
                         });
                     }
                 }
-                Effect::HpEffect { .. } | Effect::ApplyBuff { .. } => {}
+                Effect::Teleport { max_distance } => {
+                    let in_range = manhattan_distance(caster_pos, pos) <= *max_distance;
+                    if in_range && !tile_occupied {
+                        entries.push(EffectEntry {
+                            caster: caster_id,
+                            skill_name: skill_name.to_string(),
+                            target: CheckTarget::Unit(caster_id),
+                            check: CheckResult::Auto,
+                            check_detail: None,
+                            effect: ResolvedEffect::Teleport { to: pos },
+                        });
+                    }
+                }
+                Effect::Summon {
+                    unit_type,
+                    duration,
+                } => {
+                    entries.push(summon_entry(caster_id, skill_name, unit_type, *duration));
+                }
+                Effect::HpEffect { .. }
+                | Effect::ApplyBuff { .. }
+                | Effect::Dispel { .. }
+                | Effect::Chain { .. }
+                | Effect::Pull { .. }
+                | Effect::Shield { .. }
+                | Effect::If { .. }
+                | Effect::Random { .. } => {}
                 _ => unimplemented!(
                     "Effect type not supported for position target yet: {:?}",
                     effect
@@ -505,18 +941,16 @@ fn resolve_branch_check(
     caster: &CombatStats,
     target: &CombatStats,
     condition: &EffectCondition,
-    flanking_bonus: i32,
-    adjacent_penalty: i32,
+    positional: PositionalAccuracy,
     rng: &mut impl FnMut() -> i32,
     force_hit: bool,
 ) -> (CheckResult, CheckDetail) {
     // 注意：以下「組 accuracy → 取 defender 值組 breakdown」這條組裝鏈，
     // 與 `preview_first_branch_accuracy` 重複。
     // 不抽共用的原因是參數太多：共用函數得同時吃下 caster、target、condition、
-    // flanking_bonus、adjacent_penalty 這一長串，簽名反而更難讀，
+    // positional 這一長串，簽名反而更難讀，
     // 故刻意複製。修改此段時，請同步檢查 `preview_first_branch_accuracy`。
-    let attacker_accuracy =
-        compute_attacker_accuracy(caster, condition, flanking_bonus, adjacent_penalty);
+    let attacker_accuracy = compute_attacker_accuracy(caster, condition, positional);
 
     let (defender_evasion, defender_block) =
         get_defense_values(&target.attribute, condition.defense_type);
@@ -537,6 +971,10 @@ fn resolve_branch_check(
             (outcome.check, outcome.roll)
         }
     };
+    let (hit_check, concealment_roll) = match force_hit {
+        true => (hit_check, None),
+        false => apply_concealment(hit_check, target.concealment, rng),
+    };
 
     let check = hit_to_check(hit_check, condition.defense_type);
     let detail = CheckDetail {
@@ -555,6 +993,7 @@ fn resolve_branch_check(
             crit,
         },
         roll,
+        concealment_roll,
     };
     (check, detail)
 }
@@ -584,6 +1023,112 @@ fn hit_to_check(hit: HitCheckResult, defense_type: DefenseType) -> CheckResult {
     }
 }
 
+/// 召喚效果條目：不論目標為何，召喚物都出現在施放者身旁
+fn summon_entry(
+    caster_id: ID,
+    skill_name: &str,
+    unit_type: &TypeName,
+    duration: u32,
+) -> EffectEntry {
+    EffectEntry {
+        caster: caster_id,
+        skill_name: skill_name.to_string(),
+        target: CheckTarget::Unit(caster_id),
+        check: CheckResult::Auto,
+        check_detail: None,
+        effect: ResolvedEffect::Summon {
+            unit_type: unit_type.clone(),
+            duration,
+        },
+    }
+}
+
+/// 計算 HP 效果的原始值與套用判定結果（爆擊加倍、格擋減免）後的最終值
+fn compute_hp_change(
+    scaling: &Scaling,
+    caster: &CombatStats,
+    target: &CombatStats,
+    check: CheckResult,
+) -> (i32, i32) {
+    let raw_amount = compute_scaling(scaling, caster, target);
+    let crit_multiplier = match check {
+        CheckResult::Hit { crit: true } | CheckResult::Block { crit: true } => {
+            CRIT_DAMAGE_MULTIPLIER
+        }
+        _ => 1,
+    };
+    let final_amount = raw_amount * crit_multiplier;
+    let final_amount = match check {
+        CheckResult::Block { .. } => {
+            apply_block_protection(final_amount, target.attribute.block_protection.0)
+        }
+        CheckResult::Auto
+        | CheckResult::Hit { .. }
+        | CheckResult::Evade
+        | CheckResult::Resisted
+        | CheckResult::Affected => final_amount,
+    };
+    (raw_amount, final_amount)
+}
+
+/// 依承受者對該傷害屬性的抗性調整傷害（治療與無屬性傷害不受影響）
+///
+/// 抗性最高視為 100%（免疫），弱點不設上限
+fn apply_damage_resistance(amount: i32, damage_type: DamageType, target: &CombatStats) -> i32 {
+    if amount >= 0 || damage_type == DamageType::Neutral {
+        return amount;
+    }
+    let resistance = target
+        .resistances
+        .get(&damage_type)
+        .copied()
+        .unwrap_or(0)
+        .min(PERCENT_BASE);
+    amount * (PERCENT_BASE - resistance) / PERCENT_BASE
+}
+
+/// 找出連鎖的跳躍順序（不含起點）
+///
+/// 每次從目前位置跳到 jump_range 內最近、尚未被擊中且符合篩選的單位；
+/// 距離相同時取座標較小者，確保結果穩定
+fn chain_hops<'a>(
+    caster: &UnitInfo,
+    start: Occupant,
+    filter: TargetFilter,
+    max_jumps: usize,
+    jump_range: Coord,
+    units_on_board: &'a HashMap<Position, CombatStats>,
+) -> Vec<&'a CombatStats> {
+    let mut current = match units_on_board
+        .iter()
+        .find(|(_, stats)| stats.unit_info.occupant == start)
+    {
+        Some((pos, _)) => *pos,
+        None => return Vec::new(),
+    };
+    let mut visited: HashSet<Occupant> = HashSet::from([start]);
+    let mut hops = Vec::new();
+
+    while hops.len() < max_jumps {
+        let next = units_on_board
+            .iter()
+            .filter(|(_, stats)| !visited.contains(&stats.unit_info.occupant))
+            .filter(|(_, stats)| is_in_filter(caster, &stats.unit_info, filter))
+            .map(|(pos, stats)| (manhattan_distance(current, *pos), *pos, stats))
+            .filter(|(distance, _, _)| *distance <= jump_range)
+            .min_by_key(|(distance, pos, _)| (*distance, *pos));
+        match next {
+            Some((_, pos, stats)) => {
+                visited.insert(stats.unit_info.occupant);
+                hops.push(stats);
+                current = pos;
+            }
+            None => break,
+        }
+    }
+    hops
+}
+
 /// 計算 Scaling 的數值
 fn compute_scaling(scaling: &Scaling, caster: &CombatStats, target: &CombatStats) -> i32 {
     let source_stats = match scaling.source {
@@ -591,7 +1136,7 @@ fn compute_scaling(scaling: &Scaling, caster: &CombatStats, target: &CombatStats
         CasterOrTarget::Target => target,
     };
     let base = get_attribute_value(&source_stats.attribute, scaling.source_attribute);
-    base * scaling.value_percent / 100
+    base * scaling.value_percent / PERCENT_BASE
 }
 
 /// 從 Attribute enum 取得 AttributeBundle 中對應的值
@@ -644,12 +1189,7 @@ fn is_flanked(
     units_on_board: &HashMap<Position, CombatStats>,
     board: Board,
 ) -> bool {
-    let neighbors = [(1_i32, 0_i32), (-1, 0), (0, 1), (0, -1)];
-    let ally_count = neighbors
-        .iter()
-        .filter_map(|(dx, dy)| {
-            try_position(board, target_pos.x as i32 + dx, target_pos.y as i32 + dy)
-        })
+    let ally_count = neighbors4(board, target_pos)
         .filter(|neighbor| {
             units_on_board
                 .get(neighbor)
@@ -666,14 +1206,10 @@ fn is_adjacent_to_enemy(
     units_on_board: &HashMap<Position, CombatStats>,
     board: Board,
 ) -> bool {
-    let neighbors = [(1_i32, 0_i32), (-1, 0), (0, 1), (0, -1)];
-    neighbors.iter().any(|(dx, dy)| {
-        match try_position(board, caster_pos.x as i32 + dx, caster_pos.y as i32 + dy) {
-            Some(neighbor) => units_on_board
-                .get(&neighbor)
-                .is_some_and(|stats| stats.unit_info.alliance_id != caster.alliance_id),
-            None => false,
-        }
+    neighbors4(board, caster_pos).any(|neighbor| {
+        units_on_board
+            .get(&neighbor)
+            .is_some_and(|stats| stats.unit_info.alliance_id != caster.alliance_id)
     })
 }
 
@@ -1,15 +1,17 @@
-use super::normalize_direction;
+use super::{line_direction, metric_length, normalize_direction};
 use crate::domain::alias::Coord;
-use crate::domain::core_types::Area;
+use crate::domain::core_types::{Area, EffectNode, RangeMetric, SkillType};
 use crate::ecs_types::components::Position;
 use crate::ecs_types::resources::Board;
 use crate::error::Result;
 use crate::logic::board;
+use std::f64::consts::SQRT_2;
 
-/// 計算射程內所有格子（曼哈頓距離在 [min_range, max_range] 內）
+/// 計算射程內所有格子（依 metric 計算的距離在 [min_range, max_range] 內）
 pub(crate) fn compute_range_positions(
     caster: Position,
     range: (Coord, Coord),
+    metric: RangeMetric,
     board: Board,
 ) -> Vec<Position> {
     let (min_range, max_range) = range;
@@ -17,12 +19,12 @@ pub(crate) fn compute_range_positions(
 
     let caster_x = caster.x as i32;
     let caster_y = caster.y as i32;
-    let max_range = max_range as i32;
+    let max_range = clamp_extent(max_range, board);
     let min_range = min_range as i32;
 
     for dy in -max_range..=max_range {
         for dx in -max_range..=max_range {
-            let distance = dx.abs() + dy.abs();
+            let distance = offset_length(metric, dx, dy);
             if distance < min_range || distance > max_range {
                 continue;
             }
@@ -41,56 +43,158 @@ pub(crate) fn compute_range_positions(
 
 /// 計算 AOE 影響的所有位置
 /// - Single: 回傳該格
-/// - Diamond/Cross: 以 target 為中心，忽略 caster
+/// - Diamond/Circle/Cross: 以 target 為中心，忽略 caster
 /// - Line: 以 caster→target 方向延伸
+/// - Rectangle: 以 target 為近端中心，沿 caster→target 方向延伸
+///
+/// `is_blocking` 判斷格子是否阻擋非穿透的直線（其他範圍不使用）
 pub(crate) fn compute_affected_positions(
     area: &Area,
     caster: Position,
     target: Position,
     board: Board,
+    is_blocking: impl Fn(Position) -> bool,
 ) -> Result<Vec<Position>> {
     match area {
         Area::Single => Ok(vec![target]),
-        Area::Diamond { radius } => Ok(compute_diamond(target, *radius, board)),
+        Area::Diamond { radius } => Ok(compute_range_positions(
+            target,
+            (0, *radius),
+            RangeMetric::Manhattan,
+            board,
+        )),
+        Area::Circle { radius, metric } => Ok(compute_range_positions(
+            target,
+            (0, *radius),
+            *metric,
+            board,
+        )),
         Area::Cross { length } => Ok(compute_cross(target, *length, board)),
-        Area::Line { length } => compute_line(caster, target, *length, board),
+        Area::Line {
+            length,
+            snap_to_facing,
+            pierce,
+        } => {
+            let line = compute_line(caster, target, *length, *snap_to_facing, board)?;
+            match pierce {
+                true => Ok(line),
+                false => Ok(truncate_at_blocker(line, is_blocking)),
+            }
+        }
+        Area::Rectangle { width, depth } => {
+            compute_rectangle(caster, target, *width, *depth, board)
+        }
     }
 }
 
-// ============================================================================
-// AOE 計算
-// ============================================================================
-
-/// 計算菱形 AOE（曼哈頓距離）
-fn compute_diamond(target: Position, radius: Coord, board: Board) -> Vec<Position> {
-    let mut positions = Vec::new();
-    let target_x = target.x as i32;
-    let target_y = target.y as i32;
-    let radius = radius as i32;
+/// 技能可能影響的格子與起點的最遠曼哈頓距離（射程上限加上範圍延伸）
+///
+/// 主動技能以施放者為起點；反應技能以觸發範圍上限作為射程；被動技能不影響格子，回傳 0。
+pub fn max_affected_distance(skill: &SkillType) -> usize {
+    match skill {
+        SkillType::Active {
+            target, effects, ..
+        } => {
+            let range = metric_reach(target.range_metric, target.range.1);
+            max_area_distance(effects, range).max(area_distance(&target.area, range))
+        }
+        SkillType::Reaction {
+            triggering_unit,
+            effects,
+            ..
+        } => {
+            let range = triggering_unit.source_range.1;
+            max_area_distance(effects, range).max(range)
+        }
+        SkillType::Passive { .. } => 0,
+    }
+}
 
-    for dy in -(radius)..=radius {
-        for dx in -(radius)..=radius {
-            if (dx.abs() + dy.abs()) > radius {
-                continue;
+/// 效果樹中所有 Area 節點的最遠影響距離
+fn max_area_distance(nodes: &[EffectNode], range: Coord) -> Coord {
+    nodes
+        .iter()
+        .map(|node| match node {
+            EffectNode::Area { area, nodes, .. } => {
+                area_distance(area, range).max(max_area_distance(nodes, range))
             }
-            let x = target_x + dx;
-            let y = target_y + dy;
+            EffectNode::Branch {
+                on_success,
+                on_failure,
+                ..
+            } => max_area_distance(on_success, range).max(max_area_distance(on_failure, range)),
+            EffectNode::Leaf { .. } => range,
+        })
+        .max()
+        .unwrap_or(range)
+}
 
-            if let Some(pos) = board::try_position(board, x, y) {
-                positions.push(pos);
-            }
-        }
+/// 單一範圍在射程上限 `range` 下的最遠影響距離
+///
+/// Line 從施放者出發而非目標，距離只取決於長度（吸附斜角時每步距離為 2）。
+fn area_distance(area: &Area, range: Coord) -> Coord {
+    match area {
+        Area::Single => range,
+        Area::Diamond { radius } => range.saturating_add(*radius),
+        Area::Circle { radius, metric } => range.saturating_add(metric_reach(*metric, *radius)),
+        Area::Cross { length } => range.saturating_add(*length),
+        Area::Line {
+            length,
+            snap_to_facing,
+            ..
+        } => match snap_to_facing {
+            true => length.saturating_mul(2),
+            false => *length,
+        },
+        Area::Rectangle { width, depth } => range
+            .saturating_add(depth.saturating_sub(1))
+            .saturating_add(width / 2),
+    }
+}
+
+/// 依 metric 距離在 radius 以內的格子，與中心的最遠曼哈頓距離（上界）
+///
+/// 歐幾里得取連續圓上的最遠點 r·√2，浮點轉整數時自動飽和。
+fn metric_reach(metric: RangeMetric, radius: Coord) -> Coord {
+    match metric {
+        RangeMetric::Manhattan => radius,
+        RangeMetric::Chebyshev => radius.saturating_mul(2),
+        RangeMetric::Euclidean => (radius as f64 * SQRT_2) as Coord,
     }
+}
 
-    positions
+/// 位移 (dx, dy) 依 metric 計算的長度
+fn offset_length(metric: RangeMetric, dx: i32, dy: i32) -> i32 {
+    metric_length(
+        metric,
+        dx.unsigned_abs() as Coord,
+        dy.unsigned_abs() as Coord,
+    ) as i32
+}
+
+/// 棋盤上任兩格的最大曼哈頓距離上界
+fn board_reach(board: Board) -> i32 {
+    (board.width + board.height) as i32
+}
+
+/// 將範圍長度截斷到棋盤可及的距離並轉為 i32
+///
+/// 超過棋盤寬高總和的部分必然落在棋盤外，截斷後結果不變，
+/// 同時避免巨大數值在轉型或座標相加時溢位，以及迴圈次數失控。
+fn clamp_extent(extent: Coord, board: Board) -> i32 {
+    extent.min(board.width + board.height) as i32
 }
 
+// ============================================================================
+// AOE 計算
+// ============================================================================
+
 /// 計算十字形 AOE
 fn compute_cross(target: Position, length: Coord, board: Board) -> Vec<Position> {
     let mut positions = vec![target];
     let target_x = target.x as i32;
     let target_y = target.y as i32;
-    let length = length as i32;
+    let length = clamp_extent(length, board);
 
     const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
 
@@ -110,18 +214,21 @@ fn compute_cross(target: Position, length: Coord, board: Board) -> Vec<Position>
 }
 
 /// 計算直線 AOE（從施放者到目標方向，長度為 length 格）
+///
+/// `snap_to_facing` 為 true 時方向吸附到 8 方位，斜角方向每步同時前進 x 與 y。
 fn compute_line(
     caster: Position,
     target: Position,
     length: Coord,
+    snap_to_facing: bool,
     board: Board,
 ) -> Result<Vec<Position>> {
-    let (step_x, step_y) = normalize_direction(caster, target)?;
+    let (step_x, step_y) = line_direction(caster, target, snap_to_facing)?;
 
     let mut positions = Vec::new();
     let caster_x = caster.x as i32;
     let caster_y = caster.y as i32;
-    let length = length as i32;
+    let length = clamp_extent(length, board);
 
     for i in 0..=length {
         let x = caster_x + step_x * i;
@@ -135,3 +242,50 @@ fn compute_line(
 
     Ok(positions)
 }
+
+/// 截斷到第一個阻擋格（保留該格）；起點為施放者所在格，不視為阻擋
+fn truncate_at_blocker(
+    mut line: Vec<Position>,
+    is_blocking: impl Fn(Position) -> bool,
+) -> Vec<Position> {
+    if let Some(index) = line.iter().skip(1).position(|pos| is_blocking(*pos)) {
+        line.truncate(index + 2);
+    }
+    line
+}
+
+/// 計算矩形 AOE（近端中心為目標，沿施放者→目標方向延伸 depth 格，橫向寬 width 格）
+///
+/// 偶數寬度時，多出的一格落在垂直方向的正側。超出棋盤的格子直接略過。
+fn compute_rectangle(
+    caster: Position,
+    target: Position,
+    width: Coord,
+    depth: Coord,
+    board: Board,
+) -> Result<Vec<Position>> {
+    let (step_x, step_y) = normalize_direction(caster, target)?;
+    // 垂直於方向的單位向量
+    let (side_x, side_y) = (-step_y, step_x);
+
+    let target_x = target.x as i32;
+    let target_y = target.y as i32;
+    // 寬度超過兩側皆已出界的長度時，奇偶不再影響結果，可安全截斷
+    let width = clamp_extent(width, board).min(2 * board_reach(board) + 1);
+    let depth = clamp_extent(depth, board);
+    let side_min = -(width - 1) / 2;
+    let side_max = side_min + width - 1;
+
+    let mut positions = Vec::new();
+    for forward in 0..depth {
+        for side in side_min..=side_max {
+            let x = target_x + step_x * forward + side_x * side;
+            let y = target_y + step_y * forward + side_y * side;
+            if let Some(pos) = board::try_position(board, x, y) {
+                positions.push(pos);
+            }
+        }
+    }
+
+    Ok(positions)
+}
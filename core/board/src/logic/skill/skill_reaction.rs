@@ -15,6 +15,8 @@ use std::collections::{HashMap, HashSet};
 pub struct ReactionUnitInfo<'a> {
     pub unit_info: UnitInfo,
     pub remaining_reactions: i32,
+    /// 當前 MP，負擔不起 cost 的反應技能不列入
+    pub current_mp: i32,
     pub skills: Vec<&'a SkillType>,
 }
 
@@ -23,8 +25,11 @@ pub struct ReactionUnitInfo<'a> {
 pub struct MoveReaction {
     pub occupant: Occupant,
     pub skill_names: Vec<SkillName>,
-    /// 該反應者觸發的步驟索引：移動者離開的那格（path 的 from 索引）
+    /// 該反應者觸發的步驟索引：反應發動時移動者所在的格子
+    /// （藉機攻擊為將離開的那格，警戒為剛踏入的那格）
     pub from_index: usize,
+    /// 觸發類型：該步驟有任一警戒技能符合即為 Overwatch，否則為 AttackOfOpportunity
+    pub trigger: ReactionTrigger,
 }
 
 /// collect_move_reactions 的回傳值
@@ -35,7 +40,7 @@ pub struct MoveReaction {
 /// - 實際移動：過濾出最早觸發步驟（`earliest_from_index`）的那批。
 #[derive(Debug)]
 pub struct CollectMoveReactionsResult {
-    /// 最早觸發步驟的下一格（移動者實際會停下的位置）；無反應時為路徑終點
+    /// 最早觸發步驟的下一格（觸發於終點時即終點）；無反應時為路徑終點
     pub stop_position: Position,
     /// 最早觸發的步驟索引；無反應時為 path.len() - 2
     pub earliest_from_index: usize,
@@ -43,12 +48,14 @@ pub struct CollectMoveReactionsResult {
     pub reactions: Vec<MoveReaction>,
 }
 
-/// 收集移動路徑上所有會觸發藉機攻擊的反應者
+/// 收集移動路徑上所有會觸發藉機攻擊或警戒的反應者
 ///
 /// 外層遍歷每個反應者，內層掃描路徑找到該反應者最早觸發的步驟。
 /// 不收斂：每個反應者各自記錄其最早觸發步驟，全部保留供呼叫端自行取用。
 ///
-/// `blocks_sight`：阻擋視線的格子集合。反應者對觸發格（移動者離開的那格）無視線時，
+/// 警戒技能只在反應者已架設時才會出現在 `units_on_board` 的 skills 中，此處不再檢查。
+///
+/// `blocks_sight`：阻擋視線的格子集合。反應者對觸發格無視線時，
 /// 該步驟不觸發反應，比照 execute_skill 的 caster ↔ target 判定。
 pub(crate) fn collect_move_reactions(
     mover: &UnitInfo,
@@ -68,7 +75,7 @@ pub(crate) fn collect_move_reactions(
             continue;
         }
 
-        // 預過濾：只保留 Reaction 且 trigger 為 AttackOfOpportunity 且 filter 符合的技能
+        // 預過濾：只保留 Reaction 且 trigger 為 AttackOfOpportunity / Overwatch、filter 符合且 MP 足夠的技能
         let reaction_skills: Vec<_> = reactor
             .skills
             .iter()
@@ -76,12 +83,14 @@ pub(crate) fn collect_move_reactions(
                 SkillType::Reaction {
                     name,
                     triggering_unit,
+                    cost,
                     ..
                 } => {
                     if matches!(
                         triggering_unit.trigger,
-                        ReactionTrigger::AttackOfOpportunity
+                        ReactionTrigger::AttackOfOpportunity | ReactionTrigger::Overwatch
                     ) && is_in_filter(&reactor.unit_info, mover, triggering_unit.source_filter)
+                        && *cost as i32 <= reactor.current_mp
                     {
                         Some((name, triggering_unit))
                     } else {
@@ -96,31 +105,52 @@ pub(crate) fn collect_move_reactions(
             continue;
         }
 
-        // 掃描整條路徑，找該反應者最早觸發的步驟
-        for (step_index, from) in path.iter().enumerate().take(last_from_idx + 1) {
-            // 視線基準：反應者 ↔ 觸發格（移動者離開的那格），被阻擋則此步驟不觸發
-            if !has_line_of_sight(*reactor_pos, *from, blocks_sight) {
+        let is_in_range = |pos: Position, range: (usize, usize)| {
+            let distance = manhattan_distance(*reactor_pos, pos);
+            distance >= range.0 && distance <= range.1
+        };
+
+        // 掃描整條路徑，找該反應者最早觸發的步驟：
+        // - 藉機攻擊：移動者將離開 path[step_index]
+        // - 警戒：移動者由範圍外踏入 path[step_index]
+        for (step_index, pos) in path.iter().enumerate() {
+            // 視線基準：反應者 ↔ 觸發格，被阻擋則此步驟不觸發
+            if !has_line_of_sight(*reactor_pos, *pos, blocks_sight) {
                 continue;
             }
 
-            let distance = manhattan_distance(*reactor_pos, *from);
-
-            let is_in_range = |range: (usize, usize)| distance >= range.0 && distance <= range.1;
-
-            let matching_skills: Vec<SkillName> = reaction_skills
+            let matching: Vec<_> = reaction_skills
                 .iter()
-                .filter(|(_, t)| is_in_range(t.source_range))
-                .map(|(name, _)| (*name).clone())
+                .filter(|(_, t)| match t.trigger {
+                    ReactionTrigger::AttackOfOpportunity => {
+                        step_index <= last_from_idx && is_in_range(*pos, t.source_range)
+                    }
+                    ReactionTrigger::Overwatch => {
+                        step_index > 0
+                            && is_in_range(*pos, t.source_range)
+                            && !is_in_range(path[step_index - 1], t.source_range)
+                    }
+                    ReactionTrigger::TakesDamage => false,
+                })
                 .collect();
 
-            if matching_skills.is_empty() {
+            if matching.is_empty() {
                 continue;
             }
 
+            let trigger = if matching
+                .iter()
+                .any(|(_, t)| matches!(t.trigger, ReactionTrigger::Overwatch))
+            {
+                ReactionTrigger::Overwatch
+            } else {
+                ReactionTrigger::AttackOfOpportunity
+            };
             reactions.push(MoveReaction {
                 occupant: reactor.unit_info.occupant,
-                skill_names: matching_skills,
+                skill_names: matching.iter().map(|(name, _)| (*name).clone()).collect(),
                 from_index: step_index,
+                trigger,
             });
             // 這個 reactor 已找到最早觸發步驟，不需繼續掃
             break;
@@ -135,7 +165,7 @@ pub(crate) fn collect_move_reactions(
         .unwrap_or(last_from_idx);
 
     Ok(CollectMoveReactionsResult {
-        stop_position: path[earliest_from_index + 1],
+        stop_position: path[(earliest_from_index + 1).min(path.len() - 1)],
         reactions,
         earliest_from_index,
     })
@@ -145,6 +175,8 @@ pub(crate) fn collect_move_reactions(
 pub struct TakesDamageUnitInfo {
     pub pos: Position,
     pub remaining_reaction_point: i32,
+    /// 當前 MP，負擔不起 cost 的反應技能不列入
+    pub current_mp: i32,
     pub skill_names: Vec<SkillName>,
 }
 
@@ -168,9 +200,11 @@ pub(crate) fn collect_takes_damage_reactions(
         .iter()
         .filter_map(|(name, skill_type)| match skill_type {
             SkillType::Reaction {
-                triggering_unit, ..
+                triggering_unit,
+                cost,
+                ..
             } if matches!(triggering_unit.trigger, ReactionTrigger::TakesDamage) => {
-                Some((name, triggering_unit))
+                Some((name, (triggering_unit, *cost)))
             }
             _ => None,
         })
@@ -185,6 +219,8 @@ pub(crate) fn collect_takes_damage_reactions(
             }
             _ => None,
         })
+        // 施放者自傷（如反噬）不觸發自己的受傷反應
+        .filter(|damaged_occupant| *damaged_occupant != attacker)
         .filter_map(|damaged_occupant| {
             let info = unit_reaction_info.get(&damaged_occupant)?;
             if info.remaining_reaction_point <= 0 {
@@ -192,6 +228,7 @@ pub(crate) fn collect_takes_damage_reactions(
             }
             let available_skills = filter_takes_damage_skills(
                 info.pos,
+                info.current_mp,
                 &info.skill_names,
                 attacker_pos,
                 &takes_damage_skills,
@@ -212,15 +249,19 @@ pub(crate) fn collect_takes_damage_reactions(
 
 fn filter_takes_damage_skills<'a>(
     damaged_pos: Position,
+    damaged_mp: i32,
     damaged_skills: &[SkillName],
     attacker_pos: Position,
-    takes_damage_skills: &HashMap<&'a SkillName, &'a TriggeringSource>,
+    takes_damage_skills: &HashMap<&'a SkillName, (&'a TriggeringSource, u32)>,
     unit_stats_on_board: &HashMap<Position, CombatStats>,
 ) -> Vec<SkillName> {
     damaged_skills
         .iter()
         .filter_map(|skill_name| {
-            let triggering_unit = takes_damage_skills.get(skill_name)?;
+            let (triggering_unit, cost) = takes_damage_skills.get(skill_name)?;
+            if *cost as i32 > damaged_mp {
+                return None;
+            }
             let distance = manhattan_distance(damaged_pos, attacker_pos);
             let (min_range, max_range) = triggering_unit.source_range;
             if distance < min_range || distance > max_range {
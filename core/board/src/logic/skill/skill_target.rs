@@ -1,11 +1,11 @@
 use crate::domain::alias::Coord;
-use crate::domain::core_types::{Area, Target, TargetFilter, TargetSelection};
+use crate::domain::core_types::{Area, RangeMetric, Target, TargetFilter, TargetSelection};
 use crate::ecs_types::components::Position;
 use crate::ecs_types::resources::Board;
 use crate::error::{BoardError, Result};
 use crate::logic::board;
 use crate::logic::skill::{
-    CasterInfo, UnitInfo, is_in_filter, manhattan_distance, normalize_direction,
+    CasterInfo, UnitInfo, is_in_filter, line_direction, metric_distance, normalize_direction,
 };
 use std::collections::{HashMap, HashSet};
 
@@ -39,9 +39,21 @@ pub(crate) fn validate_skill_targets(
     }
 
     for &target_pos in target_positions {
-        validate_range(caster.position, target_pos, min_range, max_range, board)?;
-        if let Area::Line { .. } = target.area {
-            normalize_direction(caster.position, target_pos)?;
+        validate_range(
+            caster.position,
+            target_pos,
+            (min_range, max_range),
+            target.range_metric,
+            board,
+        )?;
+        match target.area {
+            Area::Line { snap_to_facing, .. } => {
+                line_direction(caster.position, target_pos, snap_to_facing)?;
+            }
+            Area::Rectangle { .. } => {
+                normalize_direction(caster.position, target_pos)?;
+            }
+            Area::Single | Area::Diamond { .. } | Area::Circle { .. } | Area::Cross { .. } => {}
         }
         if is_targeting_unit {
             let target_unit = get_unit_at(target_pos, units_on_board)?;
@@ -82,26 +94,20 @@ fn validate_no_duplicates(targets: &[Position]) -> Result<()> {
     Ok(())
 }
 
-/// 驗證射程（曼哈頓距離）
+/// 驗證射程（依 metric 計算距離）
 fn validate_range(
     caster_pos: Position,
     target_pos: Position,
-    min_range: Coord,
-    max_range: Coord,
+    (min_range, max_range): (Coord, Coord),
+    metric: RangeMetric,
     board: Board,
 ) -> Result<()> {
-    if !board::is_valid_position(board, target_pos) {
-        return Err(BoardError::OutOfBoard {
-            x: target_pos.x,
-            y: target_pos.y,
-            width: board.width,
-            height: board.height,
-        }
-        .into());
-    }
-    let distance = manhattan_distance(caster_pos, target_pos);
+    board::require_position_in_board(board, target_pos)?;
+    let distance = metric_distance(metric, caster_pos, target_pos);
     if distance < min_range || distance > max_range {
         return Err(BoardError::OutOfRange {
+            x: target_pos.x,
+            y: target_pos.y,
             distance,
             min_range,
             max_range,
@@ -1,14 +1,17 @@
 //! 單位屬性計算邏輯
 
 use crate::domain::alias::SkillName;
-use crate::domain::core_types::{Attribute, BuffType, ContinuousEffect, SkillType};
+use crate::domain::core_types::{
+    Attribute, BuffType, ContinuousEffect, DamageType, SkillType, StackingPolicy,
+};
 use crate::ecs_types::components::*;
 use crate::error::{Result, UnitError};
+use crate::logic::skill::{UnitInfo, manhattan_distance};
 use std::collections::HashMap;
 
 pub(crate) fn filter_continuous_effect<'a>(
     skill_names: &'a [SkillName],
-    buffs: &'a [BuffType],
+    buffs: impl IntoIterator<Item = &'a BuffType>,
     skill_map: &'a HashMap<SkillName, SkillType>,
 ) -> Result<impl Iterator<Item = &'a ContinuousEffect>> {
     let passives = skill_names
@@ -35,15 +38,273 @@ pub(crate) fn filter_continuous_effect<'a>(
         })
         .flatten();
 
-    let from_buffs = buffs.iter().flat_map(|buff| buff.while_active.iter());
+    let from_buffs = effective_buffs(buffs)
+        .into_iter()
+        .flat_map(|buff| buff.while_active.iter());
 
     Ok(passives.chain(from_buffs))
 }
 
+/// 光環計算所需的單位資料
+pub(crate) struct AuraUnit<'a> {
+    pub position: Position,
+    pub unit_info: UnitInfo,
+    pub skills: &'a [SkillName],
+}
+
+/// 收集各單位從周圍光環獲得的持續性效果（沒有受光環影響的單位不列出）
+///
+/// 來源為單位被動技能中的 `Aura`，受影響者為範圍內的其他同盟單位（不含來源自身）。
+/// 光環效果中再巢狀的 `Aura` 不會擴散。
+pub(crate) fn collect_aura_effects<'a>(
+    units: &[AuraUnit<'a>],
+    skill_map: &'a HashMap<SkillName, SkillType>,
+) -> Result<HashMap<Occupant, Vec<&'a ContinuousEffect>>> {
+    let mut result: HashMap<Occupant, Vec<&'a ContinuousEffect>> = HashMap::new();
+    for source in units {
+        let auras = filter_continuous_effect(source.skills, &[], skill_map)?.filter_map(|effect| {
+            match effect {
+                ContinuousEffect::Aura { range, effects } => Some((*range, effects)),
+                _ => None,
+            }
+        });
+        for (range, effects) in auras {
+            let receivers = units.iter().filter(|unit| {
+                unit.unit_info.occupant != source.unit_info.occupant
+                    && unit.unit_info.alliance_id == source.unit_info.alliance_id
+                    && manhattan_distance(source.position, unit.position) <= range
+            });
+            for receiver in receivers {
+                result
+                    .entry(receiver.unit_info.occupant)
+                    .or_default()
+                    .extend(effects.iter());
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// 依疊加規則挑出實際生效的 buff 實例
+///
+/// - Stack：全部生效
+/// - HighestOnly：同名只取固定值總和最高者
+/// - RefreshDuration：同名只取第一個
+fn effective_buffs<'a>(buffs: impl IntoIterator<Item = &'a BuffType>) -> Vec<&'a BuffType> {
+    let mut result: Vec<&BuffType> = Vec::new();
+    for buff in buffs {
+        if buff.stacking == StackingPolicy::Stack {
+            result.push(buff);
+            continue;
+        }
+        let existing = result.iter_mut().find(|b| b.name == buff.name);
+        match (existing, buff.stacking) {
+            (None, _) => result.push(buff),
+            (Some(kept), StackingPolicy::HighestOnly) => {
+                if flat_total(buff) > flat_total(kept) {
+                    *kept = buff;
+                }
+            }
+            (Some(_), _) => {}
+        }
+    }
+    result
+}
+
+/// buff 所有固定值屬性效果的總和（HighestOnly 比較用）
+fn flat_total(buff: &BuffType) -> i32 {
+    buff.while_active
+        .iter()
+        .map(|effect| match effect {
+            ContinuousEffect::AttributeFlat { value, .. } => *value,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// 上限變動後的當前值：上限提高時當前值同步增加差值，上限降低時當前值壓到新上限
+pub(crate) fn adjust_current_for_max_change(current: i32, old_max: i32, new_max: i32) -> i32 {
+    if new_max > old_max {
+        current + (new_max - old_max)
+    } else {
+        current.min(new_max)
+    }
+}
+
+/// 只由技能計算的屬性累計值，可用 [`apply_skill_delta`] 增量更新
+///
+/// 固定值直接累加；倍率效果含截斷，結果與套用順序有關，
+/// 因此依技能順序保留並於取值時重播，使增量結果與全量重算一致。
+#[derive(Debug, Default, Clone)]
+pub struct SkillAttributeTotals {
+    skill_names: Vec<SkillName>,
+    flat: CalculatedAttributes,
+    scaling: Vec<(SkillName, Attribute, i32)>,
+}
+
+/// 由技能列表全量計算屬性累計值（忽略非被動技能）
+pub fn recalc_from_skills(
+    skill_names: &[SkillName],
+    skill_map: &HashMap<SkillName, SkillType>,
+) -> Result<SkillAttributeTotals> {
+    let mut totals = SkillAttributeTotals::default();
+    apply_skill_delta(&mut totals, skill_names, &[], skill_map)?;
+    Ok(totals)
+}
+
+/// 增量更新屬性累計值：先移除 removed（各移除第一個同名技能），再依序加入 added
+///
+/// 只處理變動的技能，結果與對更新後技能列表呼叫 [`recalc_from_skills`] 相同。
+/// 任一技能不存在（或欲移除的技能不在列表中）時回傳錯誤且不修改累計值。
+pub fn apply_skill_delta(
+    totals: &mut SkillAttributeTotals,
+    added: &[SkillName],
+    removed: &[SkillName],
+    skill_map: &HashMap<SkillName, SkillType>,
+) -> Result<()> {
+    // 讀取階段：先驗證全部變動，避免部分套用
+    let mut remaining = totals.skill_names.clone();
+    for name in removed {
+        let index = remaining
+            .iter()
+            .position(|skill| skill == name)
+            .ok_or_else(|| UnitError::SkillNotFound {
+                skill_name: name.clone(),
+            })?;
+        remaining.remove(index);
+    }
+    let removed_effects = passive_effects(removed, skill_map)?;
+    let added_effects = passive_effects(added, skill_map)?;
+
+    // 寫入階段
+    for (name, effects) in removed_effects {
+        let mut flat_effects = Vec::new();
+        let mut scaling_effects = Vec::new();
+        for effect in effects {
+            collect_continuous_effect(effect, &mut flat_effects, &mut scaling_effects);
+        }
+        for (attribute, value) in flat_effects {
+            add_attribute_value(&mut totals.flat, attribute, -value);
+        }
+        // 同名技能的倍率效果依列表順序連續排列，移除第一組即對應第一個同名技能
+        let first_index = totals.scaling.iter().position(|(skill, ..)| skill == name);
+        if let Some(first_index) = first_index {
+            totals
+                .scaling
+                .drain(first_index..first_index + scaling_effects.len());
+        }
+    }
+    for (name, effects) in added_effects {
+        let mut flat_effects = Vec::new();
+        let mut scaling_effects = Vec::new();
+        for effect in effects {
+            collect_continuous_effect(effect, &mut flat_effects, &mut scaling_effects);
+        }
+        for (attribute, value) in flat_effects {
+            add_attribute_value(&mut totals.flat, attribute, value);
+        }
+        totals.scaling.extend(
+            scaling_effects
+                .into_iter()
+                .map(|(attribute, multiplier)| (name.clone(), attribute, multiplier)),
+        );
+    }
+    remaining.extend(added.iter().cloned());
+    totals.skill_names = remaining;
+    Ok(())
+}
+
+/// 由屬性累計值產生單位屬性
+pub fn totals_to_attributes(totals: &SkillAttributeTotals) -> AttributeBundle {
+    let mut attributes = totals.flat.clone();
+    apply_scaling(
+        &mut attributes,
+        totals
+            .scaling
+            .iter()
+            .map(|(_, attribute, multiplier)| (*attribute, *multiplier)),
+    );
+    attributes.into()
+}
+
+/// 查出各技能的被動效果（非被動技能效果為空）
+fn passive_effects<'a>(
+    skill_names: &'a [SkillName],
+    skill_map: &'a HashMap<SkillName, SkillType>,
+) -> Result<Vec<(&'a SkillName, &'a [ContinuousEffect])>> {
+    skill_names
+        .iter()
+        .map(|name| match skill_map.get(name) {
+            Some(SkillType::Passive { effects, .. }) => Ok((name, effects.as_slice())),
+            Some(SkillType::Active { .. } | SkillType::Reaction { .. }) => Ok((name, &[][..])),
+            None => Err(UnitError::SkillNotFound {
+                skill_name: name.clone(),
+            }
+            .into()),
+        })
+        .collect()
+}
+
 /// 計算單位屬性
 pub(crate) fn calculate_attributes<'a>(
     effects: impl Iterator<Item = &'a ContinuousEffect>,
 ) -> AttributeBundle {
+    accumulate_attributes(effects).into()
+}
+
+/// 在屬性 component 的現值上疊加身上 buff 與所受光環的修正（戰鬥結算用）
+///
+/// component 保存由被動技能算出的值，修正量為「被動技能 + buff + 光環」與「只有被動技能」兩次重算的差值，
+/// 倍率效果因此也作用在 buff 與光環的加成上。HP/MP 上限已由 `refresh_max_hp_mp` 同步，
+/// 當前 HP/MP 與反應點為戰鬥中的狀態，皆不再疊加。
+pub(crate) fn apply_attribute_modifiers<'a>(
+    current: AttributeBundle,
+    skill_names: &'a [SkillName],
+    buffs: impl IntoIterator<Item = &'a BuffType>,
+    auras: impl IntoIterator<Item = &'a ContinuousEffect>,
+    skill_map: &'a HashMap<SkillName, SkillType>,
+) -> Result<AttributeBundle> {
+    let base = accumulate_attributes(filter_continuous_effect(skill_names, &[], skill_map)?);
+    let modified = accumulate_attributes(
+        filter_continuous_effect(skill_names, buffs, skill_map)?.chain(auras),
+    );
+    let delta = |attribute| {
+        get_attribute_value(&modified, attribute) - get_attribute_value(&base, attribute)
+    };
+    Ok(AttributeBundle {
+        initiative: Initiative(current.initiative.0 + delta(Attribute::Initiative)),
+        physical_attack: PhysicalAttack(
+            current.physical_attack.0 + delta(Attribute::PhysicalAttack),
+        ),
+        magical_attack: MagicalAttack(current.magical_attack.0 + delta(Attribute::MagicalAttack)),
+        physical_accuracy: PhysicalAccuracy(
+            current.physical_accuracy.0 + delta(Attribute::PhysicalAccuracy),
+        ),
+        magical_accuracy: MagicalAccuracy(
+            current.magical_accuracy.0 + delta(Attribute::MagicalAccuracy),
+        ),
+        fortitude: Fortitude(current.fortitude.0 + delta(Attribute::Fortitude)),
+        agility: Agility(current.agility.0 + delta(Attribute::Agility)),
+        block: Block(current.block.0 + delta(Attribute::Block)),
+        block_protection: BlockProtection(
+            current.block_protection.0 + delta(Attribute::BlockProtection),
+        ),
+        will: Will(current.will.0 + delta(Attribute::Will)),
+        movement_point: MovementPoint(current.movement_point.0 + delta(Attribute::MovementPoint)),
+        max_reaction_point: MaxReactionPoint(
+            current.max_reaction_point.0 + delta(Attribute::ReactionPoint),
+        ),
+        flanking_accuracy_bonus: FlankingAccuracyBonus(
+            current.flanking_accuracy_bonus.0 + delta(Attribute::FlankingAccuracyBonus),
+        ),
+        ..current
+    })
+}
+
+/// 依序累加固定值並套用倍率，得到屬性累計值
+fn accumulate_attributes<'a>(
+    effects: impl Iterator<Item = &'a ContinuousEffect>,
+) -> CalculatedAttributes {
     let mut attributes = CalculatedAttributes::default();
 
     // 收集所有被動技能效果
@@ -60,13 +321,38 @@ pub(crate) fn calculate_attributes<'a>(
     }
 
     // 第二階段：應用所有倍率效果
+    apply_scaling(&mut attributes, scaling_effects);
+
+    attributes
+}
+
+/// 累加各傷害屬性的抗性百分比（同屬性相加，負值為弱點）
+pub(crate) fn calculate_resistances<'a>(
+    effects: impl Iterator<Item = &'a ContinuousEffect>,
+) -> HashMap<DamageType, i32> {
+    let mut resistances = HashMap::new();
+    for effect in effects {
+        if let ContinuousEffect::DamageResistance {
+            damage_type,
+            percent,
+        } = effect
+        {
+            *resistances.entry(*damage_type).or_insert(0) += *percent;
+        }
+    }
+    resistances
+}
+
+/// 依序套用倍率效果（每次乘算後截斷）
+fn apply_scaling(
+    attributes: &mut CalculatedAttributes,
+    scaling_effects: impl IntoIterator<Item = (Attribute, i32)>,
+) {
     for (attribute, multiplier) in scaling_effects {
-        let base_value = get_attribute_value(&attributes, attribute);
+        let base_value = get_attribute_value(attributes, attribute);
         let new_value = (base_value * multiplier) / 100;
-        set_attribute_value(&mut attributes, attribute, new_value);
+        set_attribute_value(attributes, attribute, new_value);
     }
-
-    attributes.into()
 }
 
 fn collect_continuous_effect(
@@ -91,8 +377,12 @@ fn collect_continuous_effect(
         ContinuousEffect::Perception { .. }
         | ContinuousEffect::DamageToMp { .. }
         | ContinuousEffect::EmitLight { .. }
-        | ContinuousEffect::Blinded => {
-            // 不影響屬性
+        | ContinuousEffect::Blinded
+        | ContinuousEffect::DeathNova { .. }
+        | ContinuousEffect::Regen { .. }
+        | ContinuousEffect::DamageResistance { .. }
+        | ContinuousEffect::Aura { .. } => {
+            // 不影響屬性（光環效果由 collect_aura_effects 注入受影響單位）
         }
     }
 }
@@ -4,6 +4,7 @@ use crate::domain::turn::TurnEntry;
 use crate::ecs_types::components::Occupant;
 use crate::error::{BoardError, Result};
 use std::cmp::Ordering;
+use std::collections::HashMap;
 
 /// 計算順序的輸入資料
 pub struct TurnOrderInput {
@@ -49,16 +50,53 @@ pub(crate) fn calculate_turn_order(
         })
         .collect();
 
-    // 按 total 降序排序，若相同則按 tiebreaker 降序
-    entries.sort_by(|a, b| match b.total.cmp(&a.total) {
+    entries.sort_by(compare_entries);
+
+    entries
+}
+
+/// 依新的 INI 重排本輪尚未輪到的單位（純邏輯，不操作 World）
+///
+/// - 只影響當前行動單位之後的單位：已行動者與當前行動單位維持原位
+/// - 骰子結果與 tiebreaker 的隨機部分保留，只套用 INI 的差值
+/// - 沒有任何 INI 變動時不重排，保留延後行動造成的順序
+pub(crate) fn resort_pending_entries(
+    entries: &mut [TurnEntry],
+    initiatives: &HashMap<Occupant, i32>,
+) {
+    let current_index = match get_active_index(entries) {
+        Some(idx) => idx,
+        None => return,
+    };
+    let pending = &mut entries[current_index + 1..];
+
+    let mut changed = false;
+    for entry in pending.iter_mut() {
+        let new_initiative = match initiatives.get(&entry.occupant) {
+            Some(value) if *value != entry.initiative => *value,
+            _ => continue,
+        };
+        let delta = new_initiative - entry.initiative;
+        entry.initiative = new_initiative;
+        entry.total += delta;
+        entry.tiebreaker += delta as f64 * 10.0;
+        changed = true;
+    }
+
+    if changed {
+        pending.sort_by(compare_entries);
+    }
+}
+
+/// 行動順序比較：total 降序，相同則 tiebreaker 降序
+fn compare_entries(a: &TurnEntry, b: &TurnEntry) -> Ordering {
+    match b.total.cmp(&a.total) {
         Ordering::Equal => b
             .tiebreaker
             .partial_cmp(&a.tiebreaker)
             .unwrap_or(Ordering::Equal),
         other => other,
-    });
-
-    entries
+    }
 }
 
 /// 將當前單位延後到 target_index 位置（只能往後）
@@ -74,24 +112,19 @@ pub(crate) fn delay_unit(entries: &mut Vec<TurnEntry>, target_index: usize) -> R
     let current_index = get_active_index(entries).ok_or(BoardError::NoActiveUnit)?;
 
     if target_index <= current_index {
-        return Err(BoardError::InvalidDelay {
+        return Err(BoardError::DelayNotBackward {
             occupant: entries[current_index].occupant,
-            reason: format!(
-                "無法向後延遲到位置 {}: 當前位置 {}",
-                target_index, current_index
-            ),
+            target_index,
+            current_index,
         }
         .into());
     }
 
     if target_index >= entries.len() {
-        return Err(BoardError::InvalidDelay {
+        return Err(BoardError::DelayOutOfTurnOrder {
             occupant: entries[current_index].occupant,
-            reason: format!(
-                "目標位置 {} 超出回合表範圍（{}）",
-                target_index,
-                entries.len()
-            ),
+            target_index,
+            len: entries.len(),
         }
         .into());
     }
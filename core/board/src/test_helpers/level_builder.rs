@@ -127,6 +127,7 @@ pub struct LevelBuilder {
     deploy_marker: Option<String>,
     victory_conditions: OutcomeBranches,
     defeat_conditions: OutcomeBranches,
+    max_rounds: Option<u32>,
 }
 
 impl LevelBuilder {
@@ -161,6 +162,7 @@ impl LevelBuilder {
             deploy_marker: None,
             victory_conditions: Vec::new(),
             defeat_conditions: Vec::new(),
+            max_rounds: None,
         }
     }
 
@@ -207,6 +209,12 @@ impl LevelBuilder {
         self
     }
 
+    /// 設定回合上限（超過即判定平手）
+    pub fn max_rounds(mut self, n: u32) -> Self {
+        self.max_rounds = Some(n);
+        self
+    }
+
     /// 從 ASCII 棋盤和單位標記建立 Board + 以 marker 為 key 的 UnitInfo map
     ///
     /// 每個單位標記的位置會自動分配遞增的 Occupant::Unit ID。
@@ -261,6 +269,18 @@ impl LevelBuilder {
 
     /// 組裝完整 TOML 字串
     pub fn to_toml(self) -> Result<String> {
+        let level = self.to_level()?;
+        toml::to_string_pretty(&level).map_err(|e| {
+            LoadError::SerializeError {
+                format: "level".to_string(),
+                reason: e.to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// 組裝 LevelType（不經序列化）
+    pub fn to_level(self) -> Result<LevelType> {
         let (board, markers) = load_from_ascii(&self.ascii)?;
 
         // 計算 max_player_units：若未設定，等於部署標記的位置數
@@ -332,14 +352,10 @@ impl LevelBuilder {
             object_placements,
             victory_conditions: self.victory_conditions,
             defeat_conditions: self.defeat_conditions,
+            max_rounds: self.max_rounds,
+            tile_labels: Vec::new(),
+            reinforcements: Vec::new(),
         };
-
-        toml::to_string_pretty(&level).map_err(|e| {
-            LoadError::SerializeError {
-                format: "level".to_string(),
-                reason: e.to_string(),
-            }
-            .into()
-        })
+        Ok(level)
     }
 }
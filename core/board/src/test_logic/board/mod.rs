@@ -1,10 +1,24 @@
 mod test_adjacent_penalty;
+mod test_cliff;
 mod test_collect_move_reactions;
 mod test_compute_affected_positions;
 mod test_compute_range_positions;
+mod test_concealment;
+mod test_conditional_effect;
+mod test_content_hash;
+mod test_effect_order;
+mod test_error_display;
 mod test_flanking;
+mod test_friendly_fire;
+mod test_high_ground;
 mod test_is_valid_position;
+mod test_level_edit;
+mod test_level_stats;
 mod test_line_of_sight;
+mod test_max_affected_distance;
 mod test_movement;
+mod test_new_board;
+mod test_position_helpers;
+mod test_random_effect;
 mod test_skill_area;
 mod test_skill_single_execution;
@@ -3,8 +3,8 @@
 use crate::domain::alias::ID;
 use crate::domain::constants::{ACCURACY_PENALTY_WHEN_ENEMY_ADJACENT, PLAYER_FACTION_ID};
 use crate::domain::core_types::{
-    AccuracySource, CasterOrTarget, DefenseType, Effect, EffectCondition, EffectNode, Scaling,
-    SkillTag,
+    AccuracySource, CasterOrTarget, DamageType, DefenseType, Effect, EffectCondition, EffectNode,
+    Scaling, SkillTag,
 };
 use crate::ecs_types::components::*;
 use crate::ecs_types::resources::Board;
@@ -65,6 +65,10 @@ fn build_setup(adjacent: bool, third_faction_id: ID) -> Setup {
             let mut stats = CombatStats {
                 unit_info: entry.unit_info.clone(),
                 attribute: AttributeBundle::default(),
+                elevation: 0,
+                concealment: 0,
+                buffs: Vec::new(),
+                resistances: HashMap::new(),
             };
             // 目標設定固定的閃避門檻，方便驗證命中是否被推過門檻
             if entry.unit_info.occupant == unit_markers["T"][0].unit_info.occupant {
@@ -109,6 +113,7 @@ fn physical_attack_node() -> EffectNode {
                     source_attribute: crate::domain::core_types::Attribute::PhysicalAttack,
                     value_percent: 100,
                 },
+                damage_type: DamageType::Neutral,
             },
         }],
         on_failure: vec![],
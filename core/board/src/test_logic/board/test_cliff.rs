@@ -0,0 +1,92 @@
+use crate::domain::alias::TypeName;
+use crate::domain::constants::{BASIC_MOVEMENT_COST, IMPASSABLE_MOVEMENT_COST};
+use crate::ecs_types::components::Position;
+use crate::ecs_types::resources::Board;
+use crate::loader_schema::ObjectType;
+use crate::logic::cliff::{CliffWarning, cliff_blocks, cliff_placement_warnings};
+use crate::logic::movement::Direction;
+use crate::test_helpers::level_builder::LevelBuilder;
+use std::collections::HashMap;
+
+const BOARD: Board = Board {
+    width: 3,
+    height: 3,
+};
+
+fn object_types() -> HashMap<TypeName, ObjectType> {
+    [
+        ObjectType {
+            name: "wall".to_string(),
+            movement_cost: IMPASSABLE_MOVEMENT_COST,
+            ..Default::default()
+        },
+        ObjectType {
+            name: "cliff_down".to_string(),
+            movement_cost: BASIC_MOVEMENT_COST,
+            cliff_facing: Some(Direction::Down),
+            ..Default::default()
+        },
+    ]
+    .into_iter()
+    .map(|o| (o.name.clone(), o))
+    .collect()
+}
+
+/// 朝下的水平懸崖：可從崖頂往下跳，不可從崖下攀上；其他方向進出不受影響
+#[test]
+fn test_cliff_blocks_one_direction() {
+    let cliff = Position { x: 1, y: 1 };
+    let below = Position { x: 1, y: 2 };
+    let above = Position { x: 1, y: 0 };
+    let side = Position { x: 0, y: 1 };
+
+    let test_data = [
+        ("崖下攀上", below, cliff, true),
+        ("崖頂跳下", cliff, below, false),
+        ("從上方進入崖頂", above, cliff, false),
+        ("從側面進入崖頂", side, cliff, false),
+        ("與懸崖無關的移動", side, below, false),
+    ];
+
+    for (label, from, to, expected) in test_data {
+        assert_eq!(
+            cliff_blocks(BOARD, from, to, cliff, Direction::Down),
+            expected,
+            "{label}"
+        );
+    }
+}
+
+#[test]
+fn test_cliff_placement_warnings() {
+    let level = LevelBuilder::from_ascii(
+        "
+        . c .
+        . w .
+        . . c
+        ",
+    )
+    .object("c", "cliff_down")
+    .object("w", "wall")
+    .to_level()
+    .expect("建立關卡失敗");
+
+    let mut warnings = cliff_placement_warnings(&level, &object_types()).expect("檢查懸崖應成功");
+    warnings.sort_by_key(|warning| match warning {
+        CliffWarning::FacingOffBoard { position }
+        | CliffWarning::FacingImpassable { position, .. } => (position.x, position.y),
+    });
+
+    assert_eq!(
+        warnings,
+        vec![
+            CliffWarning::FacingImpassable {
+                position: Position { x: 1, y: 0 },
+                blocked_by: "wall".to_string(),
+            },
+            CliffWarning::FacingOffBoard {
+                position: Position { x: 2, y: 2 },
+            },
+        ]
+    );
+}
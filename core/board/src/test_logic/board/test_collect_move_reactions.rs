@@ -18,6 +18,9 @@ const OA12_NAME: &str = "opportunity-attack-1-2";
 const OA22_NAME: &str = "opportunity-attack-2-2";
 const OB11_NAME: &str = "opportunity-buff-1-1";
 
+/// 反應者預設 MP（足以負擔測試技能）
+const REACTOR_MP: i32 = 10;
+
 fn standard_board(
     ascii: &str,
 ) -> Result<(
@@ -53,6 +56,7 @@ fn to_reaction_map<'a>(
                     ReactionUnitInfo {
                         unit_info: entry.unit_info.clone(),
                         remaining_reactions: config.remaining_reactions,
+                        current_mp: config.current_mp,
                         skills: config.skills.iter().collect(),
                     },
                 )
@@ -63,12 +67,14 @@ fn to_reaction_map<'a>(
 
 struct ReactionConfig {
     remaining_reactions: i32,
+    current_mp: i32,
     skills: Vec<SkillType>,
 }
 
 fn reaction_config(remaining: i32, skills: Vec<SkillType>) -> ReactionConfig {
     ReactionConfig {
         remaining_reactions: remaining,
+        current_mp: REACTOR_MP,
         skills,
     }
 }
@@ -401,3 +407,44 @@ fn collect_move_reactions_collects_whole_path() {
         "earliest_from_index 應為 Ea 觸發的步驟"
     );
 }
+
+/// MP 不足以負擔 cost 的反應技能不觸發；MP 足夠時照常觸發
+#[test]
+fn collect_move_reactions_skips_unaffordable_skills() {
+    let ascii = "
+        .  Ea .
+        S  P1 T
+        ";
+    let (markers, unit_markers) = standard_board(ascii).expect("建立棋盤失敗");
+    let mover = &unit_markers["S"][0].unit_info;
+    let path = resolve_path(&markers, &["S", "P1", "T"]);
+    let skill_cost = 5;
+
+    // (反應者 MP, 是否觸發)
+    let test_data = [(skill_cost as i32 - 1, false), (skill_cost as i32, true)];
+    for (current_mp, expect_reaction) in test_data {
+        let mut skill = reaction_skill(OA11_NAME, 1, 1, TargetFilter::Enemy);
+        if let SkillType::Reaction { cost, .. } = &mut skill {
+            *cost = skill_cost;
+        }
+        let reaction_configs = HashMap::from([(
+            "Ea",
+            ReactionConfig {
+                remaining_reactions: 1,
+                current_mp,
+                skills: vec![skill],
+            },
+        )]);
+        let units_on_board = to_reaction_map(&unit_markers, &reaction_configs);
+
+        let blocks_sight = HashSet::new();
+        let result = collect_move_reactions(mover, &path, &units_on_board, &blocks_sight)
+            .expect("collect 失敗");
+
+        assert_eq!(
+            !result.reactions.is_empty(),
+            expect_reaction,
+            "MP {current_mp}、cost {skill_cost} 的觸發結果不符"
+        );
+    }
+}
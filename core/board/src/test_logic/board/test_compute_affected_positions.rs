@@ -1,6 +1,7 @@
 //! compute_affected_positions 測試
 
-use crate::domain::core_types::Area;
+use crate::domain::alias::Coord;
+use crate::domain::core_types::{Area, RangeMetric};
 use crate::ecs_types::components::Position;
 use crate::logic::skill::skill_range::compute_affected_positions;
 use crate::test_helpers::level_builder::load_from_ascii;
@@ -241,7 +242,11 @@ fn test_compute_affected_positions() {
         ),
         // Line 測試：C=caster, T=target 決定方向
         (
-            Area::Line { length: 1 },
+            Area::Line {
+                length: 1,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -252,7 +257,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 1 },
+            Area::Line {
+                length: 1,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . C . . . .
@@ -263,7 +272,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 1 },
+            Area::Line {
+                length: 1,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -274,7 +287,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 1 },
+            Area::Line {
+                length: 1,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -285,7 +302,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 2 },
+            Area::Line {
+                length: 2,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . T . .
@@ -296,7 +317,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 2 },
+            Area::Line {
+                length: 2,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -307,7 +332,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 2 },
+            Area::Line {
+                length: 2,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -318,7 +347,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 2 },
+            Area::Line {
+                length: 2,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -329,7 +362,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 2 },
+            Area::Line {
+                length: 2,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -340,7 +377,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 3 },
+            Area::Line {
+                length: 3,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . T .
@@ -351,7 +392,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 3 },
+            Area::Line {
+                length: 3,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . C . . .
@@ -362,7 +407,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 3 },
+            Area::Line {
+                length: 3,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -373,7 +422,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 3 },
+            Area::Line {
+                length: 3,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -384,7 +437,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 3 },
+            Area::Line {
+                length: 3,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -395,7 +452,11 @@ fn test_compute_affected_positions() {
             ",
         ),
         (
-            Area::Line { length: 3 },
+            Area::Line {
+                length: 3,
+                snap_to_facing: false,
+                pierce: true,
+            },
             "
             . . . . . .
             . . . . . .
@@ -413,9 +474,321 @@ fn test_compute_affected_positions() {
         let target = markers.get("T").map(|v| v[0]).unwrap_or(caster);
         let expected_set = collect_affected(&markers);
 
-        let result = compute_affected_positions(&shape, caster, target, board)
+        let result = compute_affected_positions(&shape, caster, target, board, |_| false)
             .expect(&format!("計算失敗：{ascii}"));
         let result_set: HashSet<_> = result.into_iter().collect();
         assert_eq!(result_set, expected_set, "測試失敗：{ascii}");
     }
 }
+
+/// 3×2 矩形：近端中心為 T，沿 C→T 方向延伸 2 格、橫向寬 3 格（C 不在範圍內）
+#[test]
+fn test_compute_rectangle() {
+    let test_data = [
+        (
+            "向上",
+            "
+            . . . . .
+            . A A A .
+            . A T A .
+            . . . . .
+            . . C . .
+            ",
+        ),
+        (
+            "向右",
+            "
+            . . . . .
+            . . A A .
+            C . T A .
+            . . A A .
+            . . . . .
+            ",
+        ),
+        (
+            "靠近邊界裁切",
+            "
+            . . . . .
+            . . . . .
+            . . . . .
+            . . . . A
+            . . C . T
+            ",
+        ),
+    ];
+    let area = Area::Rectangle { width: 3, depth: 2 };
+
+    for (label, ascii) in test_data {
+        let (board, markers) = load_from_ascii(ascii).expect("載入棋盤失敗");
+        let caster = markers["C"][0];
+        let target = markers["T"][0];
+        let expected: HashSet<Position> = ["T", "A"]
+            .iter()
+            .flat_map(|key| markers.get(*key).into_iter().flatten().copied())
+            .collect();
+
+        let result: HashSet<Position> =
+            compute_affected_positions(&area, caster, target, board, |_| false)
+                .expect("計算失敗")
+                .into_iter()
+                .collect();
+        assert_eq!(result, expected, "{label}");
+    }
+}
+
+/// 吸附 8 方位的直線：接近正交的目標吸附為正交，接近斜角的目標吸附為斜角（T 本身不一定在線上）
+#[test]
+fn test_line_snap_to_facing() {
+    let test_data = [
+        (
+            "偏右（3, 1）吸附為正交",
+            "
+            . . . . . .
+            . . . . . .
+            . . . . T .
+            . C A A A .
+            . . . . . .
+            ",
+        ),
+        (
+            "偏右下（3, 2）吸附為斜角",
+            "
+            C . . . . .
+            . A . . . .
+            . . A T . .
+            . . . A . .
+            . . . . . .
+            ",
+        ),
+        (
+            "偏上（-1, -3）吸附為正交",
+            "
+            . . . . . .
+            . . T A . .
+            . . . A . .
+            . . . A . .
+            . . . C . .
+            ",
+        ),
+    ];
+    let area = Area::Line {
+        length: 3,
+        snap_to_facing: true,
+        pierce: true,
+    };
+
+    for (label, ascii) in test_data {
+        let (board, markers) = load_from_ascii(ascii).expect("載入棋盤失敗");
+        let caster = markers["C"][0];
+        let target = markers["T"][0];
+        let expected: HashSet<Position> = ["C", "A"]
+            .iter()
+            .flat_map(|key| markers.get(*key).into_iter().flatten().copied())
+            .collect();
+
+        let result: HashSet<Position> =
+            compute_affected_positions(&area, caster, target, board, |_| false)
+                .expect("計算失敗")
+                .into_iter()
+                .collect();
+        assert_eq!(result, expected, "{label}");
+    }
+}
+
+/// 未開啟吸附時，非正交目標仍視為錯誤
+#[test]
+fn test_line_without_snap_rejects_diagonal() {
+    let (board, markers) = load_from_ascii(
+        "
+        C . . . .
+        . . . . .
+        . . . T .
+        . . . . .
+        ",
+    )
+    .expect("載入棋盤失敗");
+    let area = Area::Line {
+        length: 3,
+        snap_to_facing: false,
+        pierce: true,
+    };
+
+    let result =
+        compute_affected_positions(&area, markers["C"][0], markers["T"][0], board, |_| false);
+    assert!(result.is_err(), "未吸附的斜向直線應失敗");
+}
+
+/// 非穿透直線停在第一個阻擋格（包含該格），穿透直線照常延伸；施放者所在格不算阻擋
+#[test]
+fn test_line_pierce_against_blocker() {
+    let (board, markers) = load_from_ascii("C T . w . .").expect("載入棋盤失敗");
+    let caster = markers["C"][0];
+    let wall = markers["w"][0];
+    let blockers: HashSet<Position> = [caster, wall].into_iter().collect();
+
+    let test_data = [
+        ("穿透直線穿過牆延伸到底", true, 6),
+        ("非穿透直線在牆處截斷", false, 4),
+    ];
+
+    for (label, pierce, expected_len) in test_data {
+        let area = Area::Line {
+            length: 5,
+            snap_to_facing: false,
+            pierce,
+        };
+        let result = compute_affected_positions(&area, caster, markers["T"][0], board, |pos| {
+            blockers.contains(&pos)
+        })
+        .expect("計算失敗");
+        assert_eq!(result.len(), expected_len, "{label}");
+        assert!(result.contains(&wall), "{label}：阻擋格本身應受影響");
+    }
+}
+
+/// 巨大範圍貼近棋盤邊緣時安全裁切：不溢位、不越界，結果只含棋盤內格子
+#[test]
+fn test_huge_area_near_edge_clips_safely() {
+    let (board, markers) = load_from_ascii(
+        "
+        . . . .
+        . . . C
+        . . . T
+        ",
+    )
+    .expect("載入棋盤失敗");
+    let caster = markers["C"][0];
+    let target = markers["T"][0];
+    let all_positions: HashSet<Position> = (0..board.height)
+        .flat_map(|y| (0..board.width).map(move |x| Position { x, y }))
+        .collect();
+
+    let test_data = [
+        (
+            "Diamond 覆蓋全棋盤",
+            Area::Diamond { radius: Coord::MAX },
+            12,
+        ),
+        ("Cross 只取同列同行", Area::Cross { length: Coord::MAX }, 6),
+        (
+            "Line 含施放者向下兩格後出界",
+            Area::Line {
+                length: Coord::MAX,
+                snap_to_facing: false,
+                pierce: true,
+            },
+            2,
+        ),
+        (
+            "Rectangle 寬深皆巨大",
+            Area::Rectangle {
+                width: Coord::MAX,
+                depth: Coord::MAX,
+            },
+            4,
+        ),
+    ];
+
+    for (label, area, expected_len) in test_data {
+        let result =
+            compute_affected_positions(&area, caster, target, board, |_| false).expect("計算失敗");
+        let unique: HashSet<Position> = result.iter().copied().collect();
+        assert!(unique.is_subset(&all_positions), "{label}：含棋盤外格子");
+        assert_eq!(unique.len(), expected_len, "{label}");
+    }
+}
+
+/// Circle 半徑 2：依度量列舉 5x5 棋盤中央為目標時涵蓋的格子
+#[test]
+fn test_circle_radius_two_per_metric() {
+    let test_data = [
+        (
+            RangeMetric::Manhattan,
+            "
+            . . A . .
+            . A A A .
+            A A T A A
+            . A A A .
+            . . A . .
+            ",
+        ),
+        (
+            RangeMetric::Chebyshev,
+            "
+            A A A A A
+            A A A A A
+            A A T A A
+            A A A A A
+            A A A A A
+            ",
+        ),
+        (
+            RangeMetric::Euclidean,
+            "
+            . . A . .
+            . A A A .
+            A A T A A
+            . A A A .
+            . . A . .
+            ",
+        ),
+    ];
+
+    for (metric, ascii) in test_data {
+        let (board, markers) = load_from_ascii(ascii).expect("載入棋盤失敗");
+        let target = markers["T"][0];
+        let expected: HashSet<Position> = ["T", "A"]
+            .iter()
+            .flat_map(|key| markers.get(*key).into_iter().flatten().copied())
+            .collect();
+        let area = Area::Circle { radius: 2, metric };
+
+        let result: HashSet<Position> =
+            compute_affected_positions(&area, target, target, board, |_| false)
+                .expect("計算失敗")
+                .into_iter()
+                .collect();
+        assert_eq!(result, expected, "{metric}");
+    }
+}
+
+/// 歐幾里得 Circle 半徑 3 與菱形不同：包含 (2, 2)，不含 (3, 1)
+#[test]
+fn test_circle_euclidean_differs_from_diamond() {
+    let (board, markers) = load_from_ascii(
+        "
+        . . . . . . .
+        . . . . . . .
+        . . . . . . .
+        . . . T . . .
+        . . . . . . .
+        . . . . . . .
+        . . . . . . .
+        ",
+    )
+    .expect("載入棋盤失敗");
+    let target = markers["T"][0];
+    let circle = Area::Circle {
+        radius: 3,
+        metric: RangeMetric::Euclidean,
+    };
+    let result: HashSet<Position> =
+        compute_affected_positions(&circle, target, target, board, |_| false)
+            .expect("計算失敗")
+            .into_iter()
+            .collect();
+
+    assert!(
+        result.contains(&Position { x: 5, y: 5 }),
+        "(2, 2) 距離 √8 應在內"
+    );
+    assert!(
+        !result.contains(&Position { x: 6, y: 4 }),
+        "(3, 1) 距離 √10 應在外"
+    );
+    assert!(
+        result.contains(&Position { x: 6, y: 3 }),
+        "(3, 0) 距離 3 應在內"
+    );
+    assert_eq!(result.len(), 29);
+}
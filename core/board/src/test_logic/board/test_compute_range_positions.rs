@@ -1,5 +1,6 @@
 //! compute_range_positions 測試
 
+use crate::domain::core_types::RangeMetric;
 use crate::ecs_types::components::Position;
 use crate::logic::skill::skill_range::compute_range_positions;
 use crate::test_helpers::level_builder::load_from_ascii;
@@ -158,7 +159,12 @@ fn test_compute_range_positions() {
             expected_set.insert(caster); // 包含施法者自己
         }
 
-        let result = compute_range_positions(caster, (min_range, max_range), board);
+        let result = compute_range_positions(
+            caster,
+            (min_range, max_range),
+            RangeMetric::Manhattan,
+            board,
+        );
         let result_set: HashSet<_> = result.into_iter().collect();
 
         assert_eq!(
@@ -168,3 +174,31 @@ fn test_compute_range_positions() {
         );
     }
 }
+
+/// 射程度量：切比雪夫射程包含斜角，曼哈頓不含
+#[test]
+fn test_compute_range_positions_with_metric() {
+    let (board, markers) = load_from_ascii(
+        "
+        . . . . .
+        . . . . .
+        . . P . .
+        . . . . .
+        . . . . .
+        ",
+    )
+    .expect("載入棋盤失敗");
+    let caster = markers["P"][0];
+    let corner = Position { x: 4, y: 4 };
+    let test_data = [
+        (RangeMetric::Manhattan, 12, false),
+        (RangeMetric::Chebyshev, 24, true),
+        (RangeMetric::Euclidean, 12, false),
+    ];
+
+    for (metric, expected_count, has_corner) in test_data {
+        let result = compute_range_positions(caster, (1, 2), metric, board);
+        assert_eq!(result.len(), expected_count, "{metric}");
+        assert_eq!(result.contains(&corner), has_corner, "{metric}");
+    }
+}
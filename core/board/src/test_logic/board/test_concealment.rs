@@ -0,0 +1,211 @@
+//! 地形隱蔽測試（透過 resolve_effect_tree 以固定骰序驗證隱蔽平骰）
+
+use crate::domain::alias::ID;
+use crate::domain::constants::PLAYER_FACTION_ID;
+use crate::domain::core_types::{
+    AccuracySource, Attribute, CasterOrTarget, DamageType, DefenseType, Effect, EffectCondition,
+    EffectNode, Scaling, SkillTag,
+};
+use crate::ecs_types::components::*;
+use crate::logic::skill::skill_execution::{
+    CheckResult, CombatStats, ResolvedEffect, resolve_effect_tree,
+};
+use crate::test_helpers::level_builder::LevelBuilder;
+use std::collections::HashMap;
+
+const ENEMY_FACTION_ID: ID = 2;
+const TEST_CASTER_ID: ID = 9999;
+const TEST_SKILL_NAME: &str = "concealment_test";
+const FOREST_CONCEALMENT: i32 = 20;
+/// 命中骰：介於強制失敗與強制成功之間，預設屬性下必定命中
+const HIT_ROLL: i32 = 50;
+/// 命中骰：強制失敗
+const MISS_ROLL: i32 = 1;
+
+/// 物理命中判定 + 扣血的 Branch 節點
+fn physical_attack_node() -> EffectNode {
+    EffectNode::Branch {
+        condition: EffectCondition {
+            defense_type: DefenseType::AgilityAndBlock,
+            accuracy_source: AccuracySource::Physical,
+            accuracy_bonus: 0,
+            crit_bonus: 0,
+        },
+        on_success: vec![EffectNode::Leaf {
+            who: CasterOrTarget::Target,
+            effect: Effect::HpEffect {
+                scaling: Scaling {
+                    source: CasterOrTarget::Caster,
+                    source_attribute: Attribute::PhysicalAttack,
+                    value_percent: 100,
+                },
+                damage_type: DamageType::Neutral,
+            },
+        }],
+        on_failure: vec![],
+    }
+}
+
+/// 表格驅動：依序餵入命中骰與隱蔽平骰，驗證判定結果與平骰紀錄
+#[test]
+fn test_concealment_flat_check() {
+    let test_data = [
+        (
+            "隱蔽平骰落在機率內 → 閃避",
+            FOREST_CONCEALMENT,
+            vec![HIT_ROLL, FOREST_CONCEALMENT],
+            CheckResult::Evade,
+            Some(FOREST_CONCEALMENT),
+        ),
+        (
+            "隱蔽平骰超過機率 → 命中",
+            FOREST_CONCEALMENT,
+            vec![HIT_ROLL, FOREST_CONCEALMENT + 1],
+            CheckResult::Hit { crit: false },
+            Some(FOREST_CONCEALMENT + 1),
+        ),
+        (
+            "原本未命中 → 不擲隱蔽平骰",
+            FOREST_CONCEALMENT,
+            vec![MISS_ROLL],
+            CheckResult::Evade,
+            None,
+        ),
+        (
+            "無隱蔽 → 不擲隱蔽平骰",
+            0,
+            vec![HIT_ROLL],
+            CheckResult::Hit { crit: false },
+            None,
+        ),
+    ];
+
+    for (label, concealment, rolls, expected_check, expected_flat_roll) in test_data {
+        let (board, _positions, unit_markers) = LevelBuilder::from_ascii("C . T")
+            .unit("C", "caster", PLAYER_FACTION_ID)
+            .unit("T", "target", ENEMY_FACTION_ID)
+            .to_unit_map()
+            .expect("建構隱蔽測試棋盤應成功");
+        let caster = &unit_markers["C"][0];
+        let target = &unit_markers["T"][0];
+
+        let caster_stats = CombatStats {
+            unit_info: caster.unit_info.clone(),
+            attribute: AttributeBundle::default(),
+            elevation: 0,
+            concealment: 0,
+            buffs: Vec::new(),
+            resistances: HashMap::new(),
+        };
+        let units_on_board: HashMap<Position, CombatStats> = [
+            (caster.position, caster_stats.clone()),
+            (
+                target.position,
+                CombatStats {
+                    unit_info: target.unit_info.clone(),
+                    attribute: AttributeBundle::default(),
+                    elevation: 0,
+                    concealment,
+                    buffs: Vec::new(),
+                    resistances: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut remaining = rolls.into_iter();
+        let entries = resolve_effect_tree(
+            TEST_CASTER_ID,
+            TEST_SKILL_NAME,
+            &[SkillTag::Melee],
+            std::slice::from_ref(&physical_attack_node()),
+            &caster_stats,
+            caster.position,
+            target.position,
+            &units_on_board,
+            &HashMap::new(),
+            board,
+            &mut || remaining.next().expect("骰序不應被用完"),
+            false,
+        )
+        .expect("resolve_effect_tree 應成功執行");
+
+        assert_eq!(remaining.next(), None, "{label}: 骰序應剛好用完");
+        assert_eq!(entries[0].check, expected_check, "{label}: 判定結果不符");
+        let detail = entries[0]
+            .check_detail
+            .as_ref()
+            .expect("命中判定應有 detail");
+        assert_eq!(
+            detail.concealment_roll, expected_flat_roll,
+            "{label}: 隱蔽平骰紀錄不符"
+        );
+        let hit = matches!(entries[0].effect, ResolvedEffect::HpChange { .. });
+        assert_eq!(
+            hit,
+            matches!(expected_check, CheckResult::Hit { .. }),
+            "{label}: 僅命中時才應結算傷害"
+        );
+    }
+}
+
+/// 強制命中（傷害預覽）不擲骰：即使骰序全為強制失敗也維持命中，且無隱蔽平骰
+#[test]
+fn test_concealment_skipped_when_force_hit() {
+    let (board, _positions, unit_markers) = LevelBuilder::from_ascii("C T")
+        .unit("C", "caster", PLAYER_FACTION_ID)
+        .unit("T", "target", ENEMY_FACTION_ID)
+        .to_unit_map()
+        .expect("建構隱蔽測試棋盤應成功");
+    let caster = &unit_markers["C"][0];
+    let target = &unit_markers["T"][0];
+
+    let caster_stats = CombatStats {
+        unit_info: caster.unit_info.clone(),
+        attribute: AttributeBundle::default(),
+        elevation: 0,
+        concealment: 0,
+        buffs: Vec::new(),
+        resistances: HashMap::new(),
+    };
+    let units_on_board: HashMap<Position, CombatStats> = [
+        (caster.position, caster_stats.clone()),
+        (
+            target.position,
+            CombatStats {
+                unit_info: target.unit_info.clone(),
+                attribute: AttributeBundle::default(),
+                elevation: 0,
+                concealment: FOREST_CONCEALMENT,
+                buffs: Vec::new(),
+                resistances: HashMap::new(),
+            },
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    let entries = resolve_effect_tree(
+        TEST_CASTER_ID,
+        TEST_SKILL_NAME,
+        &[SkillTag::Melee],
+        std::slice::from_ref(&physical_attack_node()),
+        &caster_stats,
+        caster.position,
+        target.position,
+        &units_on_board,
+        &HashMap::new(),
+        board,
+        &mut || MISS_ROLL,
+        true,
+    )
+    .expect("resolve_effect_tree 應成功執行");
+
+    assert_eq!(entries[0].check, CheckResult::Hit { crit: false });
+    let detail = entries[0]
+        .check_detail
+        .as_ref()
+        .expect("命中判定應有 detail");
+    assert_eq!(detail.concealment_roll, None);
+}
@@ -0,0 +1,167 @@
+//! 條件效果（Effect::If）測試
+
+use crate::domain::alias::ID;
+use crate::domain::constants::PLAYER_FACTION_ID;
+use crate::domain::core_types::{
+    Attribute, CasterOrTarget, DamageType, Effect, EffectNode, Scaling, TargetCondition,
+};
+use crate::ecs_types::components::*;
+use crate::logic::skill::skill_execution::{CombatStats, ResolvedEffect, resolve_effect_tree};
+use crate::test_helpers::level_builder::LevelBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const ENEMY_FACTION_ID: ID = 2;
+const TEST_CASTER_ID: ID = 9999;
+const TEST_SKILL_NAME: &str = "execute_test";
+const TARGET_MAX_HP: i32 = 100;
+const CASTER_ATTACK: i32 = 50;
+const MARK_BUFF: &str = "mark";
+
+/// 條件成立時對目標造成施放者物攻 100% 傷害的 Leaf 節點
+fn conditional_strike(condition: TargetCondition) -> EffectNode {
+    EffectNode::Leaf {
+        who: CasterOrTarget::Target,
+        effect: Effect::If {
+            condition,
+            then: Box::new(Effect::HpEffect {
+                scaling: Scaling {
+                    source: CasterOrTarget::Caster,
+                    source_attribute: Attribute::PhysicalAttack,
+                    value_percent: -100,
+                },
+                damage_type: DamageType::Neutral,
+            }),
+        },
+    }
+}
+
+/// 表格驅動：條件成立才產生內層效果條目
+#[test]
+fn test_conditional_effect_applies_only_when_condition_holds() {
+    let below_30 = TargetCondition::TargetBelowHp { percent: 30 };
+    let has_mark = TargetCondition::TargetHasBuff {
+        buff: MARK_BUFF.to_string(),
+    };
+
+    let test_data = [
+        ("HP 29% 低於門檻 → 套用", below_30.clone(), 29, vec![], true),
+        ("HP 30% 未低於門檻 → 不套用", below_30, 30, vec![], false),
+        (
+            "目標帶有標記 → 套用",
+            has_mark.clone(),
+            TARGET_MAX_HP,
+            vec![MARK_BUFF.to_string()],
+            true,
+        ),
+        (
+            "目標只有其他 Buff → 不套用",
+            has_mark,
+            TARGET_MAX_HP,
+            vec!["poison".to_string()],
+            false,
+        ),
+    ];
+
+    for (label, condition, target_hp, target_buffs, expect_applied) in test_data {
+        let (board, _positions, unit_markers) = LevelBuilder::from_ascii("C T")
+            .unit("C", "caster", PLAYER_FACTION_ID)
+            .unit("T", "target", ENEMY_FACTION_ID)
+            .to_unit_map()
+            .expect("建構條件效果測試棋盤應成功");
+        let caster = &unit_markers["C"][0];
+        let target = &unit_markers["T"][0];
+
+        let caster_attribute = AttributeBundle {
+            physical_attack: PhysicalAttack(CASTER_ATTACK),
+            ..AttributeBundle::default()
+        };
+        let caster_stats = CombatStats {
+            unit_info: caster.unit_info.clone(),
+            attribute: caster_attribute,
+            elevation: 0,
+            concealment: 0,
+            buffs: Vec::new(),
+            resistances: HashMap::new(),
+        };
+        let target_attribute = AttributeBundle {
+            max_hp: MaxHp(TARGET_MAX_HP),
+            current_hp: CurrentHp(target_hp),
+            ..AttributeBundle::default()
+        };
+        let units_on_board: HashMap<Position, CombatStats> = [
+            (caster.position, caster_stats.clone()),
+            (
+                target.position,
+                CombatStats {
+                    unit_info: target.unit_info.clone(),
+                    attribute: target_attribute,
+                    elevation: 0,
+                    concealment: 0,
+                    buffs: target_buffs,
+                    resistances: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let entries = resolve_effect_tree(
+            TEST_CASTER_ID,
+            TEST_SKILL_NAME,
+            &[],
+            std::slice::from_ref(&conditional_strike(condition)),
+            &caster_stats,
+            caster.position,
+            target.position,
+            &units_on_board,
+            &HashMap::new(),
+            board,
+            &mut || 1,
+            false,
+        )
+        .expect("resolve_effect_tree 應成功執行");
+
+        let damage: Vec<i32> = entries
+            .iter()
+            .filter_map(|entry| match entry.effect {
+                ResolvedEffect::HpChange { final_amount, .. } => Some(final_amount),
+                _ => None,
+            })
+            .collect();
+        let expected = if expect_applied {
+            vec![-CASTER_ATTACK]
+        } else {
+            vec![]
+        };
+        assert_eq!(damage, expected, "{label}");
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EffectWrapper {
+    effect: Effect,
+}
+
+/// 巢狀 Box<Effect> 可經 TOML 來回轉換
+#[test]
+fn test_conditional_effect_toml_round_trip() {
+    let node = conditional_strike(TargetCondition::TargetBelowHp { percent: 25 });
+    let effect = match node {
+        EffectNode::Leaf { effect, .. } => effect,
+        other => panic!("應為 Leaf 節點，實際為 {other:?}"),
+    };
+    let toml_str = toml::to_string(&EffectWrapper { effect }).expect("序列化條件效果應成功");
+    let parsed: EffectWrapper = toml::from_str(&toml_str).expect("反序列化條件效果應成功");
+
+    match parsed.effect {
+        Effect::If { condition, then } => {
+            assert_eq!(condition, TargetCondition::TargetBelowHp { percent: 25 });
+            assert!(
+                matches!(*then, Effect::HpEffect { ref scaling, .. } if scaling.value_percent == -100),
+                "內層效果應保留為 HpEffect，實際為 {then:?}"
+            );
+        }
+        other => panic!("應為 If 效果，實際為 {other:?}"),
+    }
+}
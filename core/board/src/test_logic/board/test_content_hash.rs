@@ -0,0 +1,17 @@
+use crate::logic::content_hash::StableHasher;
+use std::hash::Hasher;
+
+/// FNV-1a 64-bit 標準測試向量，確保演算法固定不隨版本改變
+#[test]
+fn test_stable_hasher_matches_fnv1a_vectors() {
+    let test_data: &[(&[u8], u64)] = &[
+        (b"", 0xcbf2_9ce4_8422_2325),
+        (b"a", 0xaf63_dc4c_8601_ec8c),
+        (b"foobar", 0x8594_4171_f739_67e8),
+    ];
+    for &(input, expected) in test_data {
+        let mut hasher = StableHasher::default();
+        hasher.write(input);
+        assert_eq!(hasher.finish(), expected, "輸入 {input:?}");
+    }
+}
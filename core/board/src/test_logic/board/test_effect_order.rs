@@ -0,0 +1,73 @@
+//! 頂層效果節點套用順序測試
+
+use crate::domain::core_types::{
+    AccuracySource, CasterOrTarget, DefenseType, Effect, EffectCondition, EffectNode, EffectOrder,
+};
+use crate::logic::skill::skill_execution::order_effect_nodes;
+
+fn mp_leaf(value: i32) -> EffectNode {
+    EffectNode::Leaf {
+        who: CasterOrTarget::Target,
+        effect: Effect::MpEffect { value },
+    }
+}
+
+/// 命中後才拉動的 Branch 節點（位移效果位於子節點）
+fn pull_branch() -> EffectNode {
+    EffectNode::Branch {
+        condition: EffectCondition {
+            defense_type: DefenseType::AgilityAndBlock,
+            accuracy_source: AccuracySource::Physical,
+            accuracy_bonus: 0,
+            crit_bonus: 0,
+        },
+        on_success: vec![EffectNode::Leaf {
+            who: CasterOrTarget::Target,
+            effect: Effect::Pull { distance: 1 },
+        }],
+        on_failure: vec![],
+    }
+}
+
+/// 回傳排序後各節點在原列表中的索引
+fn ordered_indices(nodes: &[EffectNode], order: EffectOrder) -> Vec<usize> {
+    order_effect_nodes(nodes, order)
+        .into_iter()
+        .map(|ordered| {
+            nodes
+                .iter()
+                .position(|node| std::ptr::eq(node, ordered))
+                .expect("排序結果應來自原列表")
+        })
+        .collect()
+}
+
+#[test]
+fn test_order_effect_nodes() {
+    let nodes = vec![
+        mp_leaf(-1),
+        pull_branch(),
+        mp_leaf(-2),
+        EffectNode::Leaf {
+            who: CasterOrTarget::Caster,
+            effect: Effect::Teleport { max_distance: 3 },
+        },
+    ];
+
+    let test_data = [
+        (
+            "AsListed 維持列出順序",
+            EffectOrder::AsListed,
+            vec![0, 1, 2, 3],
+        ),
+        (
+            "MovementFirst 位移節點（含巢狀）優先，同類維持原順序",
+            EffectOrder::MovementFirst,
+            vec![1, 3, 0, 2],
+        ),
+    ];
+
+    for (desc, order, expected) in test_data {
+        assert_eq!(ordered_indices(&nodes, order), expected, "{desc}");
+    }
+}
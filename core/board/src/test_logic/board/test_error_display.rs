@@ -0,0 +1,87 @@
+//! 帶座標的錯誤訊息測試：Display 必須包含出錯的位置
+
+use crate::error::{BoardError, DeploymentError, ErrorKind};
+
+const X: usize = 3;
+const Y: usize = 4;
+
+/// 表格驅動：每個帶座標的變體，訊息都應包含 "(X, Y)"
+#[test]
+fn test_positional_error_display_contains_position() {
+    let test_data: Vec<(&str, ErrorKind)> = vec![
+        (
+            "OutOfBoard",
+            BoardError::OutOfBoard {
+                x: X,
+                y: Y,
+                width: 2,
+                height: 2,
+            }
+            .into(),
+        ),
+        ("Unreachable", BoardError::Unreachable { x: X, y: Y }.into()),
+        (
+            "InvalidSkillTarget",
+            BoardError::InvalidSkillTarget {
+                shape: "Single".to_string(),
+                caster_x: 0,
+                caster_y: 0,
+                target_x: X,
+                target_y: Y,
+            }
+            .into(),
+        ),
+        (
+            "OutOfRange",
+            BoardError::OutOfRange {
+                x: X,
+                y: Y,
+                distance: 7,
+                min_range: 1,
+                max_range: 2,
+            }
+            .into(),
+        ),
+        (
+            "NoLineOfSight",
+            BoardError::NoLineOfSight { x: X, y: Y }.into(),
+        ),
+        (
+            "TargetFilterMismatch",
+            BoardError::TargetFilterMismatch {
+                x: X,
+                y: Y,
+                filter: "Enemy".to_string(),
+            }
+            .into(),
+        ),
+        (
+            "NoUnitAtTarget",
+            BoardError::NoUnitAtTarget { x: X, y: Y }.into(),
+        ),
+        (
+            "DuplicateTarget",
+            BoardError::DuplicateTarget {
+                targets: vec![(X, Y), (X, Y)],
+            }
+            .into(),
+        ),
+        (
+            "PositionNotDeployable",
+            DeploymentError::PositionNotDeployable { x: X, y: Y }.into(),
+        ),
+        (
+            "NothingToUndeploy",
+            DeploymentError::NothingToUndeploy { x: X, y: Y }.into(),
+        ),
+    ];
+
+    let expected = format!("({X}, {Y})");
+    for (label, kind) in test_data {
+        let message = kind.to_string();
+        assert!(
+            message.contains(&expected),
+            "{label}: 訊息應包含 {expected}，實際為 {message}"
+        );
+    }
+}
@@ -3,8 +3,8 @@
 use crate::domain::alias::ID;
 use crate::domain::constants::PLAYER_FACTION_ID;
 use crate::domain::core_types::{
-    AccuracySource, CasterOrTarget, DefenseType, Effect, EffectCondition, EffectNode, Scaling,
-    SkillTag,
+    AccuracySource, CasterOrTarget, DamageType, DefenseType, Effect, EffectCondition, EffectNode,
+    Scaling, SkillTag,
 };
 use crate::ecs_types::components::*;
 use crate::ecs_types::resources::Board;
@@ -76,6 +76,10 @@ fn build_setup(include_ally: bool, flanking_bonus: i32) -> Setup {
             let mut stats = CombatStats {
                 unit_info: entry.unit_info.clone(),
                 attribute: AttributeBundle::default(),
+                elevation: 0,
+                concealment: 0,
+                buffs: Vec::new(),
+                resistances: HashMap::new(),
             };
             // 敵軍設定固定的閃避門檻，方便驗證命中是否被推過門檻
             if entry.unit_info.faction_id == ENEMY_FACTION_ID {
@@ -120,6 +124,7 @@ fn physical_attack_node() -> EffectNode {
                     source_attribute: crate::domain::core_types::Attribute::PhysicalAttack,
                     value_percent: 100,
                 },
+                damage_type: DamageType::Neutral,
             },
         }],
         on_failure: vec![],
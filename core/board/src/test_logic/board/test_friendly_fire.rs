@@ -0,0 +1,116 @@
+//! 友軍傷害開關（SkillTag::NoFriendlyFire）測試
+
+use crate::domain::alias::ID;
+use crate::domain::constants::PLAYER_FACTION_ID;
+use crate::domain::core_types::{
+    Area, Attribute, CasterOrTarget, DamageType, Effect, EffectNode, Scaling, SkillTag,
+    TargetFilter,
+};
+use crate::ecs_types::components::*;
+use crate::logic::skill::skill_execution::{CheckTarget, CombatStats, resolve_effect_tree};
+use crate::test_helpers::level_builder::LevelBuilder;
+use std::collections::{HashMap, HashSet};
+
+const ENEMY_FACTION_ID: ID = 2;
+const TEST_CASTER_ID: ID = 9999;
+const TEST_SKILL_NAME: &str = "fireball_test";
+
+/// 火球：以目標格為中心的菱形範圍，對範圍內所有單位造成傷害
+fn fireball() -> EffectNode {
+    EffectNode::Area {
+        area: Area::Diamond { radius: 1 },
+        filter: TargetFilter::Any,
+        nodes: vec![EffectNode::Leaf {
+            who: CasterOrTarget::Target,
+            effect: Effect::HpEffect {
+                scaling: Scaling {
+                    source: CasterOrTarget::Caster,
+                    source_attribute: Attribute::MagicalAttack,
+                    value_percent: -100,
+                },
+                damage_type: DamageType::Fire,
+            },
+        }],
+    }
+}
+
+fn unit_id(occupant: Occupant) -> ID {
+    match occupant {
+        Occupant::Unit(id) => id,
+        Occupant::Object(_) => panic!("標記應為單位"),
+    }
+}
+
+/// 施放者與友軍都站在火球範圍內：關閉友軍傷害時只有敵人受影響
+#[test]
+fn test_fireball_spares_allies_when_friendly_fire_off() {
+    let (board, positions, unit_markers) = LevelBuilder::from_ascii(
+        "
+        . C .
+        A T E
+        ",
+    )
+    .unit("C", "caster", PLAYER_FACTION_ID)
+    .unit("A", "ally", PLAYER_FACTION_ID)
+    .unit("E", "enemy", ENEMY_FACTION_ID)
+    .to_unit_map()
+    .expect("建構友軍傷害測試棋盤應成功");
+    let caster = &unit_markers["C"][0];
+    let units_on_board: HashMap<Position, CombatStats> = unit_markers
+        .values()
+        .flatten()
+        .map(|entry| {
+            (
+                entry.position,
+                CombatStats {
+                    unit_info: entry.unit_info.clone(),
+                    attribute: AttributeBundle::default(),
+                    elevation: 0,
+                    concealment: 0,
+                    buffs: Vec::new(),
+                    resistances: HashMap::new(),
+                },
+            )
+        })
+        .collect();
+    let caster_stats = units_on_board[&caster.position].clone();
+    let id_of = |marker: &str| unit_id(unit_markers[marker][0].unit_info.occupant);
+
+    let test_data = [
+        (
+            "開啟友軍傷害：範圍內全部受影響",
+            vec![],
+            vec!["C", "A", "E"],
+        ),
+        (
+            "關閉友軍傷害：只有敵人受影響",
+            vec![SkillTag::NoFriendlyFire],
+            vec!["E"],
+        ),
+    ];
+
+    for (label, tags, expected_markers) in test_data {
+        let entries = resolve_effect_tree(
+            TEST_CASTER_ID,
+            TEST_SKILL_NAME,
+            &tags,
+            &[fireball()],
+            &caster_stats,
+            caster.position,
+            positions["T"][0],
+            &units_on_board,
+            &HashMap::new(),
+            board,
+            &mut || 1,
+            false,
+        )
+        .expect("resolve_effect_tree 應成功執行");
+
+        let hit: HashSet<CheckTarget> = entries.iter().map(|entry| entry.target).collect();
+        let expected: HashSet<CheckTarget> = expected_markers
+            .iter()
+            .map(|marker| CheckTarget::Unit(id_of(marker)))
+            .collect();
+        assert_eq!(hit, expected, "{label}");
+    }
+}
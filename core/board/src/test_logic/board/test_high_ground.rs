@@ -0,0 +1,127 @@
+//! 高處遠程命中加成測試（透過 resolve_effect_tree 驗證命中值明細）
+
+use crate::domain::alias::ID;
+use crate::domain::constants::{HIGH_GROUND_ACCURACY_BONUS, PLAYER_FACTION_ID};
+use crate::domain::core_types::{
+    AccuracySource, Attribute, CasterOrTarget, DamageType, DefenseType, Effect, EffectCondition,
+    EffectNode, Scaling, SkillTag,
+};
+use crate::ecs_types::components::*;
+use crate::logic::skill::skill_execution::{CombatStats, resolve_effect_tree};
+use crate::test_helpers::level_builder::LevelBuilder;
+use std::collections::HashMap;
+
+const ENEMY_FACTION_ID: ID = 2;
+const TEST_CASTER_ID: ID = 9999;
+const TEST_SKILL_NAME: &str = "high_ground_test";
+const ROLL: i32 = 50;
+
+/// 物理命中判定 + 扣血的 Branch 節點
+fn physical_attack_node() -> EffectNode {
+    EffectNode::Branch {
+        condition: EffectCondition {
+            defense_type: DefenseType::AgilityAndBlock,
+            accuracy_source: AccuracySource::Physical,
+            accuracy_bonus: 0,
+            crit_bonus: 0,
+        },
+        on_success: vec![EffectNode::Leaf {
+            who: CasterOrTarget::Target,
+            effect: Effect::HpEffect {
+                scaling: Scaling {
+                    source: CasterOrTarget::Caster,
+                    source_attribute: Attribute::PhysicalAttack,
+                    value_percent: 100,
+                },
+                damage_type: DamageType::Neutral,
+            },
+        }],
+        on_failure: vec![],
+    }
+}
+
+/// 表格驅動：僅 Ranged 技能且施放者所在高度高於目標時才有加成
+#[test]
+fn test_high_ground_accuracy_bonus() {
+    let ranged = &[SkillTag::Ranged][..];
+    let melee = &[SkillTag::Melee][..];
+
+    let test_data = [
+        (
+            "遠程 + 高處 → 加成",
+            ranged,
+            2,
+            0,
+            HIGH_GROUND_ACCURACY_BONUS,
+        ),
+        ("遠程 + 同高 → 無加成", ranged, 1, 1, 0),
+        ("遠程 + 低處 → 無加成", ranged, 0, 2, 0),
+        ("近戰 + 高處 → 無加成", melee, 2, 0, 0),
+    ];
+
+    for (label, tags, caster_elevation, target_elevation, expected_bonus) in test_data {
+        let (board, _positions, unit_markers) = LevelBuilder::from_ascii("C . . T")
+            .unit("C", "caster", PLAYER_FACTION_ID)
+            .unit("T", "target", ENEMY_FACTION_ID)
+            .to_unit_map()
+            .expect("建構高處測試棋盤應成功");
+        let caster = &unit_markers["C"][0];
+        let target = &unit_markers["T"][0];
+
+        let caster_stats = CombatStats {
+            unit_info: caster.unit_info.clone(),
+            attribute: AttributeBundle::default(),
+            elevation: caster_elevation,
+            concealment: 0,
+            buffs: Vec::new(),
+            resistances: HashMap::new(),
+        };
+        let units_on_board: HashMap<Position, CombatStats> = [
+            (caster.position, caster_stats.clone()),
+            (
+                target.position,
+                CombatStats {
+                    unit_info: target.unit_info.clone(),
+                    attribute: AttributeBundle::default(),
+                    elevation: target_elevation,
+                    concealment: 0,
+                    buffs: Vec::new(),
+                    resistances: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let entries = resolve_effect_tree(
+            TEST_CASTER_ID,
+            TEST_SKILL_NAME,
+            tags,
+            std::slice::from_ref(&physical_attack_node()),
+            &caster_stats,
+            caster.position,
+            target.position,
+            &units_on_board,
+            &HashMap::new(),
+            board,
+            &mut || ROLL,
+            false,
+        )
+        .expect("resolve_effect_tree 應成功執行");
+
+        let detail = entries[0]
+            .check_detail
+            .as_ref()
+            .expect("命中判定應有 detail");
+        let accuracy = detail.breakdowns.attacker_accuracy;
+        assert_eq!(
+            accuracy.high_ground_bonus, expected_bonus,
+            "{label}: high_ground_bonus 不符"
+        );
+        assert_eq!(
+            accuracy.total,
+            accuracy.base + expected_bonus,
+            "{label}: total 應含高處加成"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::ecs_types::components::Position;
-use crate::logic::board::is_valid_position;
+use crate::error::{BoardError, ErrorKind};
+use crate::logic::board::{is_valid_position, require_position_in_board};
 use crate::test_helpers::level_builder::load_from_ascii;
 
 #[test]
@@ -73,3 +74,33 @@ fn test_ascii_to_board_8x8() {
     assert!(!is_valid_position(board, Position { x: 0, y: 8 }));
     assert!(!is_valid_position(board, Position { x: 8, y: 8 }));
 }
+
+/// 超出邊界時回傳帶座標與邊界的 OutOfBoard；邊界內原樣回傳位置
+#[test]
+fn test_require_position_in_board() {
+    let (board, _markers) = load_from_ascii(
+        r#"
+. . .
+. . .
+    "#,
+    )
+    .expect("load_from_ascii 應成功");
+
+    let inside = Position { x: 2, y: 1 };
+    assert_eq!(
+        require_position_in_board(board, inside).expect("邊界內應成功"),
+        inside
+    );
+
+    for pos in [Position { x: 3, y: 0 }, Position { x: 0, y: 2 }] {
+        let err = require_position_in_board(board, pos).expect_err("邊界外應回傳錯誤");
+        assert!(
+            matches!(
+                err.kind(),
+                ErrorKind::Board(BoardError::OutOfBoard { x, y, width: 3, height: 2 })
+                    if *x == pos.x && *y == pos.y
+            ),
+            "應回報出錯位置 {pos:?}，實際為 {err:?}"
+        );
+    }
+}
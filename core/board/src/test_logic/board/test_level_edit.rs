@@ -0,0 +1,252 @@
+use crate::domain::constants::PLAYER_FACTION_ID;
+use crate::ecs_types::components::Position;
+use crate::error::{BoardError, ErrorKind};
+use crate::loader_schema::LevelType;
+use crate::logic::level_edit::{
+    apply_border, clear_border, perimeter_positions, rect_positions, replace_object_type,
+    translate_units,
+};
+use crate::test_helpers::level_builder::LevelBuilder;
+use std::collections::HashSet;
+
+const WALL: &str = "wall";
+const SWAMP: &str = "swamp";
+const WARRIOR: &str = "warrior";
+
+/// 指定類型物件所在的格子
+fn positions_of(level: &LevelType, type_name: &str) -> HashSet<Position> {
+    level
+        .object_placements
+        .iter()
+        .filter(|placement| placement.object_type_name == type_name)
+        .map(|placement| placement.position)
+        .collect()
+}
+
+/// 各單位所在的格子（依放置順序）
+fn unit_positions(level: &LevelType) -> Vec<Position> {
+    level
+        .unit_placements
+        .iter()
+        .map(|placement| placement.position)
+        .collect()
+}
+
+/// 兩名相鄰單位 a、b，右側有一面牆與一個部署點
+fn group_level() -> LevelType {
+    LevelBuilder::from_ascii(
+        "
+        a b . w
+        . . . .
+        . . . D
+        ",
+    )
+    .unit("a", WARRIOR, PLAYER_FACTION_ID)
+    .unit("b", WARRIOR, PLAYER_FACTION_ID)
+    .object("w", WALL)
+    .deploy("D")
+    .to_level()
+    .expect("建立關卡失敗")
+}
+
+#[test]
+fn test_perimeter_positions() {
+    let test_data = [
+        ("一般棋盤", 4, 3, 10),
+        ("單列", 5, 1, 5),
+        ("單格", 1, 1, 1),
+        ("空棋盤", 0, 3, 0),
+    ];
+
+    for (label, width, height, expected_len) in test_data {
+        let positions = perimeter_positions(width, height);
+        let unique: HashSet<Position> = positions.iter().copied().collect();
+        assert_eq!(unique.len(), positions.len(), "{label}：不應重複");
+        assert_eq!(positions.len(), expected_len, "{label}");
+    }
+}
+
+#[test]
+fn test_apply_border_fills_perimeter_only() {
+    let mut level = LevelBuilder::from_ascii(
+        "
+        . w . .
+        . s . .
+        . . . .
+        ",
+    )
+    .object("w", WALL)
+    .object("s", SWAMP)
+    .to_level()
+    .expect("建立關卡失敗");
+
+    apply_border(&mut level, WALL);
+
+    let perimeter: HashSet<Position> = perimeter_positions(4, 3).into_iter().collect();
+    assert_eq!(positions_of(&level, WALL), perimeter, "外圈應全為牆");
+    assert_eq!(
+        level.object_placements.len(),
+        perimeter.len() + 1,
+        "已有牆的格子不應重複放置"
+    );
+    assert_eq!(
+        positions_of(&level, SWAMP),
+        HashSet::from([Position { x: 1, y: 1 }]),
+        "內部物件不受影響"
+    );
+}
+
+#[test]
+fn test_clear_border_removes_only_perimeter_of_type() {
+    let mut level = LevelBuilder::from_ascii(
+        "
+        . . . .
+        . w s .
+        . . . s
+        ",
+    )
+    .object("w", WALL)
+    .object("s", SWAMP)
+    .to_level()
+    .expect("建立關卡失敗");
+    apply_border(&mut level, WALL);
+
+    clear_border(&mut level, WALL);
+
+    assert_eq!(
+        positions_of(&level, WALL),
+        HashSet::from([Position { x: 1, y: 1 }]),
+        "內部的牆應保留"
+    );
+    assert_eq!(
+        positions_of(&level, SWAMP),
+        HashSet::from([Position { x: 2, y: 1 }, Position { x: 3, y: 2 }]),
+        "其他類型的外圈物件應保留"
+    );
+}
+
+#[test]
+fn test_rect_positions_any_corner_order() {
+    let a = Position { x: 2, y: 0 };
+    let b = Position { x: 0, y: 1 };
+    let positions: HashSet<Position> = rect_positions(a, b).into_iter().collect();
+    assert_eq!(positions.len(), 6);
+    assert_eq!(positions, rect_positions(b, a).into_iter().collect());
+}
+
+#[test]
+fn test_translate_units_moves_whole_group() {
+    let test_data = [
+        ("向下平移", (0, 1), vec![(0, 1), (1, 1)]),
+        (
+            "向右平移一格：組內互相讓位不算衝突",
+            (1, 0),
+            vec![(1, 0), (2, 0)],
+        ),
+    ];
+
+    for (label, offset, expected) in test_data {
+        let mut level = group_level();
+        let selected = unit_positions(&level);
+        let moved = translate_units(&mut level, &selected, offset).expect(label);
+        let expected: Vec<Position> = expected
+            .into_iter()
+            .map(|(x, y)| Position { x, y })
+            .collect();
+        assert_eq!(unit_positions(&level), expected, "{label}");
+        assert_eq!(moved, expected, "{label}");
+    }
+}
+
+#[test]
+fn test_translate_units_conflict_is_all_or_nothing() {
+    let test_data = [
+        ("超出左邊界", (-1, 0), "OffsetOutOfBoard"),
+        ("超出下邊界", (0, 3), "OffsetOutOfBoard"),
+        ("b 撞上牆", (2, 0), "PositionOccupied"),
+        ("b 撞上部署點", (2, 2), "PositionOccupied"),
+    ];
+
+    for (label, offset, expected_error) in test_data {
+        let mut level = group_level();
+        let selected = unit_positions(&level);
+        let error = translate_units(&mut level, &selected, offset).expect_err(label);
+        let matched = match error.kind() {
+            ErrorKind::Board(BoardError::OffsetOutOfBoard { .. }) => "OffsetOutOfBoard",
+            ErrorKind::Board(BoardError::PositionOccupied { .. }) => "PositionOccupied",
+            _ => "other",
+        };
+        assert_eq!(matched, expected_error, "{label}");
+        assert_eq!(unit_positions(&level), selected, "{label}：關卡不應被改動");
+    }
+}
+
+#[test]
+fn test_translate_units_blocked_by_unselected_unit() {
+    let mut level = group_level();
+    let a = Position { x: 0, y: 0 };
+    let error = translate_units(&mut level, &[a], (1, 0)).expect_err("a 撞上未選取的 b");
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Board(BoardError::PositionOccupied { x: 1, y: 0 })
+    ));
+    assert_eq!(unit_positions(&level)[0], a, "a 不應移動");
+}
+
+#[test]
+fn test_replace_object_type_counts_and_keeps_others() {
+    let mut level = LevelBuilder::from_ascii(
+        "
+        s s w
+        . s .
+        ",
+    )
+    .object("w", WALL)
+    .object("s", SWAMP)
+    .to_level()
+    .expect("建立關卡失敗");
+    let swamps = positions_of(&level, SWAMP);
+
+    let replaced = replace_object_type(&mut level, SWAMP, "water");
+
+    assert_eq!(replaced, 3);
+    assert_eq!(positions_of(&level, "water"), swamps, "沼澤應全換成深水");
+    assert!(positions_of(&level, SWAMP).is_empty());
+    assert_eq!(
+        positions_of(&level, WALL),
+        HashSet::from([Position { x: 2, y: 0 }]),
+        "不符合的物件不受影響"
+    );
+}
+
+#[test]
+fn test_replace_object_type_merges_into_existing() {
+    let mut level = LevelBuilder::from_ascii(
+        "
+        s w .
+        ",
+    )
+    .object("w", WALL)
+    .object("s", SWAMP)
+    .to_level()
+    .expect("建立關卡失敗");
+    // 讓 (1, 0) 同時有牆與沼澤
+    let mut extra = level.object_placements[0].clone();
+    extra.object_type_name = SWAMP.to_string();
+    extra.position = Position { x: 1, y: 0 };
+    level.object_placements.push(extra);
+
+    let replaced = replace_object_type(&mut level, SWAMP, WALL);
+
+    assert_eq!(replaced, 2);
+    assert_eq!(level.object_placements.len(), 2, "同格不應出現兩面牆");
+    assert_eq!(
+        positions_of(&level, WALL),
+        HashSet::from([Position { x: 0, y: 0 }, Position { x: 1, y: 0 }])
+    );
+    assert_eq!(
+        replace_object_type(&mut level, WALL, WALL),
+        0,
+        "同類型取代不做事"
+    );
+}
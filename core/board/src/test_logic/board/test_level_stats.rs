@@ -0,0 +1,140 @@
+use crate::domain::alias::TypeName;
+use crate::domain::constants::{BASIC_MOVEMENT_COST, IMPASSABLE_MOVEMENT_COST};
+use crate::loader_schema::ObjectType;
+use crate::logic::level_stats::{level_stats, open_space_ratio};
+use crate::test_helpers::level_builder::LevelBuilder;
+use std::collections::HashMap;
+
+fn object_types() -> HashMap<TypeName, ObjectType> {
+    [
+        ObjectType {
+            name: "wall".to_string(),
+            movement_cost: IMPASSABLE_MOVEMENT_COST,
+            blocks_sight: true,
+            ..Default::default()
+        },
+        ObjectType {
+            name: "water".to_string(),
+            movement_cost: BASIC_MOVEMENT_COST,
+            hazardous: true,
+            ..Default::default()
+        },
+        ObjectType {
+            name: "swamp".to_string(),
+            movement_cost: BASIC_MOVEMENT_COST,
+            ..Default::default()
+        },
+    ]
+    .into_iter()
+    .map(|o| (o.name.clone(), o))
+    .collect()
+}
+
+#[test]
+fn test_level_stats_counts() {
+    let level = LevelBuilder::from_ascii(
+        "
+        D D . w w
+        . . . t .
+        E . . . A
+        E . P . .
+    ",
+    )
+    .unit("P", "warrior", 0)
+    .unit("A", "mage", 0)
+    .unit("E", "warrior", 1)
+    .object("w", "wall")
+    .object("t", "tree")
+    .deploy("D")
+    .to_level()
+    .expect("LevelBuilder::to_level 應成功");
+
+    let stats = level_stats(&level);
+
+    assert_eq!(stats.total_tiles, 20);
+    assert_eq!(stats.deployable_tiles, 2);
+    // 3 物件 + 4 單位佔用，部署點不算佔用
+    assert_eq!(stats.empty_tiles, 13);
+    assert_eq!(stats.object_counts.get("wall"), Some(&2));
+    assert_eq!(stats.object_counts.get("tree"), Some(&1));
+    assert_eq!(stats.unit_type_counts.get("warrior"), Some(&3));
+    assert_eq!(stats.unit_type_counts.get("mage"), Some(&1));
+    assert_eq!(stats.unit_counts_by_faction.get(&0), Some(&2));
+    assert_eq!(stats.unit_counts_by_faction.get(&1), Some(&2));
+}
+
+#[test]
+fn test_level_stats_empty_level() {
+    let level = LevelBuilder::from_ascii(
+        "
+        . .
+        . .
+    ",
+    )
+    .to_level()
+    .expect("LevelBuilder::to_level 應成功");
+
+    let stats = level_stats(&level);
+
+    assert_eq!(stats.total_tiles, 4);
+    assert_eq!(stats.empty_tiles, 4);
+    assert_eq!(stats.deployable_tiles, 0);
+    assert!(stats.object_counts.is_empty());
+    assert!(stats.unit_counts_by_faction.is_empty());
+}
+
+#[test]
+fn test_open_space_ratio_split_by_wall() {
+    let level = LevelBuilder::from_ascii(
+        "
+        D . w . .
+        . . w . .
+        . s w . .
+        . . w . .
+    ",
+    )
+    .object("w", "wall")
+    .object("s", "swamp")
+    .deploy("D")
+    .to_level()
+    .expect("LevelBuilder::to_level 應成功");
+
+    let ratio = open_space_ratio(&level, &object_types()).expect("應成功計算");
+
+    // 可走 16 格，左半（含沼澤）8 格可達
+    assert_eq!(ratio, 0.5);
+}
+
+#[test]
+fn test_open_space_ratio_fully_open() {
+    let level = LevelBuilder::from_ascii(
+        "
+        D . ~
+        . . .
+    ",
+    )
+    .object("~", "water")
+    .deploy("D")
+    .to_level()
+    .expect("LevelBuilder::to_level 應成功");
+
+    let ratio = open_space_ratio(&level, &object_types()).expect("應成功計算");
+
+    // 水格不算可走，其餘 5 格皆可達
+    assert_eq!(ratio, 1.0);
+}
+
+#[test]
+fn test_open_space_ratio_unknown_object_type() {
+    let level = LevelBuilder::from_ascii(
+        "
+        D x
+    ",
+    )
+    .object("x", "nonexistent")
+    .deploy("D")
+    .to_level()
+    .expect("LevelBuilder::to_level 應成功");
+
+    assert!(open_space_ratio(&level, &object_types()).is_err());
+}
@@ -1,5 +1,5 @@
 use crate::ecs_types::components::Position;
-use crate::logic::skill::line_of_sight::has_line_of_sight;
+use crate::logic::skill::line_of_sight::{has_elevated_line_of_sight, has_line_of_sight};
 use crate::test_helpers::level_builder::load_from_ascii;
 use std::collections::{HashMap, HashSet};
 
@@ -161,3 +161,37 @@ A . . . ."#;
         "起點等於終點應回傳 true"
     );
 }
+
+#[test]
+fn test_elevated_los() {
+    // M = 山地（高度 2）、H = 丘陵阻擋物（高度 1）、P = 平地（高度 0）
+    // 站在山地可越過丘陵看到 T；站在平地則被丘陵擋住
+    let ascii = r#"
+M . H . T
+P . H . T"#;
+    let (_, m) = load_from_ascii(ascii).expect("ASCII 解析失敗");
+    let mountain = get_first(&m, "M");
+    let plain = get_first(&m, "P");
+    let hills: HashSet<Position> = m["H"].iter().copied().collect();
+    let mut elevations: HashMap<Position, i32> = hills.iter().map(|pos| (*pos, 1)).collect();
+    elevations.insert(mountain, 2);
+
+    let test_data = [
+        (mountain, m["T"][0], true, "山地越過丘陵"),
+        (plain, m["T"][1], false, "平地被丘陵阻擋"),
+    ];
+    for (from, to, expected, label) in test_data {
+        assert_eq!(
+            has_elevated_line_of_sight(from, to, &hills, &elevations),
+            expected,
+            "{label}"
+        );
+    }
+
+    // 無高度資料時與 has_line_of_sight 一致
+    let flat = HashMap::new();
+    assert!(
+        !has_elevated_line_of_sight(mountain, m["T"][0], &hills, &flat),
+        "無高度資料應視為平地"
+    );
+}
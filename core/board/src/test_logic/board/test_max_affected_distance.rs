@@ -0,0 +1,114 @@
+//! max_affected_distance 測試
+
+use crate::domain::alias::Coord;
+use crate::domain::core_types::{
+    Area, CasterOrTarget, Effect, EffectNode, EffectOrder, RangeMetric, SkillType, Target,
+    TargetFilter, TargetSelection,
+};
+use crate::logic::skill::skill_range::max_affected_distance;
+use std::sync::Arc;
+
+/// 建立射程上限為 max_range、效果樹為單一 Area 節點的主動技能
+fn active_skill_with_area(max_range: Coord, area: Area) -> SkillType {
+    SkillType::Active {
+        name: "test_skill".to_string(),
+        tags: Vec::new(),
+        cost: 0,
+        requires: Vec::new(),
+        hits: 1,
+        recoil: 0,
+        effect_order: EffectOrder::AsListed,
+        channel_turns: 0,
+        target: Target {
+            range: (0, max_range),
+            range_metric: RangeMetric::Manhattan,
+            selection: TargetSelection::Ground,
+            selectable_filter: TargetFilter::Any,
+            count: 1,
+            allow_same_target: false,
+            area,
+        },
+        effects: Arc::from([EffectNode::Area {
+            area,
+            filter: TargetFilter::Any,
+            nodes: vec![EffectNode::Leaf {
+                who: CasterOrTarget::Target,
+                effect: Effect::MpEffect { value: -1 },
+            }],
+        }]),
+    }
+}
+
+#[test]
+fn test_max_affected_distance() {
+    let test_data = [
+        ("Single 只看射程", 3, Area::Single, 3),
+        ("Diamond 射程加半徑", 3, Area::Diamond { radius: 2 }, 5),
+        ("Cross 射程加長度", 2, Area::Cross { length: 4 }, 6),
+        (
+            "Line 由施放者出發，取射程與長度較大者",
+            1,
+            Area::Line {
+                length: 4,
+                snap_to_facing: false,
+                pierce: true,
+            },
+            4,
+        ),
+        (
+            "吸附斜角的 Line 每步距離為 2",
+            1,
+            Area::Line {
+                length: 4,
+                snap_to_facing: true,
+                pierce: true,
+            },
+            8,
+        ),
+        (
+            "Rectangle 射程加深度減一加半寬",
+            2,
+            Area::Rectangle { width: 3, depth: 2 },
+            4,
+        ),
+        (
+            "切比雪夫 Circle 的角落距離為兩倍半徑",
+            3,
+            Area::Circle {
+                radius: 2,
+                metric: RangeMetric::Chebyshev,
+            },
+            7,
+        ),
+        (
+            "歐幾里得 Circle 取圓上最遠的斜向格",
+            3,
+            Area::Circle {
+                radius: 3,
+                metric: RangeMetric::Euclidean,
+            },
+            7,
+        ),
+        (
+            "巨大數值飽和而不溢位",
+            Coord::MAX,
+            Area::Diamond { radius: Coord::MAX },
+            Coord::MAX,
+        ),
+    ];
+
+    for (label, max_range, area, expected) in test_data {
+        let skill = active_skill_with_area(max_range, area);
+        assert_eq!(max_affected_distance(&skill), expected, "{label}");
+    }
+}
+
+#[test]
+fn test_max_affected_distance_passive_is_zero() {
+    let skill = SkillType::Passive {
+        name: "test_passive".to_string(),
+        tags: Vec::new(),
+        effects: Vec::new(),
+    };
+    assert_eq!(max_affected_distance(&skill), 0);
+}
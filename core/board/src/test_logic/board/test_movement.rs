@@ -4,7 +4,8 @@ use crate::domain::alias::MovementCost;
 use crate::domain::constants::{BASIC_MOVEMENT_COST, IMPASSABLE_MOVEMENT_COST, PLAYER_ALLIANCE_ID};
 use crate::ecs_types::components::Position;
 use crate::logic::movement::{
-    Direction, Mover, ReachableInfo, reachable_positions, reconstruct_path, step_in_direction,
+    Direction, Mover, ReachableInfo, nearest_reachable, pull_destination, reachable_positions,
+    reconstruct_path, step_in_direction,
 };
 use crate::test_helpers::level_builder::load_from_ascii;
 use std::collections::HashSet;
@@ -168,7 +169,14 @@ fn test_reachable_positions_out_of_bound() {
             pos: from,
             faction_alliance: PLAYER_ALLIANCE_ID,
         };
-        let result = reachable_positions(board, mover, NORMAL_COST, |_| None, |_| NORMAL_COST);
+        let result = reachable_positions(
+            board,
+            mover,
+            NORMAL_COST,
+            |_| None,
+            |_| NORMAL_COST,
+            |_, _| false,
+        );
         assert!(
             result.is_err(),
             "From position {:?} should be out of bound",
@@ -281,8 +289,15 @@ S . . . .
             pos: from,
             faction_alliance: PLAYER_ALLIANCE_ID,
         };
-        let result = reachable_positions(board, mover, *budget, |_| None, |_| NORMAL_COST)
-            .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
+        let result = reachable_positions(
+            board,
+            mover,
+            *budget,
+            |_| None,
+            |_| NORMAL_COST,
+            |_, _| false,
+        )
+        .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
         let expected_set: HashSet<_> = expected.iter().cloned().collect();
         let result_set: HashSet<_> = result
             .iter()
@@ -408,8 +423,15 @@ S E . . .
             }
         };
 
-        let result = reachable_positions(board, mover, *budget, get_occupant, |_| NORMAL_COST)
-            .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
+        let result = reachable_positions(
+            board,
+            mover,
+            *budget,
+            get_occupant,
+            |_| NORMAL_COST,
+            |_, _| false,
+        )
+        .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
         let expected_set: HashSet<_> = expected.iter().cloned().collect();
         let result_set: HashSet<_> = result
             .iter()
@@ -527,8 +549,15 @@ S A . . .
             }
         };
 
-        let result = reachable_positions(board, mover, *budget, get_occupant, |_| NORMAL_COST)
-            .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
+        let result = reachable_positions(
+            board,
+            mover,
+            *budget,
+            get_occupant,
+            |_| NORMAL_COST,
+            |_, _| false,
+        )
+        .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
 
         let expected_set: HashSet<_> = expected.iter().cloned().collect();
         let result_set: HashSet<_> = result
@@ -630,8 +659,15 @@ S # . . .
             }
         };
 
-        let result = reachable_positions(board, mover, *budget, |_| None, get_terrain_cost)
-            .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
+        let result = reachable_positions(
+            board,
+            mover,
+            *budget,
+            |_| None,
+            get_terrain_cost,
+            |_, _| false,
+        )
+        .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
         let expected_set: HashSet<_> = expected.iter().cloned().collect();
         let result_set: HashSet<_> = result
             .iter()
@@ -847,8 +883,15 @@ S w w . .
             }
         };
 
-        let result = reachable_positions(board, mover, *budget, |_| None, get_terrain_cost)
-            .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
+        let result = reachable_positions(
+            board,
+            mover,
+            *budget,
+            |_| None,
+            get_terrain_cost,
+            |_, _| false,
+        )
+        .unwrap_or_else(|e| panic!("Case {} failed: {:?}", idx, e));
         let expected_set: HashSet<_> = expected.iter().cloned().collect();
         let result_set: HashSet<_> = result
             .iter()
@@ -1080,8 +1123,15 @@ fn test_reconstruct_path() {
         };
         let budget = NORMAL_COST * 10;
 
-        let reachable = reachable_positions(board, mover, budget, get_occupant, get_terrain_cost)
-            .unwrap_or_else(|e| panic!("Case {} reachable failed: {:?}", desc, e));
+        let reachable = reachable_positions(
+            board,
+            mover,
+            budget,
+            get_occupant,
+            get_terrain_cost,
+            |_, _| false,
+        )
+        .unwrap_or_else(|e| panic!("Case {} reachable failed: {:?}", desc, e));
 
         let path = reconstruct_path(&reachable, start, target);
         let expected: Vec<Position> = expected
@@ -1091,3 +1141,237 @@ fn test_reconstruct_path() {
         assert_eq!(path, expected, "Case {} path mismatch", desc);
     }
 }
+
+/// 多條等成本路徑時回傳的路徑固定（golden）：同成本先展開座標較小者，因此先沿 x 較小的一側走
+#[test]
+fn test_reconstruct_path_equal_cost_is_canonical() {
+    let test_data = [
+        (
+            "右下斜向：先向下走到底再向右",
+            r#"
+            S . .
+            . . .
+            . . T
+            "#,
+            vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)],
+        ),
+        (
+            "左上斜向：先向左走到底再向上",
+            r#"
+            T . .
+            . . .
+            . . S
+            "#,
+            vec![(2, 2), (1, 2), (0, 2), (0, 1), (0, 0)],
+        ),
+        (
+            "繞過中央障礙：走 x 較小的左側",
+            r#"
+            . S .
+            . # .
+            . T .
+            "#,
+            vec![(1, 0), (0, 0), (0, 1), (0, 2), (1, 2)],
+        ),
+    ];
+
+    for (desc, ascii, expected) in test_data {
+        let (board, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+        let start = markers["S"][0];
+        let target = markers["T"][0];
+        let walls: Vec<Position> = markers.get("#").cloned().unwrap_or_default();
+        let get_terrain_cost = |pos: Position| {
+            if walls.contains(&pos) {
+                IMPASSABLE_MOVEMENT_COST
+            } else {
+                NORMAL_COST
+            }
+        };
+
+        // 重複計算多次，確認結果不受 HashMap 雜湊種子影響
+        for _ in 0..5 {
+            let mover = Mover {
+                pos: start,
+                faction_alliance: PLAYER_ALLIANCE_ID,
+            };
+            let reachable = reachable_positions(
+                board,
+                mover,
+                NORMAL_COST * 10,
+                |_| None,
+                get_terrain_cost,
+                |_, _| false,
+            )
+            .unwrap_or_else(|e| panic!("Case {} reachable failed: {:?}", desc, e));
+            let path = reconstruct_path(&reachable, start, target);
+            let expected: Vec<Position> =
+                expected.iter().map(|&(x, y)| Position { x, y }).collect();
+            assert_eq!(path, expected, "Case {} path mismatch", desc);
+        }
+    }
+}
+
+// ============================================================================
+// nearest_reachable 測試
+// ============================================================================
+
+#[test]
+fn test_nearest_reachable() {
+    // (描述, 棋盤, 目標 marker, 預算, 預期 (目標 marker, 成本, 路徑))
+    let test_data = [
+        (
+            "牆後的 A 直線較近但要繞路，選路徑成本較低的 B",
+            r#"
+            S # A
+            . # .
+            . # .
+            B . .
+            "#,
+            vec!["A", "B"],
+            NORMAL_COST * 10,
+            Some(("B", NORMAL_COST * 3, vec![(0, 0), (0, 1), (0, 2), (0, 3)])),
+        ),
+        (
+            "拆掉牆後 A 較近",
+            r#"
+            S . A
+            . . .
+            . . .
+            B . .
+            "#,
+            vec!["A", "B"],
+            NORMAL_COST * 10,
+            Some(("A", NORMAL_COST * 2, vec![(0, 0), (1, 0), (2, 0)])),
+        ),
+        (
+            "友軍佔據的目標不可停留，選下一個",
+            r#"
+            S F . B
+            "#,
+            vec!["F", "B"],
+            NORMAL_COST * 10,
+            Some(("B", NORMAL_COST * 3, vec![(0, 0), (1, 0), (2, 0), (3, 0)])),
+        ),
+        (
+            "起點即為目標",
+            r#"
+            S . B
+            "#,
+            vec!["S", "B"],
+            NORMAL_COST * 10,
+            Some(("S", 0, vec![(0, 0)])),
+        ),
+        (
+            "預算內沒有目標",
+            r#"
+            S # A
+            . # .
+            . # .
+            B . .
+            "#,
+            vec!["A", "B"],
+            NORMAL_COST * 2,
+            None,
+        ),
+    ];
+
+    for (desc, ascii, goal_markers, budget, expected) in test_data {
+        let (board, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+        let walls: Vec<Position> = markers.get("#").cloned().unwrap_or_default();
+        let allies: Vec<Position> = markers.get("F").cloned().unwrap_or_default();
+        let goals: Vec<Position> = goal_markers
+            .iter()
+            .map(|marker| markers[*marker][0])
+            .collect();
+        let mover = Mover {
+            pos: markers["S"][0],
+            faction_alliance: PLAYER_ALLIANCE_ID,
+        };
+        let get_terrain_cost = |pos: Position| {
+            if walls.contains(&pos) {
+                IMPASSABLE_MOVEMENT_COST
+            } else {
+                NORMAL_COST
+            }
+        };
+
+        let result = nearest_reachable(
+            board,
+            mover,
+            budget,
+            &goals,
+            |pos| allies.contains(&pos).then_some(PLAYER_ALLIANCE_ID),
+            get_terrain_cost,
+            |_, _| false,
+        )
+        .unwrap_or_else(|e| panic!("Case {} failed: {:?}", desc, e));
+        let expected = expected.map(|(marker, cost, path)| {
+            let path: Vec<Position> = path.into_iter().map(|(x, y)| Position { x, y }).collect();
+            (markers[marker][0], cost, path)
+        });
+        assert_eq!(result, expected, "Case {} mismatch", desc);
+    }
+}
+
+// ============================================================================
+// pull_destination 測試
+// ============================================================================
+
+/// C=施放者，T=目標，E=預期落點，w=阻擋格
+#[test]
+fn test_pull_destination() {
+    let test_data = [
+        ("拉動兩格", "C . . . T", 2, "C . E . T"),
+        ("停在施放者相鄰格", "C . . T", 5, "C E . T"),
+        ("施放者相鄰格被阻擋", "C w . T", 2, "C w E T"),
+        (
+            "垂直拉動",
+            "
+            C
+            .
+            .
+            T
+            ",
+            2,
+            "
+            C
+            E
+            .
+            T
+            ",
+        ),
+        (
+            "非直線時沿主軸移動",
+            "
+            C . .
+            . . T
+            ",
+            1,
+            "
+            C . .
+            . E T
+            ",
+        ),
+    ];
+
+    for (label, ascii, distance, expected_ascii) in test_data {
+        let (board, markers) =
+            load_from_ascii(ascii).unwrap_or_else(|e| panic!("{label}: 解析失敗 {e:?}"));
+        let (_, expected_markers) =
+            load_from_ascii(expected_ascii).unwrap_or_else(|e| panic!("{label}: 解析失敗 {e:?}"));
+        let caster = markers["C"][0];
+        let blocked: HashSet<Position> = markers
+            .get("w")
+            .into_iter()
+            .flatten()
+            .copied()
+            .chain([caster])
+            .collect();
+
+        let result = pull_destination(board, markers["T"][0], caster, distance, |pos| {
+            blocked.contains(&pos)
+        });
+
+        assert_eq!(result, expected_markers["E"][0], "{label}");
+    }
+}
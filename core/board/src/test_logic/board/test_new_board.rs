@@ -0,0 +1,36 @@
+use crate::domain::constants::{MAX_BOARD_AREA, MAX_BOARD_SIDE};
+use crate::error::{BoardError, ErrorKind};
+use crate::logic::board::new_board;
+
+#[test]
+fn test_new_board_within_limits() {
+    let board = new_board(8, 5).expect("8x5 應合法");
+    assert_eq!(board.width, 8);
+    assert_eq!(board.height, 5);
+
+    let side = MAX_BOARD_AREA / MAX_BOARD_SIDE;
+    new_board(MAX_BOARD_SIDE, side).expect("恰好等於面積上限應合法");
+}
+
+#[test]
+fn test_new_board_rejects_invalid_sizes() {
+    let side = MAX_BOARD_AREA / MAX_BOARD_SIDE;
+    let cases = [
+        (0, 5),
+        (5, 0),
+        (MAX_BOARD_SIDE + 1, 1),
+        (MAX_BOARD_SIDE, side + 1),
+        (usize::MAX, usize::MAX),
+    ];
+    for (width, height) in cases {
+        let err = new_board(width, height).expect_err("應拒絕不合法尺寸");
+        assert!(
+            matches!(
+                err.kind(),
+                ErrorKind::Board(BoardError::InvalidBoardSize { .. })
+            ),
+            "{width}x{height} 應回傳 InvalidBoardSize，實際 {:?}",
+            err.kind()
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use crate::ecs_types::components::Position;
+use crate::ecs_types::resources::Board;
+use crate::logic::board::{chebyshev_distance, checked_offset, neighbors4, neighbors8};
+use crate::logic::skill::manhattan_distance;
+use std::collections::HashSet;
+
+const BOARD: Board = Board {
+    width: 3,
+    height: 3,
+};
+
+fn pos(x: usize, y: usize) -> Position {
+    Position { x, y }
+}
+
+#[test]
+fn test_checked_offset() {
+    let test_data = [
+        (pos(0, 0), -1, 0, None, "原點往左溢位"),
+        (pos(0, 0), 0, -1, None, "原點往上溢位"),
+        (pos(0, 0), 1, 1, Some(pos(1, 1)), "原點往右下"),
+        (pos(2, 3), -2, -3, Some(pos(0, 0)), "恰好回到原點"),
+        (pos(5, 5), 10, 0, Some(pos(15, 5)), "不檢查棋盤邊界"),
+    ];
+    for (from, dx, dy, expected, label) in test_data {
+        assert_eq!(checked_offset(from, dx, dy), expected, "{label}");
+    }
+}
+
+#[test]
+fn test_neighbors_clipped_to_board() {
+    let test_data: [(Position, Vec<Position>, Vec<Position>, &str); 2] = [
+        (
+            pos(0, 0),
+            vec![pos(1, 0), pos(0, 1)],
+            vec![pos(1, 0), pos(0, 1), pos(1, 1)],
+            "角落",
+        ),
+        (
+            pos(1, 1),
+            vec![pos(0, 1), pos(2, 1), pos(1, 0), pos(1, 2)],
+            vec![
+                pos(0, 0),
+                pos(1, 0),
+                pos(2, 0),
+                pos(0, 1),
+                pos(2, 1),
+                pos(0, 2),
+                pos(1, 2),
+                pos(2, 2),
+            ],
+            "中央",
+        ),
+    ];
+    for (center, expected4, expected8, label) in test_data {
+        let actual4: HashSet<Position> = neighbors4(BOARD, center).collect();
+        let actual8: HashSet<Position> = neighbors8(BOARD, center).collect();
+        assert_eq!(actual4, expected4.into_iter().collect(), "{label} 4 鄰格");
+        assert_eq!(actual8, expected8.into_iter().collect(), "{label} 8 鄰格");
+    }
+}
+
+#[test]
+fn test_distance_metrics() {
+    // (a, b, 曼哈頓, 切比雪夫)
+    let test_data = [
+        (pos(0, 0), pos(0, 0), 0, 0),
+        (pos(0, 0), pos(3, 0), 3, 3),
+        (pos(0, 0), pos(2, 2), 4, 2),
+        (pos(4, 1), pos(1, 3), 5, 3),
+    ];
+    for (a, b, manhattan, chebyshev) in test_data {
+        assert_eq!(manhattan_distance(a, b), manhattan, "{a:?} → {b:?} 曼哈頓");
+        assert_eq!(
+            chebyshev_distance(a, b),
+            chebyshev,
+            "{a:?} → {b:?} 切比雪夫"
+        );
+        assert_eq!(chebyshev_distance(b, a), chebyshev, "切比雪夫應對稱");
+    }
+}
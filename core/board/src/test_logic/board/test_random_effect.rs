@@ -0,0 +1,203 @@
+//! 隨機效果（Effect::Random）測試
+
+use crate::domain::alias::ID;
+use crate::domain::constants::PLAYER_FACTION_ID;
+use crate::domain::core_types::{
+    Attribute, CasterOrTarget, DamageType, Effect, EffectNode, Scaling, WeightedEffect,
+};
+use crate::ecs_types::components::*;
+use crate::logic::skill::skill_execution::{
+    CombatStats, ResolvedEffect, pick_weighted, resolve_effect_tree,
+};
+use crate::test_helpers::level_builder::LevelBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const ENEMY_FACTION_ID: ID = 2;
+const TEST_CASTER_ID: ID = 9999;
+const TEST_SKILL_NAME: &str = "wild_magic_test";
+const CASTER_ATTACK: i32 = 40;
+
+fn damage_effect() -> Effect {
+    Effect::HpEffect {
+        scaling: Scaling {
+            source: CasterOrTarget::Caster,
+            source_attribute: Attribute::PhysicalAttack,
+            value_percent: -100,
+        },
+        damage_type: DamageType::Neutral,
+    }
+}
+
+fn dispel_effect() -> Effect {
+    Effect::Dispel { beneficial: true }
+}
+
+/// 權重 1 : 3 的狂野魔法：傷害或驅散
+fn wild_magic_options() -> Vec<WeightedEffect> {
+    vec![
+        WeightedEffect {
+            weight: 1,
+            effect: damage_effect(),
+        },
+        WeightedEffect {
+            weight: 3,
+            effect: dispel_effect(),
+        },
+    ]
+}
+
+/// 表格驅動：d100 骰值換算落點後的抽選邊界
+#[test]
+fn test_pick_weighted_boundaries() {
+    let options = wild_magic_options();
+    let test_data = [
+        ("最小骰值落在第一個選項", 1, Some(0)),
+        ("25 為第一個選項的上界", 25, Some(0)),
+        ("26 進入第二個選項", 26, Some(1)),
+        ("最大骰值落在最後一個選項", 100, Some(1)),
+    ];
+
+    for (label, roll, expected_index) in test_data {
+        let picked = pick_weighted(&options, roll).map(|effect| {
+            options
+                .iter()
+                .position(|option| std::ptr::eq(&option.effect, effect))
+                .expect("抽中的效果應來自選項列表")
+        });
+        assert_eq!(picked, expected_index, "{label}");
+    }
+}
+
+#[test]
+fn test_pick_weighted_skips_zero_weight_and_empty() {
+    let options = vec![
+        WeightedEffect {
+            weight: 0,
+            effect: damage_effect(),
+        },
+        WeightedEffect {
+            weight: 2,
+            effect: dispel_effect(),
+        },
+    ];
+    assert!(
+        matches!(pick_weighted(&options, 1), Some(Effect::Dispel { .. })),
+        "權重 0 的選項永遠不會被抽中"
+    );
+    assert!(pick_weighted(&[], 50).is_none(), "沒有選項時不抽");
+}
+
+/// 以固定骰值解析效果樹，每次施放只套用抽中的一個選項
+#[test]
+fn test_random_effect_resolves_one_option_per_cast() {
+    let (board, _positions, unit_markers) = LevelBuilder::from_ascii("C T")
+        .unit("C", "caster", PLAYER_FACTION_ID)
+        .unit("T", "target", ENEMY_FACTION_ID)
+        .to_unit_map()
+        .expect("建構隨機效果測試棋盤應成功");
+    let caster = &unit_markers["C"][0];
+    let target = &unit_markers["T"][0];
+    let caster_stats = CombatStats {
+        unit_info: caster.unit_info.clone(),
+        attribute: AttributeBundle {
+            physical_attack: PhysicalAttack(CASTER_ATTACK),
+            ..AttributeBundle::default()
+        },
+        elevation: 0,
+        concealment: 0,
+        buffs: Vec::new(),
+        resistances: HashMap::new(),
+    };
+    let units_on_board: HashMap<Position, CombatStats> = [
+        (caster.position, caster_stats.clone()),
+        (
+            target.position,
+            CombatStats {
+                unit_info: target.unit_info.clone(),
+                attribute: AttributeBundle::default(),
+                elevation: 0,
+                concealment: 0,
+                buffs: Vec::new(),
+                resistances: HashMap::new(),
+            },
+        ),
+    ]
+    .into_iter()
+    .collect();
+    let node = EffectNode::Leaf {
+        who: CasterOrTarget::Target,
+        effect: Effect::Random {
+            options: wild_magic_options(),
+        },
+    };
+
+    let test_data = [
+        (
+            "骰 25 → 傷害",
+            25,
+            ResolvedEffect::HpChange {
+                raw_amount: -CASTER_ATTACK,
+                final_amount: -CASTER_ATTACK,
+            },
+        ),
+        (
+            "骰 26 → 驅散",
+            26,
+            ResolvedEffect::Dispel { beneficial: true },
+        ),
+    ];
+
+    for (label, roll, expected) in test_data {
+        let entries = resolve_effect_tree(
+            TEST_CASTER_ID,
+            TEST_SKILL_NAME,
+            &[],
+            std::slice::from_ref(&node),
+            &caster_stats,
+            caster.position,
+            target.position,
+            &units_on_board,
+            &HashMap::new(),
+            board,
+            &mut || roll,
+            false,
+        )
+        .expect("resolve_effect_tree 應成功執行");
+
+        assert_eq!(entries.len(), 1, "{label}: 每次施放只抽一個選項");
+        assert_eq!(entries[0].effect, expected, "{label}");
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EffectWrapper {
+    effect: Effect,
+}
+
+/// 巢狀效果與權重可經 TOML 來回轉換
+#[test]
+fn test_random_effect_toml_round_trip() {
+    let toml_str = toml::to_string(&EffectWrapper {
+        effect: Effect::Random {
+            options: wild_magic_options(),
+        },
+    })
+    .expect("序列化隨機效果應成功");
+    let parsed: EffectWrapper = toml::from_str(&toml_str).expect("反序列化隨機效果應成功");
+
+    let options = match parsed.effect {
+        Effect::Random { options } => options,
+        other => panic!("應為 Random 效果，實際為 {other:?}"),
+    };
+    let weights: Vec<u32> = options.iter().map(|option| option.weight).collect();
+    assert_eq!(weights, vec![1, 3], "權重應保留");
+    assert!(
+        matches!(options[0].effect, Effect::HpEffect { ref scaling, .. } if scaling.value_percent == -100),
+        "第一個選項應保留為 HpEffect"
+    );
+    assert!(
+        matches!(options[1].effect, Effect::Dispel { beneficial: true }),
+        "第二個選項應保留為 Dispel"
+    );
+}
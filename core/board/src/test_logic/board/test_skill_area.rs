@@ -53,6 +53,7 @@ fn target_with(
 ) -> Target {
     Target {
         range,
+        range_metric: RangeMetric::Manhattan,
         selection,
         selectable_filter: filter,
         count,
@@ -139,6 +140,7 @@ fn hp_leaf(source_attribute: Attribute, value_percent: i32) -> EffectNode {
                 source_attribute,
                 value_percent,
             },
+            damage_type: DamageType::Neutral,
         },
     }
 }
@@ -147,7 +149,11 @@ fn hp_leaf(source_attribute: Attribute, value_percent: i32) -> EffectNode {
 fn wrap_area(node: EffectNode, skill_target: &Target) -> Vec<EffectNode> {
     match skill_target.area {
         Area::Single => vec![node],
-        Area::Diamond { .. } | Area::Cross { .. } | Area::Line { .. } => {
+        Area::Diamond { .. }
+        | Area::Circle { .. }
+        | Area::Cross { .. }
+        | Area::Line { .. }
+        | Area::Rectangle { .. } => {
             vec![EffectNode::Area {
                 area: skill_target.area,
                 filter: skill_target.selectable_filter.clone(),
@@ -279,6 +285,10 @@ fn build_stats(unit_info: UnitInfo) -> CombatStats {
     CombatStats {
         unit_info,
         attribute: AttributeBundle::default(),
+        elevation: 0,
+        concealment: 0,
+        buffs: Vec::new(),
+        resistances: HashMap::new(),
     }
 }
 
@@ -1092,7 +1102,11 @@ fn test_normal_case() {
                         TargetFilter::Any,
                         1,
                         false,
-                        Area::Line { length: 1 },
+                        Area::Line {
+                            length: 1,
+                            snap_to_facing: false,
+                            pierce: true,
+                        },
                     ),
                     vec![
                         (vec!["Pa"], Some(vec!["C", "Pa"])),
@@ -1104,7 +1118,14 @@ fn test_normal_case() {
                     ],
                 ),
                 (
-                    target_with_fixed_range(TargetFilter::Any, Area::Line { length: 2 }),
+                    target_with_fixed_range(
+                        TargetFilter::Any,
+                        Area::Line {
+                            length: 2,
+                            snap_to_facing: false,
+                            pierce: true,
+                        },
+                    ),
                     vec![
                         (vec!["Pa"], Some(vec!["C", "Pa", "Pb"])),
                         (vec!["Pb"], Some(vec!["C", "Pa", "Pb"])),
@@ -1117,7 +1138,11 @@ fn test_normal_case() {
                 (
                     target_with_fixed_range(
                         TargetFilter::AnyExceptCaster,
-                        Area::Line { length: 2 },
+                        Area::Line {
+                            length: 2,
+                            snap_to_facing: false,
+                            pierce: true,
+                        },
                     ),
                     vec![
                         (vec!["Pa"], Some(vec!["Pa", "Pb"])),
@@ -1129,7 +1154,14 @@ fn test_normal_case() {
                     ],
                 ),
                 (
-                    target_with_fixed_range(TargetFilter::Enemy, Area::Line { length: 2 }),
+                    target_with_fixed_range(
+                        TargetFilter::Enemy,
+                        Area::Line {
+                            length: 2,
+                            snap_to_facing: false,
+                            pierce: true,
+                        },
+                    ),
                     vec![
                         (vec!["Pa"], None),
                         (vec!["Pb"], None),
@@ -1140,7 +1172,14 @@ fn test_normal_case() {
                     ],
                 ),
                 (
-                    target_with_fixed_range(TargetFilter::Ally, Area::Line { length: 2 }),
+                    target_with_fixed_range(
+                        TargetFilter::Ally,
+                        Area::Line {
+                            length: 2,
+                            snap_to_facing: false,
+                            pierce: true,
+                        },
+                    ),
                     vec![
                         (vec!["Pa"], Some(vec!["C", "Pa", "Pb"])),
                         (vec!["Pb"], Some(vec!["C", "Pa", "Pb"])),
@@ -1153,7 +1192,11 @@ fn test_normal_case() {
                 (
                     target_with_fixed_range(
                         TargetFilter::AllyExceptCaster,
-                        Area::Line { length: 2 },
+                        Area::Line {
+                            length: 2,
+                            snap_to_facing: false,
+                            pierce: true,
+                        },
                     ),
                     vec![
                         (vec!["Pa"], Some(vec!["Pa", "Pb"])),
@@ -1527,8 +1570,16 @@ fn test_area_line() {
         };
         for (m, expected) in test_case {
             let msg = format!("line - {filter:?} - 不用瞄準單位:{m}");
-            let skill_target =
-                target_with_ground_target(filter, 1, false, Area::Line { length: 2 });
+            let skill_target = target_with_ground_target(
+                filter,
+                1,
+                false,
+                Area::Line {
+                    length: 2,
+                    snap_to_facing: false,
+                    pierce: true,
+                },
+            );
             let targets = vec![markers[m][0]];
             let result =
                 validate_skill_targets(&caster, &skill_target, &targets, &position_map, board);
@@ -8,7 +8,7 @@ use crate::ecs_types::resources::Board;
 use crate::logic::skill::UnitInfo;
 use crate::logic::skill::skill_execution::{
     CheckDetail, CheckResult, CheckTarget, CombatStats, EffectEntry, ObjectOnBoard, ResolvedEffect,
-    resolve_effect_tree,
+    resolve_effect_tree, resolve_hits,
 };
 use crate::test_helpers::level_builder::LevelBuilder;
 use std::collections::HashMap;
@@ -120,6 +120,10 @@ fn build_stats(unit_info: UnitInfo) -> CombatStats {
     CombatStats {
         unit_info,
         attribute: AttributeBundle::default(),
+        elevation: 0,
+        concealment: 0,
+        buffs: Vec::new(),
+        resistances: HashMap::new(),
     }
 }
 
@@ -147,6 +151,7 @@ fn hp_leaf_target(source_attribute: Attribute, value_percent: i32) -> EffectNode
                 source_attribute,
                 value_percent,
             },
+            damage_type: DamageType::Neutral,
         },
     }
 }
@@ -160,6 +165,7 @@ fn hp_leaf_caster(source_attribute: Attribute, value_percent: i32) -> EffectNode
                 source_attribute,
                 value_percent,
             },
+            damage_type: DamageType::Neutral,
         },
     }
 }
@@ -178,14 +184,15 @@ fn spawn_leaf(object_type: &str) -> EffectNode {
 fn apply_buff_leaf(buff: BuffType) -> EffectNode {
     EffectNode::Leaf {
         who: CasterOrTarget::Target,
-        effect: Effect::ApplyBuff { buff },
+        effect: Effect::ApplyBuff { buff: buff.into() },
     }
 }
 
 fn poison_buff() -> BuffType {
     BuffType {
         name: "poison".to_string(),
-        stackable: false,
+        beneficial: false,
+        stacking: StackingPolicy::Stack,
         while_active: vec![ContinuousEffect::AttributeFlat {
             attribute: Attribute::PhysicalAttack,
             value: -10,
@@ -638,6 +645,7 @@ fn test_nested_branch_hit_then_fort() {
                                     skill_bonus: 0,
                                     flanking_bonus: 0,
                                     adjacent_penalty: 0,
+                                    high_ground_bonus: 0,
                                     total: 0,
                                 },
                                 defender_evasion: EvasionBreakdown {
@@ -651,6 +659,7 @@ fn test_nested_branch_hit_then_fort() {
                                 crit: 0,
                             },
                             roll: 50,
+                            concealment_roll: None,
                         }),
                         effect: ResolvedEffect::NoEffect,
                     },
@@ -680,6 +689,7 @@ fn test_nested_branch_hit_then_fort() {
                                     skill_bonus: 0,
                                     flanking_bonus: 0,
                                     adjacent_penalty: 0,
+                                    high_ground_bonus: 0,
                                     total: 0,
                                 },
                                 defender_evasion: EvasionBreakdown {
@@ -693,6 +703,7 @@ fn test_nested_branch_hit_then_fort() {
                                 crit: 0,
                             },
                             roll: 50,
+                            concealment_roll: None,
                         }),
                         effect: ResolvedEffect::HpChange {
                             raw_amount: -1000,
@@ -718,6 +729,7 @@ fn test_nested_branch_hit_then_fort() {
                                     skill_bonus: 0,
                                     flanking_bonus: 0,
                                     adjacent_penalty: 0,
+                                    high_ground_bonus: 0,
                                     total: 0,
                                 },
                                 defender_evasion: EvasionBreakdown {
@@ -728,6 +740,7 @@ fn test_nested_branch_hit_then_fort() {
                                 crit: 0,
                             },
                             roll: 50,
+                            concealment_roll: None,
                         }),
                         effect: ResolvedEffect::ApplyBuff("poison".to_string()),
                     },
@@ -758,6 +771,7 @@ fn test_nested_branch_hit_then_fort() {
                                     skill_bonus: 0,
                                     flanking_bonus: 0,
                                     adjacent_penalty: 0,
+                                    high_ground_bonus: 0,
                                     total: 0,
                                 },
                                 defender_evasion: EvasionBreakdown {
@@ -771,6 +785,7 @@ fn test_nested_branch_hit_then_fort() {
                                 crit: 0,
                             },
                             roll: 50,
+                            concealment_roll: None,
                         }),
                         effect: ResolvedEffect::HpChange {
                             raw_amount: -1000,
@@ -796,6 +811,7 @@ fn test_nested_branch_hit_then_fort() {
                                     skill_bonus: 0,
                                     flanking_bonus: 0,
                                     adjacent_penalty: 0,
+                                    high_ground_bonus: 0,
                                     total: 0,
                                 },
                                 defender_evasion: EvasionBreakdown {
@@ -806,6 +822,7 @@ fn test_nested_branch_hit_then_fort() {
                                 crit: 0,
                             },
                             roll: 50,
+                            concealment_roll: None,
                         }),
                         effect: ResolvedEffect::HpChange {
                             raw_amount: -500,
@@ -818,3 +835,363 @@ fn test_nested_branch_hit_then_fort() {
         }
     }
 }
+
+// ============================================================================
+// 案例 5：Dispel — 對目標產生驅散增益/減益條目
+// ============================================================================
+
+#[test]
+fn test_dispel_leaf_targets_unit() {
+    let sb = build_shared_board();
+    let caster_stats = sb.units_on_board[&sb.caster_pos].clone();
+
+    let test_data = [("驅散增益", true), ("驅散減益", false)];
+
+    for (label, beneficial) in test_data {
+        let nodes = vec![EffectNode::Leaf {
+            who: CasterOrTarget::Target,
+            effect: Effect::Dispel { beneficial },
+        }];
+        let mut rng = always_hit_rng();
+        let entries = resolve_effect_tree(
+            TEST_CASTER_ID,
+            TEST_SKILL_NAME,
+            &[],
+            &nodes,
+            &caster_stats,
+            sb.caster_pos,
+            sb.enemy_pos,
+            &sb.units_on_board,
+            &sb.objects_on_board,
+            sb.board,
+            &mut rng,
+            false,
+        )
+        .expect("resolve_effect_tree 應成功執行");
+
+        let enemy_entries = find_entries_for(&entries, &sb.enemy_occupant);
+        assert_eq!(enemy_entries.len(), 1, "{label}: 目標應有 1 個效果條目");
+        assert_eq!(
+            enemy_entries[0].effect,
+            ResolvedEffect::Dispel { beneficial },
+            "{label}: 條目應為驅散"
+        );
+    }
+}
+
+// ============================================================================
+// 案例 6：Chain — 由目標依序跳到最近的敵人，數值逐跳衰減
+// ============================================================================
+
+/// 佈局：
+/// ```text
+/// C  E  F  .  G  A  .  .  H
+/// ```
+/// E 為主目標；F 距 E 1 格、G 距 F 2 格；A 為友軍（不符篩選）；H 超出跳躍範圍
+#[test]
+fn test_chain_hops_to_nearest_enemies_with_falloff() {
+    let (board, _, unit_markers) = LevelBuilder::from_ascii("C E F . G A . . H")
+        .unit("C", "caster", PLAYER_FACTION_ID)
+        .unit("E", "enemy", ENEMY_FACTION_ID)
+        .unit("F", "enemy", ENEMY_FACTION_ID)
+        .unit("G", "enemy", ENEMY_FACTION_ID)
+        .unit("A", "ally", PLAYER_FACTION_ID)
+        .unit("H", "enemy", ENEMY_FACTION_ID)
+        .to_unit_map()
+        .expect("建構連鎖棋盤應成功");
+
+    let caster_pos = unit_markers["C"][0].position;
+    let units_on_board: HashMap<Position, CombatStats> = unit_markers
+        .values()
+        .flatten()
+        .map(|entry| {
+            (
+                entry.position,
+                build_stats_with_atk(entry.unit_info.clone(), 40),
+            )
+        })
+        .collect();
+    let caster_stats = units_on_board[&caster_pos].clone();
+
+    let nodes = vec![EffectNode::Leaf {
+        who: CasterOrTarget::Target,
+        effect: Effect::Chain {
+            scaling: Scaling {
+                source: CasterOrTarget::Caster,
+                source_attribute: Attribute::PhysicalAttack,
+                value_percent: -100,
+            },
+            damage_type: DamageType::Neutral,
+            filter: TargetFilter::Enemy,
+            max_jumps: 3,
+            jump_range: 2,
+            falloff_percent: 50,
+        },
+    }];
+    let mut rng = always_hit_rng();
+    let entries = resolve_effect_tree(
+        TEST_CASTER_ID,
+        TEST_SKILL_NAME,
+        &[],
+        &nodes,
+        &caster_stats,
+        caster_pos,
+        unit_markers["E"][0].position,
+        &units_on_board,
+        &HashMap::new(),
+        board,
+        &mut rng,
+        false,
+    )
+    .expect("resolve_effect_tree 應成功執行");
+
+    let actual: Vec<(CheckTarget, ResolvedEffect)> = entries
+        .iter()
+        .map(|entry| (entry.target, entry.effect.clone()))
+        .collect();
+    let expected: Vec<(CheckTarget, ResolvedEffect)> = [("E", -40), ("F", -20), ("G", -10)]
+        .into_iter()
+        .map(|(marker, amount)| {
+            (
+                occupant_to_check_target(unit_markers[marker][0].unit_info.occupant),
+                ResolvedEffect::HpChange {
+                    raw_amount: amount,
+                    final_amount: amount,
+                },
+            )
+        })
+        .collect();
+    assert_eq!(actual, expected, "連鎖應依 E → F → G 跳躍並逐跳減半");
+}
+
+/// 佈局：
+/// ```text
+/// C  E  F  G
+/// ```
+/// 閃電連鎖不衰減：E 抗閃電 50%、F 弱閃電 50%、G 無抗性，每一跳都依承受者抗性調整
+#[test]
+fn test_chain_applies_resistance_on_every_hop() {
+    let (board, _, unit_markers) = LevelBuilder::from_ascii("C E F G")
+        .unit("C", "caster", PLAYER_FACTION_ID)
+        .unit("E", "enemy", ENEMY_FACTION_ID)
+        .unit("F", "enemy", ENEMY_FACTION_ID)
+        .unit("G", "enemy", ENEMY_FACTION_ID)
+        .to_unit_map()
+        .expect("建構連鎖棋盤應成功");
+
+    let caster_pos = unit_markers["C"][0].position;
+    let resistances = HashMap::from([("E", 50), ("F", -50)]);
+    let units_on_board: HashMap<Position, CombatStats> = unit_markers
+        .iter()
+        .flat_map(|(marker, entries)| entries.iter().map(move |entry| (marker, entry)))
+        .map(|(marker, entry)| {
+            let mut stats = build_stats_with_atk(entry.unit_info.clone(), 40);
+            if let Some(percent) = resistances.get(marker.as_str()) {
+                stats.resistances.insert(DamageType::Lightning, *percent);
+            }
+            (entry.position, stats)
+        })
+        .collect();
+    let caster_stats = units_on_board[&caster_pos].clone();
+
+    let nodes = vec![EffectNode::Leaf {
+        who: CasterOrTarget::Target,
+        effect: Effect::Chain {
+            scaling: Scaling {
+                source: CasterOrTarget::Caster,
+                source_attribute: Attribute::PhysicalAttack,
+                value_percent: -100,
+            },
+            damage_type: DamageType::Lightning,
+            filter: TargetFilter::Enemy,
+            max_jumps: 2,
+            jump_range: 1,
+            falloff_percent: 0,
+        },
+    }];
+    let mut rng = always_hit_rng();
+    let entries = resolve_effect_tree(
+        TEST_CASTER_ID,
+        TEST_SKILL_NAME,
+        &[],
+        &nodes,
+        &caster_stats,
+        caster_pos,
+        unit_markers["E"][0].position,
+        &units_on_board,
+        &HashMap::new(),
+        board,
+        &mut rng,
+        false,
+    )
+    .expect("resolve_effect_tree 應成功執行");
+
+    let actual: Vec<(CheckTarget, ResolvedEffect)> = entries
+        .iter()
+        .map(|entry| (entry.target, entry.effect.clone()))
+        .collect();
+    let expected: Vec<(CheckTarget, ResolvedEffect)> = [("E", -20), ("F", -60), ("G", -40)]
+        .into_iter()
+        .map(|(marker, final_amount)| {
+            (
+                occupant_to_check_target(unit_markers[marker][0].unit_info.occupant),
+                ResolvedEffect::HpChange {
+                    raw_amount: -40,
+                    final_amount,
+                },
+            )
+        })
+        .collect();
+    assert_eq!(actual, expected, "連鎖每一跳都應套用承受者的抗性");
+}
+
+// ============================================================================
+// 案例 7：Teleport — 越過牆壁瞬移到空格；目標有單位、被物件佔據或超出距離則不產生條目
+// ============================================================================
+
+/// 佈局：
+/// ```text
+/// C  W  .  E
+/// ```
+#[test]
+fn test_teleport_over_wall() {
+    let (board, positions, unit_markers) = LevelBuilder::from_ascii("C W . E")
+        .unit("C", "caster", PLAYER_FACTION_ID)
+        .unit("E", "enemy", ENEMY_FACTION_ID)
+        .object("W", "wall")
+        .to_unit_map()
+        .expect("建構瞬移棋盤應成功");
+
+    let caster_pos = unit_markers["C"][0].position;
+    let wall_pos = positions["W"][0];
+    let empty_pos = Position { x: 2, y: 0 };
+    let enemy_pos = unit_markers["E"][0].position;
+    let units_on_board: HashMap<Position, CombatStats> = unit_markers
+        .values()
+        .flatten()
+        .map(|entry| (entry.position, build_stats(entry.unit_info.clone())))
+        .collect();
+    let objects_on_board = HashMap::from([(
+        wall_pos,
+        ObjectOnBoard {
+            occupant: Occupant::Object(100),
+            occupies_tile: true,
+        },
+    )]);
+    let caster_stats = units_on_board[&caster_pos].clone();
+
+    let test_data = [
+        ("越過牆壁到空格", empty_pos, 2, true),
+        ("超出最大距離", empty_pos, 1, false),
+        ("目標格有單位", enemy_pos, 3, false),
+        ("目標格被牆壁佔據", wall_pos, 3, false),
+    ];
+
+    for (label, target_pos, max_distance, expect_teleport) in test_data {
+        let nodes = vec![EffectNode::Leaf {
+            who: CasterOrTarget::Caster,
+            effect: Effect::Teleport { max_distance },
+        }];
+        let mut rng = always_hit_rng();
+        let entries = resolve_effect_tree(
+            TEST_CASTER_ID,
+            TEST_SKILL_NAME,
+            &[],
+            &nodes,
+            &caster_stats,
+            caster_pos,
+            target_pos,
+            &units_on_board,
+            &objects_on_board,
+            board,
+            &mut rng,
+            false,
+        )
+        .expect("resolve_effect_tree 應成功執行");
+
+        let teleports: Vec<&EffectEntry> = entries
+            .iter()
+            .filter(|entry| matches!(entry.effect, ResolvedEffect::Teleport { .. }))
+            .collect();
+        match expect_teleport {
+            true => {
+                assert_eq!(teleports.len(), 1, "{label}: 應產生 1 個瞬移條目");
+                assert_eq!(teleports[0].target, CheckTarget::Unit(TEST_CASTER_ID));
+                assert_eq!(
+                    teleports[0].effect,
+                    ResolvedEffect::Teleport { to: target_pos },
+                    "{label}: 瞬移目的地不符"
+                );
+            }
+            false => assert!(teleports.is_empty(), "{label}: 不應產生瞬移條目"),
+        }
+    }
+}
+
+// ============================================================================
+// 案例 8：多段攻擊 — 每段獨立擲骰，只有命中的段落造成傷害
+// ============================================================================
+
+#[test]
+fn test_multi_hit_rolls_each_hit_independently() {
+    let mut sb = build_shared_board();
+
+    let caster_atk = 10;
+    let mut units_on_board = std::mem::take(&mut sb.units_on_board);
+    units_on_board.insert(
+        sb.caster_pos,
+        build_stats_with_atk(units_on_board[&sb.caster_pos].unit_info.clone(), caster_atk),
+    );
+    let caster_stats = units_on_board[&sb.caster_pos].clone();
+
+    let nodes = vec![physical_hit_branch(
+        DefenseType::AgilityAndBlock,
+        vec![hp_leaf_target(Attribute::PhysicalAttack, -100)],
+        vec![],
+    )];
+
+    // 依序：命中、強制失敗、命中
+    let rolls = [50, 1, 50];
+    let mut roll_iter = rolls.into_iter();
+    let mut rng = || roll_iter.next().expect("擲骰次數不應超過段數");
+
+    let hits = 3;
+    let entries = resolve_hits(hits, || {
+        resolve_effect_tree(
+            TEST_CASTER_ID,
+            TEST_SKILL_NAME,
+            &[],
+            &nodes,
+            &caster_stats,
+            sb.caster_pos,
+            sb.enemy_pos,
+            &units_on_board,
+            &sb.objects_on_board,
+            sb.board,
+            &mut rng,
+            false,
+        )
+    })
+    .expect("resolve_hits 應成功執行");
+
+    let enemy_entries = find_entries_for(&entries, &sb.enemy_occupant);
+    let checks: Vec<CheckResult> = enemy_entries.iter().map(|e| e.check).collect();
+    assert_eq!(
+        checks,
+        vec![
+            CheckResult::Hit { crit: false },
+            CheckResult::Evade,
+            CheckResult::Hit { crit: false },
+        ],
+        "每段應各自判定一次"
+    );
+
+    let total_damage: i32 = enemy_entries
+        .iter()
+        .map(|e| match e.effect {
+            ResolvedEffect::HpChange { final_amount, .. } => final_amount,
+            _ => 0,
+        })
+        .sum();
+    assert_eq!(total_damage, -2 * caster_atk, "只加總命中的兩段");
+}
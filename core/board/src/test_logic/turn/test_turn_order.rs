@@ -4,7 +4,9 @@ use crate::domain::turn::TurnEntry;
 use crate::ecs_types::components::Occupant;
 use crate::logic::turn_order::{
     TurnOrderInput, calculate_turn_order, delay_unit, get_active_index, remove_unit,
+    resort_pending_entries,
 };
+use std::collections::HashMap;
 
 /// 取出當前未行動單位的 Occupant，方便斷言
 fn get_active_unit(entries: &[TurnEntry]) -> Option<Occupant> {
@@ -241,6 +243,50 @@ fn test_delay_then_continue() {
     assert_eq!(get_active_unit(&entries), None);
 }
 
+/// 模擬回合中 INI 變動：只重排尚未輪到的單位
+#[test]
+fn test_resort_pending_after_initiative_change() {
+    let inputs = vec![
+        input(1, 10, true),
+        input(2, 8, false),
+        input(3, 5, true),
+        input(4, 3, false),
+    ];
+    let mut rng_int = || 3;
+    let mut rng_float = || 0.5;
+    let mut entries = calculate_turn_order(&inputs, &mut rng_int, &mut rng_float);
+    assert_eq!(occupant_ids(&entries), vec![1, 2, 3, 4]);
+    entries[0].has_acted = true;
+
+    // (INI 變動, 預期順序, 說明)
+    let test_data = [
+        (vec![(2, 0)], vec![1, 2, 3, 4], "當前行動單位降 INI 不移動"),
+        (vec![(3, 0)], vec![1, 2, 4, 3], "未輪到的單位降 INI 後移"),
+        (
+            vec![(4, 20)],
+            vec![1, 2, 4, 3],
+            "未輪到的單位升 INI 前移，但不超過當前單位",
+        ),
+        (vec![(1, 0)], vec![1, 2, 3, 4], "已行動單位降 INI 不移動"),
+        (vec![], vec![1, 2, 3, 4], "無變動不重排"),
+    ];
+
+    for (changes, expected, note) in test_data {
+        let mut entries = entries.clone();
+        let initiatives: HashMap<Occupant, i32> = changes
+            .into_iter()
+            .map(|(id, initiative)| (unit(id), initiative))
+            .collect();
+        resort_pending_entries(&mut entries, &initiatives);
+        assert_eq!(occupant_ids(&entries), expected, "{note}");
+        assert_eq!(
+            get_active_unit(&entries),
+            Some(unit(2)),
+            "{note}：當前單位不變"
+        );
+    }
+}
+
 /// 模擬中途移除單位後繼續回合
 #[test]
 fn test_remove_unit_then_continue() {
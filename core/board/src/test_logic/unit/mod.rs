@@ -1,3 +1,5 @@
 mod test_attribute;
 mod test_id;
+mod test_shield;
+mod test_skill_category;
 mod test_skill_check;
@@ -1,9 +1,15 @@
 use crate::domain::core_types::{
-    Area, Attribute, BuffType, CasterOrTarget, ContinuousEffect, Effect, EffectNode, Scaling,
-    SkillType, Target, TargetFilter, TargetSelection,
+    Area, Attribute, BuffType, CasterOrTarget, ContinuousEffect, DamageType, Effect, EffectNode,
+    EffectOrder, RangeMetric, Scaling, SkillType, StackingPolicy, Target, TargetFilter,
+    TargetSelection,
 };
 use crate::ecs_types::components::*;
-use crate::logic::skill::unit_attributes::{calculate_attributes, filter_continuous_effect};
+use crate::logic::skill::unit_attributes::{
+    adjust_current_for_max_change, apply_attribute_modifiers, apply_skill_delta,
+    calculate_attributes, filter_continuous_effect, recalc_from_skills, totals_to_attributes,
+};
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -29,8 +35,14 @@ fn create_active_skill() -> SkillType {
         name: SKILL_BASIC_ATTACK.to_string(),
         tags: Vec::new(),
         cost: 0,
+        requires: Vec::new(),
+        hits: 1,
+        recoil: 0,
+        effect_order: EffectOrder::AsListed,
+        channel_turns: 0,
         target: Target {
             range: (1, 1),
+            range_metric: RangeMetric::Manhattan,
             selection: TargetSelection::Unit,
             selectable_filter: TargetFilter::Enemy,
             count: 1,
@@ -45,6 +57,7 @@ fn create_active_skill() -> SkillType {
                     source_attribute: Attribute::PhysicalAttack,
                     value_percent: 100,
                 },
+                damage_type: DamageType::Neutral,
             },
         }]),
     }
@@ -325,7 +338,8 @@ fn test_calculate_attributes() {
             .into_iter()
             .map(|e| BuffType {
                 name: String::new(),
-                stackable: false,
+                beneficial: false,
+                stacking: StackingPolicy::Stack,
                 while_active: vec![e],
                 per_turn_effects: vec![],
                 end_conditions: vec![],
@@ -355,3 +369,241 @@ fn test_calculate_attributes_skill_not_found() {
     let result = filter_continuous_effect(&skill_names, &[], &skill_map);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_calculate_attributes_buff_stacking_policy() {
+    let make_buff = |stacking: StackingPolicy, value: i32| BuffType {
+        name: "evasion".to_string(),
+        beneficial: false,
+        stacking,
+        while_active: vec![flat(Attribute::Agility, value)],
+        per_turn_effects: vec![],
+        end_conditions: vec![],
+    };
+
+    let test_data = [
+        ("Stack 兩個同名 buff 累加", StackingPolicy::Stack, 25),
+        ("HighestOnly 只取較高者", StackingPolicy::HighestOnly, 15),
+        (
+            "RefreshDuration 只算第一個",
+            StackingPolicy::RefreshDuration,
+            10,
+        ),
+    ];
+
+    let skill_map = HashMap::new();
+    for (desc, stacking, expected) in test_data {
+        let buffs = vec![make_buff(stacking, 10), make_buff(stacking, 15)];
+        let effects = filter_continuous_effect(&[], &buffs, &skill_map)
+            .unwrap_or_else(|e| panic!("測試 '{}' 應該成功：{:?}", desc, e.kind()));
+        let attrs = calculate_attributes(effects);
+        assert_eq!(attrs.agility.0, expected, "測試 '{}' - 迴避不符", desc);
+    }
+}
+
+#[test]
+fn test_calculate_attributes_different_buffs_always_stack() {
+    let buffs = ["evasion", "dodge"].map(|name| BuffType {
+        name: name.to_string(),
+        beneficial: false,
+        stacking: StackingPolicy::HighestOnly,
+        while_active: vec![flat(Attribute::Agility, 10)],
+        per_turn_effects: vec![],
+        end_conditions: vec![],
+    });
+
+    let skill_map = HashMap::new();
+    let effects = filter_continuous_effect(&[], &buffs, &skill_map).expect("應該成功");
+    let attrs = calculate_attributes(effects);
+    assert_eq!(attrs.agility.0, 20, "不同名 buff 應各自生效");
+}
+
+#[test]
+fn test_apply_attribute_modifiers() {
+    let skill_map: HashMap<String, SkillType> = [create_passive_skill(
+        "attack_double",
+        vec![
+            flat(Attribute::PhysicalAttack, 10),
+            scaling(Attribute::PhysicalAttack, 200),
+        ],
+    )]
+    .into_iter()
+    .map(|skill| match &skill {
+        SkillType::Passive { name, .. } => (name.clone(), skill.clone()),
+        SkillType::Active { .. } | SkillType::Reaction { .. } => unreachable!(),
+    })
+    .collect();
+    let skill_names = vec!["attack_double".to_string()];
+    let buff = BuffType {
+        name: "might".to_string(),
+        beneficial: true,
+        stacking: StackingPolicy::Stack,
+        while_active: vec![flat(Attribute::PhysicalAttack, 5), flat(Attribute::Hp, 30)],
+        per_turn_effects: vec![],
+        end_conditions: vec![],
+    };
+    let aura = flat(Attribute::Agility, 7);
+
+    // component 現值與被動技能重算結果不同（例如被直接覆寫），修正量疊加在現值上
+    let current = AttributeBundle {
+        physical_attack: PhysicalAttack(25),
+        agility: Agility(3),
+        max_hp: MaxHp(100),
+        current_hp: CurrentHp(40),
+        ..Default::default()
+    };
+    let attrs = apply_attribute_modifiers(current, &skill_names, [&buff], [&aura], &skill_map)
+        .expect("應該成功");
+
+    // 被動 (10) × 200% = 20；加上 buff 後 (10 + 5) × 200% = 30，差值 10
+    assert_eq!(attrs.physical_attack.0, 35, "buff 修正應經過倍率");
+    assert_eq!(attrs.agility.0, 10, "光環修正應疊加");
+    assert_eq!(attrs.max_hp.0, 100, "HP 上限已另行同步，不應重複疊加");
+    assert_eq!(attrs.current_hp.0, 40, "當前 HP 不受修正影響");
+}
+
+#[test]
+fn test_adjust_current_for_max_change() {
+    // (說明, 當前值, 舊上限, 新上限, 預期當前值)
+    let test_data = [
+        ("上限提高：當前值增加差值", 100, 100, 120, 120),
+        ("上限提高：受傷時同樣增加差值", 50, 100, 120, 70),
+        ("上限降低：超出新上限壓到新上限", 120, 120, 100, 100),
+        ("上限降低：未超出新上限不變", 70, 120, 100, 70),
+        ("上限不變", 80, 100, 100, 80),
+    ];
+    for (desc, current, old_max, new_max, expected) in test_data {
+        assert_eq!(
+            adjust_current_for_max_change(current, old_max, new_max),
+            expected,
+            "{desc}"
+        );
+    }
+}
+
+/// 增量測試用技能庫：同屬性混合固定值與會截斷的倍率，技能順序會影響結果
+fn create_delta_skill_map() -> HashMap<String, SkillType> {
+    let skills = [
+        create_passive_skill("hp_flat", vec![flat(Attribute::Hp, 7)]),
+        create_passive_skill("hp_scale_up", vec![scaling(Attribute::Hp, 150)]),
+        create_passive_skill("hp_scale_down", vec![scaling(Attribute::Hp, 50)]),
+        create_passive_skill(
+            "agility_mixed",
+            vec![
+                flat(Attribute::Agility, 3),
+                scaling(Attribute::Agility, 133),
+                scaling(Attribute::Hp, 150),
+            ],
+        ),
+        create_passive_skill(
+            "accuracy_mixed",
+            vec![
+                scaling(Attribute::PhysicalAccuracy, 75),
+                flat(Attribute::PhysicalAccuracy, 11),
+                flat(Attribute::Hp, -2),
+            ],
+        ),
+        create_active_skill(),
+    ];
+    skills
+        .into_iter()
+        .map(|skill| match &skill {
+            SkillType::Active { name, .. }
+            | SkillType::Reaction { name, .. }
+            | SkillType::Passive { name, .. } => (name.clone(), skill.clone()),
+        })
+        .collect()
+}
+
+#[test]
+fn test_apply_skill_delta_matches_recalc() {
+    const SEEDS: u64 = 20;
+    const STEPS: usize = 30;
+
+    let skill_map = create_delta_skill_map();
+    let mut pool: Vec<String> = skill_map.keys().cloned().collect();
+    pool.sort();
+
+    for seed in 0..SEEDS {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut skill_names: Vec<String> = Vec::new();
+        let mut totals = recalc_from_skills(&skill_names, &skill_map).expect("空技能列表應成功");
+
+        for step in 0..STEPS {
+            // 隨機移除既有技能（可能重複），再隨機加入技能（允許同名）
+            let mut removed = Vec::new();
+            for _ in 0..rng.random_range(0..=2usize) {
+                if skill_names.is_empty() {
+                    break;
+                }
+                // 移除規則為「第一個同名技能」，模型列表須對應調整
+                let name = skill_names[rng.random_range(0..skill_names.len())].clone();
+                let first = skill_names
+                    .iter()
+                    .position(|s| *s == name)
+                    .expect("選出的技能應存在");
+                skill_names.remove(first);
+                removed.push(name);
+            }
+            let added: Vec<String> = (0..rng.random_range(0..=2usize))
+                .map(|_| pool[rng.random_range(0..pool.len())].clone())
+                .collect();
+            skill_names.extend(added.iter().cloned());
+
+            apply_skill_delta(&mut totals, &added, &removed, &skill_map)
+                .unwrap_or_else(|e| panic!("seed {seed} step {step} 應成功：{:?}", e.kind()));
+
+            let incremental = totals_to_attributes(&totals);
+            let full = calculate_attributes(
+                filter_continuous_effect(&skill_names, &[], &skill_map).expect("全量重算應成功"),
+            );
+            let recalc = totals_to_attributes(
+                &recalc_from_skills(&skill_names, &skill_map).expect("全量重算應成功"),
+            );
+            for attrs in [&full, &recalc] {
+                assert_eq!(
+                    (
+                        incremental.max_hp.0,
+                        incremental.agility.0,
+                        incremental.physical_accuracy.0
+                    ),
+                    (attrs.max_hp.0, attrs.agility.0, attrs.physical_accuracy.0),
+                    "seed {seed} step {step}：增量結果應與全量重算一致，技能 {skill_names:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_apply_skill_delta_rejects_invalid_change() {
+    let skill_map = create_delta_skill_map();
+    let skill_names = vec!["hp_flat".to_string()];
+    let mut totals = recalc_from_skills(&skill_names, &skill_map).expect("應成功");
+    let before = totals_to_attributes(&totals).max_hp.0;
+
+    // (說明, 加入, 移除)
+    let test_data = [
+        (
+            "加入不存在的技能",
+            vec![SKILL_NONEXISTENT.to_string()],
+            vec![],
+        ),
+        (
+            "移除不在列表中的技能",
+            vec!["hp_scale_up".to_string()],
+            vec!["agility_mixed".to_string()],
+        ),
+    ];
+    for (desc, added, removed) in test_data {
+        assert!(
+            apply_skill_delta(&mut totals, &added, &removed, &skill_map).is_err(),
+            "{desc} 應回傳錯誤"
+        );
+        assert_eq!(
+            totals_to_attributes(&totals).max_hp.0,
+            before,
+            "{desc} 失敗時不應修改累計值"
+        );
+    }
+}
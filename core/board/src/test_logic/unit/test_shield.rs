@@ -0,0 +1,18 @@
+//! 護盾吸收計算測試
+
+use crate::logic::skill::skill_execution::absorb_with_shield;
+
+#[test]
+fn test_absorb_with_shield() {
+    let test_data = [
+        ("傷害超過護盾：護盾歸 0，餘傷扣 HP", 10, -15, (0, -5)),
+        ("傷害小於護盾：全數吸收", 10, -4, (6, 0)),
+        ("傷害等於護盾：剛好抵銷", 10, -10, (0, 0)),
+        ("無護盾：原樣扣 HP", 0, -7, (0, -7)),
+        ("治療不受護盾影響", 10, 8, (10, 8)),
+    ];
+
+    for (label, shield, amount, expected) in test_data {
+        assert_eq!(absorb_with_shield(shield, amount), expected, "{label}");
+    }
+}
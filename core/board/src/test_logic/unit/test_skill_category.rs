@@ -0,0 +1,230 @@
+use crate::domain::constants::SKILL_TAG_EXCLUSIVE_GROUPS;
+use crate::domain::core_types::{ExclusiveTagGroup, SkillTag, SkillType};
+use crate::loader_schema::UnitType;
+use crate::logic::skill::skill_category::{validate_skill_tags, validate_unit_skills};
+
+// 技能名稱常數
+const SKILL_SLASH: &str = "slash";
+const SKILL_ARROW: &str = "arrow";
+const SKILL_TOUGHNESS: &str = "toughness";
+const SKILL_NONEXISTENT: &str = "nonexistent";
+
+/// 建立帶標籤的被動技能（類別驗證只看標籤，技能種類不影響）
+fn create_skill(name: &str, tags: Vec<SkillTag>) -> SkillType {
+    SkillType::Passive {
+        name: name.to_string(),
+        tags,
+        effects: Vec::new(),
+    }
+}
+
+fn create_skills() -> Vec<SkillType> {
+    vec![
+        create_skill(SKILL_SLASH, vec![SkillTag::Flankable, SkillTag::Melee]),
+        create_skill(SKILL_ARROW, vec![SkillTag::Ranged]),
+        create_skill(SKILL_TOUGHNESS, Vec::new()),
+    ]
+}
+
+fn create_unit(skills: &[&str], allowed_categories: Vec<SkillTag>) -> UnitType {
+    UnitType {
+        name: "unit".to_string(),
+        skills: skills.iter().map(|s| s.to_string()).collect(),
+        allowed_categories,
+        max_skills: None,
+    }
+}
+
+#[test]
+fn test_validate_unit_skills() {
+    let skills = create_skills();
+
+    // (單位技能, 可學類別, 預期違規技能)
+    let test_data: [(&[&str], Vec<SkillTag>, &[&str]); 5] = [
+        // 近戰單位學近戰技能與通用技能
+        (&[SKILL_SLASH, SKILL_TOUGHNESS], vec![SkillTag::Melee], &[]),
+        // 近戰單位被指派遠程技能
+        (
+            &[SKILL_SLASH, SKILL_ARROW],
+            vec![SkillTag::Melee],
+            &[SKILL_ARROW],
+        ),
+        // 未設定可學類別則不限制
+        (&[SKILL_SLASH, SKILL_ARROW], Vec::new(), &[]),
+        // 多類別單位
+        (
+            &[SKILL_SLASH, SKILL_ARROW],
+            vec![SkillTag::Melee, SkillTag::Ranged],
+            &[],
+        ),
+        // 不存在的技能不論類別設定都要回報
+        (&[SKILL_NONEXISTENT], Vec::new(), &[SKILL_NONEXISTENT]),
+    ];
+
+    for (unit_skills, allowed, expected) in test_data {
+        let unit = create_unit(unit_skills, allowed.clone());
+        let result = validate_unit_skills(&unit, &skills);
+
+        match result {
+            Ok(()) => assert!(
+                expected.is_empty(),
+                "技能 {:?}（可學 {:?}）應回報 {:?}",
+                unit_skills,
+                allowed,
+                expected
+            ),
+            Err(errors) => {
+                assert_eq!(
+                    errors.len(),
+                    expected.len(),
+                    "技能 {:?}（可學 {:?}）的錯誤：{:?}",
+                    unit_skills,
+                    allowed,
+                    errors
+                );
+                for (error, name) in errors.iter().zip(expected) {
+                    assert!(
+                        error.contains(name),
+                        "錯誤訊息應提及「{}」：{}",
+                        name,
+                        error
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_validate_unit_skill_slots() {
+    let skills = create_skills();
+
+    // (單位技能, 可學類別, 欄位上限, 預期錯誤數)
+    type SlotCase = (&'static [&'static str], Vec<SkillTag>, Option<usize>, usize);
+    let test_data: [SlotCase; 4] = [
+        // 剛好填滿欄位
+        (
+            &[SKILL_SLASH, SKILL_TOUGHNESS],
+            vec![SkillTag::Melee],
+            Some(2),
+            0,
+        ),
+        // 塞入超過欄位上限的技能
+        (
+            &[SKILL_SLASH, SKILL_ARROW, SKILL_TOUGHNESS],
+            Vec::new(),
+            Some(2),
+            1,
+        ),
+        // 同時超過上限與違反類別，兩者皆回報
+        (
+            &[SKILL_SLASH, SKILL_ARROW],
+            vec![SkillTag::Melee],
+            Some(1),
+            2,
+        ),
+        // 未設定上限則不限制
+        (
+            &[SKILL_SLASH, SKILL_ARROW, SKILL_TOUGHNESS],
+            Vec::new(),
+            None,
+            0,
+        ),
+    ];
+
+    for (unit_skills, allowed, max_skills, expected) in test_data {
+        let unit = UnitType {
+            max_skills,
+            ..create_unit(unit_skills, allowed)
+        };
+        let errors = validate_unit_skills(&unit, &skills)
+            .err()
+            .unwrap_or_default();
+
+        assert_eq!(
+            errors.len(),
+            expected,
+            "技能 {:?}（上限 {:?}）的錯誤：{:?}",
+            unit_skills,
+            max_skills,
+            errors
+        );
+        if max_skills.is_some_and(|max| unit_skills.len() > max) {
+            assert!(
+                errors[0].contains("欄位上限"),
+                "超過上限應回報欄位錯誤：{:?}",
+                errors
+            );
+        }
+    }
+}
+
+#[test]
+fn test_validate_skill_tags() {
+    // 測試用必選群組：屬性標籤恰選一個
+    const ELEMENT_GROUP: &[ExclusiveTagGroup] = &[ExclusiveTagGroup {
+        name: "屬性",
+        tags: &[SkillTag::Fire],
+        min: 1,
+        max: 1,
+    }];
+
+    // (說明, 互斥群組, 標籤, 預期錯誤訊息片段；None 表示通過)
+    type TagCase = (
+        &'static str,
+        &'static [ExclusiveTagGroup],
+        Vec<SkillTag>,
+        Option<&'static str>,
+    );
+    let test_data: [TagCase; 7] = [
+        (
+            "單一類別",
+            SKILL_TAG_EXCLUSIVE_GROUPS,
+            vec![SkillTag::Flankable, SkillTag::Melee],
+            None,
+        ),
+        (
+            "未選類別視為通用技能",
+            SKILL_TAG_EXCLUSIVE_GROUPS,
+            vec![SkillTag::Fire],
+            None,
+        ),
+        (
+            "近戰與遠程互斥",
+            SKILL_TAG_EXCLUSIVE_GROUPS,
+            vec![SkillTag::Melee, SkillTag::Ranged],
+            Some("技能類別"),
+        ),
+        (
+            "近戰與法術互斥",
+            SKILL_TAG_EXCLUSIVE_GROUPS,
+            vec![SkillTag::Magic, SkillTag::Flankable, SkillTag::Melee],
+            Some("技能類別"),
+        ),
+        (
+            "標籤重複",
+            SKILL_TAG_EXCLUSIVE_GROUPS,
+            vec![SkillTag::Flankable, SkillTag::Flankable],
+            Some("重複"),
+        ),
+        (
+            "必選群組未選",
+            ELEMENT_GROUP,
+            vec![SkillTag::Melee],
+            Some("至少"),
+        ),
+        ("必選群組已選", ELEMENT_GROUP, vec![SkillTag::Fire], None),
+    ];
+
+    for (desc, groups, tags, expected) in test_data {
+        let result = validate_skill_tags(&tags, groups);
+        match (result, expected) {
+            (Ok(()), None) => {}
+            (Err(error), Some(fragment)) => assert!(
+                error.contains(fragment),
+                "{desc}：錯誤訊息應提及「{fragment}」：{error}"
+            ),
+            (result, expected) => panic!("{desc}：預期 {expected:?}，實際 {result:?}"),
+        }
+    }
+}
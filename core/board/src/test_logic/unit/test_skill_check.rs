@@ -223,6 +223,7 @@ fn test_hit_probabilities() {
                 skill_bonus: 0,
                 flanking_bonus: 0,
                 adjacent_penalty: 0,
+                high_ground_bonus: 0,
                 total: attacker_hit,
             },
             defender_evasion: EvasionBreakdown {
@@ -4,17 +4,30 @@ pub const SKILL_WARRIOR_ACTIVE_2: &str = "warrior-active-2";
 pub const SKILL_WARRIOR_ACTIVE_4: &str = "warrior-active-4";
 pub const SKILL_DIAMOND_AOE: &str = "diamond-aoe-1";
 pub const SKILL_SUMMON_WALL_AOE: &str = "summon-wall-aoe";
+pub const SKILL_SUMMON_WARRIOR: &str = "summon-warrior";
+pub const SKILL_COMBO_FINISHER: &str = "combo-finisher";
+pub const SKILL_RECKLESS_STRIKE: &str = "reckless-strike";
+pub const SKILL_STRIKE_THEN_PULL: &str = "strike-then-pull";
+pub const SKILL_PULL_THEN_STRIKE: &str = "pull-then-strike";
 pub const SKILL_WARRIOR_REACTION: &str = "warrior-reaction";
 pub const SKILL_WARRIOR_REACTION_2: &str = "warrior-reaction-2";
 pub const SKILL_WARRIOR_COUNTER: &str = "warrior-counter";
+pub const SKILL_OVERWATCH_SHOT: &str = "overwatch-shot";
 pub const UNIT_TYPE_WARRIOR: &str = "warrior";
 pub const UNIT_TYPE_WARRIOR_B: &str = "warrior-b";
 pub const UNIT_TYPE_WARRIOR_COUNTER_ONLY: &str = "warrior-counter-only";
 pub const UNIT_TYPE_MAGE: &str = "mage";
+pub const UNIT_TYPE_BOMBER: &str = "bomber";
+pub const UNIT_TYPE_SENTRY: &str = "sentry";
 pub const OBJECT_TYPE_WALL: &str = "wall";
 pub const OBJECT_TYPE_SPIKE: &str = "spike";
 pub const OBJECT_TYPE_SWAMP: &str = "swamp";
 pub const OBJECT_TYPE_FOG: &str = "fog";
+pub const OBJECT_TYPE_CLIFF: &str = "cliff";
+pub const OBJECT_TYPE_SHALLOW_WATER: &str = "shallow-water";
+pub const OBJECT_TYPE_DEEP_WATER: &str = "deep-water";
+pub const OBJECT_TYPE_TRAP: &str = "trap";
+pub const OBJECT_TYPE_OBJECTIVE: &str = "objective";
 
 pub const SKILLS_TOML: &str = r#"
 [[skills]]
@@ -160,7 +173,7 @@ value_percent = -100
 
 [skills.Active]
 name = "diamond-aoe-1"
-tags = []
+tags = ["Fire"]
 cost = 0
 
 [skills.Active.target]
@@ -260,6 +273,182 @@ who = "Target"
 source = "Caster"
 source_attribute = "PhysicalAttack"
 value_percent = -100
+
+[[skills]]
+
+[skills.Active]
+name = "summon-warrior"
+tags = []
+cost = 0
+
+[skills.Active.target]
+range = [0, 0]
+selection = "Unit"
+selectable_filter = "CasterOnly"
+count = 1
+allow_same_target = false
+area = "Single"
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Caster"
+
+[skills.Active.effects.Leaf.effect.Summon]
+unit_type = "warrior"
+duration = 1
+
+[[skills]]
+
+[skills.Reaction]
+name = "overwatch-shot"
+tags = []
+cost = 0
+
+[skills.Reaction.triggering_unit]
+source_range = [1, 2]
+source_filter = "Enemy"
+trigger = "Overwatch"
+
+[[skills.Reaction.effects]]
+
+[skills.Reaction.effects.Leaf]
+who = "Target"
+
+[skills.Reaction.effects.Leaf.effect.HpEffect.scaling]
+source = "Caster"
+source_attribute = "PhysicalAttack"
+value_percent = -100
+
+[[skills]]
+
+[skills.Passive]
+name = "death-nova-passive"
+tags = []
+
+[[skills.Passive.effects]]
+
+[skills.Passive.effects.DeathNova]
+value = -5
+
+[[skills]]
+
+[skills.Active]
+name = "combo-finisher"
+tags = []
+cost = 0
+requires = ["diamond-aoe-1"]
+
+[skills.Active.target]
+range = [1, 1]
+selection = "Unit"
+selectable_filter = "Enemy"
+count = 1
+allow_same_target = false
+area = "Single"
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.HpEffect.scaling]
+source = "Caster"
+source_attribute = "PhysicalAttack"
+value_percent = -100
+
+[[skills]]
+
+[skills.Active]
+name = "reckless-strike"
+tags = []
+cost = 0
+recoil = 7
+
+[skills.Active.target]
+range = [1, 1]
+selection = "Unit"
+selectable_filter = "Enemy"
+count = 1
+allow_same_target = false
+area = "Single"
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.HpEffect.scaling]
+source = "Caster"
+source_attribute = "PhysicalAttack"
+value_percent = -100
+
+[[skills]]
+
+[skills.Active]
+name = "strike-then-pull"
+tags = []
+cost = 0
+
+[skills.Active.target]
+range = [2, 2]
+selection = "Unit"
+selectable_filter = "Enemy"
+count = 1
+allow_same_target = false
+area = "Single"
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.HpEffect.scaling]
+source = "Caster"
+source_attribute = "PhysicalAttack"
+value_percent = -100
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.Pull]
+distance = 1
+
+[[skills]]
+
+[skills.Active]
+name = "pull-then-strike"
+tags = []
+cost = 0
+effect_order = "MovementFirst"
+
+[skills.Active.target]
+range = [2, 2]
+selection = "Unit"
+selectable_filter = "Enemy"
+count = 1
+allow_same_target = false
+area = "Single"
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.HpEffect.scaling]
+source = "Caster"
+source_attribute = "PhysicalAttack"
+value_percent = -100
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.Pull]
+distance = 1
 "#;
 
 /// 最小單位 TOML：包含一個 warrior 單位類型
@@ -270,18 +459,26 @@ skills = ["warrior-passive", "melee-attack", "warrior-active-2", "warrior-active
 
 [[units]]
 name = "warrior-b"
-skills = ["warrior-passive", "melee-attack", "warrior-reaction", "warrior-reaction-2"]
+skills = ["warrior-passive", "melee-attack", "warrior-reaction", "warrior-reaction-2", "combo-finisher", "reckless-strike", "strike-then-pull", "pull-then-strike"]
 
 [[units]]
 name = "mage"
-skills = ["mage-passive", "melee-attack", "diamond-aoe-1", "summon-wall-aoe"]
+skills = ["mage-passive", "melee-attack", "diamond-aoe-1", "summon-wall-aoe", "summon-warrior", "combo-finisher"]
 
 [[units]]
 name = "warrior-counter-only"
 skills = ["warrior-passive", "warrior-counter"]
+
+[[units]]
+name = "bomber"
+skills = ["warrior-passive", "death-nova-passive"]
+
+[[units]]
+name = "sentry"
+skills = ["warrior-passive", "overwatch-shot"]
 "#;
 
-/// 最小物件 TOML：包含一個 wall 物件類型
+/// 最小物件 TOML：包含 wall、spike、swamp、fog、朝下的 cliff、淺水、深水與陷阱、目標點物件類型
 pub const OBJECTS_TOML: &str = r#"
 [[objects]]
 name = "wall"
@@ -310,4 +507,43 @@ movement_cost = 0
 blocks_sight = true
 blocks_sound = false
 hazardous = false
+
+[[objects]]
+name = "cliff"
+movement_cost = 0
+blocks_sight = false
+blocks_sound = false
+hazardous = false
+cliff_facing = "Down"
+
+[[objects]]
+name = "shallow-water"
+movement_cost = 10
+blocks_sight = false
+blocks_sound = false
+hazardous = false
+
+[[objects]]
+name = "deep-water"
+movement_cost = 10000
+blocks_sight = false
+blocks_sound = false
+hazardous = false
+forbidden_skill_tags = ["Fire"]
+
+[[objects]]
+name = "trap"
+movement_cost = 0
+blocks_sight = false
+blocks_sound = false
+hazardous = true
+trigger = { Damage = { amount = 15 } }
+
+[[objects]]
+name = "objective"
+movement_cost = 0
+blocks_sight = false
+blocks_sound = false
+hazardous = false
+trigger = "Objective"
 "#;
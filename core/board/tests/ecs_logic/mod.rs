@@ -1,28 +1,58 @@
 mod constants;
+mod test_aura;
+mod test_buff_dispel;
+mod test_buff_max_hp;
+mod test_buff_regen;
+mod test_buff_stacking;
 mod test_buff_ttl;
+mod test_channel;
+mod test_content_check;
+mod test_damage_type;
+mod test_death_nova;
+mod test_debug_tools;
 mod test_deployment;
+mod test_distance_field;
+mod test_effect_order;
 mod test_level_outcome;
 mod test_loader;
+mod test_measure;
 mod test_move_reaction_los;
 mod test_move_reaction_preview;
 mod test_movement;
+mod test_overwatch;
 mod test_query;
 mod test_reaction;
+mod test_recoil;
+mod test_reinforcement;
+mod test_reload_skills;
+mod test_save;
+mod test_scenario_bundle;
+mod test_shield;
 mod test_skill;
 mod test_skill_list;
+mod test_skill_requires;
 mod test_skill_targeting;
 mod test_spawner;
+mod test_summon;
+mod test_tile_label;
+mod test_tile_trigger;
 mod test_turn;
+mod test_visibility;
+mod test_water;
 
 use bevy_ecs::prelude::{Entity, World};
 use board::domain::constants::PLAYER_FACTION_ID;
-use board::ecs_logic::loader::parse_and_insert_game_data;
+use board::ecs_logic::loader::{parse_and_insert_game_data, parse_skills_toml, reload_skills};
 use board::ecs_logic::spawner::spawn_level;
-use board::ecs_types::components::{CurrentMp, Initiative, Occupant, Position};
+use board::ecs_logic::turn::start_new_round;
+use board::ecs_types::components::{
+    CurrentMp, Initiative, Occupant, PhysicalAttack, Position, Skills,
+};
 use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
 use constants::{
-    OBJECT_TYPE_SWAMP, OBJECT_TYPE_WALL, OBJECTS_TOML, SKILLS_TOML, UNIT_TYPE_MAGE,
-    UNIT_TYPE_WARRIOR, UNITS_TOML,
+    OBJECT_TYPE_CLIFF, OBJECT_TYPE_DEEP_WATER, OBJECT_TYPE_SHALLOW_WATER, OBJECT_TYPE_SWAMP,
+    OBJECT_TYPE_WALL, OBJECTS_TOML, SKILLS_TOML, UNIT_TYPE_MAGE, UNIT_TYPE_WARRIOR,
+    UNIT_TYPE_WARRIOR_B, UNITS_TOML,
 };
 use std::collections::HashMap;
 
@@ -46,6 +76,9 @@ fn build_warrior_world(ascii: &str) -> (World, Occupant, HashMap<String, Vec<Pos
         .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
         .object("w", OBJECT_TYPE_WALL)
         .object("p", OBJECT_TYPE_SWAMP)
+        .object("c", OBJECT_TYPE_CLIFF)
+        .object("s", OBJECT_TYPE_SHALLOW_WATER)
+        .object("d", OBJECT_TYPE_DEEP_WATER)
         .to_toml()
         .expect("LevelBuilder::to_toml 應成功");
     let mut world = setup_world_with_level(&level_toml);
@@ -75,6 +108,7 @@ fn build_mage_world(ascii: &str) -> (World, Occupant, HashMap<String, Vec<Positi
         .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
         .object("w", OBJECT_TYPE_WALL)
         .object("p", OBJECT_TYPE_SWAMP)
+        .object("d", OBJECT_TYPE_DEEP_WATER)
         .to_toml()
         .expect("LevelBuilder::to_toml 應成功");
     let mut world = setup_world_with_level(&level_toml);
@@ -92,3 +126,61 @@ fn build_mage_world(ascii: &str) -> (World, Occupant, HashMap<String, Vec<Positi
     world.entity_mut(player_entity).insert(CurrentMp(100));
     (world, occupant, markers)
 }
+
+/// 取得指定位置上的單位
+fn entity_at(world: &mut World, pos: Position) -> Entity {
+    world
+        .query::<(Entity, &Position, &Occupant)>()
+        .iter(world)
+        .find(|(_, p, occ)| **p == pos && matches!(occ, Occupant::Unit(_)))
+        .map(|(entity, _, _)| entity)
+        .expect("應找到單位")
+}
+
+/// 玩家（warrior-b）與相鄰敵人（warrior）一對一的場景
+struct Duel {
+    world: World,
+    caster: Entity,
+    caster_pos: Position,
+    target: Entity,
+    target_pos: Position,
+}
+
+/// 建立 "P E" 對峙並輪到玩家行動
+///
+/// `extra_skills_toml` 會接在 SKILLS_TOML 後重新載入，`caster_skills` 加入玩家技能清單；
+/// 玩家固定先手，物理攻擊設為 `physical_attack`
+fn setup_duel(extra_skills_toml: &str, caster_skills: &[&str], physical_attack: i32) -> Duel {
+    let ascii = "P E";
+    let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ascii)
+        .unit("P", UNIT_TYPE_WARRIOR_B, PLAYER_FACTION_ID)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    let (skills, _) = parse_skills_toml(&format!("{SKILLS_TOML}{extra_skills_toml}"))
+        .expect("技能 TOML 應可解析");
+    reload_skills(&mut world, skills).expect("reload_skills 應成功");
+
+    let caster_pos = markers["P"][0];
+    let caster = entity_at(&mut world, caster_pos);
+    world
+        .entity_mut(caster)
+        .insert((Initiative(100), PhysicalAttack(physical_attack)))
+        .get_mut::<Skills>()
+        .expect("應有 Skills")
+        .0
+        .extend(caster_skills.iter().map(|skill| skill.to_string()));
+    start_new_round(&mut world).expect("start_new_round 應成功");
+
+    let target_pos = markers["E"][0];
+    let target = entity_at(&mut world, target_pos);
+    Duel {
+        world,
+        caster,
+        caster_pos,
+        target,
+        target_pos,
+    }
+}
@@ -0,0 +1,254 @@
+//! 光環被動測試：來源存活期間，範圍內的其他同盟單位獲得效果
+
+use super::constants::{SKILL_MELEE, SKILLS_TOML, UNIT_TYPE_WARRIOR};
+use super::{ENEMY_FACTION_ID, entity_at, setup_world_with_level};
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::ecs_logic::loader::{parse_skills_toml, reload_skills};
+use board::ecs_logic::movement::{AdvanceMoveResult, advance_move, plan_move};
+use board::ecs_logic::query::get_calculated_attributes;
+use board::ecs_logic::skill::execute_skill;
+use board::ecs_logic::turn::{resolve_deaths, start_new_round};
+use board::ecs_types::components::{CurrentHp, Initiative, MaxHp, Occupant, Position, Skills};
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+
+const SKILL_COMMAND_AURA: &str = "command-aura";
+const AURA_ATTACK_BONUS: i32 = 5;
+const AURA_HP_BONUS: i32 = 10;
+
+/// 距離 1 內的友軍物攻 +5、HP 上限 +10
+const AURA_SKILLS_TOML: &str = r#"
+[[skills]]
+
+[skills.Passive]
+name = "command-aura"
+tags = []
+
+[[skills.Passive.effects]]
+
+[skills.Passive.effects.Aura]
+range = 1
+
+[[skills.Passive.effects.Aura.effects]]
+
+[skills.Passive.effects.Aura.effects.AttributeFlat]
+attribute = "PhysicalAttack"
+value = 5
+
+[[skills.Passive.effects.Aura.effects]]
+
+[skills.Passive.effects.Aura.effects.AttributeFlat]
+attribute = "Hp"
+value = 10
+"#;
+
+struct AuraField {
+    world: World,
+    source: Entity,
+    ally: Occupant,
+    enemy: Occupant,
+    inside: Position,
+    outside: Position,
+}
+
+fn occupant_of(world: &World, entity: Entity) -> Occupant {
+    *world
+        .entity(entity)
+        .get::<Occupant>()
+        .expect("應有 Occupant")
+}
+
+fn physical_attack(world: &mut World, occupant: Occupant) -> i32 {
+    get_calculated_attributes(world, occupant)
+        .expect("get_calculated_attributes 應成功")
+        .physical_attack
+        .0
+}
+
+fn max_hp(world: &mut World, occupant: Occupant) -> i32 {
+    world
+        .query::<(&Occupant, &MaxHp)>()
+        .iter(world)
+        .find(|(occ, _)| **occ == occupant)
+        .map(|(_, max_hp)| max_hp.0)
+        .expect("應找到單位")
+}
+
+/// 光環來源 S 與敵人 E 相鄰，友軍 A（先手）站在光環範圍外
+fn setup_aura_field() -> AuraField {
+    let ascii = "E S I . A";
+    let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ascii)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .unit("S", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .unit("A", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    let (skills, _) =
+        parse_skills_toml(&format!("{SKILLS_TOML}{AURA_SKILLS_TOML}")).expect("技能 TOML 應可解析");
+    reload_skills(&mut world, skills).expect("reload_skills 應成功");
+
+    let source = entity_at(&mut world, markers["S"][0]);
+    world
+        .entity_mut(source)
+        .get_mut::<Skills>()
+        .expect("應有 Skills")
+        .0
+        .push(SKILL_COMMAND_AURA.to_string());
+    let ally_entity = entity_at(&mut world, markers["A"][0]);
+    world.entity_mut(ally_entity).insert(Initiative(100));
+    let ally = occupant_of(&world, ally_entity);
+    let enemy_entity = entity_at(&mut world, markers["E"][0]);
+    let enemy = occupant_of(&world, enemy_entity);
+    start_new_round(&mut world).expect("start_new_round 應成功");
+
+    AuraField {
+        world,
+        source,
+        ally,
+        enemy,
+        inside: markers["I"][0],
+        outside: markers["A"][0],
+    }
+}
+
+fn move_current_unit(world: &mut World, target: Position) {
+    plan_move(world, target).expect("plan_move 應成功");
+    let result = advance_move(world).expect("advance_move 應成功");
+    assert!(
+        matches!(result, AdvanceMoveResult::Completed { .. }),
+        "移動不應被反應打斷"
+    );
+}
+
+#[test]
+fn test_ally_gains_aura_in_range_and_loses_it_out_of_range() {
+    let mut field = setup_aura_field();
+    let base_attack = physical_attack(&mut field.world, field.ally);
+    let base_max_hp = max_hp(&mut field.world, field.ally);
+    let enemy_attack = physical_attack(&mut field.world, field.enemy);
+
+    move_current_unit(&mut field.world, field.inside);
+    assert_eq!(
+        physical_attack(&mut field.world, field.ally),
+        base_attack + AURA_ATTACK_BONUS,
+        "進入光環範圍應獲得物攻加成"
+    );
+    assert_eq!(
+        max_hp(&mut field.world, field.ally),
+        base_max_hp + AURA_HP_BONUS,
+        "進入光環範圍後應同步 HP 上限"
+    );
+    assert_eq!(
+        physical_attack(&mut field.world, field.enemy),
+        enemy_attack,
+        "範圍內的敵人不受光環影響"
+    );
+
+    move_current_unit(&mut field.world, field.outside);
+    assert_eq!(
+        physical_attack(&mut field.world, field.ally),
+        base_attack,
+        "離開光環範圍應失去物攻加成"
+    );
+    assert_eq!(
+        max_hp(&mut field.world, field.ally),
+        base_max_hp,
+        "離開光環範圍後應同步 HP 上限"
+    );
+}
+
+#[test]
+fn test_aura_stops_when_source_dies() {
+    let mut field = setup_aura_field();
+    let base_attack = physical_attack(&mut field.world, field.ally);
+    move_current_unit(&mut field.world, field.inside);
+    assert_eq!(
+        physical_attack(&mut field.world, field.ally),
+        base_attack + AURA_ATTACK_BONUS
+    );
+
+    field
+        .world
+        .entity_mut(field.source)
+        .get_mut::<CurrentHp>()
+        .expect("應有 HP")
+        .0 = 0;
+    assert_eq!(
+        physical_attack(&mut field.world, field.ally),
+        base_attack,
+        "來源死亡後光環應失效"
+    );
+}
+
+#[test]
+fn test_source_death_drops_max_hp_mid_turn() {
+    let mut field = setup_aura_field();
+    let base_max_hp = max_hp(&mut field.world, field.ally);
+    move_current_unit(&mut field.world, field.inside);
+    assert_eq!(
+        max_hp(&mut field.world, field.ally),
+        base_max_hp + AURA_HP_BONUS
+    );
+
+    field
+        .world
+        .entity_mut(field.source)
+        .get_mut::<CurrentHp>()
+        .expect("應有 HP")
+        .0 = 0;
+    resolve_deaths(&mut field.world).expect("resolve_deaths 應成功");
+
+    // 仍在友軍的回合內，不需等到下一個回合開始
+    assert_eq!(
+        max_hp(&mut field.world, field.ally),
+        base_max_hp,
+        "來源死亡後應立即失去 HP 上限加成"
+    );
+}
+
+/// 友軍 A（先手）夾在敵人 E 與光環來源 S 之間，以近戰攻擊敵人，回傳敵人受到的傷害
+fn melee_damage_next_to_source(source_has_aura: bool) -> i32 {
+    let ascii = "E A S";
+    let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ascii)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .unit("A", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .unit("S", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    let (skills, _) =
+        parse_skills_toml(&format!("{SKILLS_TOML}{AURA_SKILLS_TOML}")).expect("技能 TOML 應可解析");
+    reload_skills(&mut world, skills).expect("reload_skills 應成功");
+
+    if source_has_aura {
+        let source = entity_at(&mut world, markers["S"][0]);
+        world
+            .entity_mut(source)
+            .get_mut::<Skills>()
+            .expect("應有 Skills")
+            .0
+            .push(SKILL_COMMAND_AURA.to_string());
+    }
+    let ally = entity_at(&mut world, markers["A"][0]);
+    world.entity_mut(ally).insert(Initiative(100));
+    let enemy = entity_at(&mut world, markers["E"][0]);
+    start_new_round(&mut world).expect("start_new_round 應成功");
+
+    let hp_before = world.entity(enemy).get::<CurrentHp>().expect("應有 HP").0;
+    execute_skill(&mut world, &SKILL_MELEE.to_string(), &[markers["E"][0]])
+        .expect("近戰攻擊應成功");
+    hp_before - world.entity(enemy).get::<CurrentHp>().expect("應有 HP").0
+}
+
+#[test]
+fn test_aura_attack_bonus_applies_to_skill_damage() {
+    let base_damage = melee_damage_next_to_source(false);
+    assert_eq!(
+        melee_damage_next_to_source(true),
+        base_damage + AURA_ATTACK_BONUS,
+        "光環的物攻加成應反映在技能傷害上"
+    );
+}
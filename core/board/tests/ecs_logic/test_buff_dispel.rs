@@ -0,0 +1,68 @@
+use super::build_warrior_world;
+use bevy_ecs::prelude::World;
+use board::domain::core_types::{BuffType, EndCondition, StackingPolicy};
+use board::ecs_logic::buff::{apply_buff, dispel_buffs};
+use board::ecs_types::components::{AppliedBuff, Occupant};
+
+fn make_buff(name: &str, beneficial: bool) -> BuffType {
+    BuffType {
+        name: name.to_string(),
+        beneficial,
+        stacking: StackingPolicy::Stack,
+        while_active: vec![],
+        per_turn_effects: vec![],
+        end_conditions: vec![EndCondition::Duration(3)],
+    }
+}
+
+fn buff_names(world: &mut World, target: Occupant) -> Vec<String> {
+    let mut names: Vec<String> = world
+        .query::<&AppliedBuff>()
+        .iter(world)
+        .filter(|buff| buff.target == target)
+        .map(|buff| buff.def.name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_dispel_removes_only_selected_kind() {
+    let test_data = [
+        ("驅散增益", true, vec!["poison".to_string()]),
+        ("驅散減益", false, vec!["haste".to_string()]),
+    ];
+
+    for (label, beneficial, expected) in test_data {
+        let (mut world, player, _) = build_warrior_world("P");
+        apply_buff(&mut world, make_buff("haste", true), player, player).expect("施加增益應成功");
+        apply_buff(&mut world, make_buff("poison", false), player, player).expect("施加減益應成功");
+
+        let removed = dispel_buffs(&mut world, player, beneficial).expect("驅散應成功");
+
+        assert_eq!(removed, 1, "{label}: 應只移除 1 個 buff");
+        assert_eq!(
+            buff_names(&mut world, player),
+            expected,
+            "{label}: 剩餘 buff 不符"
+        );
+    }
+}
+
+#[test]
+fn test_dispel_only_affects_target() {
+    let (mut world, player, _) = build_warrior_world("P A");
+    let ally = world
+        .query::<&Occupant>()
+        .iter(&world)
+        .copied()
+        .find(|occupant| *occupant != player)
+        .expect("應有友軍");
+    apply_buff(&mut world, make_buff("haste", true), player, player).expect("施加應成功");
+    apply_buff(&mut world, make_buff("haste", true), player, ally).expect("施加應成功");
+
+    dispel_buffs(&mut world, player, true).expect("驅散應成功");
+
+    assert!(buff_names(&mut world, player).is_empty());
+    assert_eq!(buff_names(&mut world, ally), vec!["haste".to_string()]);
+}
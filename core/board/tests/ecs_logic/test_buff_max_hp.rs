@@ -0,0 +1,130 @@
+//! HP/MP 上限類 buff 的當前值調整測試
+
+use super::build_warrior_world;
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::core_types::{
+    Attribute, BuffType, ContinuousEffect, EndCondition, StackingPolicy,
+};
+use board::ecs_logic::buff::{apply_buff, dispel_buffs};
+use board::ecs_logic::turn::{end_current_turn, start_new_round};
+use board::ecs_types::components::{CurrentHp, CurrentMp, MaxHp, MaxMp, Occupant};
+
+const BONUS: i32 = 20;
+
+fn max_buff(attribute: Attribute, value: i32, duration: u32) -> BuffType {
+    BuffType {
+        name: "vigor".to_string(),
+        beneficial: value > 0,
+        stacking: StackingPolicy::Stack,
+        while_active: vec![ContinuousEffect::AttributeFlat { attribute, value }],
+        per_turn_effects: vec![],
+        end_conditions: vec![EndCondition::Duration(duration)],
+    }
+}
+
+fn entity_of(world: &mut World, occupant: Occupant) -> Entity {
+    world
+        .query::<(Entity, &Occupant)>()
+        .iter(world)
+        .find(|(_, occ)| **occ == occupant)
+        .map(|(entity, _)| entity)
+        .expect("應找到單位")
+}
+
+/// (上限, 當前值)
+fn hp(world: &mut World, occupant: Occupant) -> (i32, i32) {
+    let entity = entity_of(world, occupant);
+    let entity_ref = world.entity(entity);
+    (
+        entity_ref.get::<MaxHp>().expect("應有 MaxHp").0,
+        entity_ref.get::<CurrentHp>().expect("應有 CurrentHp").0,
+    )
+}
+
+/// (上限, 當前值)
+fn mp(world: &mut World, occupant: Occupant) -> (i32, i32) {
+    let entity = entity_of(world, occupant);
+    let entity_ref = world.entity(entity);
+    (
+        entity_ref.get::<MaxMp>().expect("應有 MaxMp").0,
+        entity_ref.get::<CurrentMp>().expect("應有 CurrentMp").0,
+    )
+}
+
+/// 上限提高時當前值同步提高；驅散後上限回復並壓低當前值
+#[test]
+fn test_max_hp_gain_then_dispel_clamps() {
+    let (mut world, player, _) = build_warrior_world("P E");
+    let (base_max, base_current) = hp(&mut world, player);
+
+    apply_buff(
+        &mut world,
+        max_buff(Attribute::Hp, BONUS, 3),
+        player,
+        player,
+    )
+    .expect("施加應成功");
+    assert_eq!(
+        hp(&mut world, player),
+        (base_max + BONUS, base_current + BONUS),
+        "上限提高時當前值應增加差值"
+    );
+
+    dispel_buffs(&mut world, player, true).expect("驅散應成功");
+    assert_eq!(
+        hp(&mut world, player),
+        (base_max, base_max),
+        "上限回復後當前值應壓到新上限"
+    );
+}
+
+/// 受傷後 buff 失效：當前值低於新上限則維持不變
+#[test]
+fn test_max_hp_loss_keeps_damaged_current() {
+    let (mut world, player, _) = build_warrior_world("P E");
+    let (base_max, _) = hp(&mut world, player);
+
+    apply_buff(
+        &mut world,
+        max_buff(Attribute::Hp, BONUS, 3),
+        player,
+        player,
+    )
+    .expect("施加應成功");
+    let damaged = base_max / 2;
+    let entity = entity_of(&mut world, player);
+    world.entity_mut(entity).insert(CurrentHp(damaged));
+
+    dispel_buffs(&mut world, player, true).expect("驅散應成功");
+    assert_eq!(hp(&mut world, player), (base_max, damaged));
+}
+
+/// MP 上限增益到期：得到時當前值增加差值，到期移除後壓回原上限
+#[test]
+fn test_max_mp_buff_expires() {
+    let (mut world, player, _) = build_warrior_world("P E");
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let (base_max, base_current) = mp(&mut world, player);
+
+    apply_buff(
+        &mut world,
+        max_buff(Attribute::Mp, BONUS, 1),
+        player,
+        player,
+    )
+    .expect("施加應成功");
+    assert_eq!(
+        mp(&mut world, player),
+        (base_max + BONUS, base_current + BONUS)
+    );
+
+    // 走完一整輪使 buff 到期，玩家下一輪回合開始時移除
+    end_current_turn(&mut world).expect("結束回合應成功");
+    end_current_turn(&mut world).expect("結束回合應成功");
+
+    assert_eq!(
+        mp(&mut world, player),
+        (base_max, base_max),
+        "到期後上限回復，當前值壓回原上限"
+    );
+}
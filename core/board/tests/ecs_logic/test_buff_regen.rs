@@ -0,0 +1,95 @@
+//! 再生（Regen）buff 測試
+
+use super::build_warrior_world;
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::core_types::{BuffType, ContinuousEffect, EndCondition, StackingPolicy};
+use board::ecs_logic::turn::{end_current_turn, start_new_round};
+use board::ecs_types::components::{AppliedBuff, CurrentHp, MaxHp, Occupant};
+
+const REGEN_AMOUNT: i32 = 10;
+
+/// 對 target 施加持續 ttl 輪、每回合回復 REGEN_AMOUNT HP 的 buff
+fn spawn_regen_buff(world: &mut World, target: Occupant, ttl: u32) {
+    world.spawn((AppliedBuff {
+        def: BuffType {
+            name: "regen".to_string(),
+            beneficial: true,
+            stacking: StackingPolicy::Stack,
+            while_active: vec![ContinuousEffect::Regen {
+                amount: REGEN_AMOUNT,
+            }],
+            per_turn_effects: vec![],
+            end_conditions: vec![EndCondition::Duration(ttl)],
+        }
+        .into(),
+        caster: target,
+        target,
+        remaining_duration: Some(ttl),
+        inherited_defense: None,
+    },));
+}
+
+fn find_unit(world: &mut World, occupant: Occupant) -> Entity {
+    world
+        .query::<(Entity, &Occupant)>()
+        .iter(world)
+        .find(|(_, occ)| **occ == occupant)
+        .map(|(entity, _)| entity)
+        .expect("應找到單位")
+}
+
+/// 將單位 HP 設為 max_hp - missing，回傳 max_hp
+fn wound(world: &mut World, entity: Entity, missing: i32) -> i32 {
+    let max_hp = world.entity(entity).get::<MaxHp>().expect("應有 MaxHp").0;
+    world.entity_mut(entity).insert(CurrentHp(max_hp - missing));
+    max_hp
+}
+
+fn current_hp(world: &World, entity: Entity) -> i32 {
+    world.entity(entity).get::<CurrentHp>().expect("應有 HP").0
+}
+
+/// 單一單位的棋盤中，每次 end_current_turn 都會換輪並開始該單位的回合
+#[test]
+fn test_regen_heals_each_turn_until_expiry() {
+    let (mut world, player, _) = build_warrior_world("P");
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let entity = find_unit(&mut world, player);
+    let max_hp = wound(&mut world, entity, 30);
+    spawn_regen_buff(&mut world, player, 3);
+
+    let expected_after_each_turn = [max_hp - 20, max_hp - 10, max_hp - 10];
+    for (turn, expected) in expected_after_each_turn.into_iter().enumerate() {
+        end_current_turn(&mut world).expect("end_current_turn 應成功");
+        assert_eq!(
+            current_hp(&world, entity),
+            expected,
+            "第 {} 次回合開始後 HP 不符（第 3 次時 buff 已到期不再回復）",
+            turn + 1
+        );
+    }
+    assert_eq!(
+        world.query::<&AppliedBuff>().iter(&world).count(),
+        0,
+        "到期的再生 buff 應被移除"
+    );
+}
+
+#[test]
+fn test_regen_does_not_exceed_max_hp() {
+    let (mut world, player, _) = build_warrior_world("P");
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let entity = find_unit(&mut world, player);
+    let max_hp = wound(&mut world, entity, REGEN_AMOUNT / 2);
+    spawn_regen_buff(&mut world, player, 3);
+
+    end_current_turn(&mut world).expect("end_current_turn 應成功");
+    assert_eq!(current_hp(&world, entity), max_hp, "再生不應超過 HP 上限");
+
+    end_current_turn(&mut world).expect("end_current_turn 應成功");
+    assert_eq!(
+        current_hp(&world, entity),
+        max_hp,
+        "滿血時再生不應超過 HP 上限"
+    );
+}
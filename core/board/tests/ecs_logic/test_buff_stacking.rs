@@ -0,0 +1,162 @@
+use super::build_warrior_world;
+use super::constants::SKILLS_TOML;
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::core_types::{BuffType, EndCondition, StackingPolicy};
+use board::ecs_logic::buff::apply_buff;
+use board::ecs_logic::loader::{parse_skills_toml, reload_skills};
+use board::ecs_logic::skill::execute_skill;
+use board::ecs_logic::turn::start_new_round;
+use board::ecs_types::components::{ActionState, AppliedBuff, Occupant, Skills};
+
+fn make_buff(stacking: StackingPolicy, duration: u32) -> BuffType {
+    BuffType {
+        name: "evasion".to_string(),
+        beneficial: false,
+        stacking,
+        while_active: vec![],
+        per_turn_effects: vec![],
+        end_conditions: vec![EndCondition::Duration(duration)],
+    }
+}
+
+fn buff_durations(world: &mut World, target: Occupant) -> Vec<Option<u32>> {
+    world
+        .query::<&AppliedBuff>()
+        .iter(world)
+        .filter(|buff| buff.target == target)
+        .map(|buff| buff.remaining_duration)
+        .collect()
+}
+
+#[test]
+fn test_apply_buff_stack_and_highest_only_add_instances() {
+    for stacking in [StackingPolicy::Stack, StackingPolicy::HighestOnly] {
+        let (mut world, player, _) = build_warrior_world("P");
+
+        apply_buff(&mut world, make_buff(stacking, 2), player, player).expect("第一次施加應成功");
+        apply_buff(&mut world, make_buff(stacking, 3), player, player).expect("第二次施加應成功");
+
+        let mut durations = buff_durations(&mut world, player);
+        durations.sort();
+        assert_eq!(
+            durations,
+            vec![Some(2), Some(3)],
+            "{stacking} 應保留兩個實例"
+        );
+    }
+}
+
+#[test]
+fn test_apply_buff_refresh_duration_resets_existing() {
+    let (mut world, player, _) = build_warrior_world("P");
+
+    apply_buff(
+        &mut world,
+        make_buff(StackingPolicy::RefreshDuration, 3),
+        player,
+        player,
+    )
+    .expect("第一次施加應成功");
+    for mut buff in world.query::<&mut AppliedBuff>().iter_mut(&mut world) {
+        buff.remaining_duration = Some(1);
+    }
+    apply_buff(
+        &mut world,
+        make_buff(StackingPolicy::RefreshDuration, 3),
+        player,
+        player,
+    )
+    .expect("第二次施加應成功");
+
+    assert_eq!(
+        buff_durations(&mut world, player),
+        vec![Some(3)],
+        "RefreshDuration 應只有一個實例且剩餘回合被重置"
+    );
+}
+
+#[test]
+fn test_apply_buff_zero_duration_is_permanent() {
+    let (mut world, player, _) = build_warrior_world("P");
+
+    apply_buff(
+        &mut world,
+        make_buff(StackingPolicy::Stack, 0),
+        player,
+        player,
+    )
+    .expect("施加應成功");
+
+    assert_eq!(buff_durations(&mut world, player), vec![None]);
+}
+
+/// 對自身施加 RefreshDuration 的 buff（持續 3 回合）
+const GUARD_SKILLS_TOML: &str = r#"
+[[skills]]
+
+[skills.Active]
+name = "guard-stance"
+tags = []
+cost = 0
+
+[skills.Active.target]
+range = [0, 0]
+selection = "Unit"
+selectable_filter = "CasterOnly"
+count = 1
+allow_same_target = false
+area = "Single"
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.ApplyBuff.buff]
+name = "guard"
+beneficial = true
+stacking = "RefreshDuration"
+while_active = []
+per_turn_effects = []
+end_conditions = [{ Duration = 3 }]
+"#;
+
+#[test]
+fn test_execute_skill_twice_honors_refresh_duration() {
+    let (mut world, player, markers) = build_warrior_world("P");
+    let (skills, _) = parse_skills_toml(&format!("{SKILLS_TOML}{GUARD_SKILLS_TOML}"))
+        .expect("技能 TOML 應可解析");
+    reload_skills(&mut world, skills).expect("reload_skills 應成功");
+    let player_entity = world
+        .query::<(Entity, &Occupant)>()
+        .iter(&world)
+        .find(|(_, occupant)| **occupant == player)
+        .map(|(entity, _)| entity)
+        .expect("應找到玩家單位");
+    world
+        .entity_mut(player_entity)
+        .get_mut::<Skills>()
+        .expect("應有 Skills")
+        .0
+        .push("guard-stance".to_string());
+    start_new_round(&mut world).expect("start_new_round 應成功");
+
+    let player_pos = markers["P"][0];
+    execute_skill(&mut world, &"guard-stance".to_string(), &[player_pos])
+        .expect("第一次施放應成功");
+    for mut buff in world.query::<&mut AppliedBuff>().iter_mut(&mut world) {
+        buff.remaining_duration = Some(1);
+    }
+    // 重置行動狀態，讓同一回合可再次施放
+    world
+        .entity_mut(player_entity)
+        .insert(ActionState::Moved { cost: 0 });
+    execute_skill(&mut world, &"guard-stance".to_string(), &[player_pos])
+        .expect("第二次施放應成功");
+
+    assert_eq!(
+        buff_durations(&mut world, player),
+        vec![Some(3)],
+        "經由技能重複施加 RefreshDuration 應只保留一個實例並重置剩餘回合"
+    );
+}
@@ -1,12 +1,14 @@
 use super::build_warrior_world;
-use board::domain::core_types::{BuffType, EndCondition};
+use board::domain::core_types::{BuffType, EndCondition, StackingPolicy};
+use board::ecs_logic::buff::apply_buff;
 use board::ecs_logic::turn::{end_current_turn, start_new_round};
 use board::ecs_types::components::{AppliedBuff, Occupant, Position};
 
 fn make_buff(name: &str, end_conditions: Vec<EndCondition>) -> BuffType {
     BuffType {
         name: name.to_string(),
-        stackable: false,
+        beneficial: false,
+        stacking: StackingPolicy::Stack,
         while_active: vec![],
         per_turn_effects: vec![],
         end_conditions,
@@ -23,7 +25,7 @@ fn spawn_buff_with_ttl(
 ) {
     let remaining = (ttl != 0).then_some(ttl);
     world.spawn((AppliedBuff {
-        def: make_buff(name, vec![EndCondition::Duration(ttl)]),
+        def: make_buff(name, vec![EndCondition::Duration(ttl)]).into(),
         caster: target,
         target,
         remaining_duration: remaining,
@@ -148,3 +150,48 @@ fn test_expired_buff_is_removed_on_next_turn_start() {
         );
     }
 }
+
+/// 永久 buff（Duration(0) 或未設定 Duration）經過多輪仍存在且剩餘回合不變；
+/// 同時施加的有限 buff 依排程到期
+#[test]
+fn test_permanent_buff_persists_while_finite_expires() {
+    const ROUNDS: u32 = 10;
+    const FINITE_TTL: u32 = 2;
+
+    let (mut world, player_occupant, _) = build_warrior_world("P A");
+    start_new_round(&mut world).expect("start_new_round should succeed");
+    let buffs = [
+        make_buff("blessing", vec![EndCondition::Duration(0)]),
+        make_buff("curse", vec![]),
+        make_buff(
+            "burn",
+            vec![
+                EndCondition::Duration(0),
+                EndCondition::Duration(FINITE_TTL),
+            ],
+        ),
+    ];
+    for buff in buffs {
+        apply_buff(&mut world, buff, player_occupant, player_occupant)
+            .expect("apply_buff should succeed");
+    }
+
+    // 兩個單位，每輪兩次 end_current_turn；P 先手，有限 buff 在第 FINITE_TTL 輪輪替後、
+    // P 的回合開始時移除
+    for turn in 1..=ROUNDS * 2 {
+        end_current_turn(&mut world).expect("end_current_turn should succeed");
+        let mut remaining: Vec<(String, Option<u32>)> = world
+            .query::<&AppliedBuff>()
+            .iter(&world)
+            .map(|buff| (buff.def.name.clone(), buff.remaining_duration))
+            .collect();
+        remaining.sort();
+
+        let mut expected = vec![("blessing".to_string(), None), ("curse".to_string(), None)];
+        if turn < FINITE_TTL * 2 {
+            expected.push(("burn".to_string(), Some(FINITE_TTL - turn / 2)));
+        }
+        expected.sort();
+        assert_eq!(remaining, expected, "turn {turn}");
+    }
+}
@@ -0,0 +1,229 @@
+//! 引導技能測試：施放後維持數回合才結算，受到傷害即中斷
+
+use super::constants::{
+    SKILL_RECKLESS_STRIKE, SKILLS_TOML, UNIT_TYPE_WARRIOR, UNIT_TYPE_WARRIOR_B,
+};
+use super::{ENEMY_FACTION_ID, entity_at, setup_duel, setup_world_with_level};
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::battle_log::LogEvent;
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::ecs_logic::loader::{parse_skills_toml, reload_skills};
+use board::ecs_logic::query::get_battle_log;
+use board::ecs_logic::skill::execute_skill;
+use board::ecs_logic::turn::{end_current_turn, get_turn_order, resolve_deaths, start_new_round};
+use board::ecs_types::components::{
+    ActionState, Channeling, CurrentHp, Initiative, Occupant, PhysicalAttack, Skills,
+};
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+
+const SKILL_CHARGED_STRIKE: &str = "charged-strike";
+const STRIKE_DAMAGE: i32 = 20;
+
+/// 引導 2 回合的近戰攻擊
+const CHANNEL_SKILLS_TOML: &str = r#"
+[[skills]]
+
+[skills.Active]
+name = "charged-strike"
+tags = []
+cost = 0
+channel_turns = 2
+
+[skills.Active.target]
+range = [1, 1]
+selection = "Unit"
+selectable_filter = "Enemy"
+count = 1
+allow_same_target = false
+area = "Single"
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.HpEffect.scaling]
+source = "Caster"
+source_attribute = "PhysicalAttack"
+value_percent = -100
+"#;
+
+fn hp_of(world: &World, entity: Entity) -> i32 {
+    world.entity(entity).get::<CurrentHp>().expect("應有 HP").0
+}
+
+fn push_skill(world: &mut World, entity: Entity, skill: &str) {
+    world
+        .entity_mut(entity)
+        .get_mut::<Skills>()
+        .expect("應有 Skills")
+        .0
+        .push(skill.to_string());
+}
+
+/// 結束玩家與敵人各一個回合，回到玩家的回合開始
+fn pass_full_round(world: &mut World) {
+    end_current_turn(world).expect("結束玩家回合應成功");
+    end_current_turn(world).expect("結束敵人回合應成功");
+}
+
+#[test]
+fn test_two_turn_channel_resolves_on_schedule() {
+    let mut duel = setup_duel(CHANNEL_SKILLS_TOML, &[SKILL_CHARGED_STRIKE], STRIKE_DAMAGE);
+    let hp_before = hp_of(&duel.world, duel.target);
+
+    let entries = execute_skill(
+        &mut duel.world,
+        &SKILL_CHARGED_STRIKE.to_string(),
+        &[duel.target_pos],
+    )
+    .expect("開始引導應成功");
+    assert!(entries.is_empty(), "引導開始時不應立即結算");
+    assert_eq!(hp_of(&duel.world, duel.target), hp_before);
+
+    // 第 1 個自身回合：仍在引導，不可行動
+    pass_full_round(&mut duel.world);
+    let caster_ref = duel.world.entity(duel.caster);
+    assert_eq!(
+        caster_ref
+            .get::<Channeling>()
+            .expect("應仍在引導")
+            .remaining_turns,
+        1
+    );
+    assert!(
+        matches!(caster_ref.get::<ActionState>(), Some(ActionState::Done)),
+        "引導中的單位本回合不可行動"
+    );
+    assert_eq!(hp_of(&duel.world, duel.target), hp_before);
+
+    // 第 2 個自身回合開始：引導完成並結算
+    pass_full_round(&mut duel.world);
+    assert!(duel.world.entity(duel.caster).get::<Channeling>().is_none());
+    assert_eq!(
+        hp_of(&duel.world, duel.target),
+        hp_before - STRIKE_DAMAGE,
+        "引導完成後應造成傷害"
+    );
+    let logged = get_battle_log(&duel.world)
+        .expect("應有 BattleLog")
+        .iter()
+        .any(|event| {
+            matches!(event, LogEvent::Skill { skill_name, .. } if skill_name == SKILL_CHARGED_STRIKE)
+        });
+    assert!(logged, "引導結算應記錄技能 log");
+    assert!(
+        !matches!(
+            duel.world.entity(duel.caster).get::<ActionState>(),
+            Some(ActionState::Done)
+        ),
+        "引導完成的回合可正常行動"
+    );
+}
+
+#[test]
+fn test_damage_interrupts_channel() {
+    let mut duel = setup_duel(CHANNEL_SKILLS_TOML, &[SKILL_CHARGED_STRIKE], STRIKE_DAMAGE);
+    execute_skill(
+        &mut duel.world,
+        &SKILL_CHARGED_STRIKE.to_string(),
+        &[duel.target_pos],
+    )
+    .expect("開始引導應成功");
+
+    // 敵人回合：攻擊引導中的玩家
+    end_current_turn(&mut duel.world).expect("結束玩家回合應成功");
+    push_skill(&mut duel.world, duel.target, SKILL_RECKLESS_STRIKE);
+    execute_skill(
+        &mut duel.world,
+        &SKILL_RECKLESS_STRIKE.to_string(),
+        &[duel.caster_pos],
+    )
+    .expect("敵人攻擊應成功");
+    assert!(
+        duel.world.entity(duel.caster).get::<Channeling>().is_none(),
+        "受到傷害應中斷引導"
+    );
+
+    // 之後的回合不再結算（敵人 HP 只受自身反噬影響，之後不再變動）
+    let hp_after_attack = hp_of(&duel.world, duel.target);
+    end_current_turn(&mut duel.world).expect("結束敵人回合應成功");
+    pass_full_round(&mut duel.world);
+    assert_eq!(
+        hp_of(&duel.world, duel.target),
+        hp_after_attack,
+        "中斷的引導不應結算"
+    );
+}
+
+/// 最後行動的單位死亡而開新一輪時，先手施放者的引導在回合開始結算；
+/// 引導造成的死亡也須在同一次 resolve_deaths 中移除
+#[test]
+fn test_lethal_channel_completing_inside_resolve_deaths() {
+    let ascii = "P E . D";
+    let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ascii)
+        .unit("P", UNIT_TYPE_WARRIOR_B, PLAYER_FACTION_ID)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .unit("D", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    let (skills, _) = parse_skills_toml(&format!("{SKILLS_TOML}{CHANNEL_SKILLS_TOML}"))
+        .expect("技能 TOML 應可解析");
+    reload_skills(&mut world, skills).expect("reload_skills 應成功");
+
+    // 行動順序固定為 P → E → D（initiative 差距大於擲骰範圍）
+    let caster = entity_at(&mut world, markers["P"][0]);
+    let target = entity_at(&mut world, markers["E"][0]);
+    let last = entity_at(&mut world, markers["D"][0]);
+    world
+        .entity_mut(caster)
+        .insert((Initiative(100), PhysicalAttack(STRIKE_DAMAGE)));
+    world.entity_mut(target).insert(Initiative(50));
+    world.entity_mut(last).insert(Initiative(0));
+    push_skill(&mut world, caster, SKILL_CHARGED_STRIKE);
+    start_new_round(&mut world).expect("start_new_round 應成功");
+
+    let target_occupant = *world
+        .entity(target)
+        .get::<Occupant>()
+        .expect("應有 Occupant");
+    execute_skill(
+        &mut world,
+        &SKILL_CHARGED_STRIKE.to_string(),
+        &[markers["E"][0]],
+    )
+    .expect("開始引導應成功");
+
+    // 第 1 輪剩下 E、D；第 2 輪玩家仍在引導，再輪到 E、D
+    for _ in 0..5 {
+        end_current_turn(&mut world).expect("end_current_turn 應成功");
+    }
+    assert!(
+        world.entity(caster).get::<Channeling>().is_some(),
+        "應仍在引導"
+    );
+
+    // D 死亡 → 開新一輪 → 玩家回合開始時引導結算並擊殺 E
+    world.entity_mut(target).insert(CurrentHp(STRIKE_DAMAGE));
+    world.entity_mut(last).insert(CurrentHp(0));
+    resolve_deaths(&mut world).expect("resolve_deaths 應成功");
+
+    assert!(
+        world.entity(caster).get::<Channeling>().is_none(),
+        "引導應已結算"
+    );
+    assert!(
+        world.get_entity(target).is_err(),
+        "被引導擊殺的目標應被移除"
+    );
+    let turn_order = get_turn_order(&world).expect("應取得 TurnOrder");
+    assert!(
+        turn_order
+            .entries
+            .iter()
+            .all(|entry| entry.occupant != target_occupant),
+        "被引導擊殺的目標應移出回合表"
+    );
+}
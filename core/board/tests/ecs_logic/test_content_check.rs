@@ -0,0 +1,164 @@
+//! 內容檔案引用完整性檢查測試
+
+use super::ENEMY_FACTION_ID;
+use super::constants::{
+    OBJECT_TYPE_WALL, OBJECTS_TOML, SKILL_COMBO_FINISHER, SKILL_SUMMON_WALL_AOE,
+    SKILL_SUMMON_WARRIOR, SKILLS_TOML, UNIT_TYPE_MAGE, UNIT_TYPE_WARRIOR, UNITS_TOML,
+};
+use board::domain::alias::TypeName;
+use board::loader_schema::{LevelType, ObjectsToml, SkillsToml, UnitsToml};
+use board::logic::content_check::{DanglingReference, check_references};
+use board::test_helpers::level_builder::LevelBuilder;
+
+const LEVEL_NAME: &str = "content-check";
+const MISSING: &str = "missing";
+
+/// 測試用內容檔案：一個關卡與測試資料的單位、技能、物件，名冊為 mage
+struct Content {
+    levels: Vec<LevelType>,
+    units_toml: String,
+    skills_toml: String,
+    objects_toml: String,
+    roster: Vec<TypeName>,
+}
+
+fn create_content() -> Content {
+    let mut level = LevelBuilder::from_ascii(
+        "
+        D . E
+        . w .
+        ",
+    )
+    .deploy("D")
+    .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+    .object("w", OBJECT_TYPE_WALL)
+    .to_level()
+    .expect("LevelBuilder::to_level 應成功");
+    level.name = LEVEL_NAME.to_string();
+    Content {
+        levels: vec![level],
+        units_toml: UNITS_TOML.to_string(),
+        skills_toml: SKILLS_TOML.to_string(),
+        objects_toml: OBJECTS_TOML.to_string(),
+        roster: vec![UNIT_TYPE_MAGE.to_string()],
+    }
+}
+
+fn check(content: &Content) -> Vec<DanglingReference> {
+    let units: UnitsToml = toml::from_str(&content.units_toml).expect("單位 TOML 應可解析");
+    let skills: SkillsToml = toml::from_str(&content.skills_toml).expect("技能 TOML 應可解析");
+    let objects: ObjectsToml = toml::from_str(&content.objects_toml).expect("物件 TOML 應可解析");
+    check_references(
+        &content.levels,
+        &units.units,
+        &skills.skills,
+        &objects.objects,
+        &content.roster,
+    )
+}
+
+#[test]
+fn test_check_references_clean_content() {
+    assert_eq!(check(&create_content()), vec![], "測試資料不應有懸空引用");
+}
+
+#[test]
+fn test_check_references_reports_each_broken_file() {
+    type BreakFn = fn(&mut Content);
+    // (情境, 破壞內容的方式, 預期回報)
+    let test_data: [(&str, BreakFn, DanglingReference); 7] = [
+        (
+            "關卡放置不存在的單位",
+            |content| content.levels[0].unit_placements[0].unit_type_name = MISSING.to_string(),
+            DanglingReference::LevelUnitType {
+                level: LEVEL_NAME.to_string(),
+                unit_type: MISSING.to_string(),
+            },
+        ),
+        (
+            "關卡放置不存在的物件",
+            |content| content.levels[0].object_placements[0].object_type_name = MISSING.to_string(),
+            DanglingReference::LevelObjectType {
+                level: LEVEL_NAME.to_string(),
+                object_type: MISSING.to_string(),
+            },
+        ),
+        (
+            "單位引用不存在的技能",
+            |content| {
+                content.units_toml = content
+                    .units_toml
+                    .replace("\"summon-warrior\"", "\"missing\"")
+            },
+            DanglingReference::UnitSkill {
+                unit_type: UNIT_TYPE_MAGE.to_string(),
+                skill: MISSING.to_string(),
+            },
+        ),
+        (
+            "技能的前置技能不存在",
+            |content| {
+                content.skills_toml = content
+                    .skills_toml
+                    .replace("requires = [\"diamond-aoe-1\"]", "requires = [\"missing\"]")
+            },
+            DanglingReference::SkillRequires {
+                skill: SKILL_COMBO_FINISHER.to_string(),
+                required: MISSING.to_string(),
+            },
+        ),
+        (
+            "技能召喚不存在的單位",
+            |content| {
+                content.skills_toml = content
+                    .skills_toml
+                    .replace("unit_type = \"warrior\"", "unit_type = \"missing\"")
+            },
+            DanglingReference::SkillUnitType {
+                skill: SKILL_SUMMON_WARRIOR.to_string(),
+                unit_type: MISSING.to_string(),
+            },
+        ),
+        (
+            "技能生成不存在的物件",
+            |content| {
+                content.skills_toml = content
+                    .skills_toml
+                    .replace("object_type = \"wall\"", "object_type = \"missing\"")
+            },
+            DanglingReference::SkillObjectType {
+                skill: SKILL_SUMMON_WALL_AOE.to_string(),
+                object_type: MISSING.to_string(),
+            },
+        ),
+        (
+            "名冊引用不存在的單位",
+            |content| content.roster.push(MISSING.to_string()),
+            DanglingReference::RosterUnitType {
+                unit_type: MISSING.to_string(),
+            },
+        ),
+    ];
+
+    for (case, break_content, expected) in test_data {
+        let mut content = create_content();
+        break_content(&mut content);
+        assert_eq!(check(&content), vec![expected], "{case}");
+    }
+}
+
+#[test]
+fn test_check_references_reports_once_per_referrer() {
+    let mut content = create_content();
+    let mut extra = content.levels[0].unit_placements[0].clone();
+    extra.unit_type_name = MISSING.to_string();
+    content.levels[0].unit_placements = vec![extra.clone(), extra];
+    assert_eq!(
+        check(&content),
+        vec![DanglingReference::LevelUnitType {
+            level: LEVEL_NAME.to_string(),
+            unit_type: MISSING.to_string(),
+        }],
+        "同一關卡重複放置的懸空單位只回報一次"
+    );
+}
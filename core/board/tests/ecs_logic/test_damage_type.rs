@@ -0,0 +1,144 @@
+//! 傷害屬性與抗性測試
+
+use super::constants::SKILL_RECKLESS_STRIKE;
+use super::{Duel, setup_duel};
+use bevy_ecs::prelude::World;
+use board::domain::core_types::{
+    BuffType, ContinuousEffect, DamageType, EndCondition, StackingPolicy,
+};
+use board::ecs_logic::skill::execute_skill;
+use board::ecs_types::components::{AppliedBuff, CurrentHp, Occupant, Skills};
+
+const SKILL_FIRE_STRIKE: &str = "fire-strike";
+const SKILL_FIRE_WARD: &str = "fire-ward";
+const STRIKE_DAMAGE: i32 = 20;
+
+/// 火屬性近戰攻擊，與火焰抗性 50% 的被動技能
+const FIRE_SKILLS_TOML: &str = r#"
+[[skills]]
+
+[skills.Active]
+name = "fire-strike"
+tags = ["Fire"]
+cost = 0
+
+[skills.Active.target]
+range = [1, 1]
+selection = "Unit"
+selectable_filter = "Enemy"
+count = 1
+allow_same_target = false
+area = "Single"
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.HpEffect]
+damage_type = "Fire"
+
+[skills.Active.effects.Leaf.effect.HpEffect.scaling]
+source = "Caster"
+source_attribute = "PhysicalAttack"
+value_percent = -100
+
+[[skills]]
+
+[skills.Passive]
+name = "fire-ward"
+tags = []
+
+[[skills.Passive.effects]]
+
+[skills.Passive.effects.DamageResistance]
+damage_type = "Fire"
+percent = 50
+"#;
+
+fn spawn_resistance_buff(world: &mut World, target: Occupant, percent: i32) {
+    world.spawn((AppliedBuff {
+        def: BuffType {
+            name: "fire-resistance".to_string(),
+            beneficial: true,
+            stacking: StackingPolicy::Stack,
+            while_active: vec![ContinuousEffect::DamageResistance {
+                damage_type: DamageType::Fire,
+                percent,
+            }],
+            per_turn_effects: vec![],
+            end_conditions: vec![EndCondition::Duration(3)],
+        }
+        .into(),
+        caster: target,
+        target,
+        remaining_duration: Some(3),
+        inherited_defense: None,
+    },));
+}
+
+/// 施放技能並回傳目標失去的 HP
+fn damage_dealt(duel: &mut Duel, skill: &str) -> i32 {
+    let hp_before = duel
+        .world
+        .entity(duel.target)
+        .get::<CurrentHp>()
+        .expect("應有 HP")
+        .0;
+    execute_skill(&mut duel.world, &skill.to_string(), &[duel.target_pos])
+        .expect("execute_skill 應成功");
+    let hp_after = duel
+        .world
+        .entity(duel.target)
+        .get::<CurrentHp>()
+        .expect("應有 HP")
+        .0;
+    hp_before - hp_after
+}
+
+#[test]
+fn test_fire_damage_adjusted_by_resistance() {
+    // (情境, 技能, 目標的火焰抗性 buff, 預期傷害)
+    let test_data = [
+        ("無抗性", SKILL_FIRE_STRIKE, None, STRIKE_DAMAGE),
+        ("火焰抗性 50%", SKILL_FIRE_STRIKE, Some(50), 10),
+        ("火焰弱點 -50%", SKILL_FIRE_STRIKE, Some(-50), 30),
+        ("抗性超過 100% 視為免疫", SKILL_FIRE_STRIKE, Some(150), 0),
+        (
+            "無屬性技能不受火焰抗性影響",
+            SKILL_RECKLESS_STRIKE,
+            Some(50),
+            STRIKE_DAMAGE,
+        ),
+    ];
+
+    for (case, skill, resistance, expected) in test_data {
+        let mut duel = setup_duel(FIRE_SKILLS_TOML, &[SKILL_FIRE_STRIKE], STRIKE_DAMAGE);
+        if let Some(percent) = resistance {
+            let target = *duel
+                .world
+                .entity(duel.target)
+                .get::<Occupant>()
+                .expect("應有 Occupant");
+            spawn_resistance_buff(&mut duel.world, target, percent);
+        }
+        assert_eq!(damage_dealt(&mut duel, skill), expected, "{case}");
+    }
+}
+
+#[test]
+fn test_fire_resistant_passive_reduces_fire_damage() {
+    let mut duel = setup_duel(FIRE_SKILLS_TOML, &[SKILL_FIRE_STRIKE], STRIKE_DAMAGE);
+    duel.world
+        .entity_mut(duel.target)
+        .get_mut::<Skills>()
+        .expect("應有 Skills")
+        .0
+        .push(SKILL_FIRE_WARD.to_string());
+
+    assert_eq!(
+        damage_dealt(&mut duel, SKILL_FIRE_STRIKE),
+        STRIKE_DAMAGE / 2,
+        "被動技能的火焰抗性 50% 應減半火焰傷害"
+    );
+}
@@ -0,0 +1,125 @@
+//! 死亡爆發測試
+
+use super::constants::{UNIT_TYPE_BOMBER, UNIT_TYPE_WARRIOR};
+use super::setup_world_with_level;
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::battle_log::LogEvent;
+use board::ecs_logic::query::get_battle_log;
+use board::ecs_logic::turn::{resolve_deaths, start_new_round};
+use board::ecs_types::components::{CurrentHp, Occupant, Position};
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+use std::collections::HashMap;
+
+/// fixture 中 death-nova-passive 的 HP 變化
+const NOVA_VALUE: i32 = -5;
+
+/// 以 B/C 為 bomber、其餘字母為 warrior 建立 World 並開始回合
+fn build_nova_world(ascii: &str) -> (World, HashMap<String, Vec<Position>>) {
+    let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ascii)
+        .unit("B", UNIT_TYPE_BOMBER, 2)
+        .unit("C", UNIT_TYPE_BOMBER, 2)
+        .unit("P", UNIT_TYPE_WARRIOR, 1)
+        .unit("E", UNIT_TYPE_WARRIOR, 2)
+        .unit("F", UNIT_TYPE_WARRIOR, 2)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    (world, markers)
+}
+
+fn unit_entity_at(world: &mut World, pos: Position) -> Option<Entity> {
+    world
+        .query::<(Entity, &Position, &Occupant)>()
+        .iter(world)
+        .find(|(_, p, occupant)| **p == pos && matches!(occupant, Occupant::Unit(_)))
+        .map(|(entity, _, _)| entity)
+}
+
+fn hp_at(world: &mut World, pos: Position) -> Option<i32> {
+    let entity = unit_entity_at(world, pos)?;
+    world.entity(entity).get::<CurrentHp>().map(|hp| hp.0)
+}
+
+fn set_hp_at(world: &mut World, pos: Position, hp: i32) {
+    let entity = unit_entity_at(world, pos).expect("應找到單位");
+    world.entity_mut(entity).insert(CurrentHp(hp));
+}
+
+/// 佈局：
+/// ```text
+/// P  B  E
+/// .  F  .
+/// .  .  .
+/// ```
+/// B 死亡時，相鄰的 P、E、F 各受一次爆發傷害；不相鄰的格子不受影響
+#[test]
+fn test_death_nova_damages_adjacent_units_once() {
+    let (mut world, markers) = build_nova_world(
+        "
+        P  B  E
+        .  F  .
+        .  .  .
+        ",
+    );
+    let neighbors = [markers["P"][0], markers["E"][0], markers["F"][0]];
+    let hp_before: Vec<i32> = neighbors
+        .iter()
+        .map(|pos| hp_at(&mut world, *pos).expect("應有 HP"))
+        .collect();
+
+    set_hp_at(&mut world, markers["B"][0], 0);
+    resolve_deaths(&mut world).expect("resolve_deaths 應成功");
+
+    for (pos, before) in neighbors.iter().zip(hp_before) {
+        assert_eq!(
+            hp_at(&mut world, *pos),
+            Some(before + NOVA_VALUE),
+            "{pos:?} 應只受一次爆發傷害"
+        );
+    }
+    assert_eq!(hp_at(&mut world, markers["B"][0]), None, "爆發者應被移除");
+
+    let log = get_battle_log(&world).expect("應可取得 BattleLog");
+    let nova_count = log
+        .iter()
+        .filter(|event| matches!(event, LogEvent::DeathNova { .. }))
+        .count();
+    assert_eq!(nova_count, neighbors.len(), "每個相鄰單位應有一筆爆發 log");
+}
+
+/// 佈局：
+/// ```text
+/// B  C  P
+/// ```
+/// B 的爆發擊殺 C；C 一併移除但不再爆發，因此 P 不受傷害
+#[test]
+fn test_death_nova_does_not_chain() {
+    let (mut world, markers) = build_nova_world("B C P");
+    let player_pos = markers["P"][0];
+    let player_hp = hp_at(&mut world, player_pos).expect("應有 HP");
+
+    set_hp_at(&mut world, markers["C"][0], -NOVA_VALUE);
+    set_hp_at(&mut world, markers["B"][0], 0);
+    resolve_deaths(&mut world).expect("resolve_deaths 應成功");
+
+    assert_eq!(hp_at(&mut world, markers["B"][0]), None, "B 應被移除");
+    assert_eq!(
+        hp_at(&mut world, markers["C"][0]),
+        None,
+        "被波及致死的 C 應被移除"
+    );
+    assert_eq!(
+        hp_at(&mut world, player_pos),
+        Some(player_hp),
+        "C 不應觸發連鎖爆發"
+    );
+
+    let log = get_battle_log(&world).expect("應可取得 BattleLog");
+    let deaths = log
+        .iter()
+        .filter(|event| matches!(event, LogEvent::Death { .. }))
+        .count();
+    assert_eq!(deaths, 2, "應有 B、C 兩筆死亡 log");
+}
@@ -0,0 +1,100 @@
+//! 壓力測試調試工具（jitter_units）測試
+
+use super::constants::{UNIT_TYPE_MAGE, UNIT_TYPE_WARRIOR};
+use super::{ENEMY_FACTION_ID, setup_world_with_level};
+use bevy_ecs::prelude::{With, World};
+use board::ecs_logic::debug_tools::jitter_units;
+use board::ecs_logic::deployment::deploy_unit;
+use board::ecs_types::components::{CurrentHp, MaxHp, Position, Unit};
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::collections::HashSet;
+
+const SEEDS: u64 = 20;
+const HP_PCT: u32 = 40;
+const BOARD: &str = "
+D D D . .
+D D . . E
+";
+
+/// 部署兩名 mage 到前兩個部署點，E 為關卡預放的敵方 warrior
+fn build_world() -> (World, HashSet<Position>, Position) {
+    let (_, markers) = load_from_ascii(BOARD).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(BOARD)
+        .deploy("D")
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    for pos in &markers["D"][..2] {
+        deploy_unit(&mut world, &UNIT_TYPE_MAGE.to_string(), *pos).expect("deploy_unit 應成功");
+    }
+    let deployment: HashSet<Position> = markers["D"].iter().copied().collect();
+    (world, deployment, markers["E"][0])
+}
+
+/// 所有單位的 (位置, 目前 HP, 最大 HP)，依座標排序
+fn unit_states(world: &mut World) -> Vec<(Position, i32, i32)> {
+    let mut states: Vec<(Position, i32, i32)> = world
+        .query_filtered::<(&Position, &CurrentHp, &MaxHp), With<Unit>>()
+        .iter(world)
+        .map(|(pos, hp, max_hp)| (*pos, hp.0, max_hp.0))
+        .collect();
+    states.sort_by_key(|(pos, _, _)| (pos.y, pos.x));
+    states
+}
+
+#[test]
+fn test_jitter_units_hp_within_bounds() {
+    for seed in 0..SEEDS {
+        let (mut world, _, _) = build_world();
+        let mut rng = StdRng::seed_from_u64(seed);
+        jitter_units(&mut world, &mut rng, HP_PCT, false).expect("jitter_units 應成功");
+
+        for (pos, hp, max_hp) in unit_states(&mut world) {
+            let min_hp = (max_hp * (100 - HP_PCT as i32) / 100).max(1);
+            assert!(
+                (min_hp..=max_hp).contains(&hp),
+                "seed {seed}：{pos:?} 的 HP {hp} 應在 [{min_hp}, {max_hp}]"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_jitter_units_reposition_stays_on_deployment_tiles() {
+    for seed in 0..SEEDS {
+        let (mut world, deployment, enemy_pos) = build_world();
+        let mut rng = StdRng::seed_from_u64(seed);
+        jitter_units(&mut world, &mut rng, 0, true).expect("jitter_units 應成功");
+
+        let states = unit_states(&mut world);
+        let positions: HashSet<Position> = states.iter().map(|(pos, _, _)| *pos).collect();
+        assert_eq!(positions.len(), 3, "seed {seed}：單位不應重疊");
+        assert!(
+            positions.contains(&enemy_pos),
+            "seed {seed}：預放單位不應移動"
+        );
+        let deployed = positions
+            .iter()
+            .filter(|pos| deployment.contains(pos))
+            .count();
+        assert_eq!(deployed, 2, "seed {seed}：部署單位應仍在部署點上");
+        assert!(
+            states.iter().all(|(_, hp, max_hp)| hp == max_hp),
+            "seed {seed}：hp_pct 為 0 時 HP 應維持滿值"
+        );
+    }
+}
+
+#[test]
+fn test_jitter_units_same_seed_same_result() {
+    let run = |seed| {
+        let (mut world, _, _) = build_world();
+        let mut rng = StdRng::seed_from_u64(seed);
+        jitter_units(&mut world, &mut rng, HP_PCT, true).expect("jitter_units 應成功");
+        unit_states(&mut world)
+    };
+    assert_eq!(run(7), run(7), "相同種子應得到相同結果");
+}
@@ -0,0 +1,100 @@
+//! 全點對距離場快取測試
+
+use super::build_warrior_world;
+use bevy_ecs::prelude::{Entity, With, World};
+use board::domain::constants::MAX_DISTANCE_FIELD_TILES;
+use board::ecs_logic::movement::{get_distance_field, measure_between};
+use board::ecs_types::components::{Object, Position};
+use board::ecs_types::resources::Board;
+use board::error::{BoardError, ErrorKind};
+
+/// 牆（w）、懸崖（c）與沼澤（p）混合的小棋盤
+const BOARD: &str = "
+. . . . .
+P . w . T
+. c w . .
+. . p . .
+";
+
+/// 逐一比對距離場與即時 Dijkstra（measure_between）的每一組兩兩距離
+fn assert_matches_on_demand(world: &mut World, case: &str) {
+    let board = *world.resource::<Board>();
+    let positions: Vec<Position> = (0..board.height)
+        .flat_map(|y| (0..board.width).map(move |x| Position { x, y }))
+        .collect();
+    for &from in &positions {
+        for &to in &positions {
+            let expected = measure_between(world, from, to)
+                .expect("測距應成功")
+                .movement_cost;
+            let cached = get_distance_field(world)
+                .expect("get_distance_field 應成功")
+                .distance(from, to);
+            assert_eq!(cached, expected, "[{case}] {from:?} → {to:?} 距離不符");
+        }
+    }
+}
+
+#[test]
+fn test_distance_field_matches_on_demand_dijkstra() {
+    let (mut world, _, _) = build_warrior_world(BOARD);
+    assert_matches_on_demand(&mut world, "初始地形");
+
+    let field = get_distance_field(&mut world).expect("get_distance_field 應成功");
+    assert_eq!(
+        field.distance(Position { x: 0, y: 0 }, Position { x: 99, y: 0 }),
+        None,
+        "棋盤外的格子應回傳 None"
+    );
+}
+
+#[test]
+fn test_distance_field_rebuilds_after_terrain_edit() {
+    let (mut world, _, markers) = build_warrior_world(BOARD);
+    let from = markers["P"][0];
+    let to = markers["T"][0];
+    let before = get_distance_field(&mut world)
+        .expect("get_distance_field 應成功")
+        .distance(from, to);
+
+    // 拆掉 P 與 T 之間的牆
+    let wall = markers["w"][0];
+    let wall_entity = world
+        .query_filtered::<(Entity, &Position), With<Object>>()
+        .iter(&world)
+        .find(|(_, pos)| **pos == wall)
+        .map(|(entity, _)| entity)
+        .expect("應找到牆");
+    world.despawn(wall_entity);
+
+    let after = get_distance_field(&mut world)
+        .expect("get_distance_field 應成功")
+        .distance(from, to);
+    assert!(
+        after < before,
+        "拆牆後距離應縮短：before {before:?}, after {after:?}"
+    );
+    assert_matches_on_demand(&mut world, "拆牆後");
+}
+
+#[test]
+fn test_distance_field_rejects_oversized_board() {
+    // 邊長取剛好超過格數上限的正方形
+    let side = (1..)
+        .find(|side| side * side > MAX_DISTANCE_FIELD_TILES)
+        .expect("應找到邊長");
+    let row = vec!["."; side].join(" ");
+    let mut rows = vec![row; side];
+    rows[0] = format!("P{}", &rows[0][1..]);
+    let (mut world, _, _) = build_warrior_world(&rows.join("\n"));
+
+    let error = get_distance_field(&mut world).expect_err("超過格數上限的棋盤應拒絕建立距離場");
+    assert!(
+        matches!(
+            error.kind(),
+            ErrorKind::Board(BoardError::DistanceFieldTooLarge { .. })
+        ),
+        "應為 DistanceFieldTooLarge，實際為 {:?}",
+        error.kind()
+    );
+}
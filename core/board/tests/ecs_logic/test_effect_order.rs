@@ -0,0 +1,202 @@
+//! 技能效果套用順序測試
+
+use super::constants::{
+    SKILL_PULL_THEN_STRIKE, SKILL_STRIKE_THEN_PULL, SKILLS_TOML, UNIT_TYPE_WARRIOR,
+    UNIT_TYPE_WARRIOR_B,
+};
+use super::setup_world_with_level;
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::ecs_logic::loader::{parse_skills_toml, reload_skills};
+use board::ecs_logic::skill::{execute_skill, preview_skill_effect};
+use board::ecs_logic::turn::start_new_round;
+use board::ecs_types::components::{CurrentHp, Initiative, PhysicalAttack, Position, Skills};
+use board::logic::skill::skill_execution::ResolvedEffect;
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+use std::collections::HashMap;
+
+const ENEMY_FACTION_ID: u32 = 2;
+const SKILL_DOUBLE_HOOK: &str = "double-hook";
+
+/// 兩段攻擊：每段打擊後拉動 1 格
+const DOUBLE_HOOK_TOML: &str = r#"
+[[skills]]
+
+[skills.Active]
+name = "double-hook"
+tags = []
+cost = 0
+hits = 2
+
+[skills.Active.target]
+range = [3, 3]
+selection = "Unit"
+selectable_filter = "Enemy"
+count = 1
+allow_same_target = false
+area = "Single"
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.HpEffect.scaling]
+source = "Caster"
+source_attribute = "PhysicalAttack"
+value_percent = -100
+
+[[skills.Active.effects]]
+
+[skills.Active.effects.Leaf]
+who = "Target"
+
+[skills.Active.effects.Leaf.effect.Pull]
+distance = 1
+"#;
+
+/// P 為持有拉擊技能的 warrior-b（先攻），E 為敵方 warrior
+fn build_pull_world(ascii: &str) -> (World, Entity, HashMap<String, Vec<Position>>) {
+    let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ascii)
+        .unit("P", UNIT_TYPE_WARRIOR_B, PLAYER_FACTION_ID)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    let caster = world
+        .query::<(Entity, &Position)>()
+        .iter(&world)
+        .find(|(_, pos)| **pos == markers["P"][0])
+        .map(|(entity, _)| entity)
+        .expect("應找到玩家單位");
+    world.entity_mut(caster).insert(Initiative(100));
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let enemy = world
+        .query::<(Entity, &Position)>()
+        .iter(&world)
+        .find(|(_, pos)| **pos == markers["E"][0])
+        .map(|(entity, _)| entity)
+        .expect("應找到敵方單位");
+    (world, enemy, markers)
+}
+
+fn read_hp_and_position(world: &World, entity: Entity) -> (i32, Position) {
+    let entity_ref = world.entity(entity);
+    (
+        entity_ref.get::<CurrentHp>().expect("應有 HP").0,
+        *entity_ref.get::<Position>().expect("應有位置"),
+    )
+}
+
+/// E 被朝 P 拉近一格後的位置
+fn pulled_position(markers: &HashMap<String, Vec<Position>>) -> Position {
+    let enemy = markers["E"][0];
+    Position {
+        x: enemy.x - 1,
+        y: enemy.y,
+    }
+}
+
+#[test]
+fn test_as_listed_strikes_before_pull() {
+    let (mut world, enemy, markers) = build_pull_world("P . E");
+    let (hp_before, _) = read_hp_and_position(&world, enemy);
+
+    execute_skill(
+        &mut world,
+        &SKILL_STRIKE_THEN_PULL.to_string(),
+        &[markers["E"][0]],
+    )
+    .expect("execute_skill 應成功");
+
+    let (hp_after, pos_after) = read_hp_and_position(&world, enemy);
+    assert!(hp_after < hp_before, "打擊應在拉動前命中原位置的目標");
+    assert_eq!(pos_after, pulled_position(&markers), "目標應被拉近一格");
+}
+
+#[test]
+fn test_movement_first_strikes_after_pull() {
+    let (mut world, enemy, markers) = build_pull_world("P . E");
+    let (hp_before, _) = read_hp_and_position(&world, enemy);
+
+    let entries = execute_skill(
+        &mut world,
+        &SKILL_PULL_THEN_STRIKE.to_string(),
+        &[markers["E"][0]],
+    )
+    .expect("execute_skill 應成功");
+
+    let order: Vec<bool> = entries
+        .iter()
+        .map(|entry| matches!(entry.effect, ResolvedEffect::Pull { .. }))
+        .collect();
+    assert_eq!(order, vec![true, false], "拉動應排在打擊之前結算");
+    let (hp_after, pos_after) = read_hp_and_position(&world, enemy);
+    assert_eq!(pos_after, pulled_position(&markers), "目標應被拉近一格");
+    assert!(
+        hp_after < hp_before,
+        "拉動先套用後，打擊應跟著目標落在拉動後的位置"
+    );
+}
+
+#[test]
+fn test_preview_follows_effect_order() {
+    let (mut world, enemy, markers) = build_pull_world("P . E");
+    let before = read_hp_and_position(&world, enemy);
+
+    let entries = preview_skill_effect(
+        &mut world,
+        &SKILL_PULL_THEN_STRIKE.to_string(),
+        &[markers["E"][0]],
+    )
+    .expect("preview_skill_effect 應成功");
+
+    let order: Vec<bool> = entries
+        .iter()
+        .map(|entry| matches!(entry.effect, ResolvedEffect::Pull { .. }))
+        .collect();
+    assert_eq!(order, vec![true, false], "預覽也應將拉動排在打擊之前");
+    assert_eq!(
+        read_hp_and_position(&world, enemy),
+        before,
+        "預覽不應改變目標"
+    );
+}
+
+#[test]
+fn test_multi_hit_repeats_damage_but_pulls_once() {
+    let (mut world, enemy, markers) = build_pull_world("P . . E");
+    let (skills, _) =
+        parse_skills_toml(&format!("{SKILLS_TOML}{DOUBLE_HOOK_TOML}")).expect("技能 TOML 應可解析");
+    reload_skills(&mut world, skills).expect("reload_skills 應成功");
+    let caster = world
+        .query::<(Entity, &Position)>()
+        .iter(&world)
+        .find(|(_, pos)| **pos == markers["P"][0])
+        .map(|(entity, _)| entity)
+        .expect("應找到玩家單位");
+    let mut caster_mut = world.entity_mut(caster);
+    let attack = caster_mut.get::<PhysicalAttack>().expect("應有物攻").0;
+    caster_mut
+        .get_mut::<Skills>()
+        .expect("應有 Skills")
+        .0
+        .push(SKILL_DOUBLE_HOOK.to_string());
+    let (hp_before, _) = read_hp_and_position(&world, enemy);
+
+    execute_skill(
+        &mut world,
+        &SKILL_DOUBLE_HOOK.to_string(),
+        &[markers["E"][0]],
+    )
+    .expect("execute_skill 應成功");
+
+    let (hp_after, pos_after) = read_hp_and_position(&world, enemy);
+    assert_eq!(hp_after, hp_before - attack * 2, "兩段打擊都應命中");
+    assert_eq!(
+        pos_after,
+        pulled_position(&markers),
+        "位移只在第一段套用，目標只被拉近一格"
+    );
+}
@@ -1,13 +1,15 @@
 //! 關卡結局判定整合測試（透過 world 建關卡驗證結局）
 
-use super::constants::{OBJECTS_TOML, SKILLS_TOML, UNIT_TYPE_WARRIOR, UNITS_TOML};
+use super::constants::{
+    OBJECT_TYPE_OBJECTIVE, OBJECTS_TOML, SKILLS_TOML, UNIT_TYPE_WARRIOR, UNITS_TOML,
+};
 use bevy_ecs::prelude::{Entity, World};
 use board::domain::constants::PLAYER_FACTION_ID;
 use board::domain::core_types::{EndLevelCondition, LevelOutcome};
-use board::ecs_logic::level_outcome::resolve_level_outcome;
+use board::ecs_logic::level_outcome::{get_capture_progress, resolve_level_outcome};
 use board::ecs_logic::loader::parse_and_insert_game_data;
 use board::ecs_logic::spawner::spawn_level;
-use board::ecs_logic::turn::{resolve_deaths, start_new_round};
+use board::ecs_logic::turn::{end_current_turn, get_turn_order, resolve_deaths, start_new_round};
 use board::ecs_types::components::{CurrentHp, Position};
 use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
 use std::collections::HashMap;
@@ -223,3 +225,178 @@ fn test_resolve_level_outcome_without_spawn_returns_error() {
     let result = resolve_level_outcome(&mut world);
     assert!(result.is_err(), "未先 spawn_level 時應回傳錯誤");
 }
+
+/// 建立雙方都不行動的僵局關卡（只結束回合），可設定回合上限
+fn build_deadlock_world(max_rounds: Option<u32>) -> World {
+    let builder = LevelBuilder::from_ascii(LEVEL_ASCII)
+        .unit(PLAYER_MARKER, UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .unit(ALLY_MARKER, UNIT_TYPE_WARRIOR, ALLY_FACTION_ID)
+        .unit(ENEMY_MARKER, UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .victory_conditions(vec![(
+            VICTORY_KEY.to_string(),
+            vec![EndLevelCondition::EliminateFaction(ENEMY_FACTION_ID)],
+        )]);
+    let level_toml = match max_rounds {
+        Some(max_rounds) => builder.max_rounds(max_rounds),
+        None => builder,
+    }
+    .to_toml()
+    .expect("LevelBuilder::to_toml 應成功");
+
+    let mut world = World::new();
+    parse_and_insert_game_data(&mut world, UNITS_TOML, SKILLS_TOML, OBJECTS_TOML)
+        .expect("parse_and_insert_game_data 應成功");
+    spawn_level(&mut world, &level_toml, "test-level").expect("spawn_level 應成功");
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    world
+}
+
+/// 持續結束回合直到結局確定或達到 round_limit 輪，回傳 (結局, 當前輪數)
+fn end_turns_until_outcome(world: &mut World, round_limit: u32) -> (LevelOutcome, u32) {
+    loop {
+        let outcome = resolve_level_outcome(world).expect("resolve_level_outcome 應成功");
+        let round = get_turn_order(world).expect("應有回合順序").round;
+        if outcome != LevelOutcome::Undetermined || round > round_limit {
+            return (outcome, round);
+        }
+        end_current_turn(world).expect("end_current_turn 應成功");
+    }
+}
+
+#[test]
+fn test_deadlock_ends_in_draw_at_round_cap() {
+    let mut world = build_deadlock_world(Some(3));
+    let (outcome, round) = end_turns_until_outcome(&mut world, 100);
+    assert_eq!(outcome, LevelOutcome::Draw { max_rounds: 3 });
+    assert_eq!(round, 4, "第 3 輪結束、進入第 4 輪時判定平手");
+}
+
+#[test]
+fn test_deadlock_without_round_cap_stays_undetermined() {
+    let mut world = build_deadlock_world(None);
+    let (outcome, round) = end_turns_until_outcome(&mut world, 10);
+    assert_eq!(outcome, LevelOutcome::Undetermined);
+    assert_eq!(round, 11);
+}
+
+#[test]
+fn test_victory_takes_precedence_over_round_cap() {
+    let (_, markers) = load_from_ascii(LEVEL_ASCII).expect("load_from_ascii 應成功");
+    let mut world = build_deadlock_world(Some(1));
+    end_turns_until_outcome(&mut world, 100);
+
+    kill_unit_at(&mut world, marker_position(&markers, ENEMY_MARKER));
+    resolve_deaths(&mut world).expect("resolve_deaths 應成功");
+    assert_eq!(
+        resolve_level_outcome(&mut world).expect("resolve_level_outcome 應成功"),
+        LevelOutcome::Victory(VICTORY_KEY.to_string()),
+        "超過回合上限時同時達成勝利條件，應判定勝利"
+    );
+}
+
+/// 佔領目標點佈局：O 為兩格目標點（同一個佔領區）
+const CAPTURE_ASCII: &str = "
+    P . O O
+    . . . E
+";
+
+/// 建立 P（玩家）與 E（敵人）的佔領關卡：玩家佔領進度達 rounds 輪即勝利
+fn build_capture_world(rounds: u32) -> (World, HashMap<String, Vec<Position>>) {
+    let (_, markers) = load_from_ascii(CAPTURE_ASCII).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(CAPTURE_ASCII)
+        .unit(PLAYER_MARKER, UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .unit(ENEMY_MARKER, UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .object("O", OBJECT_TYPE_OBJECTIVE)
+        .victory_conditions(vec![(
+            VICTORY_KEY.to_string(),
+            vec![EndLevelCondition::HoldObjective {
+                faction_id: PLAYER_FACTION_ID,
+                rounds,
+            }],
+        )])
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+
+    let mut world = World::new();
+    parse_and_insert_game_data(&mut world, UNITS_TOML, SKILLS_TOML, OBJECTS_TOML)
+        .expect("parse_and_insert_game_data 應成功");
+    spawn_level(&mut world, &level_toml, "test-level").expect("spawn_level 應成功");
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    (world, markers)
+}
+
+/// 直接把 from 上的單位放到 to（略過移動規則，只為擺出佔領局面）
+fn place_unit(world: &mut World, from: Position, to: Position) {
+    let mut query = world.query::<&mut Position>();
+    let mut pos = query
+        .iter_mut(world)
+        .find(|p| **p == from)
+        .expect("應找到指定位置的單位");
+    *pos = to;
+}
+
+/// 讓本輪所有單位結束回合，回傳進入下一輪後的結局與玩家佔領進度
+fn finish_round(world: &mut World) -> (LevelOutcome, u32) {
+    let round = get_turn_order(world).expect("應有回合順序").round;
+    while get_turn_order(world).expect("應有回合順序").round == round {
+        end_current_turn(world).expect("end_current_turn 應成功");
+    }
+    let progress = get_capture_progress(world)
+        .expect("get_capture_progress 應成功")
+        .get(&PLAYER_FACTION_ID)
+        .copied()
+        .unwrap_or(0);
+    let outcome = resolve_level_outcome(world).expect("resolve_level_outcome 應成功");
+    (outcome, progress)
+}
+
+#[test]
+fn test_hold_objective_accrues_progress_until_threshold() {
+    let (mut world, markers) = build_capture_world(2);
+    place_unit(
+        &mut world,
+        marker_position(&markers, PLAYER_MARKER),
+        markers["O"][0],
+    );
+
+    assert_eq!(finish_round(&mut world), (LevelOutcome::Undetermined, 1));
+    assert_eq!(
+        finish_round(&mut world),
+        (LevelOutcome::Victory(VICTORY_KEY.to_string()), 2),
+        "無人爭奪時每輪累積 1，達門檻即勝利"
+    );
+}
+
+#[test]
+fn test_hold_objective_contested_zone_stalls_progress() {
+    let (mut world, markers) = build_capture_world(2);
+    let player_start = marker_position(&markers, PLAYER_MARKER);
+    let enemy_start = marker_position(&markers, ENEMY_MARKER);
+    place_unit(&mut world, player_start, markers["O"][0]);
+
+    assert_eq!(finish_round(&mut world), (LevelOutcome::Undetermined, 1));
+
+    // 敵人也進入佔領區：爭奪中，進度暫停但不歸零
+    place_unit(&mut world, enemy_start, markers["O"][1]);
+    for _ in 0..3 {
+        assert_eq!(
+            finish_round(&mut world),
+            (LevelOutcome::Undetermined, 1),
+            "雙方同時在佔領區時進度不應增加"
+        );
+    }
+    assert_eq!(
+        get_capture_progress(&world)
+            .expect("get_capture_progress 應成功")
+            .get(&ENEMY_FACTION_ID),
+        None,
+        "爭奪中敵方也不累積"
+    );
+
+    // 敵人離開後恢復累積
+    place_unit(&mut world, markers["O"][1], enemy_start);
+    assert_eq!(
+        finish_round(&mut world),
+        (LevelOutcome::Victory(VICTORY_KEY.to_string()), 2)
+    );
+}
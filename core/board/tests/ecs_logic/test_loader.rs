@@ -1,9 +1,11 @@
 use super::constants::{
     OBJECT_TYPE_SPIKE, OBJECT_TYPE_SWAMP, OBJECT_TYPE_WALL, OBJECTS_TOML, SKILL_MELEE,
-    SKILL_WARRIOR, SKILLS_TOML, UNIT_TYPE_MAGE, UNIT_TYPE_WARRIOR, UNITS_TOML,
+    SKILL_PULL_THEN_STRIKE, SKILL_STRIKE_THEN_PULL, SKILL_WARRIOR, SKILLS_TOML, UNIT_TYPE_MAGE,
+    UNIT_TYPE_WARRIOR, UNITS_TOML,
 };
 use bevy_ecs::prelude::World;
-use board::ecs_logic::loader::parse_and_insert_game_data;
+use board::domain::core_types::{Effect, EffectNode, SkillType};
+use board::ecs_logic::loader::{SkippedEffect, parse_and_insert_game_data};
 use board::ecs_types::resources::GameData;
 
 #[test]
@@ -21,7 +23,7 @@ fn test_parse_and_insert_game_data_sets_resource() {
     assert!(game_data.is_some(), "GameData resource 應已存入 World");
 
     let game_data = game_data.expect("GameData resource 應已存入 World");
-    assert_eq!(game_data.skill_map.len(), 10, "skill_map 應包含 10 個技能");
+    assert_eq!(game_data.skill_map.len(), 17, "skill_map 應包含 17 個技能");
     assert!(
         game_data.skill_map.contains_key(SKILL_WARRIOR),
         "skill_map 應包含 {SKILL_WARRIOR}"
@@ -32,8 +34,8 @@ fn test_parse_and_insert_game_data_sets_resource() {
     );
     assert_eq!(
         game_data.unit_type_map.len(),
-        4,
-        "unit_type_map 應包含 4 個單位類型"
+        6,
+        "unit_type_map 應包含 6 個單位類型"
     );
     assert!(
         game_data.unit_type_map.contains_key(UNIT_TYPE_WARRIOR),
@@ -45,8 +47,8 @@ fn test_parse_and_insert_game_data_sets_resource() {
     );
     assert_eq!(
         game_data.object_type_map.len(),
-        4,
-        "object_type_map 應包含 4 個物件類型"
+        9,
+        "object_type_map 應包含 9 個物件類型"
     );
     assert!(
         game_data.object_type_map.contains_key(OBJECT_TYPE_WALL),
@@ -61,3 +63,52 @@ fn test_parse_and_insert_game_data_sets_resource() {
         "object_type_map 應包含 {OBJECT_TYPE_SWAMP}"
     );
 }
+
+#[test]
+fn test_parse_and_insert_game_data_skips_unknown_effect() {
+    // 模擬新版資料檔：拉動效果換成目前版本不認識的效果類型
+    let skills_toml = SKILLS_TOML.replace(".effect.Pull]", ".effect.Levitate]");
+    let mut world = World::new();
+
+    let skipped = parse_and_insert_game_data(&mut world, UNITS_TOML, &skills_toml, OBJECTS_TOML)
+        .expect("含未知效果的技能檔仍應載入");
+
+    assert_eq!(
+        skipped,
+        vec![
+            SkippedEffect {
+                skill: SKILL_STRIKE_THEN_PULL.to_string(),
+                effect_type: "Levitate".to_string(),
+            },
+            SkippedEffect {
+                skill: SKILL_PULL_THEN_STRIKE.to_string(),
+                effect_type: "Levitate".to_string(),
+            },
+        ],
+        "應回報每個被略過的未知效果"
+    );
+
+    let game_data = world
+        .get_resource::<GameData>()
+        .expect("GameData resource 應已存入 World");
+    assert_eq!(game_data.skill_map.len(), 17, "所有技能都應載入");
+    let skill = game_data
+        .skill_map
+        .get(SKILL_STRIKE_THEN_PULL)
+        .expect("含未知效果的技能應保留");
+    let effects = match skill {
+        SkillType::Active { effects, .. } => effects,
+        _ => panic!("{SKILL_STRIKE_THEN_PULL} 應為主動技能"),
+    };
+    assert_eq!(effects.len(), 1, "只略過未知效果的葉節點");
+    assert!(
+        matches!(
+            &effects[0],
+            EffectNode::Leaf {
+                effect: Effect::HpEffect { .. },
+                ..
+            }
+        ),
+        "已知效果應保留"
+    );
+}
@@ -0,0 +1,65 @@
+//! 測距工具整合測試
+
+use super::build_warrior_world;
+use board::domain::constants::BASIC_MOVEMENT_COST;
+use board::ecs_logic::movement::{TileMeasurement, measure_between};
+
+/// 牆（w）擋在直線上：距離以直線計，移動成本需繞路；沼澤（p）額外加成本
+#[test]
+fn test_measure_around_wall() {
+    let (mut world, _, markers) = build_warrior_world(
+        "
+        . . . . .
+        P . w . T
+        . . w . .
+        . . p . .
+        ",
+    );
+    let from = markers["P"][0];
+    let to = markers["T"][0];
+
+    let measurement = measure_between(&mut world, from, to).expect("測距應成功");
+
+    // 最短路徑從上方繞過牆：上 1、右 4、下 1，共 6 步
+    assert_eq!(
+        measurement,
+        TileMeasurement {
+            chebyshev: 4,
+            manhattan: 4,
+            movement_cost: Some(6 * BASIC_MOVEMENT_COST),
+        }
+    );
+}
+
+/// 完全被牆圍住的目標無法抵達，但仍回報直線距離
+#[test]
+fn test_measure_unreachable() {
+    let (mut world, _, markers) = build_warrior_world(
+        "
+        P . w . .
+        . . w T w
+        . . w w w
+        ",
+    );
+    let from = markers["P"][0];
+    let to = markers["T"][0];
+
+    let measurement = measure_between(&mut world, from, to).expect("測距應成功");
+
+    assert_eq!(measurement.chebyshev, 3);
+    assert_eq!(measurement.manhattan, 4);
+    assert_eq!(measurement.movement_cost, None);
+}
+
+/// 同一格距離與成本皆為 0
+#[test]
+fn test_measure_same_tile() {
+    let (mut world, _, markers) = build_warrior_world("P . .");
+    let pos = markers["P"][0];
+
+    let measurement = measure_between(&mut world, pos, pos).expect("測距應成功");
+
+    assert_eq!(measurement.chebyshev, 0);
+    assert_eq!(measurement.manhattan, 0);
+    assert_eq!(measurement.movement_cost, Some(0));
+}
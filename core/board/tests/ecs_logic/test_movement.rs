@@ -1,11 +1,16 @@
-//! execute_move 整合測試
+//! 移動整合測試（execute_move 與地形阻擋）
 
+use super::constants::{OBJECT_TYPE_SPIKE, UNIT_TYPE_WARRIOR};
+use super::setup_world_with_level;
 use bevy_ecs::world::World;
-use board::domain::constants::BASIC_MOVEMENT_COST;
-use board::ecs_logic::movement::{AdvanceMoveResult, advance_move, plan_move};
+use board::domain::constants::{BASIC_MOVEMENT_COST, PLAYER_FACTION_ID};
+use board::ecs_logic::movement::{
+    AdvanceMoveResult, advance_move, get_nearest_reachable, get_reachable_positions, plan_move,
+    preview_move_path,
+};
 use board::ecs_logic::turn::{end_current_turn, start_new_round};
 use board::ecs_types::components::{Occupant, Position};
-use board::test_helpers::level_builder::load_from_ascii;
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
 
 /// 從 ASCII 建構 World 並回傳玩家單位 Occupant 與目的地座標
 ///
@@ -109,7 +114,7 @@ P A T . .
         (
             "穿過低移動消耗物件-右方",
             r#"
-P p T . .
+P s T . .
 . . . . .
 . . . . ."#,
             vec![(0, 0), (1, 0), (2, 0)],
@@ -119,7 +124,7 @@ P p T . .
             "穿過低移動消耗物件-下方",
             r#"
 . . P . .
-. . p . .
+. . s . .
 . . T . ."#,
             vec![(2, 0), (2, 1), (2, 2)],
             BASIC_MOVEMENT_COST * 2 + 10,
@@ -127,8 +132,8 @@ P p T . .
         (
             "穿過低移動消耗物件-繞路",
             r#"
-. p p p .
-P p p p T
+. s s s .
+P s s s T
 . . . . ."#,
             vec![(0, 1), (0, 2), (1, 2), (2, 2), (3, 2), (4, 2), (4, 1)],
             BASIC_MOVEMENT_COST * 6,
@@ -265,3 +270,117 @@ w . . . T
         assert!(result.is_err(), "Case '{}' 應回傳錯誤", desc);
     }
 }
+
+/// 朝下的懸崖（c）：可從崖頂往下跳，但不能從崖下攀回同一格
+#[test]
+fn test_cliff_descend_but_not_climb() {
+    let (mut world, occupant, markers) = super::build_warrior_world(
+        "
+        w P w
+        w c w
+        w T w
+        ",
+    );
+    let reachable = get_reachable_positions(&mut world, occupant).expect("計算可到達位置應成功");
+    assert!(reachable.contains_key(&markers["c"][0]), "從上方可進入崖頂");
+    assert!(reachable.contains_key(&markers["T"][0]), "可從崖頂跳下");
+
+    let (mut world, occupant, markers) = super::build_warrior_world(
+        "
+        w T w
+        w c w
+        w P w
+        ",
+    );
+    let reachable = get_reachable_positions(&mut world, occupant).expect("計算可到達位置應成功");
+    assert!(
+        !reachable.contains_key(&markers["c"][0]),
+        "不可從崖下攀上崖頂"
+    );
+    assert!(
+        !reachable.contains_key(&markers["T"][0]),
+        "崖頂以上無法抵達"
+    );
+}
+
+/// 多個目標時取路徑成本最低者：T1 直線較近但被牆隔開
+#[test]
+fn test_get_nearest_reachable_by_path_cost() {
+    let (mut world, occupant, targets) = build_world(
+        r#"
+P w T1
+. w .
+. w .
+T2 . ."#,
+    );
+    let (goal, cost, path) = get_nearest_reachable(&mut world, occupant, &targets)
+        .expect("get_nearest_reachable 應成功")
+        .expect("應找到可抵達的目標");
+    assert_eq!(goal, targets[1], "應選擇繞路成本較低的 T2");
+    assert_eq!(cost, BASIC_MOVEMENT_COST * 3);
+    assert_eq!(path.first(), Some(&Position { x: 0, y: 0 }));
+    assert_eq!(path.last(), Some(&targets[1]));
+}
+
+// ============================================================================
+// 危險地面繞路
+// ============================================================================
+
+/// 尖刺（x）為不增加移動消耗的危險地面：預算內有安全繞路時繞開，別無選擇時才踩上去
+#[test]
+fn test_move_path_avoids_hazard_when_possible() {
+    let test_data = [
+        (
+            "多走兩格即可繞開，改走安全路線",
+            r#"
+P x T
+. . ."#,
+            vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)],
+            BASIC_MOVEMENT_COST * 4,
+        ),
+        (
+            "沒有其他路線，只能踩上危險地面",
+            "P x T",
+            vec![(0, 0), (1, 0), (2, 0)],
+            BASIC_MOVEMENT_COST * 2,
+        ),
+        (
+            // warrior MOV=50, budget = 100：繞路需 11 格（110）超出預算
+            "繞路超出預算，只能踩上危險地面",
+            r#"
+P . . . . . . . x T
+. . . . . . . . . ."#,
+            (0..10).map(|x| (x, 0)).collect(),
+            BASIC_MOVEMENT_COST * 9,
+        ),
+    ];
+
+    for (desc, ascii, expected_path, expected_cost) in test_data {
+        let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+        let level_toml = LevelBuilder::from_ascii(ascii)
+            .unit("P", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+            .object("x", OBJECT_TYPE_SPIKE)
+            .to_toml()
+            .expect("LevelBuilder::to_toml 應成功");
+        let mut world = setup_world_with_level(&level_toml);
+        start_new_round(&mut world).expect("start_new_round 應成功");
+        let target = markers["T"][0];
+        let expected_path: Vec<Position> = expected_path
+            .into_iter()
+            .map(|(x, y)| Position { x, y })
+            .collect();
+
+        let preview = preview_move_path(&mut world, target).expect("preview_move_path 應成功");
+        assert_eq!(preview.path, expected_path, "Case '{}' 預覽路徑不符", desc);
+
+        plan_move(&mut world, target).expect("plan_move 應成功");
+        let (path_walked, cost) = match advance_move(&mut world).expect("advance_move 應成功") {
+            AdvanceMoveResult::Completed { path_walked, cost } => (path_walked, cost),
+            AdvanceMoveResult::Interrupted { .. } => {
+                panic!("Case '{}' 應 Completed，但回傳 Interrupted", desc)
+            }
+        };
+        assert_eq!(path_walked, expected_path, "Case '{}' 路徑不符", desc);
+        assert_eq!(cost, expected_cost, "Case '{}' 消耗應只計地形成本", desc);
+    }
+}
@@ -0,0 +1,260 @@
+//! 警戒（Overwatch）反應測試
+
+use super::constants::{SKILL_OVERWATCH_SHOT, UNIT_TYPE_SENTRY, UNIT_TYPE_WARRIOR};
+use super::setup_world_with_level;
+use bevy_ecs::prelude::{Entity, With, World};
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::domain::core_types::ReactionTrigger;
+use board::ecs_logic::movement::{AdvanceMoveResult, advance_move, force_advance_move, plan_move};
+use board::ecs_logic::reaction::{
+    ProcessReactionResult, get_pending_reactions, process_reactions, set_overwatch, set_reactions,
+};
+use board::ecs_logic::turn::{end_current_turn, get_current_unit, get_turn_order, start_new_round};
+use board::ecs_types::components::{
+    CurrentHp, Initiative, MaxReactionPoint, Occupant, Overwatch, Position, ReactionPoint, Unit,
+};
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+use std::collections::HashMap;
+
+const ENEMY_FACTION_ID: u32 = 2;
+
+/// 警戒射程 [1, 2]：S 在 (0, 0)，E 由 (3, 0) 走到 (2, 0)、F 由 (3, 1) 走到 (1, 1) 都會進入射程
+const BOARD: &str = "
+S . . E
+. . . F
+";
+
+/// 建立 S（sentry，玩家）與 E、F（warrior，敵人）的 World 並開始新一輪
+///
+/// S 的 Initiative 設為 100 確保先手；S 的反應點設為 2，
+/// 用以驗證「一輪只觸發一次」來自警戒本身而非反應點用盡。
+fn build_overwatch_world() -> (World, HashMap<String, Vec<Position>>) {
+    let (_, markers) = load_from_ascii(BOARD).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(BOARD)
+        .unit("S", UNIT_TYPE_SENTRY, PLAYER_FACTION_ID)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .unit("F", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_toml()
+        .expect("to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+
+    let units: Vec<(Entity, Position)> = world
+        .query_filtered::<(Entity, &Position), With<Unit>>()
+        .iter(&world)
+        .map(|(entity, pos)| (entity, *pos))
+        .collect();
+    for (entity, pos) in units {
+        let mut entity_mut = world.entity_mut(entity);
+        if pos == markers["S"][0] {
+            entity_mut
+                .insert(Initiative(100))
+                .insert(MaxReactionPoint(2))
+                .insert(ReactionPoint(2));
+        } else {
+            entity_mut
+                .insert(MaxReactionPoint(1))
+                .insert(ReactionPoint(1));
+        }
+    }
+
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    (world, markers)
+}
+
+fn current_unit(world: &World) -> Occupant {
+    get_current_unit(get_turn_order(world).expect("應有回合順序")).expect("應有當前單位")
+}
+
+fn find_unit_at(world: &mut World, pos: Position) -> (Entity, Occupant) {
+    world
+        .query_filtered::<(Entity, &Occupant, &Position), With<Unit>>()
+        .iter(world)
+        .find(|(_, _, p)| **p == pos)
+        .map(|(entity, occupant, _)| (entity, *occupant))
+        .expect("應找到指定位置的單位")
+}
+
+fn current_hp(world: &World, entity: Entity) -> i32 {
+    world.entity(entity).get::<CurrentHp>().expect("應有 HP").0
+}
+
+/// 當前單位移動到 target；若被反應中斷，依 decide 決定是否施放，處理完後走完剩餘路徑
+fn move_current_unit(world: &mut World, target: Position, decide: bool) -> bool {
+    plan_move(world, target).expect("plan_move 應成功");
+    let interrupted = matches!(
+        advance_move(world).expect("advance_move 應成功"),
+        AdvanceMoveResult::Interrupted { .. }
+    );
+    if interrupted {
+        let decisions = if decide {
+            get_pending_reactions(world)
+                .into_iter()
+                .map(|pending| (pending.reactor, pending.available_skills[0].clone()))
+                .collect()
+        } else {
+            vec![]
+        };
+        set_reactions(world, decisions).expect("set_reactions 應成功");
+        while let ProcessReactionResult::Executed { .. } =
+            process_reactions(world).expect("process_reactions 應成功")
+        {}
+        force_advance_move(world).expect("force_advance_move 應成功");
+    }
+    interrupted
+}
+
+/// 把 E、F 依回合順序移入警戒射程：回傳 [(單位 marker, 是否被中斷)]
+fn enemies_enter_range(
+    world: &mut World,
+    markers: &HashMap<String, Vec<Position>>,
+) -> Vec<(String, bool)> {
+    let destinations = HashMap::from([
+        (markers["E"][0], Position { x: 2, y: 0 }),
+        (markers["F"][0], Position { x: 1, y: 1 }),
+    ]);
+    let enemies: HashMap<Occupant, (String, Position)> = ["E", "F"]
+        .into_iter()
+        .map(|marker| {
+            let start = markers[marker][0];
+            let (_, occupant) = find_unit_at(world, start);
+            (occupant, (marker.to_string(), destinations[&start]))
+        })
+        .collect();
+
+    let mut result = Vec::new();
+    for _ in 0..enemies.len() {
+        let (marker, destination) = enemies[&current_unit(world)].clone();
+        let interrupted = move_current_unit(world, destination, true);
+        result.push((marker, interrupted));
+        end_current_turn(world).expect("end_current_turn 應成功");
+    }
+    result
+}
+
+#[test]
+fn test_overwatch_strikes_enemy_entering_range() {
+    let (mut world, markers) = build_overwatch_world();
+    let (sentry_entity, sentry) = find_unit_at(&mut world, markers["S"][0]);
+    let (enemy_entity, enemy) = find_unit_at(&mut world, markers["E"][0]);
+    let enemy_hp = current_hp(&world, enemy_entity);
+
+    set_overwatch(&mut world, &SKILL_OVERWATCH_SHOT.to_string()).expect("set_overwatch 應成功");
+    end_current_turn(&mut world).expect("end_current_turn 應成功");
+    // 讓 E 先行動
+    while current_unit(&world) != enemy {
+        end_current_turn(&mut world).expect("end_current_turn 應成功");
+    }
+
+    plan_move(&mut world, Position { x: 2, y: 0 }).expect("plan_move 應成功");
+    let result = advance_move(&mut world).expect("advance_move 應成功");
+    assert!(
+        matches!(result, AdvanceMoveResult::Interrupted { .. }),
+        "進入警戒射程應中斷移動"
+    );
+    let pending = get_pending_reactions(&world);
+    assert_eq!(pending.len(), 1, "應只有 S 的警戒反應");
+    assert_eq!(pending[0].reactor, sentry);
+    assert_eq!(pending[0].trigger, enemy);
+    assert_eq!(pending[0].trigger_event, ReactionTrigger::Overwatch);
+
+    set_reactions(&mut world, vec![(sentry, SKILL_OVERWATCH_SHOT.to_string())])
+        .expect("set_reactions 應成功");
+    let result = process_reactions(&mut world).expect("process_reactions 應成功");
+    assert!(matches!(result, ProcessReactionResult::Executed { .. }));
+    assert!(
+        current_hp(&world, enemy_entity) < enemy_hp,
+        "進入射程的敵人應受到警戒攻擊"
+    );
+    assert!(
+        world.entity(sentry_entity).get::<Overwatch>().is_none(),
+        "警戒觸發後應解除"
+    );
+    assert_eq!(
+        world
+            .entity(sentry_entity)
+            .get::<ReactionPoint>()
+            .map(|p| p.0),
+        Some(1),
+        "警戒觸發應消耗一點反應點"
+    );
+
+    assert!(matches!(
+        process_reactions(&mut world).expect("process_reactions 應成功"),
+        ProcessReactionResult::Done
+    ));
+    let result = force_advance_move(&mut world).expect("force_advance_move 應成功");
+    assert!(
+        matches!(result, AdvanceMoveResult::Completed { .. }),
+        "於終點觸發的警戒處理完後移動即結束"
+    );
+    assert_eq!(
+        world.entity(enemy_entity).get::<Position>().copied(),
+        Some(Position { x: 2, y: 0 }),
+        "E 應停在目的地"
+    );
+}
+
+#[test]
+fn test_overwatch_fires_only_once_per_round() {
+    let (mut world, markers) = build_overwatch_world();
+    let (sentry_entity, _) = find_unit_at(&mut world, markers["S"][0]);
+
+    set_overwatch(&mut world, &SKILL_OVERWATCH_SHOT.to_string()).expect("set_overwatch 應成功");
+    end_current_turn(&mut world).expect("end_current_turn 應成功");
+
+    let interrupts = enemies_enter_range(&mut world, &markers);
+    let fired: Vec<bool> = interrupts.iter().map(|(_, fired)| *fired).collect();
+    assert_eq!(
+        fired,
+        vec![true, false],
+        "同一輪中只有第一個進入射程的敵人觸發警戒：{interrupts:?}"
+    );
+    assert_eq!(
+        world
+            .entity(sentry_entity)
+            .get::<ReactionPoint>()
+            .map(|p| p.0),
+        Some(1),
+        "S 只消耗一點反應點：第二個敵人未觸發是因警戒已解除，而非反應點用盡"
+    );
+}
+
+#[test]
+fn test_overwatch_requires_setting_and_expires_on_own_turn() {
+    // 未架設警戒：敵人進入射程不觸發
+    let (mut world, markers) = build_overwatch_world();
+    end_current_turn(&mut world).expect("end_current_turn 應成功");
+    let interrupts = enemies_enter_range(&mut world, &markers);
+    assert!(
+        interrupts.iter().all(|(_, fired)| !fired),
+        "未架設警戒時不應觸發：{interrupts:?}"
+    );
+
+    // 架設後無人觸發，輪到 S 的下一個回合開始時解除
+    let (mut world, markers) = build_overwatch_world();
+    let (sentry_entity, sentry) = find_unit_at(&mut world, markers["S"][0]);
+    set_overwatch(&mut world, &SKILL_OVERWATCH_SHOT.to_string()).expect("set_overwatch 應成功");
+    assert!(world.entity(sentry_entity).get::<Overwatch>().is_some());
+    // S、E、F 各結束一次回合，新一輪由先手的 S 開始
+    for _ in 0..3 {
+        end_current_turn(&mut world).expect("end_current_turn 應成功");
+    }
+    assert_eq!(current_unit(&world), sentry);
+    assert!(
+        world.entity(sentry_entity).get::<Overwatch>().is_none(),
+        "S 的回合開始時警戒應解除"
+    );
+}
+
+#[test]
+fn test_set_overwatch_rejects_non_overwatch_skill() {
+    let (mut world, _) = build_overwatch_world();
+    assert!(
+        set_overwatch(&mut world, &"warrior-passive".to_string()).is_err(),
+        "被動技能不可架設警戒"
+    );
+    assert!(
+        set_overwatch(&mut world, &"warrior-reaction".to_string()).is_err(),
+        "不在技能列表中的技能不可架設警戒"
+    );
+}
@@ -1,10 +1,21 @@
 use super::constants::{OBJECT_TYPE_SPIKE, OBJECT_TYPE_WALL, UNIT_TYPE_MAGE, UNIT_TYPE_WARRIOR};
-use super::setup_world_with_level;
+use super::{build_warrior_world, setup_world_with_level};
+use bevy_ecs::prelude::{Entity, With};
 use board::domain::alias::ID;
 use board::domain::constants::PLAYER_FACTION_ID;
+use board::domain::core_types::{
+    Attribute, BuffType, CasterOrTarget, ContinuousEffect, StackingPolicy,
+};
+use board::ecs_logic::buff::apply_buff;
 use board::ecs_logic::deployment::deploy_unit;
-use board::ecs_logic::query::{get_all_objects, get_all_units};
-use board::ecs_types::components::{Occupant, Position, UnitBundle};
+use board::ecs_logic::query::{
+    get_all_objects, get_all_units, get_calculated_attributes, get_content_hash, get_resource,
+};
+use board::ecs_types::components::{
+    AttributeBundle, CurrentHp, Occupant, Position, Skills, Unit, UnitBundle,
+};
+use board::ecs_types::resources::GameData;
+use board::logic::skill::unit_attributes::{recalc_from_skills, totals_to_attributes};
 use board::test_helpers::level_builder::LevelBuilder;
 use std::collections::HashMap;
 
@@ -265,3 +276,127 @@ fn test_get_all_objects_empty_board() {
     let objects = get_all_objects(&mut world).expect("get_all_objects 應成功");
     assert!(objects.is_empty(), "空棋盤應無物件");
 }
+
+/// 內容相同但 placement 順序不同的關卡雜湊相同；單位狀態改變則雜湊改變
+#[test]
+fn test_content_hash_is_order_independent() {
+    let level = LevelBuilder::from_ascii(
+        "
+        A . w
+        . s B
+        ",
+    )
+    .unit("A", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+    .unit("B", UNIT_TYPE_MAGE, 2)
+    .object("w", OBJECT_TYPE_WALL)
+    .object("s", OBJECT_TYPE_SPIKE)
+    .to_level()
+    .expect("LevelBuilder::to_level 應成功");
+    let mut reversed = level.clone();
+    reversed.unit_placements.reverse();
+    reversed.object_placements.reverse();
+    reversed.factions.reverse();
+
+    let to_toml = |level| toml::to_string_pretty(level).expect("關卡序列化應成功");
+    let mut world = setup_world_with_level(&to_toml(&level));
+    let mut reversed_world = setup_world_with_level(&to_toml(&reversed));
+
+    let hash = get_content_hash(&mut world).expect("get_content_hash 應成功");
+    assert_eq!(
+        hash,
+        get_content_hash(&mut reversed_world).expect("get_content_hash 應成功"),
+        "placement 順序不同但內容相同，雜湊應相同"
+    );
+    assert_eq!(
+        hash,
+        get_content_hash(&mut world).expect("get_content_hash 應成功"),
+        "重複計算應得相同雜湊"
+    );
+
+    let unit = world
+        .query_filtered::<Entity, With<Unit>>()
+        .iter(&world)
+        .next()
+        .expect("應有單位");
+    world.entity_mut(unit).insert(CurrentHp(1));
+    assert_ne!(
+        hash,
+        get_content_hash(&mut world).expect("get_content_hash 應成功"),
+        "單位 HP 改變後雜湊應改變"
+    );
+}
+
+/// 顯示用的主要屬性（HP 上限、攻擊、命中、閃避、格擋、移動）
+fn displayed_stats(attrs: &AttributeBundle) -> [i32; 7] {
+    [
+        attrs.max_hp.0,
+        attrs.physical_attack.0,
+        attrs.magical_attack.0,
+        attrs.physical_accuracy.0,
+        attrs.agility.0,
+        attrs.block.0,
+        attrs.movement_point.0,
+    ]
+}
+
+#[test]
+fn test_get_calculated_attributes_reflects_buffs() {
+    let (mut world, player, _) = build_warrior_world("P . E");
+    let (player_entity, skills) = world
+        .query::<(Entity, &Occupant, &Skills)>()
+        .iter(&world)
+        .find(|(_, occupant, _)| **occupant == player)
+        .map(|(entity, _, skills)| (entity, skills.0.clone()))
+        .expect("應找到玩家單位");
+    let game_data = get_resource::<GameData>(&world, "應有 GameData").expect("應有 GameData");
+    let skill_only = totals_to_attributes(
+        &recalc_from_skills(&skills, &game_data.skill_map).expect("技能重算應成功"),
+    );
+    world.entity_mut(player_entity).insert(CurrentHp(1));
+
+    let attrs = get_calculated_attributes(&mut world, player).expect("應可計算屬性");
+    assert_eq!(
+        displayed_stats(&attrs),
+        displayed_stats(&skill_only),
+        "無 buff 時應與技能計算結果一致"
+    );
+
+    // 固定值先加，再套用倍率：(敏捷 + 10) * 150%、物攻 * 200%
+    let buff = BuffType {
+        name: "focus".to_string(),
+        beneficial: true,
+        stacking: StackingPolicy::Stack,
+        while_active: vec![
+            ContinuousEffect::AttributeFlat {
+                attribute: Attribute::Agility,
+                value: 10,
+            },
+            ContinuousEffect::AttributeScaling {
+                target_attribute: Attribute::Agility,
+                source: CasterOrTarget::Caster,
+                source_attribute: Attribute::Agility,
+                value_percent: 150,
+            },
+            ContinuousEffect::AttributeScaling {
+                target_attribute: Attribute::PhysicalAttack,
+                source: CasterOrTarget::Caster,
+                source_attribute: Attribute::PhysicalAttack,
+                value_percent: 200,
+            },
+        ],
+        per_turn_effects: vec![],
+        end_conditions: vec![],
+    };
+    apply_buff(&mut world, buff, player, player).expect("施加 buff 應成功");
+
+    let attrs = get_calculated_attributes(&mut world, player).expect("應可計算屬性");
+    let mut expected = displayed_stats(&skill_only);
+    expected[1] = skill_only.physical_attack.0 * 200 / 100;
+    expected[4] = (skill_only.agility.0 + 10) * 150 / 100;
+    assert_eq!(
+        displayed_stats(&attrs),
+        expected,
+        "應反映 buff 的固定值與倍率"
+    );
+    assert_eq!(attrs.current_hp.0, 1, "當前 HP 應取自單位現況");
+}
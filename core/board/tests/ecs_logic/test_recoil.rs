@@ -0,0 +1,90 @@
+//! 技能反噬測試
+
+use super::constants::{SKILL_RECKLESS_STRIKE, UNIT_TYPE_WARRIOR, UNIT_TYPE_WARRIOR_B};
+use super::setup_world_with_level;
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::ecs_logic::skill::execute_skill;
+use board::ecs_logic::turn::{resolve_deaths, start_new_round};
+use board::ecs_types::components::{CurrentHp, Initiative, Occupant, Position};
+use board::logic::skill::skill_execution::CheckTarget;
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+use std::collections::HashMap;
+
+const ENEMY_FACTION_ID: u32 = 2;
+/// fixture 中 reckless-strike 的反噬量
+const RECOIL: i32 = 7;
+
+/// P 為持有 reckless-strike 的 warrior-b（先攻），E 為敵方 warrior
+fn build_recoil_world(ascii: &str) -> (World, Entity, HashMap<String, Vec<Position>>) {
+    let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ascii)
+        .unit("P", UNIT_TYPE_WARRIOR_B, PLAYER_FACTION_ID)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    let caster = world
+        .query::<(Entity, &Position)>()
+        .iter(&world)
+        .find(|(_, pos)| **pos == markers["P"][0])
+        .map(|(entity, _)| entity)
+        .expect("應找到玩家單位");
+    world.entity_mut(caster).insert(Initiative(100));
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    (world, caster, markers)
+}
+
+#[test]
+fn test_recoil_damages_caster_after_main_effect() {
+    let (mut world, caster, markers) = build_recoil_world("P E");
+    let hp_before = world
+        .entity(caster)
+        .get::<CurrentHp>()
+        .expect("施放者應有 HP")
+        .0;
+
+    let entries = execute_skill(
+        &mut world,
+        &SKILL_RECKLESS_STRIKE.to_string(),
+        &[markers["E"][0]],
+    )
+    .expect("execute_skill 應成功");
+
+    let caster_id = match world.entity(caster).get::<Occupant>() {
+        Some(Occupant::Unit(id)) => *id,
+        other => panic!("施放者應為單位，實際為 {other:?}"),
+    };
+    let last = entries.last().expect("應有效果條目");
+    assert_eq!(
+        last.target,
+        CheckTarget::Unit(caster_id),
+        "反噬應排在主要效果之後"
+    );
+
+    let hp_after = world
+        .entity(caster)
+        .get::<CurrentHp>()
+        .expect("施放者應有 HP")
+        .0;
+    assert_eq!(hp_after, hp_before - RECOIL, "施放者應失去反噬量的 HP");
+}
+
+#[test]
+fn test_lethal_recoil_kills_caster() {
+    let (mut world, caster, markers) = build_recoil_world("P E");
+    world.entity_mut(caster).insert(CurrentHp(RECOIL));
+
+    execute_skill(
+        &mut world,
+        &SKILL_RECKLESS_STRIKE.to_string(),
+        &[markers["E"][0]],
+    )
+    .expect("致死反噬不應讓 execute_skill 失敗");
+    resolve_deaths(&mut world).expect("resolve_deaths 應成功");
+
+    assert!(
+        world.get_entity(caster).is_err(),
+        "反噬致死的施放者應被移除"
+    );
+}
@@ -0,0 +1,107 @@
+//! 定時增援測試
+
+use super::constants::UNIT_TYPE_WARRIOR;
+use super::{ENEMY_FACTION_ID, setup_world_with_level};
+use bevy_ecs::prelude::{With, World};
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::ecs_logic::turn::{end_current_turn, get_turn_order, start_new_round};
+use board::ecs_types::components::{Occupant, Position, Unit};
+use board::loader_schema::{Reinforcement, UnitPlacement};
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+use std::collections::HashMap;
+
+/// R 為增援生成點
+const BOARD: &str = "
+P . . .
+. . . R
+. . . E
+";
+
+/// 建立 P（玩家）與 E（敵人）的 World，增援於 round 輪在 R 生成 count 名敵方 warrior
+fn build_world(round: u32, count: usize) -> (World, HashMap<String, Vec<Position>>) {
+    let (_, markers) = load_from_ascii(BOARD).expect("load_from_ascii 應成功");
+    let mut level = LevelBuilder::from_ascii(BOARD)
+        .unit("P", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_level()
+        .expect("LevelBuilder::to_level 應成功");
+    let placement = UnitPlacement {
+        unit_type_name: UNIT_TYPE_WARRIOR.to_string(),
+        faction_id: ENEMY_FACTION_ID,
+        position: markers["R"][0],
+    };
+    level.reinforcements = vec![Reinforcement {
+        round,
+        units: vec![placement; count],
+    }];
+    let level_toml = toml::to_string(&level).expect("關卡應可序列化");
+    (setup_world_with_level(&level_toml), markers)
+}
+
+fn unit_positions(world: &mut World) -> HashMap<Occupant, Position> {
+    world
+        .query_filtered::<(&Occupant, &Position), With<Unit>>()
+        .iter(world)
+        .map(|(occupant, pos)| (*occupant, *pos))
+        .collect()
+}
+
+/// 讓本輪所有單位結束回合，進入下一輪
+fn finish_round(world: &mut World) {
+    let round = get_turn_order(world).expect("應有回合順序").round;
+    while get_turn_order(world).expect("應有回合順序").round == round {
+        end_current_turn(world).expect("end_current_turn 應成功");
+    }
+}
+
+#[test]
+fn test_reinforcement_spawns_at_scheduled_round_and_joins_initiative() {
+    let (mut world, markers) = build_world(3, 1);
+    let spawn_point = markers["R"][0];
+
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    for round in 1..=2 {
+        assert_eq!(get_turn_order(&world).expect("應有回合順序").round, round);
+        assert_eq!(
+            unit_positions(&mut world).len(),
+            2,
+            "第 {round} 輪增援尚未抵達"
+        );
+        finish_round(&mut world);
+    }
+
+    let turn_order = get_turn_order(&world).expect("應有回合順序");
+    assert_eq!(turn_order.round, 3);
+    let entries: Vec<Occupant> = turn_order.entries.iter().map(|e| e.occupant).collect();
+    let units = unit_positions(&mut world);
+    assert_eq!(units.len(), 3, "第 3 輪開始時增援應出現");
+    let (reinforcement, _) = units
+        .iter()
+        .find(|(_, pos)| **pos == spawn_point)
+        .expect("增援應生成在生成點");
+    assert_eq!(entries.len(), 3, "增援應加入本輪回合順序");
+    assert!(entries.contains(reinforcement), "增援應參與本輪先攻排序");
+
+    finish_round(&mut world);
+    assert_eq!(
+        unit_positions(&mut world).len(),
+        3,
+        "增援只在排定的輪數生成一次"
+    );
+}
+
+#[test]
+fn test_reinforcement_uses_adjacent_tile_when_spawn_point_occupied() {
+    let (mut world, markers) = build_world(1, 2);
+    let spawn_point = markers["R"][0];
+
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let positions: Vec<Position> = unit_positions(&mut world).into_values().collect();
+    assert_eq!(positions.len(), 4, "兩名增援都應生成");
+    assert!(positions.contains(&spawn_point), "第一名增援在生成點");
+    assert!(
+        positions.iter().any(|pos| *pos != spawn_point
+            && pos.x.abs_diff(spawn_point.x) + pos.y.abs_diff(spawn_point.y) == 1),
+        "生成點被佔據時改放相鄰空格：{positions:?}"
+    );
+}
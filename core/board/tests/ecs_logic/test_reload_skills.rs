@@ -0,0 +1,119 @@
+//! 模擬中修改技能後重算單位屬性的測試
+
+use super::constants::{SKILL_WARRIOR, SKILLS_TOML, UNIT_TYPE_MAGE, UNIT_TYPE_WARRIOR};
+use super::{entity_at, setup_world_with_level};
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::domain::core_types::{Attribute, ContinuousEffect, SkillType};
+use board::ecs_logic::loader::reload_skills;
+use board::ecs_logic::query::get_resource;
+use board::ecs_types::components::{CurrentHp, MaxHp, MovementPoint};
+use board::ecs_types::resources::GameData;
+use board::loader_schema::SkillsToml;
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+
+const BOARD: &str = "W . M";
+const ENEMY_FACTION_ID: u32 = 2;
+const DAMAGE: i32 = 30;
+
+/// 建立 W（warrior，帶 warrior-passive）與 M（mage，不帶）的 World，W 先受到 DAMAGE 點傷害
+fn build_world() -> (World, Entity, Entity) {
+    let (_, markers) = load_from_ascii(BOARD).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(BOARD)
+        .unit("W", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .unit("M", UNIT_TYPE_MAGE, ENEMY_FACTION_ID)
+        .to_toml()
+        .expect("to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    let warrior = entity_at(&mut world, markers["W"][0]);
+    let mage = entity_at(&mut world, markers["M"][0]);
+    let hp = world.entity(warrior).get::<CurrentHp>().expect("應有 HP").0;
+    world.entity_mut(warrior).insert(CurrentHp(hp - DAMAGE));
+    (world, warrior, mage)
+}
+
+/// (HP 上限, 當前 HP, 移動力)
+fn stats(world: &World, entity: Entity) -> (i32, i32, i32) {
+    let entity_ref = world.entity(entity);
+    (
+        entity_ref.get::<MaxHp>().expect("應有 MaxHp").0,
+        entity_ref.get::<CurrentHp>().expect("應有 CurrentHp").0,
+        entity_ref
+            .get::<MovementPoint>()
+            .expect("應有 MovementPoint")
+            .0,
+    )
+}
+
+fn fixture_skills() -> Vec<SkillType> {
+    toml::from_str::<SkillsToml>(SKILLS_TOML)
+        .expect("技能 TOML 應可解析")
+        .skills
+}
+
+/// 將 warrior-passive 的固定值屬性改為 attribute += delta
+fn edit_warrior_passive(skills: &mut [SkillType], attribute: Attribute, delta: i32) {
+    for skill in skills.iter_mut() {
+        if let SkillType::Passive { name, effects, .. } = skill {
+            if name != SKILL_WARRIOR {
+                continue;
+            }
+            for effect in effects.iter_mut() {
+                match effect {
+                    ContinuousEffect::AttributeFlat {
+                        attribute: a,
+                        value,
+                    } if *a == attribute => *value += delta,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reload_skills_updates_units_with_edited_passive() {
+    let (mut world, warrior, mage) = build_world();
+    let (max_hp, current_hp, movement) = stats(&world, warrior);
+    let mage_before = stats(&world, mage);
+
+    let mut skills = fixture_skills();
+    edit_warrior_passive(&mut skills, Attribute::Hp, 50);
+    edit_warrior_passive(&mut skills, Attribute::MovementPoint, -20);
+    reload_skills(&mut world, skills).expect("reload_skills 應成功");
+
+    assert_eq!(
+        stats(&world, warrior),
+        (max_hp + 50, current_hp + 50, movement - 20),
+        "帶有被修改技能的單位應重算屬性，當前 HP 隨上限增加差值"
+    );
+    assert_eq!(stats(&world, mage), mage_before, "未帶該技能的單位不變");
+
+    // 調低上限：當前 HP 壓到新上限
+    let mut skills = fixture_skills();
+    edit_warrior_passive(&mut skills, Attribute::Hp, -(max_hp - current_hp) - 10);
+    reload_skills(&mut world, skills).expect("reload_skills 應成功");
+    let (new_max, new_current, _) = stats(&world, warrior);
+    assert_eq!(new_max, current_hp - 10);
+    assert_eq!(new_current, new_max, "上限降到當前 HP 以下時應壓到新上限");
+}
+
+#[test]
+fn test_reload_skills_rejects_missing_skill() {
+    let (mut world, warrior, _) = build_world();
+    let before = stats(&world, warrior);
+
+    let mut skills = fixture_skills();
+    skills.retain(|skill| skill.name() != SKILL_WARRIOR);
+    assert!(
+        reload_skills(&mut world, skills).is_err(),
+        "單位帶有的技能被刪除時應回傳錯誤"
+    );
+
+    assert_eq!(stats(&world, warrior), before, "失敗時單位屬性不變");
+    let game_data = get_resource::<GameData>(&world, "應有 GameData").expect("應有 GameData");
+    assert!(
+        game_data.skill_map.contains_key(SKILL_WARRIOR),
+        "失敗時不應替換技能定義"
+    );
+}
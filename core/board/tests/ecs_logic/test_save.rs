@@ -0,0 +1,130 @@
+//! 戰鬥存檔／讀檔往返測試
+
+use super::constants::UNIT_TYPE_WARRIOR;
+use super::{entity_at, setup_world_with_level};
+use bevy_ecs::prelude::World;
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::domain::core_types::{
+    Attribute, BuffType, ContinuousEffect, EndCondition, StackingPolicy,
+};
+use board::ecs_logic::buff::apply_buff;
+use board::ecs_logic::movement::{AdvanceMoveResult, advance_move, plan_move};
+use board::ecs_logic::save::{load_battle, save_battle};
+use board::ecs_logic::turn::{end_current_turn, get_current_unit, get_turn_order, start_new_round};
+use board::ecs_types::components::{CurrentHp, Initiative, MaxHp, Position};
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+
+const ENEMY_FACTION_ID: u32 = 2;
+const ASCII: &str = "
+    P . . . .
+    . . . . .
+    . . . . E
+";
+const BUFF_BONUS: i32 = 20;
+const DAMAGE: i32 = 30;
+
+fn move_current_unit(world: &mut World, target: Position) {
+    plan_move(world, target).expect("plan_move 應成功");
+    let result = advance_move(world).expect("advance_move 應成功");
+    assert!(
+        matches!(result, AdvanceMoveResult::Completed { .. }),
+        "移動應走完全程"
+    );
+}
+
+/// 模擬數個行動後存檔，讀入以同一關卡重建的新 World，狀態應與存檔時一致
+#[test]
+fn test_save_load_round_trip() {
+    let (_, markers) = load_from_ascii(ASCII).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ASCII)
+        .unit("P", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+
+    let mut world = setup_world_with_level(&level_toml);
+    let player_entity = entity_at(&mut world, markers["P"][0]);
+    world.entity_mut(player_entity).insert(Initiative(100));
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let player =
+        get_current_unit(get_turn_order(&world).expect("應有回合順序")).expect("應有當前單位");
+
+    // 玩家移動、受到傷害並獲得上限 buff 後結束回合；敵人移動一步停在回合中途
+    let moved_to = Position { x: 1, y: 1 };
+    move_current_unit(&mut world, moved_to);
+    apply_buff(
+        &mut world,
+        BuffType {
+            name: "vigor".to_string(),
+            beneficial: true,
+            stacking: StackingPolicy::Stack,
+            while_active: vec![ContinuousEffect::AttributeFlat {
+                attribute: Attribute::Hp,
+                value: BUFF_BONUS,
+            }],
+            per_turn_effects: vec![],
+            end_conditions: vec![EndCondition::Duration(3)],
+        },
+        player,
+        player,
+    )
+    .expect("apply_buff 應成功");
+    let player_entity = entity_at(&mut world, moved_to);
+    let damaged_hp = world
+        .entity(player_entity)
+        .get::<CurrentHp>()
+        .expect("應有 CurrentHp")
+        .0
+        - DAMAGE;
+    world
+        .entity_mut(player_entity)
+        .insert(CurrentHp(damaged_hp));
+    end_current_turn(&mut world).expect("end_current_turn 應成功");
+    move_current_unit(&mut world, Position { x: 3, y: 2 });
+
+    let saved = save_battle(&mut world).expect("save_battle 應成功");
+
+    let mut restored = setup_world_with_level(&level_toml);
+    load_battle(&mut restored, &saved).expect("load_battle 應成功");
+
+    assert_eq!(
+        save_battle(&mut restored).expect("重新存檔應成功"),
+        saved,
+        "讀檔後重新存檔應與原存檔一致"
+    );
+    let restored_player = entity_at(&mut restored, moved_to);
+    let entity_ref = restored.entity(restored_player);
+    assert_eq!(
+        entity_ref.get::<CurrentHp>().expect("應有 CurrentHp").0,
+        damaged_hp,
+        "當前 HP 應還原為存檔值"
+    );
+    let original_max_hp = world
+        .entity(player_entity)
+        .get::<MaxHp>()
+        .expect("應有 MaxHp")
+        .0;
+    assert_eq!(
+        entity_ref.get::<MaxHp>().expect("應有 MaxHp").0,
+        original_max_hp,
+        "HP 上限應依還原的 buff 重算"
+    );
+    let current = |world: &World| {
+        get_current_unit(get_turn_order(world).expect("應有回合順序")).expect("應有當前單位")
+    };
+    assert_eq!(current(&restored), current(&world), "當前行動單位應一致");
+}
+
+#[test]
+fn test_load_rejects_malformed_save() {
+    let mut world = setup_world_with_level(
+        &LevelBuilder::from_ascii("P")
+            .unit("P", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+            .to_toml()
+            .expect("LevelBuilder::to_toml 應成功"),
+    );
+    assert!(
+        load_battle(&mut world, "round = \"oops\"").is_err(),
+        "格式錯誤的存檔應回傳錯誤"
+    );
+}
@@ -0,0 +1,128 @@
+//! 情境包打包與載入測試
+
+use super::ENEMY_FACTION_ID;
+use super::constants::{
+    OBJECT_TYPE_WALL, OBJECTS_TOML, SKILLS_TOML, UNIT_TYPE_MAGE, UNIT_TYPE_SENTRY,
+    UNIT_TYPE_WARRIOR, UNITS_TOML,
+};
+use bevy_ecs::prelude::{With, World};
+use board::ecs_logic::deployment::deploy_unit;
+use board::ecs_logic::loader::load_scenario_bundle;
+use board::ecs_types::components::{OccupantTypeName, Unit};
+use board::ecs_types::resources::GameData;
+use board::error::{DataError, ErrorKind, Result, UnitError};
+use board::loader_schema::{ObjectsToml, ScenarioBundle, SkillsToml, UnitsToml};
+use board::logic::scenario::build_scenario_bundle;
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+
+const BOARD: &str = "
+D . .
+. . E
+";
+
+/// 以測試資料打包情境：玩家名冊為 mage，關卡預放一名敵方 warrior
+fn build_bundle(roster: Vec<String>, skip_unit: Option<&str>) -> Result<ScenarioBundle> {
+    let level = LevelBuilder::from_ascii(BOARD)
+        .deploy("D")
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_level()
+        .expect("LevelBuilder::to_level 應成功");
+    let units: UnitsToml = toml::from_str(UNITS_TOML).expect("UNITS_TOML 應可解析");
+    let skills: SkillsToml = toml::from_str(SKILLS_TOML).expect("SKILLS_TOML 應可解析");
+    let objects: ObjectsToml = toml::from_str(OBJECTS_TOML).expect("OBJECTS_TOML 應可解析");
+    let units = units
+        .units
+        .into_iter()
+        .filter(|unit| Some(unit.name.as_str()) != skip_unit)
+        .collect();
+    build_scenario_bundle(level, roster, units, skills.skills, objects.objects)
+}
+
+#[test]
+fn test_scenario_bundle_round_trip_resolves_all_references() {
+    let bundle = build_bundle(vec![UNIT_TYPE_MAGE.to_string()], None).expect("打包應成功");
+
+    let unit_names: Vec<&str> = bundle.units.iter().map(|u| u.name.as_str()).collect();
+    assert!(unit_names.contains(&UNIT_TYPE_MAGE), "應包含名冊單位");
+    assert!(
+        unit_names.contains(&UNIT_TYPE_WARRIOR),
+        "應包含預放與召喚的單位"
+    );
+    assert!(
+        !unit_names.contains(&UNIT_TYPE_SENTRY),
+        "未被引用的單位不應打包"
+    );
+    assert!(
+        bundle.objects.iter().any(|o| o.name == OBJECT_TYPE_WALL),
+        "應包含技能生成的物件"
+    );
+    assert!(
+        bundle.objects.len()
+            < toml::from_str::<ObjectsToml>(OBJECTS_TOML)
+                .expect("OBJECTS_TOML 應可解析")
+                .objects
+                .len(),
+        "未被引用的物件不應打包"
+    );
+
+    let bundle_toml = toml::to_string(&bundle).expect("情境包應可序列化");
+    let mut world = World::new();
+    let roster = load_scenario_bundle(&mut world, &bundle_toml).expect("情境包應可載入");
+    assert_eq!(roster, vec![UNIT_TYPE_MAGE.to_string()]);
+
+    let game_data = world.resource::<GameData>();
+    for unit in game_data.unit_type_map.values() {
+        for skill_name in &unit.skills {
+            assert!(
+                game_data.skill_map.contains_key(skill_name),
+                "單位 {} 的技能 {skill_name} 應可解析",
+                unit.name
+            );
+        }
+    }
+
+    let (_, markers) = load_from_ascii(BOARD).expect("load_from_ascii 應成功");
+    deploy_unit(&mut world, &roster[0], markers["D"][0]).expect("名冊單位應可部署");
+    let mut type_names: Vec<String> = world
+        .query_filtered::<&OccupantTypeName, With<Unit>>()
+        .iter(&world)
+        .map(|name| name.0.clone())
+        .collect();
+    type_names.sort();
+    assert_eq!(
+        type_names,
+        vec![UNIT_TYPE_MAGE.to_string(), UNIT_TYPE_WARRIOR.to_string()]
+    );
+}
+
+#[test]
+fn test_scenario_bundle_rejects_missing_references() {
+    let error = build_bundle(vec!["unknown".to_string()], None).expect_err("未知名冊單位應失敗");
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Data(DataError::UnitTypeNotFound { .. })
+    ));
+
+    // 預放單位與 mage 的召喚技能都引用 warrior
+    let error = build_bundle(vec![UNIT_TYPE_MAGE.to_string()], Some(UNIT_TYPE_WARRIOR))
+        .expect_err("缺少召喚單位定義應失敗");
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Data(DataError::UnitTypeNotFound { .. })
+    ));
+
+    // 載入時同樣檢查：單位技能列表引用不存在的技能
+    let mut bundle = build_bundle(vec![UNIT_TYPE_MAGE.to_string()], None).expect("打包應成功");
+    bundle.units[0].skills.push("missing-skill".to_string());
+    let bundle_toml = toml::to_string(&bundle).expect("情境包應可序列化");
+    let mut world = World::new();
+    let error = load_scenario_bundle(&mut world, &bundle_toml).expect_err("缺少技能定義應失敗");
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Unit(UnitError::SkillNotFound { .. })
+    ));
+    assert!(
+        world.get_resource::<GameData>().is_none(),
+        "引用檢查失敗時不應寫入 World"
+    );
+}
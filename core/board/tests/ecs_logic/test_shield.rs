@@ -0,0 +1,101 @@
+//! 護盾（臨時 HP）測試
+
+use super::constants::SKILL_RECKLESS_STRIKE;
+use super::{build_warrior_world, setup_duel};
+use bevy_ecs::prelude::{Entity, World};
+use board::ecs_logic::save::{load_battle, save_battle};
+use board::ecs_logic::skill::execute_skill;
+use board::ecs_logic::turn::{end_current_turn, start_new_round};
+use board::ecs_types::components::{CurrentHp, Occupant, Shield};
+
+const STRIKE_DAMAGE: i32 = 15;
+const SHIELD_AMOUNT: i32 = 10;
+
+fn find_unit(world: &mut World, occupant: Occupant) -> Entity {
+    world
+        .query::<(Entity, &Occupant)>()
+        .iter(world)
+        .find(|(_, occ)| **occ == occupant)
+        .map(|(entity, _)| entity)
+        .expect("應找到單位")
+}
+
+#[test]
+fn test_shield_absorbs_damage_before_hp() {
+    let duel = setup_duel("", &[], STRIKE_DAMAGE);
+    let mut world = duel.world;
+    let target = duel.target;
+    world.entity_mut(target).insert(Shield {
+        amount: SHIELD_AMOUNT,
+        remaining_rounds: 2,
+    });
+    let hp_before = world.entity(target).get::<CurrentHp>().expect("應有 HP").0;
+
+    execute_skill(
+        &mut world,
+        &SKILL_RECKLESS_STRIKE.to_string(),
+        &[duel.target_pos],
+    )
+    .expect("execute_skill 應成功");
+
+    let hp_after = world.entity(target).get::<CurrentHp>().expect("應有 HP").0;
+    assert_eq!(
+        hp_after,
+        hp_before - (STRIKE_DAMAGE - SHIELD_AMOUNT),
+        "護盾吸收 10 點後只扣 5 點真實 HP"
+    );
+    assert!(
+        world.entity(target).get::<Shield>().is_none(),
+        "耗盡的護盾應被移除"
+    );
+}
+
+/// 單一單位的棋盤中，每次 end_current_turn 都會換輪
+#[test]
+fn test_shield_expires_after_duration() {
+    let (mut world, player, _) = build_warrior_world("P");
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let entity = find_unit(&mut world, player);
+    world.entity_mut(entity).insert(Shield {
+        amount: SHIELD_AMOUNT,
+        remaining_rounds: 2,
+    });
+
+    end_current_turn(&mut world).expect("end_current_turn 應成功");
+    assert_eq!(
+        world.entity(entity).get::<Shield>().copied(),
+        Some(Shield {
+            amount: SHIELD_AMOUNT,
+            remaining_rounds: 1,
+        }),
+        "第一次換輪後護盾應仍在，剩餘 1 輪"
+    );
+
+    end_current_turn(&mut world).expect("end_current_turn 應成功");
+    assert!(
+        world.entity(entity).get::<Shield>().is_none(),
+        "持續輪數耗盡後護盾應被移除"
+    );
+}
+
+#[test]
+fn test_shield_survives_save_and_load() {
+    let (mut world, player, _) = build_warrior_world("P");
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let entity = find_unit(&mut world, player);
+    let shield = Shield {
+        amount: SHIELD_AMOUNT,
+        remaining_rounds: 2,
+    };
+    world.entity_mut(entity).insert(shield);
+
+    let save = save_battle(&mut world).expect("save_battle 應成功");
+    load_battle(&mut world, &save).expect("load_battle 應成功");
+
+    let entity = find_unit(&mut world, player);
+    assert_eq!(
+        world.entity(entity).get::<Shield>().copied(),
+        Some(shield),
+        "讀檔後護盾應保留"
+    );
+}
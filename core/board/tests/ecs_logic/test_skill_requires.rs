@@ -0,0 +1,78 @@
+//! 技能前置需求測試
+
+use super::constants::{SKILL_COMBO_FINISHER, UNIT_TYPE_WARRIOR, UNIT_TYPE_WARRIOR_B};
+use super::{build_mage_world, setup_world_with_level};
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::ecs_logic::skill::{execute_skill, get_available_skills, start_skill_targeting};
+use board::ecs_logic::turn::start_new_round;
+use board::ecs_types::components::{Initiative, Position};
+use board::error::{ErrorKind, UnitError};
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+
+const ENEMY_FACTION_ID: u32 = 2;
+
+fn finisher_usable(world: &mut World) -> bool {
+    get_available_skills(world)
+        .expect("get_available_skills 應成功")
+        .into_iter()
+        .find(|skill| skill.name == SKILL_COMBO_FINISHER)
+        .expect("應列出 combo-finisher")
+        .usable
+}
+
+/// warrior-b 沒有前置技能 diamond-aoe-1，無法使用 combo-finisher
+#[test]
+fn test_finisher_blocked_without_prerequisite() {
+    let ascii = "P E";
+    let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ascii)
+        .unit("P", UNIT_TYPE_WARRIOR_B, PLAYER_FACTION_ID)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .to_toml()
+        .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    let player_entity = world
+        .query::<(Entity, &Position)>()
+        .iter(&world)
+        .find(|(_, pos)| **pos == markers["P"][0])
+        .map(|(entity, _)| entity)
+        .expect("應找到玩家單位");
+    world.entity_mut(player_entity).insert(Initiative(100));
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let skill_name = SKILL_COMBO_FINISHER.to_string();
+
+    assert!(!finisher_usable(&mut world), "缺少前置技能時不應可用");
+
+    let err = start_skill_targeting(&mut world, &skill_name).expect_err("應拒絕開始選目標");
+    assert!(
+        matches!(
+            err.kind(),
+            ErrorKind::Unit(UnitError::MissingPrerequisite { missing, .. })
+                if missing == &vec!["diamond-aoe-1".to_string()]
+        ),
+        "應回報缺少的前置技能，實際為 {err:?}"
+    );
+
+    let err =
+        execute_skill(&mut world, &skill_name, &[markers["E"][0]]).expect_err("應拒絕執行技能");
+    assert!(
+        matches!(
+            err.kind(),
+            ErrorKind::Unit(UnitError::MissingPrerequisite { .. })
+        ),
+        "應回報缺少前置技能，實際為 {err:?}"
+    );
+}
+
+/// mage 擁有前置技能 diamond-aoe-1，可使用 combo-finisher
+#[test]
+fn test_finisher_allowed_with_prerequisite() {
+    let (mut world, _, markers) = build_mage_world("P E");
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let skill_name = SKILL_COMBO_FINISHER.to_string();
+
+    assert!(finisher_usable(&mut world), "擁有前置技能時應可用");
+    start_skill_targeting(&mut world, &skill_name).expect("應可開始選目標");
+    execute_skill(&mut world, &skill_name, &[markers["E"][0]]).expect("應可執行技能");
+}
@@ -1,12 +1,16 @@
-use super::constants::{OBJECTS_TOML, SKILLS_TOML, UNIT_TYPE_WARRIOR, UNITS_TOML};
+use super::constants::{
+    OBJECT_TYPE_WALL, OBJECTS_TOML, SKILLS_TOML, UNIT_TYPE_WARRIOR, UNITS_TOML,
+};
 use bevy_ecs::prelude::{Without, World};
+use board::domain::constants::MAX_BOARD_AREA;
 use board::ecs_logic::loader::parse_and_insert_game_data;
 use board::ecs_logic::spawner::spawn_level;
 use board::ecs_types::components::{
     BlocksSight, BlocksSound, CurrentHp, MaxHp, Object, Occupant, OccupantTypeName, Position, Unit,
 };
 use board::ecs_types::resources::Board;
-use board::error::{DataError, ErrorKind, LoadError};
+use board::error::{BoardError, DataError, ErrorKind, LoadError};
+use board::loader_schema::LevelType;
 use board::test_helpers::level_builder::LevelBuilder;
 
 // ============================================================================
@@ -253,3 +257,121 @@ fn test_spawn_level_with_unknown_unit_type_returns_error() {
         error
     );
 }
+
+#[test]
+fn test_spawn_level_with_oversized_board_returns_error() {
+    let level_toml = LevelBuilder::from_ascii(
+        "
+        D . . . .
+        . . . . .
+    ",
+    )
+    .deploy("D")
+    .to_toml()
+    .expect("LevelBuilder::to_toml 應成功");
+    // 直接改寫尺寸模擬手改的巨大關卡檔
+    let oversized_width = MAX_BOARD_AREA;
+    let level_toml = level_toml
+        .replace(
+            "board_width = 5",
+            &format!("board_width = {oversized_width}"),
+        )
+        .replace(
+            "board_height = 2",
+            &format!("board_height = {oversized_width}"),
+        );
+
+    let mut world = World::new();
+    parse_and_insert_game_data(&mut world, UNITS_TOML, SKILLS_TOML, OBJECTS_TOML)
+        .expect("parse_and_insert_game_data 應成功");
+
+    let error = spawn_level(&mut world, &level_toml, "test-level").expect_err("應拒絕巨大棋盤");
+    assert!(
+        matches!(
+            error.kind(),
+            ErrorKind::Board(BoardError::InvalidBoardSize { .. })
+        ),
+        "應為 InvalidBoardSize，實際為 {:?}",
+        error.kind()
+    );
+    assert!(
+        world.get_resource::<Board>().is_none(),
+        "失敗時不應插入 Board resource"
+    );
+}
+
+/// 建立含兩個敵方出生點（A、B）與一面牆（W）的關卡
+fn spawn_point_level() -> LevelType {
+    let level_toml = LevelBuilder::from_ascii(
+        "
+        D . . A
+        . . W .
+        . . . B
+    ",
+    )
+    .unit("A", UNIT_TYPE_WARRIOR, 2)
+    .unit("B", UNIT_TYPE_WARRIOR, 2)
+    .object("W", OBJECT_TYPE_WALL)
+    .deploy("D")
+    .to_toml()
+    .expect("LevelBuilder::to_toml 應成功");
+    toml::from_str(&level_toml).expect("關卡 TOML 應可反序列化")
+}
+
+fn spawn(level: &LevelType) -> (World, board::error::Result<()>) {
+    let level_toml = toml::to_string(level).expect("關卡應可序列化");
+    let mut world = World::new();
+    parse_and_insert_game_data(&mut world, UNITS_TOML, SKILLS_TOML, OBJECTS_TOML)
+        .expect("parse_and_insert_game_data 應成功");
+    let result = spawn_level(&mut world, &level_toml, "test-level");
+    (world, result)
+}
+
+#[test]
+fn test_spawn_level_places_units_at_spawn_points() {
+    let level = spawn_point_level();
+    let (mut world, result) = spawn(&level);
+    result.expect("spawn_level 應成功");
+
+    let mut expected: Vec<Position> = level.unit_placements.iter().map(|u| u.position).collect();
+    let mut actual: Vec<Position> = world
+        .query::<(&Unit, &Position)>()
+        .iter(&world)
+        .map(|(_, pos)| *pos)
+        .collect();
+    expected.sort_by_key(|pos| (pos.x, pos.y));
+    actual.sort_by_key(|pos| (pos.x, pos.y));
+    assert_eq!(actual, expected, "單位應恰好生成在設定的出生點上");
+}
+
+#[test]
+fn test_spawn_level_rejects_invalid_spawn_points() {
+    let wall = spawn_point_level().object_placements[0].position;
+    let taken = spawn_point_level().unit_placements[0].position;
+    let out_of_board = Position { x: 4, y: 0 };
+
+    for (label, position, expect_occupied) in [
+        ("超出棋盤", out_of_board, false),
+        ("與其他單位重疊", taken, true),
+        ("落在牆上", wall, true),
+    ] {
+        let mut level = spawn_point_level();
+        level.unit_placements[1].position = position;
+        let (world, result) = spawn(&level);
+        let error = result.expect_err(label);
+        let kind_matches = match error.kind() {
+            ErrorKind::Board(BoardError::PositionOccupied { x, y }) => {
+                expect_occupied && (*x, *y) == (position.x, position.y)
+            }
+            ErrorKind::Board(BoardError::OutOfBoard { x, y, .. }) => {
+                !expect_occupied && (*x, *y) == (position.x, position.y)
+            }
+            _ => false,
+        };
+        assert!(kind_matches, "[{label}] 錯誤類型不符：{:?}", error.kind());
+        assert!(
+            world.get_resource::<Board>().is_none(),
+            "[{label}] 失敗時不應插入 Board resource"
+        );
+    }
+}
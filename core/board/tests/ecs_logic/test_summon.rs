@@ -0,0 +1,84 @@
+//! 召喚效果測試
+
+use super::build_mage_world;
+use super::constants::{SKILL_SUMMON_WARRIOR, UNIT_TYPE_WARRIOR};
+use bevy_ecs::prelude::{With, World};
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::ecs_logic::skill::execute_skill;
+use board::ecs_logic::turn::{end_current_turn, get_turn_order, start_new_round};
+use board::ecs_types::components::{OccupantTypeName, Position, Summoned, Unit, UnitFaction};
+
+/// 查詢場上所有召喚物（位置、類型名稱、陣營、剩餘輪數）
+fn summons(world: &mut World) -> Vec<(Position, String, u32, u32)> {
+    world
+        .query_filtered::<(&Position, &OccupantTypeName, &UnitFaction, &Summoned), With<Unit>>()
+        .iter(world)
+        .map(|(pos, name, faction, summoned)| {
+            (*pos, name.0.clone(), faction.0, summoned.remaining_rounds)
+        })
+        .collect()
+}
+
+/// 結束回合直到進入下一輪
+fn finish_round(world: &mut World) {
+    let round = get_turn_order(world).expect("應有回合表").round;
+    while get_turn_order(world).expect("應有回合表").round == round {
+        end_current_turn(world).expect("end_current_turn 應成功");
+    }
+}
+
+/// 佈局：
+/// ```text
+/// .  w  .
+/// E  P  .
+/// .  .  .
+/// ```
+/// 上方為牆壁、左方為敵人，召喚物應依「上、下、左、右」順序落在下方空格
+#[test]
+fn test_summon_places_unit_on_free_adjacent_tile() {
+    let (mut world, _, markers) = build_mage_world(
+        "
+        .  w  .
+        E  P  .
+        .  .  .
+        ",
+    );
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let caster_pos = markers["P"][0];
+
+    execute_skill(&mut world, &SKILL_SUMMON_WARRIOR.to_string(), &[caster_pos])
+        .expect("召喚應成功");
+
+    let expected_pos = Position {
+        x: caster_pos.x,
+        y: caster_pos.y + 1,
+    };
+    assert_eq!(
+        summons(&mut world),
+        vec![(
+            expected_pos,
+            UNIT_TYPE_WARRIOR.to_string(),
+            PLAYER_FACTION_ID,
+            1
+        )],
+        "召喚物應為施放者陣營的 warrior，出現在下方空格"
+    );
+}
+
+#[test]
+fn test_summon_disappears_after_duration() {
+    let (mut world, _, markers) = build_mage_world("P . E");
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    let caster_pos = markers["P"][0];
+
+    execute_skill(&mut world, &SKILL_SUMMON_WARRIOR.to_string(), &[caster_pos])
+        .expect("召喚應成功");
+    assert_eq!(summons(&mut world).len(), 1, "召喚後應有 1 個召喚物");
+
+    // duration = 1：下一輪開始時移除，且不出現在新一輪的回合表中
+    finish_round(&mut world);
+
+    assert!(summons(&mut world).is_empty(), "持續時間結束後召喚物應消失");
+    let in_turn_order = get_turn_order(&world).expect("應有回合表").entries.len();
+    assert_eq!(in_turn_order, 2, "新一輪回合表只應有原本的 2 個單位");
+}
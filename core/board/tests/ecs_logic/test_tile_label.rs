@@ -0,0 +1,75 @@
+//! 格子註記（TileLabel）序列化測試
+
+use super::constants::UNIT_TYPE_WARRIOR;
+use super::{ENEMY_FACTION_ID, setup_world_with_level};
+use bevy_ecs::prelude::Entity;
+use board::ecs_types::components::Position;
+use board::loader_schema::{LevelType, TileLabel};
+use board::test_helpers::level_builder::LevelBuilder;
+
+fn create_level() -> LevelType {
+    LevelBuilder::from_ascii(
+        "
+        D . .
+        . . E
+        ",
+    )
+    .deploy("D")
+    .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+    .to_level()
+    .expect("LevelBuilder::to_level 應成功")
+}
+
+fn round_trip(level: &LevelType) -> LevelType {
+    let level_toml = toml::to_string(level).expect("關卡應可序列化");
+    toml::from_str(&level_toml).expect("關卡應可反序列化")
+}
+
+#[test]
+fn test_tile_labels_round_trip() {
+    let without_labels = create_level();
+    assert_eq!(round_trip(&without_labels).tile_labels, vec![]);
+
+    let mut with_labels = create_level();
+    with_labels.tile_labels = vec![
+        TileLabel {
+            position: Position { x: 2, y: 1 },
+            text: "首領生成點".to_string(),
+        },
+        TileLabel {
+            position: Position { x: 0, y: 1 },
+            text: "伏兵".to_string(),
+        },
+    ];
+    assert_eq!(
+        round_trip(&with_labels).tile_labels,
+        with_labels.tile_labels
+    );
+}
+
+#[test]
+fn test_tile_labels_default_for_old_files_and_ignored_by_gameplay() {
+    let mut level_toml = toml::to_string(&create_level()).expect("關卡應可序列化");
+    level_toml = level_toml
+        .lines()
+        .filter(|line| !line.starts_with("tile_labels"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let old_level: LevelType = toml::from_str(&level_toml).expect("舊關卡檔應可載入");
+    assert!(old_level.tile_labels.is_empty(), "缺少欄位時應為空");
+
+    let count_entities = |level: &LevelType| {
+        let mut world = setup_world_with_level(&toml::to_string(level).expect("關卡應可序列化"));
+        world.query::<Entity>().iter(&world).count()
+    };
+    let mut labeled = create_level();
+    labeled.tile_labels.push(TileLabel {
+        position: Position { x: 1, y: 0 },
+        text: "註記".to_string(),
+    });
+    assert_eq!(
+        count_entities(&labeled),
+        count_entities(&create_level()),
+        "註記不應生成任何 entity"
+    );
+}
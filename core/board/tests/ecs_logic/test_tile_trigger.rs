@@ -0,0 +1,144 @@
+//! 地格機關（傷害陷阱、目標點）測試
+
+use super::constants::{OBJECT_TYPE_OBJECTIVE, OBJECT_TYPE_TRAP, UNIT_TYPE_WARRIOR};
+use super::setup_world_with_level;
+use bevy_ecs::prelude::{Entity, With, World};
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::domain::core_types::{EndLevelCondition, LevelOutcome};
+use board::ecs_logic::level_outcome::resolve_level_outcome;
+use board::ecs_logic::movement::{advance_move, plan_move};
+use board::ecs_logic::query::get_objective_holders;
+use board::ecs_logic::turn::start_new_round;
+use board::ecs_types::components::{CurrentHp, Initiative, Position, Unit, UnitFaction};
+use board::test_helpers::level_builder::{LevelBuilder, load_from_ascii};
+use std::collections::HashMap;
+
+const ENEMY_FACTION_ID: u32 = 2;
+const TRAP_DAMAGE: i32 = 15;
+const VICTORY_KEY: &str = "capture";
+
+/// 建立 P（玩家，先手）與 E（敵人）的 World；T 為傷害陷阱、O 為目標點
+fn build_world(ascii: &str) -> (World, HashMap<String, Vec<Position>>) {
+    let (_, markers) = load_from_ascii(ascii).expect("load_from_ascii 應成功");
+    let level_toml = LevelBuilder::from_ascii(ascii)
+        .unit("P", UNIT_TYPE_WARRIOR, PLAYER_FACTION_ID)
+        .unit("E", UNIT_TYPE_WARRIOR, ENEMY_FACTION_ID)
+        .object("T", OBJECT_TYPE_TRAP)
+        .object("O", OBJECT_TYPE_OBJECTIVE)
+        .victory_conditions(vec![(
+            VICTORY_KEY.to_string(),
+            vec![EndLevelCondition::CaptureObjective(PLAYER_FACTION_ID)],
+        )])
+        .to_toml()
+        .expect("to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+    let player = find_unit_at(&mut world, markers["P"][0]);
+    world.entity_mut(player).insert(Initiative(100));
+    start_new_round(&mut world).expect("start_new_round 應成功");
+    (world, markers)
+}
+
+fn find_unit_at(world: &mut World, pos: Position) -> Entity {
+    world
+        .query_filtered::<(Entity, &Position), With<Unit>>()
+        .iter(world)
+        .find(|(_, p)| **p == pos)
+        .map(|(entity, _)| entity)
+        .expect("應找到指定位置的單位")
+}
+
+fn current_hp(world: &World, entity: Entity) -> i32 {
+    world.entity(entity).get::<CurrentHp>().expect("應有 HP").0
+}
+
+fn move_to(world: &mut World, target: Position) {
+    plan_move(world, target).expect("plan_move 應成功");
+    advance_move(world).expect("advance_move 應成功");
+}
+
+#[test]
+fn test_trap_damages_unit_entering_it() {
+    // 單列棋盤，路徑必經陷阱：穿過與停在陷阱上都會受傷
+    for (label, destination) in [("穿過陷阱", "D"), ("停在陷阱上", "T")] {
+        let (mut world, markers) = build_world("P T D . E");
+        let player = find_unit_at(&mut world, markers["P"][0]);
+        let hp_before = current_hp(&world, player);
+
+        move_to(&mut world, markers[destination][0]);
+
+        assert_eq!(
+            current_hp(&world, player),
+            hp_before - TRAP_DAMAGE,
+            "[{label}] 踏入陷阱應受到 {TRAP_DAMAGE} 點傷害"
+        );
+    }
+}
+
+#[test]
+fn test_trap_not_triggered_when_path_avoids_it() {
+    // 從 P 往右走不經過陷阱（陷阱在另一列）
+    let (mut world, markers) = build_world(
+        "
+        P . D E
+        T . . .
+    ",
+    );
+    let player = find_unit_at(&mut world, markers["P"][0]);
+    let hp_before = current_hp(&world, player);
+
+    move_to(&mut world, markers["D"][0]);
+
+    assert_eq!(current_hp(&world, player), hp_before, "未踏入陷阱不應受傷");
+}
+
+#[test]
+fn test_objective_captured_when_unit_ends_on_it() {
+    let (mut world, markers) = build_world("P . O . E");
+    let objective = markers["O"][0];
+
+    assert!(
+        get_objective_holders(&mut world).is_empty(),
+        "無人站在目標點時不應有佔領者"
+    );
+    assert_eq!(
+        resolve_level_outcome(&mut world).expect("resolve_level_outcome 應成功"),
+        LevelOutcome::Undetermined
+    );
+
+    move_to(&mut world, objective);
+
+    let player = find_unit_at(&mut world, objective);
+    let faction = world
+        .entity(player)
+        .get::<UnitFaction>()
+        .expect("應有陣營")
+        .0;
+    assert_eq!(
+        get_objective_holders(&mut world),
+        HashMap::from([(objective, faction)]),
+        "停在目標點上的單位應由其陣營佔領"
+    );
+    assert_eq!(
+        resolve_level_outcome(&mut world).expect("resolve_level_outcome 應成功"),
+        LevelOutcome::Victory(VICTORY_KEY.to_string()),
+        "玩家佔領目標點應達成 CaptureObjective 勝利條件"
+    );
+}
+
+#[test]
+fn test_objective_held_by_other_faction_does_not_count() {
+    let (mut world, markers) = build_world("P . . O E");
+    // 把敵人放到目標點上
+    let enemy = find_unit_at(&mut world, markers["E"][0]);
+    world.entity_mut(enemy).insert(markers["O"][0]);
+
+    assert_eq!(
+        get_objective_holders(&mut world).get(&markers["O"][0]),
+        Some(&ENEMY_FACTION_ID)
+    );
+    assert_eq!(
+        resolve_level_outcome(&mut world).expect("resolve_level_outcome 應成功"),
+        LevelOutcome::Undetermined,
+        "敵方佔領目標點不應達成玩家的佔領條件"
+    );
+}
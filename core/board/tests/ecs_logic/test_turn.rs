@@ -5,16 +5,18 @@ use super::setup_world_with_level;
 use bevy_ecs::prelude::{Entity, With, World};
 use board::domain::battle_log::LogEvent;
 use board::domain::constants::PLAYER_FACTION_ID;
-use board::domain::core_types::{BuffType, EndCondition, PendingReaction, ReactionTrigger};
+use board::domain::core_types::{
+    BuffType, EndCondition, PendingReaction, ReactionTrigger, StackingPolicy,
+};
 use board::ecs_logic::movement::{advance_move, plan_move};
 use board::ecs_logic::query::get_battle_log;
 use board::ecs_logic::spawner::spawn_level;
 use board::ecs_logic::turn::{
     can_delay_current_unit, delay_current_unit, end_battle, end_current_turn, get_current_unit,
-    get_turn_order, resolve_deaths, start_new_round,
+    get_turn_order, refresh_turn_order, resolve_deaths, start_new_round,
 };
 use board::ecs_types::components::{
-    AppliedBuff, CurrentHp, MaxReactionPoint, Occupant, Position, ReactionPoint, Unit,
+    AppliedBuff, CurrentHp, Initiative, MaxReactionPoint, Occupant, Position, ReactionPoint, Unit,
 };
 use board::ecs_types::resources::{BattleLog, ReactionState};
 use board::test_helpers::level_builder::LevelBuilder;
@@ -274,6 +276,51 @@ fn test_delay_current_unit_moves_unit_to_target_position() {
     assert_eq!(turn_order.current_index, 1);
 }
 
+/// 驗證 INI 減益使本輪尚未行動的單位延後
+#[test]
+fn test_refresh_turn_order_pushes_debuffed_unit_later() {
+    let level_toml = LevelBuilder::from_ascii(
+        "
+        . U1 . U2 . U3 .
+        . . . . . . .
+    ",
+    )
+    .unit("U1", UNIT_TYPE_WARRIOR, 1)
+    .unit("U2", UNIT_TYPE_WARRIOR, 1)
+    .unit("U3", UNIT_TYPE_MAGE, 1)
+    .to_toml()
+    .expect("LevelBuilder::to_toml 應成功");
+    let mut world = setup_world_with_level(&level_toml);
+
+    let turn_order = start_new_round(&mut world).expect("開始回合應成功");
+    let first = turn_order.entries[0].occupant;
+    let second = turn_order.entries[1].occupant;
+    let third = turn_order.entries[2].occupant;
+
+    // 第二個單位被 INI 減益，遠低於其他單位的擲骰範圍
+    let entity = world
+        .query::<(Entity, &Occupant)>()
+        .iter(&world)
+        .find(|(_, occupant)| **occupant == second)
+        .map(|(entity, _)| entity)
+        .expect("應找到第二個單位");
+    world.entity_mut(entity).insert(Initiative(-100));
+    refresh_turn_order(&mut world).expect("refresh_turn_order 應成功");
+
+    let turn_order = get_turn_order(&world).expect("應取得 TurnOrder");
+    let result_occupants: Vec<Occupant> = turn_order.entries.iter().map(|e| e.occupant).collect();
+    assert_eq!(
+        result_occupants,
+        vec![first, third, second],
+        "被減益的單位應延後到本輪最後"
+    );
+    assert_eq!(
+        get_current_unit(turn_order).expect("應有當前單位"),
+        first,
+        "當前行動單位不受影響"
+    );
+}
+
 /// 驗證 delay 後 end_current_turn 正確完成輪次
 #[test]
 fn test_delay_then_end_turn_completes_round() {
@@ -420,11 +467,13 @@ fn spawn_buff_with_duration(world: &mut World, target: Occupant, duration: u32)
     world.spawn((AppliedBuff {
         def: BuffType {
             name: "timed".to_string(),
-            stackable: false,
+            beneficial: false,
+            stacking: StackingPolicy::Stack,
             while_active: vec![],
             per_turn_effects: vec![],
             end_conditions: vec![EndCondition::Duration(duration)],
-        },
+        }
+        .into(),
         caster: target,
         target,
         remaining_duration: Some(duration),
@@ -0,0 +1,57 @@
+//! 戰爭迷霧可見格子查詢測試
+
+use super::build_warrior_world;
+use board::domain::constants::PLAYER_FACTION_ID;
+use board::ecs_logic::query::{get_faction_visibility, get_visible_positions};
+use board::ecs_types::components::Position;
+
+const SIGHT_RANGE: usize = 4;
+
+/// 牆（w）後方的格子（s）落在牆的陰影中，不可見；其餘範圍內格子可見
+#[test]
+fn test_wall_casts_shadow() {
+    let (mut world, occupant, markers) = build_warrior_world(
+        "
+        . . . . .
+        P . w s s
+        . . . . .
+        ",
+    );
+
+    let visible =
+        get_visible_positions(&mut world, occupant, SIGHT_RANGE).expect("查詢可見格子應成功");
+
+    assert!(visible.contains(&markers["P"][0]), "自身所在格應可見");
+    for pos in &markers["s"] {
+        assert!(!visible.contains(pos), "牆後格子 {pos:?} 應不可見");
+    }
+    // 上排不受牆遮蔽，視野範圍內（x ≤ 3）皆可見
+    for x in 0..=3 {
+        let pos = Position { x, y: 0 };
+        assert!(visible.contains(&pos), "上排格子 {pos:?} 應可見");
+    }
+}
+
+/// 陣營可見格子為各單位可見格子的聯集：另一側的友軍可補上牆後陰影
+#[test]
+fn test_faction_visibility_is_union() {
+    let (mut world, _, markers) = build_warrior_world(
+        "
+        . . . . .
+        P . w . P
+        . . . . .
+        ",
+    );
+
+    let visible = get_faction_visibility(&mut world, PLAYER_FACTION_ID, SIGHT_RANGE)
+        .expect("查詢陣營可見格子應成功");
+
+    for pos in &markers["P"] {
+        assert!(visible.contains(pos), "友軍所在格 {pos:?} 應可見");
+    }
+    assert!(
+        !visible.contains(&markers["w"][0]),
+        "牆本身阻擋視線，不列入可見"
+    );
+    assert_eq!(visible.len(), 14, "除牆外的 14 格應皆可見");
+}
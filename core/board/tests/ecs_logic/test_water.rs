@@ -0,0 +1,88 @@
+//! 水域地形整合測試（淺水移動加倍、深水不可通行且禁火）
+
+use super::constants::{SKILL_DIAMOND_AOE, SKILL_MELEE};
+use super::{build_mage_world, build_warrior_world};
+use bevy_ecs::prelude::{Entity, World};
+use board::domain::constants::BASIC_MOVEMENT_COST;
+use board::domain::core_types::SkillTag;
+use board::ecs_logic::movement::{get_reachable_positions, measure_between};
+use board::ecs_logic::skill::{execute_skill, get_available_skills, start_skill_targeting};
+use board::ecs_logic::turn::start_new_round;
+use board::ecs_types::components::{Occupant, Position};
+use board::error::{ErrorKind, UnitError};
+
+fn skill_usable(world: &mut World, skill_name: &str) -> bool {
+    get_available_skills(world)
+        .expect("get_available_skills 應成功")
+        .into_iter()
+        .find(|skill| skill.name == skill_name)
+        .expect("應列出指定技能")
+        .usable
+}
+
+/// 淺水（s）進入成本為一般格的兩倍；深水（d）地面單位無法進入
+#[test]
+fn test_water_movement_cost() {
+    let (mut world, occupant, markers) = build_warrior_world(
+        "
+        P s T
+        . d .
+        ",
+    );
+    let from = markers["P"][0];
+
+    let to_shallow = measure_between(&mut world, from, markers["s"][0]).expect("測距應成功");
+    assert_eq!(to_shallow.movement_cost, Some(2 * BASIC_MOVEMENT_COST));
+
+    let reachable = get_reachable_positions(&mut world, occupant).expect("計算可到達位置應成功");
+    assert!(!reachable.contains_key(&markers["d"][0]), "深水不可通行");
+}
+
+/// 站在深水中（直接放置，地面單位無法走入）時不可施放火焰技能，其他技能不受影響
+#[test]
+fn test_fire_skill_forbidden_in_deep_water() {
+    let (mut world, occupant, markers) = build_mage_world("P d E");
+    let deep_water = markers["d"][0];
+    let caster_entity = world
+        .query::<(Entity, &Occupant)>()
+        .iter(&world)
+        .find(|(_, occ)| **occ == occupant)
+        .map(|(entity, _)| entity)
+        .expect("應找到玩家單位");
+    *world
+        .get_mut::<Position>(caster_entity)
+        .expect("玩家單位應有 Position") = deep_water;
+    start_new_round(&mut world).expect("start_new_round 應成功");
+
+    let fire_skill = SKILL_DIAMOND_AOE.to_string();
+    assert!(
+        !skill_usable(&mut world, SKILL_DIAMOND_AOE),
+        "深水中火焰技能不應可用"
+    );
+    assert!(
+        skill_usable(&mut world, SKILL_MELEE),
+        "深水中非火焰技能仍可用"
+    );
+
+    let err = start_skill_targeting(&mut world, &fire_skill).expect_err("應拒絕開始選目標");
+    assert!(
+        matches!(
+            err.kind(),
+            ErrorKind::Unit(UnitError::SkillForbiddenByTerrain {
+                tag: SkillTag::Fire,
+                ..
+            })
+        ),
+        "應回報地形禁止火焰技能，實際為 {err:?}"
+    );
+
+    let err =
+        execute_skill(&mut world, &fire_skill, &[markers["E"][0]]).expect_err("應拒絕執行技能");
+    assert!(
+        matches!(
+            err.kind(),
+            ErrorKind::Unit(UnitError::SkillForbiddenByTerrain { .. })
+        ),
+        "應回報地形禁止施放，實際為 {err:?}"
+    );
+}
@@ -1,16 +1,21 @@
 use crate::constants::{
-    DATA_DIRECTORY_PATH, FILE_EXTENSION_TOML, LIST_PANEL_WIDTH, SPACING_MEDIUM, SPACING_SMALL,
+    AUTOSAVE_INTERVAL, DATA_DIRECTORY_PATH, FILE_EXTENSION_TOML, LIST_PANEL_WIDTH,
+    MESSAGE_SHOW_COUNT, MESSAGE_TTL, SPACING_MEDIUM, SPACING_SMALL,
 };
 use crate::define_editors;
 use crate::editor_item::EditorItem;
-use crate::generic_editor::{EditMode, GenericEditorState, MessageState};
-use crate::generic_io::{load_file, save_file};
+use crate::generic_editor::{EditMode, GenericEditorState, MessageState, should_autosave};
+use crate::generic_io::{
+    autosave_file, check_autosave_recovery, load_file, recover_autosave, reload_file,
+    resolve_pending_reload, save_file,
+};
 use crate::tabs;
 use crate::utils::dnd::render_dnd_handle;
 use crate::utils::search::{match_search_query, render_search_input};
 use board::domain::core_types::SkillType;
 use board::loader_schema::{LevelType, ObjectType, UnitType};
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter};
 
@@ -67,6 +72,12 @@ impl eframe::App for EditorApp {
                     .iter()
                     .map(|obj| obj.name().to_string())
                     .collect();
+                self.skill_editor.ui_state.available_units = self
+                    .unit_editor
+                    .items
+                    .iter()
+                    .map(|unit| unit.name.clone())
+                    .collect();
 
                 render_editor_ui(
                     ui,
@@ -89,6 +100,7 @@ impl eframe::App for EditorApp {
                     .iter()
                     .map(|skill| skill.name().clone())
                     .collect();
+                self.unit_editor.ui_state.skills = self.skill_editor.items.clone();
 
                 for unit in &mut self.unit_editor.items {
                     unit.skills
@@ -131,6 +143,7 @@ fn render_editor_ui<T: EditorItem>(
     let file_path =
         PathBuf::from(DATA_DIRECTORY_PATH).join(format!("{}{}", data_key, FILE_EXTENSION_TOML));
 
+    tick_autosave(ui.ctx(), state, &file_path, data_key);
     render_file_operations_bar(ui, state, &file_path, data_key);
     ui.add_space(SPACING_MEDIUM);
 
@@ -145,6 +158,30 @@ fn render_editor_ui<T: EditorItem>(
     });
 }
 
+/// 有未儲存修改時定期寫入自動存檔（與手動儲存分開，不清除未儲存標記）
+fn tick_autosave<T: EditorItem>(
+    ctx: &egui::Context,
+    state: &mut GenericEditorState<T>,
+    file_path: &Path,
+    data_key: &str,
+) {
+    let now = Instant::now();
+    let has_unsaved_changes = !state.dirty_items.is_empty();
+    let since_last_autosave = now.duration_since(*state.last_autosave.get_or_insert(now));
+    if should_autosave(has_unsaved_changes, since_last_autosave, AUTOSAVE_INTERVAL) {
+        autosave_file(state, file_path, data_key);
+        state.last_autosave = Some(now);
+    }
+
+    if has_unsaved_changes {
+        // 閒置時 egui 不會重繪，排程下一次檢查
+        ctx.request_repaint_after(AUTOSAVE_INTERVAL);
+    } else {
+        // 沒有未儲存修改時持續重設計時，出現修改後滿一個間隔才存檔
+        state.last_autosave = Some(now);
+    }
+}
+
 /// 渲染檔案操作列（載入、儲存、訊息）
 fn render_file_operations_bar<T: EditorItem>(
     ui: &mut egui::Ui,
@@ -154,25 +191,54 @@ fn render_file_operations_bar<T: EditorItem>(
 ) {
     ui.horizontal(|ui| {
         if ui.button("載入").clicked() {
-            load_file(state, file_path, data_key);
+            reload_file(state, file_path, data_key);
         }
         if ui.button("儲存").clicked() {
             save_file(state, file_path, data_key);
         }
+        if !state.dirty_items.is_empty() {
+            ui.label(format!("（{} 個未儲存修改）", state.dirty_items.len()));
+        }
+        if state.pending_recovery {
+            ui.colored_label(egui::Color32::YELLOW, "發現比主檔新的自動存檔");
+            if ui.button("還原自動存檔").clicked() {
+                recover_autosave(state, file_path, data_key);
+            }
+            if ui.button("忽略").clicked() {
+                state.pending_recovery = false;
+            }
+        }
+        if state.pending_reload.is_some() {
+            if ui.button("保留本地修改").clicked() {
+                resolve_pending_reload(state, true);
+            }
+            if ui.button("採用磁碟版本").clicked() {
+                resolve_pending_reload(state, false);
+            }
+        }
 
         ui.add_space(SPACING_MEDIUM);
 
-        // 訊息區域
+        // 訊息區域（最新在上）
+        state.message_state.expire(Instant::now());
+        if !state.message_state.is_empty() {
+            // 閒置時 egui 不會重繪，排程讓過期訊息消失
+            ui.ctx().request_repaint_after(MESSAGE_TTL);
+        }
         if state.message_state.message_visible {
             if ui.button("隱藏").clicked() {
                 state.message_state.message_visible = false;
             }
-            let color = if state.message_state.is_error {
-                egui::Color32::RED
-            } else {
-                egui::Color32::GREEN
-            };
-            ui.colored_label(color, &state.message_state.message);
+            ui.vertical(|ui| {
+                for message in state.message_state.recent(MESSAGE_SHOW_COUNT) {
+                    let color = if message.is_error {
+                        egui::Color32::RED
+                    } else {
+                        egui::Color32::GREEN
+                    };
+                    ui.colored_label(color, &message.text);
+                }
+            });
         } else {
             if ui.button("顯示訊息").clicked() {
                 state.message_state.message_visible = true;
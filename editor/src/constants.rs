@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 pub(crate) const APP_TITLE: &str = "編輯器";
 pub(crate) const FONT_FILE_PATH: &str = "fonts/NotoSans.ttf";
 pub(crate) const FONT_NAME: &str = "NotoSans";
@@ -24,10 +26,28 @@ pub(crate) const BOTTOM_PANEL_BUTTON_WIDTH: f32 = 130.0;
 
 // UI 數值
 pub(crate) const DRAG_VALUE_SPEED: f64 = 1.0;
+pub(crate) const PERCENT_SCALE: f32 = 100.0;
 
 // 檔案相關
 pub(crate) const DATA_DIRECTORY_PATH: &str = "ignore-data/";
 pub(crate) const FILE_EXTENSION_TOML: &str = ".toml";
+pub(crate) const FILE_SUFFIX_TEMP: &str = ".tmp";
+pub(crate) const FILE_SUFFIX_BACKUP: &str = ".bak";
+pub(crate) const FILE_SUFFIX_AUTOSAVE: &str = ".autosave";
+/// 有未儲存修改時，自動存檔的最短間隔
+pub(crate) const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// 儲存時保留的舊版本數量（.bak.1 為最新）
+pub(crate) const BACKUP_COUNT: usize = 3;
+/// 序列化單一項目快照時使用的 TOML key（僅用於比對，不寫入檔案）
+pub(crate) const SNAPSHOT_KEY: &str = "item";
+
+// 操作訊息
+/// 保留的訊息數量上限（超過時捨棄最舊的）
+pub(crate) const MESSAGE_CAPACITY: usize = 20;
+/// 訊息顯示多久後自動過期
+pub(crate) const MESSAGE_TTL: Duration = Duration::from_secs(5);
+/// 訊息區顯示最新幾則
+pub(crate) const MESSAGE_SHOW_COUNT: usize = 3;
 
 // 編輯器相關
 pub(crate) const COPY_SUFFIX: &str = "-copy";
@@ -39,6 +59,12 @@ pub(crate) const CLEAR_LABEL: &str = "── 清除 ──";
 pub(crate) const BATTLEFIELD_CELL_SIZE: f32 = 36.0;
 pub(crate) const BATTLEFIELD_GRID_SPACING: f32 = 2.0;
 pub(crate) const BATTLEFIELD_TEXT_SIZE: f32 = 14.0;
+// 關卡編輯器 - 戰場預覽 - 座標尺
+pub(crate) const BATTLEFIELD_RULER_TEXT_SIZE: f32 = 10.0;
+pub(crate) const BATTLEFIELD_RULER_COLOR: egui::Color32 = egui::Color32::WHITE;
+// 關卡編輯器 - 戰場預覽 - 格子註記
+pub(crate) const BATTLEFIELD_LABEL_TEXT_SIZE: f32 = 9.0;
+pub(crate) const BATTLEFIELD_LABEL_COLOR: egui::Color32 = egui::Color32::LIGHT_YELLOW;
 
 // 關卡編輯器 - 戰場預覽 - 顏色
 pub(crate) const BATTLEFIELD_COLOR_DEPLOYMENT: egui::Color32 = egui::Color32::LIGHT_GREEN;
@@ -46,6 +72,8 @@ pub(crate) const BATTLEFIELD_COLOR_UNIT: egui::Color32 = egui::Color32::DARK_GRA
 pub(crate) const BATTLEFIELD_COLOR_OBJECT: egui::Color32 = egui::Color32::GRAY;
 pub(crate) const BATTLEFIELD_COLOR_EMPTY: egui::Color32 = egui::Color32::DARK_GREEN;
 pub(crate) const BATTLEFIELD_COLOR_HIGHLIGHT: egui::Color32 = egui::Color32::YELLOW;
+// 關卡編輯器 - 戰場預覽 - 框選範圍與已選取單位框
+pub(crate) const BATTLEFIELD_COLOR_SELECTION: egui::Color32 = egui::Color32::LIGHT_BLUE;
 // 關卡編輯器 - 戰場預覽 - 目前行動單位框
 pub(crate) const BATTLEFIELD_COLOR_CURRENT_UNIT: egui::Color32 = egui::Color32::GREEN;
 // 關卡編輯器 - 戰場預覽 - 技能相關顏色
@@ -65,4 +93,38 @@ pub(crate) const BATTLEFIELD_COLOR_MOVE_PATH: egui::Color32 =
 // 關卡編輯器 - 戰鬥 - 關卡結局字幕顏色
 pub(crate) const OUTCOME_BANNER_COLOR_VICTORY: egui::Color32 = egui::Color32::LIGHT_GREEN;
 pub(crate) const OUTCOME_BANNER_COLOR_DEFEAT: egui::Color32 = egui::Color32::LIGHT_RED;
+pub(crate) const OUTCOME_BANNER_COLOR_DRAW: egui::Color32 = egui::Color32::LIGHT_YELLOW;
 pub(crate) const OUTCOME_BANNER_TEXT_SIZE: f32 = 20.0;
+// 關卡編輯器 - 勾選限制回合時的預設上限
+pub(crate) const DEFAULT_MAX_ROUNDS: u32 = 20;
+// 關卡編輯器 - 棋盤面積超過此值時，放大須確認後才套用（低於核心的硬上限 MAX_BOARD_AREA）
+pub(crate) const LARGE_BOARD_CONFIRM_AREA: usize = 2_500;
+
+// 在地化 - 列舉顯示名稱（key 為「型別名稱::變體名稱」，未列出的變體顯示 strum 名稱）
+pub(crate) const LOCALE_ZH_TW: &[(&str, &str)] = &[
+    ("SkillTag::Flankable", "可夾擊"),
+    (
+        "SkillTag::AccuracyPenaltyWhenEnemyAdjacent",
+        "敵人相鄰時命中減值",
+    ),
+    ("SkillTag::UsableAfterDoubleMove", "兩次移動後可用"),
+    ("SkillTag::AllowedDuringGrabbing", "擒抱中可用"),
+    ("SkillTag::Melee", "近戰"),
+    ("SkillTag::Ranged", "遠程"),
+    ("SkillTag::Magic", "法術"),
+    ("SkillTag::Fire", "火焰"),
+    ("SkillTag::NoFriendlyFire", "無友軍傷害"),
+    ("RangeMetric::Manhattan", "曼哈頓"),
+    ("RangeMetric::Chebyshev", "切比雪夫"),
+    ("RangeMetric::Euclidean", "歐幾里得"),
+    ("TargetFilter::Any", "任意"),
+    ("TargetFilter::AnyExceptCaster", "施放者以外"),
+    ("TargetFilter::Ally", "友方"),
+    ("TargetFilter::AllyExceptCaster", "施放者以外的友方"),
+    ("TargetFilter::Enemy", "敵方"),
+    ("TargetFilter::CasterOnly", "僅施放者"),
+    ("TargetSelection::Unit", "單位"),
+    ("TargetSelection::Ground", "地面"),
+    ("TileTrigger::Damage", "傷害陷阱"),
+    ("TileTrigger::Objective", "目標點"),
+];
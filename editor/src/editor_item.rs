@@ -24,9 +24,14 @@ pub trait EditorItem:
         Self::type_name()
     }
 
-    /// 驗證項目（confirm_edit 時呼叫）
+    /// 驗證項目（confirm_edit 時呼叫），可參考 UI 狀態（如其他 tab 的資料）
     /// 返回 Ok(()) 表示驗證通過，Err(String) 表示驗證失敗
-    fn validate(&self, all_items: &[Self], editing_index: Option<usize>) -> Result<(), String> {
+    fn validate(
+        &self,
+        all_items: &[Self],
+        editing_index: Option<usize>,
+        _ui_state: &Self::UIState,
+    ) -> Result<(), String> {
         validate_name(self, all_items, editing_index)
     }
 
@@ -49,11 +49,9 @@ macro_rules! define_editors {
                 $(
                     {
                         let file_name = $file_fn();
-                        load_file(
-                            &mut app.$field,
-                            &data_dir.join(format!("{}.toml", file_name)),
-                            file_name,
-                        );
+                        let file_path = data_dir.join(format!("{}.toml", file_name));
+                        load_file(&mut app.$field, &file_path, file_name);
+                        check_autosave_recovery(&mut app.$field, &file_path);
                     }
                 )*
 
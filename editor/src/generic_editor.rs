@@ -1,7 +1,9 @@
 //! 泛型編輯器狀態管理
 
-use crate::constants::COPY_SUFFIX;
+use crate::constants::{COPY_SUFFIX, MESSAGE_CAPACITY, MESSAGE_TTL};
 use crate::editor_item::EditorItem;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant, SystemTime};
 
 /// 編輯模式及項目狀態
 #[derive(Debug, Clone, Default)]
@@ -15,13 +17,22 @@ pub enum EditMode<T> {
     Editing(usize, T),
 }
 
-/// 訊息狀態（成功或錯誤訊息）
-#[derive(Debug, Default)]
-pub struct MessageState {
-    /// 最後的操作訊息（成功或錯誤）
-    pub message: String,
+/// 單則操作訊息
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusMessage {
+    pub text: String,
     /// 訊息是否為錯誤
     pub is_error: bool,
+    pub created_at: Instant,
+}
+
+/// 訊息狀態：依時間排列的操作訊息佇列（最多 MESSAGE_CAPACITY 則，超過 MESSAGE_TTL 自動過期）
+///
+/// 連續操作各自留下訊息，不會互相覆蓋。
+#[derive(Debug, Default)]
+pub struct MessageState {
+    /// 舊到新排列
+    messages: VecDeque<StatusMessage>,
     /// 訊息是否可見
     pub message_visible: bool,
 }
@@ -29,17 +40,91 @@ pub struct MessageState {
 impl MessageState {
     /// 設置成功訊息
     pub fn set_success(&mut self, msg: impl Into<String>) {
-        self.message = msg.into();
-        self.is_error = false;
-        self.message_visible = true;
+        self.push_at(msg, false, Instant::now());
     }
 
     /// 設置錯誤訊息
     pub fn set_error(&mut self, msg: impl Into<String>) {
-        self.message = msg.into();
-        self.is_error = true;
+        self.push_at(msg, true, Instant::now());
+    }
+
+    /// 在指定時間加入訊息，超過容量時捨棄最舊的
+    pub fn push_at(&mut self, msg: impl Into<String>, is_error: bool, now: Instant) {
+        self.messages.push_back(StatusMessage {
+            text: msg.into(),
+            is_error,
+            created_at: now,
+        });
+        while self.messages.len() > MESSAGE_CAPACITY {
+            self.messages.pop_front();
+        }
         self.message_visible = true;
     }
+
+    /// 移除在 now 時已超過 MESSAGE_TTL 的訊息
+    pub fn expire(&mut self, now: Instant) {
+        self.messages
+            .retain(|message| now.duration_since(message.created_at) < MESSAGE_TTL);
+    }
+
+    /// 最新的 count 則訊息（新到舊）
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &StatusMessage> {
+        self.messages.iter().rev().take(count)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+/// 重新載入時單一項目的合併決策
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadDecision {
+    /// 本地未修改：直接採用磁碟版本
+    TakeDisk,
+    /// 只有本地修改、磁碟未變：保留本地版本
+    KeepLocal,
+    /// 本地與磁碟都改過：交由使用者決定
+    Conflict,
+}
+
+/// 等待使用者處理衝突的重新載入
+#[derive(Debug)]
+pub struct PendingReload<T> {
+    /// 剛從磁碟讀到的項目
+    pub disk_items: Vec<T>,
+    /// 本地與磁碟都改過的項目名稱
+    pub conflicts: Vec<String>,
+}
+
+/// 依「本地是否有未儲存修改」與「磁碟是否自上次載入後被改過」決定合併方式
+pub fn reload_decision(locally_modified: bool, disk_changed: bool) -> ReloadDecision {
+    match (locally_modified, disk_changed) {
+        (false, _) => ReloadDecision::TakeDisk,
+        (true, false) => ReloadDecision::KeepLocal,
+        (true, true) => ReloadDecision::Conflict,
+    }
+}
+
+/// 有未儲存修改且距上次自動存檔已滿 interval 時才自動存檔
+pub fn should_autosave(
+    has_unsaved_changes: bool,
+    since_last_autosave: Duration,
+    interval: Duration,
+) -> bool {
+    has_unsaved_changes && since_last_autosave >= interval
+}
+
+/// 自動存檔比主檔新時才需要提示還原；沒有自動存檔則不提示，沒有主檔則一律提示
+pub fn autosave_is_newer(
+    main_modified: Option<SystemTime>,
+    autosave_modified: Option<SystemTime>,
+) -> bool {
+    match (main_modified, autosave_modified) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(main), Some(autosave)) => autosave > main,
+    }
 }
 
 /// 泛型編輯器狀態
@@ -60,6 +145,17 @@ pub struct GenericEditorState<T: EditorItem> {
 
     /// 編輯器 UI 狀態（搜尋、拖曳等）
     pub ui_state: T::UIState,
+
+    /// 自上次載入或儲存後被新增、編輯或刪除的項目名稱
+    pub dirty_items: HashSet<String>,
+    /// 上次載入或儲存時各項目的序列化內容，用來判斷磁碟是否被外部修改
+    pub disk_snapshot: HashMap<String, String>,
+    /// 重新載入遇到衝突時暫存的磁碟資料，等待使用者選擇
+    pub pending_reload: Option<PendingReload<T>>,
+    /// 自動存檔計時起點：上次自動存檔，或最近一次沒有未儲存修改的時刻
+    pub last_autosave: Option<Instant>,
+    /// 啟動時發現比主檔新的自動存檔，等待使用者選擇是否還原
+    pub pending_recovery: bool,
 }
 
 impl<T: EditorItem> GenericEditorState<T> {
@@ -108,7 +204,7 @@ impl<T: EditorItem> GenericEditorState<T> {
         };
 
         // 驗證項目
-        if let Err(e) = item.validate(&self.items, editing_index) {
+        if let Err(e) = item.validate(&self.items, editing_index, &self.ui_state) {
             self.message_state.set_error(e);
             // 復原 edit_mode（validate 失敗時保留編輯狀態）
             self.edit_mode = match editing_index {
@@ -127,6 +223,7 @@ impl<T: EditorItem> GenericEditorState<T> {
             None => {
                 // Creating
                 let name = confirmed_item.name().to_string();
+                self.dirty_items.insert(name.clone());
                 self.items.push(confirmed_item);
                 self.message_state
                     .set_success(format!("成功新增{}：{}", T::type_name(), name));
@@ -142,6 +239,10 @@ impl<T: EditorItem> GenericEditorState<T> {
                 }
 
                 let name = confirmed_item.name().to_string();
+                // 改名時舊名稱也算被修改（等同刪除舊項目）
+                self.dirty_items
+                    .insert(self.items[index].name().to_string());
+                self.dirty_items.insert(name.clone());
                 self.items[index] = confirmed_item;
                 self.message_state
                     .set_success(format!("成功編輯{}：{}", T::type_name(), name));
@@ -165,6 +266,7 @@ impl<T: EditorItem> GenericEditorState<T> {
         }
 
         let name = self.items[index].name().to_string();
+        self.dirty_items.insert(name.clone());
         self.items.remove(index);
         self.message_state
             .set_success(format!("成功刪除{}：{}", T::type_name(), name));
@@ -1,11 +1,47 @@
 //! 泛型 TOML I/O 功能
 
+use crate::constants::{
+    BACKUP_COUNT, FILE_EXTENSION_TOML, FILE_SUFFIX_AUTOSAVE, FILE_SUFFIX_BACKUP, FILE_SUFFIX_TEMP,
+    SNAPSHOT_KEY,
+};
 use crate::editor_item::EditorItem;
-use crate::generic_editor::GenericEditorState;
+use crate::generic_editor::{
+    GenericEditorState, PendingReload, ReloadDecision, autosave_is_newer, reload_decision,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 檔案 I/O 錯誤（皆帶檔案路徑，解析錯誤另帶行列位置）
+#[derive(Debug, thiserror::Error)]
+pub enum FileIoError {
+    #[error("讀取檔案失敗：{path} - {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("解析 TOML 失敗：{path}{location} - {message}")]
+    Parse {
+        path: PathBuf,
+        /// 「:行:列」，toml 未提供 span 時為空字串
+        location: String,
+        message: String,
+    },
+    #[error("TOML 檔案中找不到 key：{path} - {key}")]
+    MissingKey { path: PathBuf, key: String },
+    #[error("序列化 TOML 失敗：{path} - {source}")]
+    Serialize {
+        path: PathBuf,
+        source: toml::ser::Error,
+    },
+    #[error("建立目錄失敗：{path} - {source}")]
+    CreateDir { path: PathBuf, source: io::Error },
+    #[error("儲存檔案失敗：{path} - {source}")]
+    Write { path: PathBuf, source: io::Error },
+    #[error("備份檔案失敗：{path} - {source}")]
+    Backup { path: PathBuf, source: io::Error },
+    #[error("取代檔案失敗：{path} - {source}")]
+    Rename { path: PathBuf, source: io::Error },
+}
 
 /// 泛型 TOML 資料容器
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,10 +58,108 @@ impl<T> ItemsData<T> {
         Self { items_map: map }
     }
 
-    /// 取得項目列表
-    pub fn get_items(&self, key: &str) -> Option<&Vec<T>> {
-        self.items_map.get(key)
+    /// 取出項目列表
+    pub fn take_items(mut self, key: &str) -> Option<Vec<T>> {
+        self.items_map.remove(key)
+    }
+}
+
+/// 從 TOML 檔案讀取指定 key 的項目列表
+pub fn read_items<T>(path: &Path, data_key: &str) -> Result<Vec<T>, FileIoError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let content = fs::read_to_string(path).map_err(|source| FileIoError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let data: ItemsData<T> = toml::from_str(&content).map_err(|e| FileIoError::Parse {
+        path: path.to_path_buf(),
+        location: e
+            .span()
+            .map(|span| {
+                let (line, column) = line_column(&content, span.start);
+                format!(":{}:{}", line, column)
+            })
+            .unwrap_or_default(),
+        message: e.message().to_string(),
+    })?;
+
+    data.take_items(data_key)
+        .ok_or_else(|| FileIoError::MissingKey {
+            path: path.to_path_buf(),
+            key: data_key.to_string(),
+        })
+}
+
+/// 將項目列表以指定 key 寫入 TOML 檔案（自動建立上層目錄）
+///
+/// 先寫入同目錄的暫存檔，成功後再 rename 覆蓋目標，寫入中途失敗不會破壞原檔
+/// 覆蓋前將原檔輪替為 `.bak.1`..`.bak.{backup_count}`，0 表示不備份
+pub fn write_items<T: Serialize>(
+    path: &Path,
+    data_key: &str,
+    items: Vec<T>,
+    backup_count: usize,
+) -> Result<(), FileIoError> {
+    let data = ItemsData::new(data_key, items);
+    let content = toml::to_string_pretty(&data).map_err(|source| FileIoError::Serialize {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|source| FileIoError::CreateDir {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+
+    let temp_path = temp_path_for(path);
+    if let Err(source) = fs::write(&temp_path, content) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(FileIoError::Write {
+            path: temp_path,
+            source,
+        });
     }
+
+    if let Err(e) = rotate_backups(path, backup_count) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    fs::rename(&temp_path, path).map_err(|source| {
+        let _ = fs::remove_file(&temp_path);
+        FileIoError::Rename {
+            path: path.to_path_buf(),
+            source,
+        }
+    })
+}
+
+/// 取得第 index 份備份檔路徑（1 為最新）
+pub fn backup_path_for(path: &Path, index: usize) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!("{}.{}", FILE_SUFFIX_BACKUP, index));
+    path.with_file_name(file_name)
+}
+
+/// 取得寫入用暫存檔路徑（與目標同目錄，確保 rename 為原子操作）
+pub fn temp_path_for(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(FILE_SUFFIX_TEMP);
+    path.with_file_name(file_name)
+}
+
+/// 取得自動存檔路徑（與主檔同目錄，如 levels.toml → levels.autosave.toml）
+pub fn autosave_path_for(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(
+        "{}{}{}",
+        stem, FILE_SUFFIX_AUTOSAVE, FILE_EXTENSION_TOML
+    ))
 }
 
 /// 載入檔案
@@ -40,32 +174,12 @@ pub fn load_file<T: EditorItem>(
         return;
     }
 
-    // 讀取檔案
-    let content = match fs::read_to_string(path) {
-        Ok(content) => content,
-        Err(e) => {
-            state
-                .message_state
-                .set_error(format!("載入檔案失敗：{} - {}", path.display(), e));
-            return;
-        }
-    };
-
-    // TOML 反序列化
-    let data: ItemsData<T> = match toml::from_str(&content) {
-        Ok(data) => data,
-        Err(e) => {
-            state
-                .message_state
-                .set_error(format!("解析 TOML 失敗：{}", e));
-            return;
-        }
-    };
-
-    // 更新狀態
-    match data.get_items(data_key) {
-        Some(items) => {
-            state.items = items.clone();
+    match read_items(path, data_key) {
+        Ok(items) => {
+            state.disk_snapshot = snapshot_items(&items);
+            state.dirty_items.clear();
+            state.pending_reload = None;
+            state.items = items;
             state.selected_index = None;
             state.message_state.set_success(format!(
                 "成功載入檔案：{}（共 {} 個{}）",
@@ -74,10 +188,8 @@ pub fn load_file<T: EditorItem>(
                 T::type_name_plural()
             ));
         }
-        None => {
-            state
-                .message_state
-                .set_error(format!("TOML 檔案中找不到 key：{}", data_key));
+        Err(e) => {
+            state.message_state.set_error(e.to_string());
         }
     }
 }
@@ -90,44 +202,246 @@ pub fn save_file<T: EditorItem>(state: &mut GenericEditorState<T>, path: &Path,
         return;
     }
 
-    // 建立資料結構
-    let data = ItemsData::new(data_key, state.items.clone());
+    match write_items(path, data_key, state.items.clone(), BACKUP_COUNT) {
+        Ok(()) => {
+            state.disk_snapshot = snapshot_items(&state.items);
+            state.dirty_items.clear();
+            state.pending_reload = None;
+            state.message_state.set_success(format!(
+                "成功儲存檔案：{}（共 {} 個{}）",
+                path.display(),
+                state.items.len(),
+                T::type_name_plural()
+            ));
+        }
+        Err(e) => {
+            state.message_state.set_error(e.to_string());
+        }
+    }
+}
+
+/// 將目前項目寫入自動存檔（不輪替備份、不影響未儲存修改的標記）
+pub fn autosave_file<T: EditorItem>(
+    state: &mut GenericEditorState<T>,
+    path: &Path,
+    data_key: &str,
+) {
+    if let Err(e) = write_items(&autosave_path_for(path), data_key, state.items.clone(), 0) {
+        state
+            .message_state
+            .set_error(format!("自動存檔失敗：{}", e));
+    }
+}
+
+/// 自動存檔比主檔新時標記等待還原
+pub fn check_autosave_recovery<T: EditorItem>(state: &mut GenericEditorState<T>, path: &Path) {
+    let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    state.pending_recovery = autosave_is_newer(modified(path), modified(&autosave_path_for(path)));
+}
+
+/// 以自動存檔取代目前項目；與主檔內容不同的項目標記為未儲存修改
+pub fn recover_autosave<T: EditorItem>(
+    state: &mut GenericEditorState<T>,
+    path: &Path,
+    data_key: &str,
+) {
+    // Fail Fast: 檢查是否正在編輯
+    if state.is_editing() {
+        state.message_state.set_error("請先完成或取消當前的編輯");
+        return;
+    }
 
-    // TOML 序列化
-    let content = match toml::to_string_pretty(&data) {
-        Ok(content) => content,
+    let autosave_path = autosave_path_for(path);
+    let items: Vec<T> = match read_items(&autosave_path, data_key) {
+        Ok(items) => items,
         Err(e) => {
-            state
-                .message_state
-                .set_error(format!("序列化 TOML 失敗：{}", e));
+            state.message_state.set_error(e.to_string());
             return;
         }
     };
 
-    // 確保目錄存在
-    if let Some(parent) = path.parent() {
-        if let Err(e) = fs::create_dir_all(parent) {
+    let recovered = snapshot_items(&items);
+    state.dirty_items = recovered
+        .iter()
+        .filter(|(name, content)| state.disk_snapshot.get(*name) != Some(*content))
+        .map(|(name, _)| name.clone())
+        .chain(
             state
-                .message_state
-                .set_error(format!("建立目錄失敗：{} - {}", parent.display(), e));
+                .disk_snapshot
+                .keys()
+                .filter(|name| !recovered.contains_key(*name))
+                .cloned(),
+        )
+        .collect();
+    state.items = items;
+    state.selected_index = None;
+    state.pending_recovery = false;
+    state.message_state.set_success(format!(
+        "已還原自動存檔：{}（{} 個未儲存修改）",
+        autosave_path.display(),
+        state.dirty_items.len()
+    ));
+}
+
+/// 重新載入檔案，不覆蓋未儲存的本地修改
+///
+/// - 沒有本地修改：等同 `load_file`
+/// - 只有本地改過的項目：保留本地版本，其餘採用磁碟版本
+/// - 本地與磁碟都改過的項目：暫存到 `pending_reload`，等待 `resolve_pending_reload`
+pub fn reload_file<T: EditorItem>(state: &mut GenericEditorState<T>, path: &Path, data_key: &str) {
+    if state.dirty_items.is_empty() {
+        load_file(state, path, data_key);
+        return;
+    }
+    // Fail Fast: 檢查是否正在編輯
+    if state.is_editing() {
+        state.message_state.set_error("請先完成或取消當前的編輯");
+        return;
+    }
+
+    let disk_items: Vec<T> = match read_items(path, data_key) {
+        Ok(items) => items,
+        Err(e) => {
+            state.message_state.set_error(e.to_string());
             return;
         }
+    };
+
+    let disk_now = snapshot_items(&disk_items);
+    let mut conflicts: Vec<String> = state
+        .dirty_items
+        .iter()
+        .filter(|name| {
+            let disk_changed = state.disk_snapshot.get(*name) != disk_now.get(*name);
+            reload_decision(true, disk_changed) == ReloadDecision::Conflict
+        })
+        .cloned()
+        .collect();
+    conflicts.sort();
+
+    if conflicts.is_empty() {
+        let keep = state.dirty_items.clone();
+        apply_reload(state, disk_items, keep);
+        return;
     }
 
-    // 寫入檔案
-    match fs::write(path, content) {
-        Ok(_) => {
-            state.message_state.set_success(format!(
-                "成功儲存檔案：{}（共 {} 個{}）",
-                path.display(),
-                state.items.len(),
-                T::type_name_plural()
-            ));
-        }
-        Err(e) => {
-            state
-                .message_state
-                .set_error(format!("儲存檔案失敗：{} - {}", path.display(), e));
+    state.message_state.set_error(format!(
+        "重新載入會覆蓋未儲存的修改：{}，請選擇保留本地或採用磁碟版本",
+        conflicts.join("、")
+    ));
+    state.pending_reload = Some(PendingReload {
+        disk_items,
+        conflicts,
+    });
+}
+
+/// 處理重新載入的衝突：`keep_local` 為 true 時衝突項目保留本地版本，否則採用磁碟版本
+pub fn resolve_pending_reload<T: EditorItem>(state: &mut GenericEditorState<T>, keep_local: bool) {
+    let pending = match state.pending_reload.take() {
+        Some(pending) => pending,
+        None => return,
+    };
+    let keep: HashSet<String> = state
+        .dirty_items
+        .iter()
+        .filter(|name| keep_local || !pending.conflicts.contains(*name))
+        .cloned()
+        .collect();
+    apply_reload(state, pending.disk_items, keep);
+}
+
+/// 以磁碟項目為底合併本地項目：`keep_local` 內的名稱採用本地版本（本地已刪除者一併移除），
+/// 其餘採用磁碟版本；只存在於本地的保留項目依本地順序附加在最後
+pub fn merge_reloaded_items<T: EditorItem>(
+    local: &[T],
+    disk: Vec<T>,
+    keep_local: &HashSet<String>,
+) -> Vec<T> {
+    let local_by_name: HashMap<&str, &T> = local.iter().map(|item| (item.name(), item)).collect();
+    let disk_names: HashSet<String> = disk.iter().map(|item| item.name().to_string()).collect();
+
+    let mut merged: Vec<T> = disk
+        .into_iter()
+        .filter_map(|item| match keep_local.contains(item.name()) {
+            false => Some(item),
+            true => local_by_name.get(item.name()).map(|local| (*local).clone()),
+        })
+        .collect();
+    merged.extend(
+        local
+            .iter()
+            .filter(|item| keep_local.contains(item.name()) && !disk_names.contains(item.name()))
+            .cloned(),
+    );
+    merged
+}
+
+// ==================== 本地輔助函數 ====================
+
+/// 套用重新載入結果：保留的本地修改仍視為未儲存
+fn apply_reload<T: EditorItem>(
+    state: &mut GenericEditorState<T>,
+    disk_items: Vec<T>,
+    keep_local: HashSet<String>,
+) {
+    state.disk_snapshot = snapshot_items(&disk_items);
+    state.items = merge_reloaded_items(&state.items, disk_items, &keep_local);
+    state.selected_index = None;
+    state.message_state.set_success(format!(
+        "已重新載入（共 {} 個{}），保留 {} 個本地修改",
+        state.items.len(),
+        T::type_name_plural(),
+        keep_local.len()
+    ));
+    state.dirty_items = keep_local;
+}
+
+/// 以與存檔相同的 TOML 格式序列化每個項目，作為比對磁碟變更的依據
+fn snapshot_items<T: EditorItem>(items: &[T]) -> HashMap<String, String> {
+    items
+        .iter()
+        .map(|item| {
+            let content =
+                toml::to_string(&ItemsData::new(SNAPSHOT_KEY, vec![item])).unwrap_or_default();
+            (item.name().to_string(), content)
+        })
+        .collect()
+}
+
+/// 輪替備份：.bak.{n-1} → .bak.{n}（最舊的被覆蓋），再把原檔複製為 .bak.1
+///
+/// 用複製而非 rename，確保 rename 暫存檔前原檔一直存在
+fn rotate_backups(path: &Path, backup_count: usize) -> Result<(), FileIoError> {
+    if backup_count == 0 || !path.exists() {
+        return Ok(());
+    }
+
+    for index in (1..backup_count).rev() {
+        let from = backup_path_for(path, index);
+        if !from.exists() {
+            continue;
         }
+        let to = backup_path_for(path, index + 1);
+        fs::rename(&from, &to).map_err(|source| FileIoError::Backup { path: to, source })?;
     }
+
+    let newest = backup_path_for(path, 1);
+    fs::copy(path, &newest).map_err(|source| FileIoError::Backup {
+        path: newest,
+        source,
+    })?;
+    Ok(())
+}
+
+/// 將位元組偏移轉為 1-based 的 (行, 列)
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|s| s.chars().count())
+        .unwrap_or(0)
+        + 1;
+    (line, column)
 }
@@ -1,20 +1,20 @@
 //! 關卡編輯器 tab
 
 mod battle;
-mod battlefield;
+pub(crate) mod battlefield;
 mod deployment;
-mod edit;
+pub(crate) mod edit;
 
 use crate::editor_item::{EditorItem, validate_name};
 use crate::generic_editor::MessageState;
 use bevy_ecs::world::World;
-use board::domain::alias::{SkillName, TypeName};
+use board::domain::alias::{Coord, SkillName, TypeName};
 use board::domain::constants::PLAYER_FACTION_ID;
 use board::domain::core_types::{LevelOutcome, SkillType};
 use board::ecs_types::components::{Occupant, Position};
 use board::ecs_types::resources::Board;
 use board::loader_schema::{LevelType, ObjectType, UnitType};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// 拖曳物體的類型和索引
 #[derive(Clone, Copy, Debug)]
@@ -30,6 +30,15 @@ pub struct DragState {
     pub object: DraggedObject,
 }
 
+/// 群組拖曳：Shift+拖曳框選單位，拖曳已選取的單位則整組平移
+#[derive(Clone, Copy, Debug)]
+pub enum GroupDrag {
+    /// 框選中，記錄起始格
+    Select { anchor: Position },
+    /// 整組平移中，記錄起始格
+    Move { origin: Position },
+}
+
 /// 反應決策草稿：玩家安排的執行順序 + 每人選的技能（None = 跳過）
 #[derive(Debug, Default)]
 pub struct ReactionDecisionState {
@@ -45,6 +54,8 @@ pub enum BattleAction {
     /// 技能模式：彈窗一直開著、戰場可互動預覽 targetable/AOE/picked
     /// 實際選中的技能與 picked 由 core 的 SkillTargeting resource 持有（未選技能時 resource 不存在）
     SkillMode,
+    /// 測距模式：左鍵依序點選兩格，顯示距離與移動成本
+    Measuring,
 }
 
 /// 右側面板顯示模式
@@ -78,9 +89,25 @@ pub struct LevelTabUIState {
 
     pub unit_search_query: TypeName,
     pub object_search_query: TypeName,
+    /// 外圈批次放置使用的物件類型
+    pub border_object_name: TypeName,
+    /// 批次取代物件：被取代與取代成的物件類型
+    pub replace_from_object: TypeName,
+    pub replace_to_object: TypeName,
 
     pub drag_state: Option<DragState>,
+    /// 框選選取的單位位置
+    pub selected_units: Vec<Position>,
+    pub group_drag: Option<GroupDrag>,
     pub scroll_offset: egui::Vec2,
+    /// 目前捲動偏移所屬的關卡名稱
+    pub viewed_level_name: String,
+    /// 各關卡上次的捲動偏移（僅存於編輯器，不寫入關卡檔）
+    pub level_scroll_offsets: HashMap<String, egui::Vec2>,
+    /// 編輯模式戰場預覽是否顯示欄列索引
+    pub show_ruler: bool,
+    /// 待確認的棋盤尺寸（寬, 高）：放大到超過 LARGE_BOARD_CONFIRM_AREA 時暫存於此，確認後才寫入關卡
+    pub pending_board_size: Option<(Coord, Coord)>,
 
     /// 模擬戰鬥專用：統一在 tabs\level_tab\edit.rs 初始化
     /// ECS World，模擬模式時存放所有 entity
@@ -91,8 +118,12 @@ pub struct LevelTabUIState {
     pub selected_right_pos: Option<Position>,
     /// 底部操作面板的當前動作狀態
     pub battle_action: BattleAction,
+    /// 測距模式已點選的格子（最多兩格，第三次點選重新開始）
+    pub measure_points: Vec<Position>,
     /// 延遲置中：下一幀 render_battlefield 時消費
     pub pending_center_pos: Option<Position>,
+    /// 延遲重設視角（顯示整個棋盤）：下一幀 render_battlefield 時消費
+    pub pending_fit_view: bool,
 
     /// 右側面板顯示模式（單位詳情 / 戰鬥 log）
     pub right_panel_view: RightPanelView,
@@ -124,12 +155,16 @@ impl EditorItem for LevelType {
         "關卡"
     }
 
-    fn validate(&self, all_items: &[Self], editing_index: Option<usize>) -> Result<(), String> {
+    fn validate(
+        &self,
+        all_items: &[Self],
+        editing_index: Option<usize>,
+        _ui_state: &Self::UIState,
+    ) -> Result<(), String> {
         validate_name(self, all_items, editing_index)?;
 
-        if self.board_width == 0 || self.board_height == 0 {
-            return Err("棋盤尺寸必須大於 0".to_string());
-        }
+        let board = board::logic::board::new_board(self.board_width, self.board_height)
+            .map_err(|e| e.kind().to_string())?;
 
         let has_player_unit = self
             .unit_placements
@@ -154,10 +189,6 @@ impl EditorItem for LevelType {
             return Err("至少需要一個陣營".to_string());
         }
 
-        let board = Board {
-            width: self.board_width,
-            height: self.board_height,
-        };
         // 檢查部署點超出棋盤範圍
         for (idx, pos) in self.deployment_positions.iter().enumerate() {
             check_position_in_bounds(board, *pos, idx + 1, "部署點")?;
@@ -42,6 +42,21 @@ pub fn render_form(
         return;
     }
 
+    // 技能編輯器修改後，以目前的技能定義重算場上單位屬性
+    if ui
+        .button("重新套用技能")
+        .on_hover_text("以技能編輯器目前的定義重算所有單位屬性")
+        .clicked()
+    {
+        match board::ecs_logic::loader::reload_skills(
+            &mut ui_state.world,
+            ui_state.available_skills.clone(),
+        ) {
+            Ok(()) => message_state.set_success("已重新套用技能"),
+            Err(e) => message_state.set_error(format!("重新套用技能失敗：{}", e)),
+        }
+    }
+
     ui.add_space(SPACING_SMALL);
 
     render_level_info(ui, &snapshot);
@@ -147,6 +162,10 @@ fn render_outcome_banner(ui: &mut egui::Ui, outcome: &LevelOutcome) -> bool {
             (format!("關卡勝利：\n{}", key), OUTCOME_BANNER_COLOR_VICTORY)
         }
         LevelOutcome::Defeat(key) => (format!("關卡失敗：\n{}", key), OUTCOME_BANNER_COLOR_DEFEAT),
+        LevelOutcome::Draw { max_rounds } => (
+            format!("平手：\n超過回合上限 {} 輪仍未分出勝負", max_rounds),
+            OUTCOME_BANNER_COLOR_DRAW,
+        ),
     };
     ui.label(
         egui::RichText::new(text)
@@ -266,6 +285,19 @@ fn render_bottom_panel(ui: &mut egui::Ui, ui_state: &mut LevelTabUIState) -> Res
                 error = Err(format!("結束回合失敗：{}", e));
                 return;
             }
+            // 回合開始時結算的引導技能可能致死
+            if let Err(e) = board::ecs_logic::turn::resolve_deaths(&mut ui_state.world) {
+                error = Err(format!("處理死亡失敗：{}", e));
+                return;
+            }
+            // 進入新的一輪可能超過回合上限
+            match board::ecs_logic::level_outcome::resolve_level_outcome(&mut ui_state.world) {
+                Ok(outcome) => ui_state.level_outcome = outcome,
+                Err(e) => {
+                    error = Err(format!("判定關卡結局失敗：{}", e));
+                    return;
+                }
+            }
             ui_state.battle_action = BattleAction::Normal;
             return;
         }
@@ -345,10 +377,64 @@ fn render_bottom_panel(ui: &mut egui::Ui, ui_state: &mut LevelTabUIState) -> Res
                 }
             }
         }
+
+        ui.separator();
+
+        // 第四顆：測距（SkillMode 下 disabled）
+        let is_measuring = ui_state.battle_action == BattleAction::Measuring;
+        let (label, next_action) = if is_measuring {
+            ("結束測距", BattleAction::Normal)
+        } else {
+            ("測距", BattleAction::Measuring)
+        };
+        let mut measure_clicked = false;
+        ui.add_enabled_ui(!is_skill_mode, |ui| {
+            if ui
+                .add_sized(
+                    button_size,
+                    egui::Button::new(label).wrap_mode(egui::TextWrapMode::Wrap),
+                )
+                .clicked()
+            {
+                measure_clicked = true;
+            }
+        });
+        if measure_clicked {
+            ui_state.measure_points.clear();
+            ui_state.battle_action = next_action;
+            return;
+        }
+        if is_measuring {
+            match format_measurement(ui_state) {
+                Ok(text) => {
+                    ui.label(text);
+                }
+                Err(e) => error = Err(e),
+            }
+        }
     });
     error
 }
 
+/// 測距結果文字：尚未選滿兩格時顯示操作提示
+fn format_measurement(ui_state: &mut LevelTabUIState) -> Result<String, String> {
+    let (from, to) = match ui_state.measure_points.as_slice() {
+        [from, to] => (*from, *to),
+        [from] => return Ok(format!("起點 ({}, {})，請點選終點", from.x, from.y)),
+        _ => return Ok("請點選起點".to_string()),
+    };
+    let measurement = board::ecs_logic::movement::measure_between(&mut ui_state.world, from, to)
+        .map_err(|e| format!("測距失敗：{}", e))?;
+    let cost = match measurement.movement_cost {
+        Some(cost) => cost.to_string(),
+        None => "無法抵達".to_string(),
+    };
+    Ok(format!(
+        "({}, {}) → ({}, {})：切比雪夫 {}，曼哈頓 {}，移動成本 {}",
+        from.x, from.y, to.x, to.y, measurement.chebyshev, measurement.manhattan, cost
+    ))
+}
+
 /// 渲染戰場預覽
 fn render_battlefield(
     ui: &mut egui::Ui,
@@ -402,6 +488,10 @@ fn render_battlefield(
         _ => (HashMap::new(), 0, None),
     };
 
+    if ui.button("重設視角").clicked() {
+        ui_state.pending_fit_view = true;
+    }
+
     let mut error = Ok(());
     let scroll_output = egui::ScrollArea::both()
         .auto_shrink([false; 2])
@@ -434,22 +524,25 @@ fn render_battlefield(
                 _ => HashSet::new(),
             };
 
-            // 計算路徑預覽（懸停時）
-            let preview_path = preview_path(current_pos, hovered_pos, &reachable_positions);
+            let can_stop = can_stop_at(hovered_pos, &reachable_positions);
 
-            // 計算路徑風險預覽（懸停在可停留目標時）：危險地面 + 藉機攻擊觸發格
-            let path_hazards = match hovered_pos {
-                Some(hover) if !preview_path.is_empty() => {
+            // 計算路徑與風險預覽（懸停在可停留目標時）：危險地面 + 藉機攻擊觸發格
+            // 路徑取 core 規劃結果，會繞開危險地面，與實際移動一致
+            let (preview_path, path_hazards) = match hovered_pos {
+                Some(hover) if can_stop => {
                     match board::ecs_logic::movement::preview_move_path(&mut ui_state.world, hover)
                     {
-                        Ok(preview_hazard) => collect_path_hazards(&preview_hazard, &preview_path),
+                        Ok(preview) => {
+                            let hazards = collect_path_hazards(&preview, &preview.path);
+                            (preview.path, hazards)
+                        }
                         Err(e) => {
                             error = Err(e);
                             return;
                         }
                     }
                 }
-                _ => HashSet::new(),
+                _ => (Vec::new(), HashSet::new()),
             };
 
             // 計算命中率預覽（SkillMode 懸停在可攻擊目標時）
@@ -517,15 +610,13 @@ fn render_battlefield(
             battlefield::render_battlefield_legend(ui);
         });
     // 處理延遲置中
-    if let Some(pos) = ui_state.pending_center_pos.take() {
-        let cell_stride = BATTLEFIELD_CELL_SIZE + BATTLEFIELD_GRID_SPACING;
-        let target_x = pos.x as f32 * cell_stride + BATTLEFIELD_CELL_SIZE / 2.0;
-        let target_y = pos.y as f32 * cell_stride + BATTLEFIELD_CELL_SIZE / 2.0;
-        let viewport = scroll_output.inner_rect.size();
-        ui_state.scroll_offset = egui::vec2(
-            (target_x - viewport.x / 2.0).max(0.0),
-            (target_y - viewport.y / 2.0).max(0.0),
-        );
+    let viewport = scroll_output.inner_rect.size();
+    if ui_state.pending_fit_view {
+        ui_state.pending_fit_view = false;
+        ui_state.pending_center_pos = None;
+        ui_state.scroll_offset = battlefield::fit_scroll_offset(viewport, board);
+    } else if let Some(pos) = ui_state.pending_center_pos.take() {
+        ui_state.scroll_offset = battlefield::center_scroll_offset(pos, viewport, board);
     } else {
         ui_state.scroll_offset = scroll_output.state.offset;
     }
@@ -632,29 +723,15 @@ fn render_skill_popup(
 
 // ==================== 輔助函數 ====================
 
-/// 重建懸停目標的有序移動路徑（含起點）；目標不可停留或不可達時回空
-fn preview_path(
-    src: Option<Position>,
+/// 懸停目標是否可停留：在可達範圍內且非僅穿越位置
+fn can_stop_at(
     dst: Option<Position>,
     reachable_positions: &HashMap<Position, ReachableInfo>,
-) -> Vec<Position> {
-    let (src, dst) = match (src, dst) {
-        (Some(src), Some(dst)) => (src, dst),
-        _ => return Vec::new(),
-    };
-    match reachable_positions.get(&dst) {
-        Some(info) => {
-            // 目的地不能停留
-            if info.passthrough_only {
-                return Vec::new();
-            }
-        }
-        // 目的地不在可達範圍內
-        None => {
-            return Vec::new();
-        }
-    };
-    board::logic::movement::reconstruct_path(&reachable_positions, src, dst)
+) -> bool {
+    match dst.and_then(|dst| reachable_positions.get(&dst)) {
+        Some(info) => !info.passthrough_only,
+        None => false,
+    }
 }
 
 /// 蒐集路徑風險格：危險地面 + 藉機攻擊觸發格（移動者被打的那一格）
@@ -741,6 +818,12 @@ fn handle_mouse_click(
                                 clicked_pos,
                             )?;
                             board::ecs_logic::movement::advance_move(&mut ui_state.world)?;
+                            // 陷阱傷害可能致死，目標點佔領可能達成結局
+                            board::ecs_logic::turn::resolve_deaths(&mut ui_state.world)?;
+                            ui_state.level_outcome =
+                                board::ecs_logic::level_outcome::resolve_level_outcome(
+                                    &mut ui_state.world,
+                                )?;
                             ui_state.selected_left_pos = Some(clicked_pos);
                             sync_reaction_decisions(ui_state);
                         }
@@ -749,6 +832,12 @@ fn handle_mouse_click(
                 }
             }
             BattleAction::Delaying => {}
+            BattleAction::Measuring => {
+                if ui_state.measure_points.len() >= 2 {
+                    ui_state.measure_points.clear();
+                }
+                ui_state.measure_points.push(clicked_pos);
+            }
             BattleAction::SkillMode => {
                 // 左鍵：若已選技能，嘗試新增目標（editor 先判斷在可攻擊範圍內）
                 let (skill_name, max_count) =
@@ -885,6 +974,9 @@ fn format_hit_preview(preview: &board::ecs_logic::skill::HitPreview) -> String {
     if acc.adjacent_penalty != 0 {
         accuracy_sources.push(format!("敵人相鄰 {:+}", acc.adjacent_penalty));
     }
+    if acc.high_ground_bonus != 0 {
+        accuracy_sources.push(format!("高處 {:+}", acc.high_ground_bonus));
+    }
 
     // 對方閃避值、格擋值來源逐項（目前僅基礎，格式比照命中值）
     let evasion_sources = vec![format!("基礎 {}", evasion.base)];
@@ -992,6 +1084,16 @@ fn render_log_event(ui: &mut egui::Ui, event: &LogEvent) {
         LogEvent::Death { unit } => {
             ui.add(egui::Label::new(format!("{} 死亡", unit)).wrap());
         }
+        LogEvent::DeathNova {
+            unit,
+            target,
+            amount,
+        } => {
+            ui.add(
+                egui::Label::new(format!("{} 死亡爆發，{} HP 變化 {}", unit, target, amount))
+                    .wrap(),
+            );
+        }
     }
 }
 
@@ -1027,7 +1129,7 @@ fn format_log_check(check: &LogCheck, detail: Option<&LogCheckDetail>) -> String
     match detail {
         None => result_str,
         Some(d) => format!(
-            "[\n{}\n命中 {} + 骰 {}\n總計：{}\n{}\n閃避 {} / 格擋 {}\n爆 {}%\n{}\n]",
+            "[\n{}\n命中 {} + 骰 {}\n總計：{}\n{}\n閃避 {} / 格擋 {}\n爆 {}%\n{}{}\n]",
             d.accuracy_source,
             d.breakdowns.attacker_accuracy.total,
             d.roll,
@@ -1036,6 +1138,10 @@ fn format_log_check(check: &LogCheck, detail: Option<&LogCheckDetail>) -> String
             d.breakdowns.defender_evasion.total,
             d.breakdowns.defender_block.total,
             d.breakdowns.crit,
+            match d.concealment_roll {
+                Some(roll) => format!("隱蔽骰 {}\n", roll),
+                None => String::new(),
+            },
             result_str,
         ),
     }
@@ -1047,6 +1153,12 @@ fn format_log_effect(effect: &LogEffect) -> String {
         LogEffect::HpChange { amount } => format!("HP 變化 {}", amount),
         LogEffect::SpawnObject { object_type } => format!("產生物件 {}", object_type),
         LogEffect::ApplyBuff { buff_name } => format!("施加狀態 {}", buff_name),
+        LogEffect::Dispel { beneficial: true } => "驅散增益".to_string(),
+        LogEffect::Dispel { beneficial: false } => "驅散減益".to_string(),
+        LogEffect::Teleport => "瞬移".to_string(),
+        LogEffect::Pull { distance } => format!("拉動最多 {} 格", distance),
+        LogEffect::Summon { unit_type } => format!("召喚 {}", unit_type),
+        LogEffect::Shield { amount } => format!("護盾 {amount}"),
     }
 }
 
@@ -1203,6 +1315,11 @@ fn render_reaction_panel(
                 ProcessReactionResult::Done => {
                     board::ecs_logic::movement::force_advance_move(&mut ui_state.world)
                         .map_err(|e| format!("繼續移動失敗：{}", e))?;
+                    board::ecs_logic::turn::resolve_deaths(&mut ui_state.world)
+                        .map_err(|e| format!("處理死亡失敗：{}", e))?;
+                    ui_state.level_outcome =
+                        board::ecs_logic::level_outcome::resolve_level_outcome(&mut ui_state.world)
+                            .map_err(|e| format!("判定關卡結局失敗：{}", e))?;
                     break;
                 }
             }
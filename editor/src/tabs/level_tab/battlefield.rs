@@ -4,11 +4,13 @@ use crate::constants::*;
 use bevy_ecs::world::World;
 use board::domain::alias::{Coord, ID};
 use board::domain::constants::PLAYER_ALLIANCE_ID;
-use board::ecs_logic::query::{ObjectQueryResult, get_all_objects, get_all_units, get_resource};
-use board::ecs_types::components::{Position, UnitBundle};
+use board::ecs_logic::query::{
+    ObjectQueryResult, get_all_objects, get_all_units, get_calculated_attributes, get_resource,
+};
+use board::ecs_types::components::{AttributeBundle, Position, UnitBundle};
 use board::ecs_types::resources::{Board, DeploymentConfig, LevelConfig};
 use board::error::Result as CResult;
-use board::loader_schema::Faction;
+use board::loader_schema::{Faction, TileLabel};
 use std::collections::{HashMap, HashSet};
 
 // ==================== 資料型別 ====================
@@ -20,6 +22,15 @@ pub struct VisibleGridRange {
     pub max: Position,
 }
 
+/// 座標尺的軸向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RulerAxis {
+    /// 沿上緣標示欄索引（x）
+    Column,
+    /// 沿左緣標示列索引（y）
+    Row,
+}
+
 /// 單一格子的高亮資訊
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CellHighlight {
@@ -34,6 +45,8 @@ pub struct Snapshot {
     pub deployment_positions: HashSet<Position>,
     pub level_config: LevelConfig,
     pub unit_map: HashMap<Position, UnitBundle>,
+    /// 各單位含 buff 的實際屬性（顯示用）
+    pub unit_attributes: HashMap<Position, AttributeBundle>,
     pub object_map: HashMap<Position, ObjectQueryResult>,
 }
 
@@ -42,6 +55,10 @@ pub struct Snapshot {
 /// 一次查詢部署/戰鬥模式所需的所有關卡資料
 pub fn query_snapshot(world: &mut World) -> CResult<Snapshot> {
     let unit_map = get_all_units(world)?;
+    let unit_attributes = unit_map
+        .iter()
+        .map(|(pos, bundle)| Ok((*pos, get_calculated_attributes(world, bundle.occupant)?)))
+        .collect::<CResult<_>>()?;
     let object_map = get_all_objects(world)?;
     let board = *get_resource::<Board>(world, "棋盤尺寸未初始化")?;
     let deployment_config = get_resource::<DeploymentConfig>(world, "部署設定未初始化")?.clone();
@@ -52,6 +69,7 @@ pub fn query_snapshot(world: &mut World) -> CResult<Snapshot> {
         deployment_positions: deployment_config.deployment_positions,
         level_config,
         unit_map,
+        unit_attributes,
         object_map,
     })
 }
@@ -89,6 +107,75 @@ pub fn calculate_visible_range(
     }
 }
 
+/// 計算讓指定格子置中於視口的捲動偏移（夾在可捲動範圍內）
+pub fn center_scroll_offset(pos: Position, viewport_size: egui::Vec2, board: Board) -> egui::Vec2 {
+    let cell_stride = BATTLEFIELD_CELL_SIZE + BATTLEFIELD_GRID_SPACING;
+    let target_x = pos.x as f32 * cell_stride + BATTLEFIELD_CELL_SIZE / 2.0;
+    let target_y = pos.y as f32 * cell_stride + BATTLEFIELD_CELL_SIZE / 2.0;
+    let max_offset = (calculate_grid_dimensions(board) - viewport_size).max(egui::Vec2::ZERO);
+    egui::vec2(
+        (target_x - viewport_size.x / 2.0).clamp(0.0, max_offset.x),
+        (target_y - viewport_size.y / 2.0).clamp(0.0, max_offset.y),
+    )
+}
+
+/// 計算顯示整個棋盤的捲動偏移：棋盤放得下時回到原點，否則置中於棋盤中央
+pub fn fit_scroll_offset(viewport_size: egui::Vec2, board: Board) -> egui::Vec2 {
+    let center = Position {
+        x: board.width / 2,
+        y: board.height / 2,
+    };
+    center_scroll_offset(center, viewport_size, board)
+}
+
+/// 切換檢視的關卡：保存舊關卡的捲動偏移，取回新關卡上次的偏移
+///
+/// 新關卡未記錄過時沿用目前偏移（改名時視角不會跳回原點）；`from` 為空代表尚未檢視任何關卡，不保存
+pub fn switch_level_view(
+    saved_offsets: &mut HashMap<String, egui::Vec2>,
+    from: &str,
+    to: &str,
+    current_offset: egui::Vec2,
+) -> egui::Vec2 {
+    if !from.is_empty() {
+        saved_offsets.insert(from.to_string(), current_offset);
+    }
+    match saved_offsets.get(to) {
+        Some(offset) => *offset,
+        None => current_offset,
+    }
+}
+
+/// 計算座標尺上可見的刻度：回傳格子索引與其中心在視口內的座標（以視口左上角為原點）
+///
+/// 欄刻度貼齊上緣（y = 0），列刻度貼齊左緣（x = 0）；中心落在視口外的格子不列入
+pub fn visible_tick_positions(
+    axis: RulerAxis,
+    scroll_offset: egui::Vec2,
+    viewport_size: egui::Vec2,
+    board: Board,
+) -> Vec<(Coord, egui::Pos2)> {
+    let cell_stride = BATTLEFIELD_CELL_SIZE + BATTLEFIELD_GRID_SPACING;
+    let (count, offset, extent) = match axis {
+        RulerAxis::Column => (board.width, scroll_offset.x, viewport_size.x),
+        RulerAxis::Row => (board.height, scroll_offset.y, viewport_size.y),
+    };
+
+    (0..count)
+        .filter_map(|index| {
+            let center = index as f32 * cell_stride + BATTLEFIELD_CELL_SIZE / 2.0 - offset;
+            if center < 0.0 || center > extent {
+                return None;
+            }
+            let pos = match axis {
+                RulerAxis::Column => egui::pos2(center, 0.0),
+                RulerAxis::Row => egui::pos2(0.0, center),
+            };
+            Some((index, pos))
+        })
+        .collect()
+}
+
 /// 將螢幕座標轉換為棋盤座標
 pub fn screen_to_board_pos(
     screen_pos: egui::Pos2,
@@ -158,27 +245,24 @@ pub fn get_cell_info(
 
 pub fn get_tooltip_info(snapshot: &Snapshot) -> impl Fn(Position) -> String {
     |pos| -> String {
+        let stats = snapshot
+            .unit_attributes
+            .get(&pos)
+            .map(format_unit_stats)
+            .unwrap_or_default();
         if snapshot.deployment_positions.contains(&pos) {
             if let Some(bundle) = snapshot.unit_map.get(&pos) {
                 format!(
-                    "({}, {})\n部署點：{}\nHP：{} / {}",
-                    pos.x,
-                    pos.y,
-                    bundle.occupant_type_name.0,
-                    bundle.attributes.current_hp.0,
-                    bundle.attributes.max_hp.0
+                    "({}, {})\n部署點：{}\n{}",
+                    pos.x, pos.y, bundle.occupant_type_name.0, stats
                 )
             } else {
                 format!("({}, {})\n空部署點", pos.x, pos.y)
             }
         } else if let Some(bundle) = snapshot.unit_map.get(&pos) {
             format!(
-                "({}, {})\n單位 {}\nHP：{} / {}",
-                pos.x,
-                pos.y,
-                bundle.occupant_type_name.0,
-                bundle.attributes.current_hp.0,
-                bundle.attributes.max_hp.0
+                "({}, {})\n單位 {}\n{}",
+                pos.x, pos.y, bundle.occupant_type_name.0, stats
             )
         } else if let Some(obj) = snapshot.object_map.get(&pos) {
             format!(
@@ -191,6 +275,21 @@ pub fn get_tooltip_info(snapshot: &Snapshot) -> impl Fn(Position) -> String {
     }
 }
 
+/// 將單位主要屬性格式化為 tooltip 文字（HP、攻擊、命中、閃避、格擋、移動）
+fn format_unit_stats(attrs: &AttributeBundle) -> String {
+    format!(
+        "HP：{} / {}\n物攻 {} / 魔攻 {}\n命中 {} / 閃避 {} / 格擋 {}\n移動：{}",
+        attrs.current_hp.0,
+        attrs.max_hp.0,
+        attrs.physical_attack.0,
+        attrs.magical_attack.0,
+        attrs.physical_accuracy.0,
+        attrs.agility.0,
+        attrs.block.0,
+        attrs.movement_point.0
+    )
+}
+
 // ==================== 渲染層 ====================
 
 /// 繪製編輯模式棋盤格子，支持拖曳預覽與背景高亮
@@ -251,6 +350,47 @@ pub fn render_grid(
     }
 }
 
+/// 在格子下緣繪製編輯器註記小字
+pub fn render_tile_labels(ui: &mut egui::Ui, rect: egui::Rect, labels: &[TileLabel]) {
+    let cell_stride = BATTLEFIELD_CELL_SIZE + BATTLEFIELD_GRID_SPACING;
+    let painter = ui.painter();
+    for label in labels {
+        let anchor = rect.min
+            + egui::vec2(
+                label.position.x as f32 * cell_stride + BATTLEFIELD_CELL_SIZE / 2.0,
+                label.position.y as f32 * cell_stride + BATTLEFIELD_CELL_SIZE,
+            );
+        painter.text(
+            anchor,
+            egui::Align2::CENTER_BOTTOM,
+            &label.text,
+            egui::FontId::proportional(BATTLEFIELD_LABEL_TEXT_SIZE),
+            BATTLEFIELD_LABEL_COLOR,
+        );
+    }
+}
+
+/// 沿視口上緣與左緣繪製欄列索引（疊加在網格上，隨捲動更新）
+pub fn render_ruler(ui: &mut egui::Ui, board: Board, scroll_offset: egui::Vec2) {
+    let clip_rect = ui.clip_rect();
+    let painter = ui.painter();
+    for axis in [RulerAxis::Column, RulerAxis::Row] {
+        let align = match axis {
+            RulerAxis::Column => egui::Align2::CENTER_TOP,
+            RulerAxis::Row => egui::Align2::LEFT_CENTER,
+        };
+        for (index, pos) in visible_tick_positions(axis, scroll_offset, clip_rect.size(), board) {
+            painter.text(
+                clip_rect.min + pos.to_vec2(),
+                align,
+                index.to_string(),
+                egui::FontId::proportional(BATTLEFIELD_RULER_TEXT_SIZE),
+                BATTLEFIELD_RULER_COLOR,
+            );
+        }
+    }
+}
+
 /// 渲染懸停提示
 pub fn render_hover_tooltip(
     ui: &mut egui::Ui,
@@ -295,8 +435,11 @@ pub fn render_details_panel(ui: &mut egui::Ui, pos: Position, snapshot: &Snapsho
     ui.heading(format!("詳情 ({}, {})", pos.x, pos.y));
     ui.add_space(SPACING_SMALL);
 
-    if let Some(bundle) = snapshot.unit_map.get(&pos) {
-        render_unit_details(ui, bundle, &snapshot.level_config.factions);
+    if let (Some(bundle), Some(attrs)) = (
+        snapshot.unit_map.get(&pos),
+        snapshot.unit_attributes.get(&pos),
+    ) {
+        render_unit_details(ui, bundle, attrs, &snapshot.level_config.factions);
     }
 
     ui.add_space(SPACING_MEDIUM);
@@ -308,7 +451,12 @@ pub fn render_details_panel(ui: &mut egui::Ui, pos: Position, snapshot: &Snapsho
     }
 }
 
-fn render_unit_details(ui: &mut egui::Ui, bundle: &UnitBundle, factions: &HashMap<ID, Faction>) {
+fn render_unit_details(
+    ui: &mut egui::Ui,
+    bundle: &UnitBundle,
+    attrs: &AttributeBundle,
+    factions: &HashMap<ID, Faction>,
+) {
     ui.label(format!("類型：單位"));
     ui.label(format!("名稱：{}", bundle.occupant_type_name.0));
 
@@ -320,9 +468,8 @@ fn render_unit_details(ui: &mut egui::Ui, bundle: &UnitBundle, factions: &HashMa
 
     ui.add_space(SPACING_SMALL);
     ui.separator();
-    ui.label("屬性：");
+    ui.label("屬性（含 buff）：");
 
-    let attrs = &bundle.attributes;
     ui.label(format!("HP：{} / {}", attrs.current_hp.0, attrs.max_hp.0));
     ui.label(format!("MP：{} / {}", attrs.current_mp.0, attrs.max_mp.0));
     ui.label(format!("先攻：{}", attrs.initiative.0));
@@ -358,6 +505,7 @@ fn render_object_details(ui: &mut egui::Ui, obj: &ObjectQueryResult) {
     ui.label(format!("移動花費：{}", obj.bundle.terrain_movement_cost.0));
     ui.label(format!("阻擋視線：{}", obj.blocks_sight));
     ui.label(format!("阻擋聲音：{}", obj.blocks_sound));
+    ui.label(format!("地形高度：{}", obj.bundle.elevation.0));
 }
 
 /// 渲染戰場圖例
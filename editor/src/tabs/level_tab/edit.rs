@@ -1,4 +1,6 @@
-use super::{BattleAction, DragState, DraggedObject, LevelTabMode, LevelTabUIState, battlefield};
+use super::{
+    BattleAction, DragState, DraggedObject, GroupDrag, LevelTabMode, LevelTabUIState, battlefield,
+};
 use crate::constants::*;
 use crate::generic_editor::MessageState;
 use crate::utils::search::{
@@ -6,14 +8,22 @@ use crate::utils::search::{
 };
 use bevy_ecs::world::World;
 use board::domain::alias::{Coord, ID, TypeName};
-use board::domain::constants::{PLAYER_ALLIANCE_ID, PLAYER_FACTION_ID};
+use board::domain::constants::{
+    MAX_BOARD_AREA, MAX_BOARD_SIDE, PLAYER_ALLIANCE_ID, PLAYER_FACTION_ID,
+};
 use board::domain::core_types::SkillType;
 use board::ecs_types::components::Position;
 use board::ecs_types::resources::Board;
 use board::loader_schema::{
-    Faction, LevelType, ObjectPlacement, ObjectType, ObjectsToml, SkillsToml, UnitPlacement,
-    UnitType, UnitsToml,
+    Faction, LevelType, ObjectPlacement, ObjectType, ObjectsToml, SkillsToml, TileLabel,
+    UnitPlacement, UnitType, UnitsToml,
 };
+use board::logic::board::require_position_in_board;
+use board::logic::cliff::{CliffWarning, cliff_placement_warnings};
+use board::logic::level_edit::{
+    apply_border, clear_border, rect_positions, replace_object_type, translate_units,
+};
+use board::logic::level_stats::{level_stats, open_space_ratio};
 use std::collections::{HashMap, HashSet};
 
 /// 渲染編輯模式的表單
@@ -23,27 +33,25 @@ pub fn render_form(
     ui_state: &mut LevelTabUIState,
     message_state: &mut MessageState,
 ) {
+    // 切換關卡時還原該關卡上次的視角
+    if ui_state.viewed_level_name != level.name {
+        ui_state.scroll_offset = battlefield::switch_level_view(
+            &mut ui_state.level_scroll_offsets,
+            &ui_state.viewed_level_name,
+            &level.name,
+            ui_state.scroll_offset,
+        );
+        ui_state.viewed_level_name = level.name.clone();
+        ui_state.pending_board_size = None;
+    }
+
     // 基本資訊區
     ui.horizontal(|ui| {
         ui.label("名稱：");
         ui.text_edit_singleline(&mut level.name);
     });
 
-    ui.horizontal(|ui| {
-        ui.label("棋盤寬度：");
-        ui.add(
-            egui::DragValue::new(&mut level.board_width)
-                .speed(DRAG_VALUE_SPEED)
-                .range(1..=Coord::MAX),
-        );
-        ui.add_space(SPACING_SMALL);
-        ui.label("棋盤高度：");
-        ui.add(
-            egui::DragValue::new(&mut level.board_height)
-                .speed(DRAG_VALUE_SPEED)
-                .range(1..=Coord::MAX),
-        );
-    });
+    render_board_size(ui, level, ui_state);
 
     ui.add_space(SPACING_MEDIUM);
     ui.separator();
@@ -81,6 +89,9 @@ pub fn render_form(
         render_deployment_positions_list(ui, &mut level.deployment_positions);
     });
 
+    ui.add_space(SPACING_SMALL);
+    render_max_rounds(ui, level);
+
     ui.add_space(SPACING_MEDIUM);
     ui.separator();
 
@@ -116,6 +127,16 @@ pub fn render_form(
         .id_salt("object_placements_header")
         .default_open(false)
         .show(ui, |ui| {
+            render_border_tools(ui, level, &object_names, &mut ui_state.border_object_name);
+            render_replace_tools(
+                ui,
+                level,
+                &object_names,
+                &mut ui_state.replace_from_object,
+                &mut ui_state.replace_to_object,
+                message_state,
+            );
+            ui.add_space(SPACING_SMALL);
             render_object_placement_list(
                 ui,
                 &mut level.object_placements,
@@ -127,10 +148,101 @@ pub fn render_form(
     ui.add_space(SPACING_MEDIUM);
     ui.separator();
 
+    // 格子註記區（僅供編輯器標示，可收合）
+    egui::CollapsingHeader::new(format!("格子註記（{}）", level.tile_labels.len()))
+        .id_salt("tile_labels_header")
+        .default_open(false)
+        .show(ui, |ui| {
+            render_tile_label_list(ui, &mut level.tile_labels);
+        });
+
+    ui.add_space(SPACING_MEDIUM);
+    ui.separator();
+
+    // 關卡統計區（平衡用，可收合）
+    egui::CollapsingHeader::new("關卡統計")
+        .id_salt("level_stats_header")
+        .default_open(false)
+        .show(ui, |ui| {
+            render_level_stats(ui, level, &ui_state.available_objects)
+        });
+
+    ui.add_space(SPACING_MEDIUM);
+    ui.separator();
+
     // 戰場預覽區
     render_battlefield(ui, level, ui_state, message_state);
 }
 
+/// 渲染關卡統計（格子、部署點、開放空間、物件與各陣營單位數量）
+fn render_level_stats(ui: &mut egui::Ui, level: &LevelType, objects: &[ObjectType]) {
+    let stats = level_stats(level);
+    ui.label(format!(
+        "總格數：{}　空格：{}　部署點：{}",
+        stats.total_tiles, stats.empty_tiles, stats.deployable_tiles
+    ));
+
+    let object_types: HashMap<TypeName, ObjectType> = objects
+        .iter()
+        .map(|o| (o.name.clone(), o.clone()))
+        .collect();
+    match open_space_ratio(level, &object_types) {
+        Ok(ratio) => {
+            ui.label(format!("開放空間：{:.0}%", ratio * PERCENT_SCALE));
+        }
+        Err(e) => {
+            ui.colored_label(
+                egui::Color32::RED,
+                format!("開放空間計算失敗：{}", e.kind()),
+            );
+        }
+    }
+
+    match cliff_placement_warnings(level, &object_types) {
+        Ok(warnings) => {
+            for warning in warnings {
+                let text = match warning {
+                    CliffWarning::FacingOffBoard { position } => {
+                        format!("懸崖 ({}, {}) 朝向棋盤外", position.x, position.y)
+                    }
+                    CliffWarning::FacingImpassable {
+                        position,
+                        blocked_by,
+                    } => format!(
+                        "懸崖 ({}, {}) 下方是不可通行的「{}」",
+                        position.x, position.y, blocked_by
+                    ),
+                };
+                ui.colored_label(egui::Color32::YELLOW, text);
+            }
+        }
+        Err(e) => {
+            ui.colored_label(egui::Color32::RED, format!("懸崖檢查失敗：{}", e.kind()));
+        }
+    }
+
+    ui.label("物件：");
+    for (name, count) in &stats.object_counts {
+        ui.label(format!("　{}：{}", name, count));
+    }
+
+    ui.label("單位類型：");
+    for (name, count) in &stats.unit_type_counts {
+        ui.label(format!("　{}：{}", name, count));
+    }
+
+    ui.label("各陣營單位：");
+    for (faction_id, count) in &stats.unit_counts_by_faction {
+        let faction_name = level
+            .factions
+            .iter()
+            .find(|f| f.id == *faction_id)
+            .map(|f| f.name.as_str())
+            .unwrap_or("未知陣營");
+        ui.label(format!("　#{} {}：{}", faction_id, faction_name, count));
+    }
+}
+
 /// 渲染「開始部署」按鈕，回傳是否已進入部署模式
 fn render_start_deploy_button(
     ui: &mut egui::Ui,
@@ -161,6 +273,102 @@ fn render_start_deploy_button(
     false
 }
 
+/// 渲染棋盤尺寸：放大到超過 LARGE_BOARD_CONFIRM_AREA 時先暫存，確認後才寫入關卡
+fn render_board_size(ui: &mut egui::Ui, level: &mut LevelType, ui_state: &mut LevelTabUIState) {
+    let (mut width, mut height) = ui_state
+        .pending_board_size
+        .unwrap_or((level.board_width, level.board_height));
+    ui.horizontal(|ui| {
+        ui.label("棋盤寬度：");
+        ui.add(
+            egui::DragValue::new(&mut width)
+                .speed(DRAG_VALUE_SPEED)
+                .range(1..=MAX_BOARD_SIDE),
+        );
+        ui.add_space(SPACING_SMALL);
+        ui.label("棋盤高度：");
+        ui.add(
+            egui::DragValue::new(&mut height)
+                .speed(DRAG_VALUE_SPEED)
+                .range(1..=MAX_BOARD_SIDE),
+        );
+    });
+
+    let current = (level.board_width, level.board_height);
+    if !board_resize_needs_confirm(current, (width, height)) {
+        level.board_width = width;
+        level.board_height = height;
+        ui_state.pending_board_size = None;
+    } else {
+        ui_state.pending_board_size = Some((width, height));
+        let board_area = width.saturating_mul(height);
+        ui.horizontal(|ui| {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("棋盤面積 {board_area} 超過 {LARGE_BOARD_CONFIRM_AREA}，確認後才套用"),
+            );
+            if ui
+                .add_enabled(board_area <= MAX_BOARD_AREA, egui::Button::new("確認"))
+                .clicked()
+            {
+                level.board_width = width;
+                level.board_height = height;
+                ui_state.pending_board_size = None;
+            }
+            if ui.button("取消").clicked() {
+                ui_state.pending_board_size = None;
+            }
+        });
+    }
+
+    let (width, height) = ui_state
+        .pending_board_size
+        .unwrap_or((level.board_width, level.board_height));
+    let board_area = width.saturating_mul(height);
+    if board_area > MAX_BOARD_AREA {
+        ui.colored_label(
+            egui::Color32::RED,
+            format!(
+                "棋盤面積 {} 超過上限 {}，無法儲存",
+                board_area, MAX_BOARD_AREA
+            ),
+        );
+    }
+}
+
+/// 調整棋盤尺寸是否須先確認：只有放大且新面積超過 LARGE_BOARD_CONFIRM_AREA 時須確認，縮小一律直接套用
+pub(crate) fn board_resize_needs_confirm(current: (Coord, Coord), new: (Coord, Coord)) -> bool {
+    let new_area = new.0.saturating_mul(new.1);
+    new_area > LARGE_BOARD_CONFIRM_AREA && new_area > current.0.saturating_mul(current.1)
+}
+
+/// 渲染回合上限（超過即判定平手）
+fn render_max_rounds(ui: &mut egui::Ui, level: &mut LevelType) {
+    ui.horizontal(|ui| {
+        ui.label("回合上限：");
+        let mut limited = level.max_rounds.is_some();
+        if ui.checkbox(&mut limited, "限制").changed() {
+            level.max_rounds = if limited {
+                Some(DEFAULT_MAX_ROUNDS)
+            } else {
+                None
+            };
+        }
+        match &mut level.max_rounds {
+            Some(max_rounds) => {
+                ui.add(
+                    egui::DragValue::new(max_rounds)
+                        .speed(DRAG_VALUE_SPEED)
+                        .range(1..=u32::MAX),
+                );
+            }
+            None => {
+                ui.label("（不限制）");
+            }
+        }
+    });
+}
+
 /// 渲染陣營列表
 fn render_faction_list(ui: &mut egui::Ui, factions: &mut Vec<Faction>) {
     if ui.button("新增陣營").clicked() {
@@ -261,6 +469,50 @@ fn render_deployment_positions_list(ui: &mut egui::Ui, positions: &mut Vec<Posit
     }
 }
 
+/// 渲染格子註記列表
+fn render_tile_label_list(ui: &mut egui::Ui, labels: &mut Vec<TileLabel>) {
+    if ui.button("新增註記").clicked() {
+        labels.push(TileLabel::default());
+    }
+
+    let mut to_remove = None;
+    for (index, label) in labels.iter_mut().enumerate() {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("#{}", index + 1));
+                if ui.button("刪除").clicked() {
+                    to_remove = Some(index);
+                }
+
+                ui.separator();
+
+                ui.label("X：");
+                ui.add(
+                    egui::DragValue::new(&mut label.position.x)
+                        .speed(DRAG_VALUE_SPEED)
+                        .range(0..=Coord::MAX),
+                );
+                ui.label("Y：");
+                ui.add(
+                    egui::DragValue::new(&mut label.position.y)
+                        .speed(DRAG_VALUE_SPEED)
+                        .range(0..=Coord::MAX),
+                );
+
+                ui.separator();
+
+                ui.label("文字：");
+                ui.text_edit_singleline(&mut label.text);
+            });
+        });
+        ui.add_space(SPACING_SMALL);
+    }
+
+    if let Some(index) = to_remove {
+        labels.remove(index);
+    }
+}
+
 /// 渲染單位配置列表
 fn render_unit_placement_list(
     ui: &mut egui::Ui,
@@ -357,6 +609,81 @@ fn render_unit_placement_list(
     }
 }
 
+/// 渲染外圈批次放置工具（選擇物件後套用或清除棋盤外圈）
+fn render_border_tools(
+    ui: &mut egui::Ui,
+    level: &mut LevelType,
+    available_objects: &[TypeName],
+    border_object_name: &mut TypeName,
+) {
+    ui.horizontal(|ui| {
+        ui.label("外圈物件：");
+        let display = if border_object_name.is_empty() {
+            "選擇物件"
+        } else {
+            border_object_name.as_str()
+        };
+        combobox_with_dynamic_height("border_object", display, available_objects.len()).show_ui(
+            ui,
+            |ui| {
+                for name in available_objects {
+                    ui.selectable_value(border_object_name, name.clone(), name);
+                }
+            },
+        );
+
+        let has_selection = !border_object_name.is_empty();
+        ui.add_enabled_ui(has_selection, |ui| {
+            if ui.button("套用外圈").clicked() {
+                apply_border(level, border_object_name);
+            }
+            if ui.button("清除外圈").clicked() {
+                clear_border(level, border_object_name);
+            }
+        });
+    });
+}
+
+/// 渲染批次取代工具（整張棋盤的某類物件換成另一類，例如換地形主題）
+fn render_replace_tools(
+    ui: &mut egui::Ui,
+    level: &mut LevelType,
+    available_objects: &[TypeName],
+    from: &mut TypeName,
+    to: &mut TypeName,
+    message_state: &mut MessageState,
+) {
+    ui.horizontal(|ui| {
+        ui.label("取代物件：");
+        for (id_salt, selected) in [
+            ("replace_from_object", &mut *from),
+            ("replace_to_object", &mut *to),
+        ] {
+            let display = if selected.is_empty() {
+                "選擇物件"
+            } else {
+                selected.as_str()
+            };
+            combobox_with_dynamic_height(id_salt, display, available_objects.len()).show_ui(
+                ui,
+                |ui| {
+                    for name in available_objects {
+                        ui.selectable_value(selected, name.clone(), name);
+                    }
+                },
+            );
+        }
+
+        let can_replace = !from.is_empty() && !to.is_empty() && from != to;
+        ui.add_enabled_ui(can_replace, |ui| {
+            if ui.button("全部取代").clicked() {
+                let replaced = replace_object_type(level, from, to);
+                message_state.set_success(format!("已將 {replaced} 格的 {from} 取代為 {to}"));
+            }
+        });
+    });
+}
+
 /// 渲染物件配置列表
 fn render_object_placement_list(
     ui: &mut egui::Ui,
@@ -449,6 +776,8 @@ fn render_battlefield(
     ui.horizontal(|ui| {
         ui.heading("戰場預覽");
         ui.add_space(SPACING_MEDIUM);
+        ui.checkbox(&mut ui_state.show_ruler, "座標尺");
+        ui.add_space(SPACING_MEDIUM);
         // 下方部署按鈕
         ui.push_id("start_deploy_bottom", |ui| {
             render_start_deploy_button(ui, level, ui_state, message_state)
@@ -466,17 +795,45 @@ fn render_battlefield(
             let (rect, response) =
                 ui.allocate_exact_size(total_size, egui::Sense::click_and_drag());
 
-            let drag_state = update_drag_state(ui_state.drag_state, &response, rect, board, level);
+            let shift_held = ui.input(|i| i.modifiers.shift);
+            let group_handled = update_group_drag(
+                ui_state,
+                &response,
+                rect,
+                board,
+                level,
+                message_state,
+                shift_held,
+            );
+            let drag_state = if group_handled {
+                None
+            } else {
+                update_drag_state(
+                    ui_state.drag_state,
+                    &response,
+                    rect,
+                    board,
+                    level,
+                    message_state,
+                )
+            };
             ui_state.drag_state = drag_state;
             let hovered_pos = battlefield::compute_hover_pos(&response, rect, board);
             let dragged_pos = drag_state.and_then(|_| hovered_pos);
+            let selection_cells: HashSet<Position> = match (ui_state.group_drag, hovered_pos) {
+                (Some(GroupDrag::Select { anchor }), Some(hovered)) => {
+                    rect_positions(anchor, hovered).into_iter().collect()
+                }
+                _ => ui_state.selected_units.iter().copied().collect(),
+            };
             // 在更新後重新建立 lookup maps
             let (deployment_set, unit_map, object_map) = prepare_lookup_maps(level);
 
             // 渲染網格
             let get_cell_info_fn =
                 get_cell_info(&level.factions, &deployment_set, &unit_map, &object_map);
-            let get_cell_highlight_fn = get_cell_highlight(drag_state, dragged_pos);
+            let get_cell_highlight_fn =
+                get_cell_highlight(drag_state, dragged_pos, &selection_cells);
             battlefield::render_grid(
                 ui,
                 rect,
@@ -485,8 +842,19 @@ fn render_battlefield(
                 get_cell_info_fn,
                 get_cell_highlight_fn,
             );
+            battlefield::render_tile_labels(ui, rect, &level.tile_labels);
+            if ui_state.show_ruler {
+                battlefield::render_ruler(ui, board, ui_state.scroll_offset);
+            }
             if let Some(hovered_pos) = hovered_pos {
-                let get_tooltip_info_fn = get_tooltip_info(&deployment_set, &unit_map, &object_map);
+                let get_base_info = get_tooltip_info(&deployment_set, &unit_map, &object_map);
+                let get_tooltip_info_fn = |pos: Position| {
+                    let info = get_base_info(pos);
+                    match level.tile_labels.iter().find(|label| label.position == pos) {
+                        Some(label) => format!("{info}\n註記：{}", label.text),
+                        None => info,
+                    }
+                };
                 battlefield::render_hover_tooltip(ui, rect, hovered_pos, get_tooltip_info_fn);
             }
 
@@ -511,7 +879,9 @@ fn render_battlefield(
     ui.add_space(SPACING_SMALL);
     battlefield::render_battlefield_legend(ui);
 
-    ui.label("快捷鍵：Ctrl+D 複製懸停格。Backspace 刪除");
+    ui.label(
+        "快捷鍵：Ctrl+D 複製懸停格。Backspace 刪除。Shift+拖曳框選單位，拖曳選取的單位整組平移",
+    );
 }
 
 // ==================== 輔助函數 ====================
@@ -574,23 +944,32 @@ fn identify_dragged_object(level: &LevelType, pos: &Position) -> Option<DraggedO
 }
 
 /// 應用拖曳更新
-fn apply_drag_update(level: &mut LevelType, state: DragState, new_pos: Position) {
-    match state.object {
-        DraggedObject::Deployment(idx) => {
-            if idx < level.deployment_positions.len() {
-                level.deployment_positions[idx] = new_pos;
-            }
-        }
-        DraggedObject::Unit(idx) => {
-            if idx < level.unit_placements.len() {
-                level.unit_placements[idx].position = new_pos;
-            }
-        }
-        DraggedObject::Object(idx) => {
-            if idx < level.object_placements.len() {
-                level.object_placements[idx].position = new_pos;
-            }
+///
+/// 目標格超出棋盤（例如拖曳途中棋盤被縮小）或被拖曳的項目已不存在時回傳錯誤，關卡保持不變
+pub(crate) fn apply_drag_update(
+    level: &mut LevelType,
+    state: DragState,
+    new_pos: Position,
+    board: Board,
+) -> Result<(), String> {
+    require_position_in_board(board, new_pos).map_err(|e| e.kind().to_string())?;
+    let slot = match state.object {
+        DraggedObject::Deployment(idx) => level.deployment_positions.get_mut(idx),
+        DraggedObject::Unit(idx) => level
+            .unit_placements
+            .get_mut(idx)
+            .map(|unit| &mut unit.position),
+        DraggedObject::Object(idx) => level
+            .object_placements
+            .get_mut(idx)
+            .map(|obj| &mut obj.position),
+    };
+    match slot {
+        Some(pos) => {
+            *pos = new_pos;
+            Ok(())
         }
+        None => Err(format!("拖曳的項目已不存在：{:?}", state.object)),
     }
 }
 
@@ -601,6 +980,7 @@ fn update_drag_state(
     rect: egui::Rect,
     board: Board,
     level: &mut LevelType,
+    message_state: &mut MessageState,
 ) -> Option<DragState> {
     // 拖曳開始：找出被點中的物件
     if response.drag_started() {
@@ -619,12 +999,80 @@ fn update_drag_state(
         None => return None,
         Some(s) => s,
     };
-    if let Some(new_pos) = battlefield::compute_hover_pos(response, rect, board) {
-        apply_drag_update(level, state, new_pos);
+    let applied = battlefield::compute_hover_pos(response, rect, board)
+        .map(|new_pos| apply_drag_update(level, state, new_pos, board));
+    if let Some(Err(msg)) = applied {
+        message_state.set_error(msg);
     }
     return None;
 }
 
+/// 處理框選與群組平移，回傳本幀的拖曳是否由群組操作處理
+///
+/// Shift+拖曳框選範圍內的單位；拖曳已選取的單位時整組平移，
+/// 任一目的地衝突則整組不動並顯示錯誤。開始一般拖曳時清除選取。
+fn update_group_drag(
+    ui_state: &mut LevelTabUIState,
+    response: &egui::Response,
+    rect: egui::Rect,
+    board: Board,
+    level: &mut LevelType,
+    message_state: &mut MessageState,
+    shift_held: bool,
+) -> bool {
+    let hovered = battlefield::compute_hover_pos(response, rect, board);
+
+    // 拖曳開始：Shift 開始框選，點中已選取的單位開始平移
+    if response.drag_started() {
+        ui_state.group_drag = match hovered {
+            Some(pos) if shift_held => Some(GroupDrag::Select { anchor: pos }),
+            Some(pos) if ui_state.selected_units.contains(&pos) => {
+                Some(GroupDrag::Move { origin: pos })
+            }
+            _ => None,
+        };
+        if ui_state.group_drag.is_none() {
+            ui_state.selected_units.clear();
+        }
+        return ui_state.group_drag.is_some();
+    }
+
+    // 拖曳中：保持狀態不變
+    if response.dragged() {
+        return ui_state.group_drag.is_some();
+    }
+
+    // 拖曳結束：完成框選或套用平移
+    let group_drag = match ui_state.group_drag.take() {
+        None => return false,
+        Some(group_drag) => group_drag,
+    };
+    match (group_drag, hovered) {
+        (GroupDrag::Select { anchor }, Some(pos)) => {
+            let in_rect: HashSet<Position> = rect_positions(anchor, pos).into_iter().collect();
+            ui_state.selected_units = level
+                .unit_placements
+                .iter()
+                .map(|unit| unit.position)
+                .filter(|unit_pos| in_rect.contains(unit_pos))
+                .collect();
+        }
+        (GroupDrag::Move { origin }, Some(pos)) => {
+            let offset = (
+                pos.x as i32 - origin.x as i32,
+                pos.y as i32 - origin.y as i32,
+            );
+            match translate_units(level, &ui_state.selected_units, offset) {
+                Ok(moved) => ui_state.selected_units = moved,
+                Err(e) => message_state.set_error(e.kind().to_string()),
+            }
+        }
+        // 放開時滑鼠不在棋盤上：取消操作
+        (_, None) => {}
+    }
+    true
+}
+
 /// 建立查詢表以加速格子內容查詢
 fn prepare_lookup_maps(
     level: &LevelType,
@@ -685,10 +1133,16 @@ fn get_cell_info(
 fn get_cell_highlight(
     drag_state: Option<DragState>,
     hovered_in_bounds: Option<Position>,
+    selection_cells: &HashSet<Position>,
 ) -> impl Fn(Position) -> battlefield::CellHighlight {
     move |pos: Position| battlefield::CellHighlight {
-        border: (drag_state.is_some() && hovered_in_bounds == Some(pos))
-            .then_some(BATTLEFIELD_COLOR_HIGHLIGHT),
+        border: if drag_state.is_some() && hovered_in_bounds == Some(pos) {
+            Some(BATTLEFIELD_COLOR_HIGHLIGHT)
+        } else {
+            selection_cells
+                .contains(&pos)
+                .then_some(BATTLEFIELD_COLOR_SELECTION)
+        },
         bg: None,
     }
 }
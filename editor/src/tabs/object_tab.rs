@@ -3,8 +3,12 @@
 use crate::constants::DRAG_VALUE_SPEED;
 use crate::editor_item::EditorItem;
 use crate::generic_editor::MessageState;
-use board::domain::constants::IMPASSABLE_MOVEMENT_COST;
+use crate::utils::i18n::label;
+use board::domain::constants::{IMPASSABLE_MOVEMENT_COST, PERCENT_BASE};
+use board::domain::core_types::{SkillTag, TileTrigger};
 use board::loader_schema::ObjectType;
+use board::logic::movement::Direction;
+use strum::IntoEnumIterator;
 
 // ==================== EditorItem 實作 ====================
 
@@ -63,4 +67,94 @@ pub fn render_form(
         ui.label("阻擋聲音：");
         ui.checkbox(&mut obj.blocks_sound, "");
     });
+
+    ui.horizontal(|ui| {
+        ui.label("地形高度：");
+        ui.add(egui::DragValue::new(&mut obj.elevation).speed(DRAG_VALUE_SPEED));
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("隱蔽（%）：");
+        ui.add(
+            egui::DragValue::new(&mut obj.concealment)
+                .speed(DRAG_VALUE_SPEED)
+                .range(0..=PERCENT_BASE),
+        );
+    });
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label("禁用技能標籤：");
+        for tag in SkillTag::iter() {
+            let mut selected = obj.forbidden_skill_tags.contains(&tag);
+            if ui.checkbox(&mut selected, label(&tag)).changed() {
+                if selected {
+                    obj.forbidden_skill_tags.push(tag);
+                } else {
+                    obj.forbidden_skill_tags.retain(|t| *t != tag);
+                }
+            }
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("機關：");
+        egui::ComboBox::from_id_salt("tile_trigger")
+            .selected_text(tile_trigger_label(obj.trigger))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut obj.trigger, None, tile_trigger_label(None));
+                for trigger in TileTrigger::iter() {
+                    // 切換類型時保留既有的傷害數值
+                    let option = match (trigger, obj.trigger) {
+                        (
+                            TileTrigger::Damage { .. },
+                            Some(current @ TileTrigger::Damage { .. }),
+                        ) => current,
+                        _ => trigger,
+                    };
+                    ui.selectable_value(
+                        &mut obj.trigger,
+                        Some(option),
+                        tile_trigger_label(Some(trigger)),
+                    );
+                }
+            });
+        if let Some(TileTrigger::Damage { amount }) = &mut obj.trigger {
+            ui.label("傷害：");
+            ui.add(
+                egui::DragValue::new(amount)
+                    .speed(DRAG_VALUE_SPEED)
+                    .range(0..=i32::MAX),
+            );
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("懸崖朝向：");
+        let display = match obj.cliff_facing {
+            Some(direction) => direction.to_string(),
+            None => "無".to_string(),
+        };
+        egui::ComboBox::from_id_salt("cliff_facing")
+            .selected_text(display)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut obj.cliff_facing, None, "無");
+                for direction in Direction::iter() {
+                    ui.selectable_value(
+                        &mut obj.cliff_facing,
+                        Some(direction),
+                        direction.to_string(),
+                    );
+                }
+            });
+    });
+}
+
+// ==================== 本地輔助函數 ====================
+
+/// 機關類型的顯示名稱
+fn tile_trigger_label(trigger: Option<TileTrigger>) -> String {
+    match trigger {
+        None => "無".to_string(),
+        Some(trigger) => label(&trigger),
+    }
 }
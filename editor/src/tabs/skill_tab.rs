@@ -4,14 +4,21 @@ use crate::constants::*;
 use crate::editor_item::{EditorItem, validate_name};
 use crate::generic_editor::MessageState;
 use crate::utils::dnd::render_dnd_handle;
+use crate::utils::i18n;
 use crate::utils::search::{
     combobox_with_dynamic_height, filter_by_search, render_filtered_options, render_search_input,
 };
-use board::domain::alias::{Coord, TypeName};
+use board::domain::alias::{Coord, SkillName, TypeName};
+use board::domain::constants::{
+    MAX_SKILL_AFFECTED_DISTANCE, PERCENT_BASE, SKILL_TAG_EXCLUSIVE_GROUPS,
+};
 use board::domain::core_types::{
     Area, Attribute, BuffType, ContinuousEffect, DefenseType, Effect, EffectCondition, EffectNode,
-    EndCondition, Scaling, SkillTag, SkillType, Target, TriggeringSource,
+    EndCondition, Scaling, SkillTag, SkillType, Target, TargetCondition, TriggeringSource,
+    WeightedEffect,
 };
+use board::logic::skill::skill_category::validate_skill_tags;
+use board::logic::skill::skill_range::max_affected_distance;
 use std::collections::HashSet;
 use std::fmt::Display;
 use std::mem::discriminant;
@@ -23,6 +30,7 @@ use strum::IntoEnumIterator;
 pub struct SkillTabUIState {
     pub available_objects: Vec<TypeName>,
     pub object_search_query: String,
+    pub available_units: Vec<TypeName>,
 }
 
 // ==================== EditorItem 實作 ====================
@@ -46,27 +54,34 @@ impl EditorItem for SkillType {
         "技能"
     }
 
-    fn validate(&self, all_items: &[Self], editing_index: Option<usize>) -> Result<(), String> {
+    fn validate(
+        &self,
+        all_items: &[Self],
+        editing_index: Option<usize>,
+        _ui_state: &Self::UIState,
+    ) -> Result<(), String> {
         validate_name(self, all_items, editing_index)?;
 
-        // 驗證 tags 不重複
         let tags = match self {
             Self::Active { tags, .. }
             | Self::Reaction { tags, .. }
             | Self::Passive { tags, .. } => tags,
         };
-        let mut seen_tags = HashSet::new();
-        for tag in tags {
-            let tag_str = tag.to_string();
-            if !seen_tags.insert(tag_str.clone()) {
-                return Err(format!("標籤「{tag_str}」重複"));
-            }
-        }
+        validate_skill_tags(tags, SKILL_TAG_EXCLUSIVE_GROUPS)?;
 
         match self {
             Self::Active {
-                target, effects, ..
+                name,
+                requires,
+                hits,
+                target,
+                effects,
+                ..
             } => {
+                if *hits == 0 {
+                    return Err("攻擊段數至少為 1".to_string());
+                }
+                validate_requires(name, requires, all_items)?;
                 validate_target(target)?;
                 validate_effect_nodes(effects)?;
             }
@@ -83,6 +98,13 @@ impl EditorItem for SkillType {
             }
         }
 
+        let distance = max_affected_distance(self);
+        if distance > MAX_SKILL_AFFECTED_DISTANCE {
+            return Err(format!(
+                "技能影響距離 {distance} 超過上限 {MAX_SKILL_AFFECTED_DISTANCE}（射程加範圍過大）"
+            ));
+        }
+
         Ok(())
     }
 
@@ -96,6 +118,27 @@ impl EditorItem for SkillType {
     }
 }
 
+/// 驗證前置技能：不可為自己、不可重複、必須是已存在的技能
+fn validate_requires(
+    name: &str,
+    requires: &[SkillName],
+    all_items: &[SkillType],
+) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for required in requires {
+        if required == name {
+            return Err("前置技能不可為自己".to_string());
+        }
+        if !seen.insert(required) {
+            return Err(format!("前置技能「{required}」重複"));
+        }
+        if !all_items.iter().any(|skill| skill.name() == required) {
+            return Err(format!("前置技能「{required}」不存在"));
+        }
+    }
+    Ok(())
+}
+
 /// 從 effects 頂層挑出最大的 Area 寫回 target.area
 fn derive_target_area(effects: &[EffectNode]) -> Area {
     effects
@@ -112,13 +155,17 @@ fn area_size(area: &Area) -> Coord {
     match area {
         Area::Single => 1,
         Area::Diamond { radius } => *radius,
+        Area::Circle { radius, .. } => *radius,
         Area::Cross { length } => *length,
-        Area::Line { length } => *length,
+        Area::Line { length, .. } => *length,
+        Area::Rectangle { width, depth } => (*width).max(*depth),
     }
 }
 
 fn area_variant_rank(area: &Area) -> u8 {
     match area {
+        Area::Rectangle { .. } => 5,
+        Area::Circle { .. } => 4,
         Area::Diamond { .. } => 3,
         Area::Cross { .. } => 2,
         Area::Line { .. } => 1,
@@ -137,18 +184,30 @@ fn validate_area(area: &Area) -> Result<(), String> {
             }
             Ok(())
         }
+        Area::Circle { radius, .. } => {
+            if *radius < 1 {
+                return Err("Circle 半徑必須 >= 1".to_string());
+            }
+            Ok(())
+        }
         Area::Cross { length } => {
             if *length < 1 {
                 return Err("Cross 長度必須 >= 1".to_string());
             }
             Ok(())
         }
-        Area::Line { length } => {
+        Area::Line { length, .. } => {
             if *length < 1 {
                 return Err("Line 長度必須 >= 1".to_string());
             }
             Ok(())
         }
+        Area::Rectangle { width, depth } => {
+            if *width < 1 || *depth < 1 {
+                return Err("Rectangle 寬度與深度必須 >= 1".to_string());
+            }
+            Ok(())
+        }
     }
 }
 
@@ -202,7 +261,11 @@ fn validate_continuous_effects(effects: &[ContinuousEffect]) -> Result<(), Strin
             | ContinuousEffect::Perception { .. }
             | ContinuousEffect::DamageToMp { .. }
             | ContinuousEffect::EmitLight { .. }
-            | ContinuousEffect::Blinded => {}
+            | ContinuousEffect::Blinded
+            | ContinuousEffect::DeathNova { .. }
+            | ContinuousEffect::Regen { .. }
+            | ContinuousEffect::DamageResistance { .. } => {}
+            ContinuousEffect::Aura { effects, .. } => validate_continuous_effects(effects)?,
         }
     }
     Ok(())
@@ -298,8 +361,89 @@ fn validate_effect(effect: &Effect) -> Result<(), String> {
             }
             Ok(())
         }
+        Effect::Pull { distance } => {
+            if *distance < 1 {
+                return Err(format!("Pull 距離必須 >= 1，目前為 {distance}"));
+            }
+            Ok(())
+        }
+        Effect::Chain {
+            max_jumps,
+            jump_range,
+            falloff_percent,
+            ..
+        } => {
+            if *max_jumps < 1 {
+                return Err(format!("Chain 跳躍次數必須 >= 1，目前為 {max_jumps}"));
+            }
+            if *jump_range < 1 {
+                return Err(format!("Chain 跳躍範圍必須 >= 1，目前為 {jump_range}"));
+            }
+            if !(0..=100).contains(falloff_percent) {
+                return Err(format!(
+                    "Chain 衰減百分比必須介於 0 到 100，目前為 {falloff_percent}"
+                ));
+            }
+            Ok(())
+        }
+        Effect::Summon {
+            unit_type,
+            duration,
+        } => {
+            if unit_type.is_empty() {
+                return Err("Summon 必須選擇單位類型".to_string());
+            }
+            if *duration < 1 {
+                return Err("Summon 持續時間必須 >= 1".to_string());
+            }
+            Ok(())
+        }
+        Effect::Teleport { max_distance } => {
+            if *max_distance < 1 {
+                return Err(format!("Teleport 距離必須 >= 1，目前為 {max_distance}"));
+            }
+            Ok(())
+        }
+        Effect::Shield { amount, duration } => {
+            if *amount < 1 {
+                return Err(format!("Shield 護盾量必須 >= 1，目前為 {amount}"));
+            }
+            if *duration < 1 {
+                return Err("Shield 持續輪數必須 >= 1".to_string());
+            }
+            Ok(())
+        }
+        Effect::If { condition, then } => {
+            match condition {
+                TargetCondition::TargetBelowHp { percent } => {
+                    if !(1..=PERCENT_BASE).contains(percent) {
+                        return Err(format!(
+                            "If 的 HP 門檻必須介於 1 到 {PERCENT_BASE}，目前為 {percent}"
+                        ));
+                    }
+                }
+                TargetCondition::TargetHasBuff { buff } => {
+                    if buff.is_empty() {
+                        return Err("If 必須填寫 Buff 名稱".to_string());
+                    }
+                }
+            }
+            validate_effect(then)
+        }
+        Effect::Random { options } => {
+            if options.is_empty() {
+                return Err("Random 至少需要一個選項".to_string());
+            }
+            if options.iter().all(|option| option.weight == 0) {
+                return Err("Random 的權重總和必須 > 0".to_string());
+            }
+            options
+                .iter()
+                .try_for_each(|option| validate_effect(&option.effect))
+        }
         Effect::HpEffect { .. }
         | Effect::MpEffect { .. }
+        | Effect::Dispel { .. }
         | Effect::AllowRemainingMovement
         | Effect::SwapPosition => Ok(()),
     }
@@ -349,8 +493,33 @@ pub fn render_form(
     // variant 專屬欄位
     match skill {
         SkillType::Active {
-            target, effects, ..
+            requires,
+            hits,
+            recoil,
+            effect_order,
+            channel_turns,
+            target,
+            effects,
+            ..
         } => {
+            drag_value(ui, "攻擊段數：", hits);
+            drag_value(ui, "反噬 HP：", recoil);
+            drag_value(ui, "引導回合：", channel_turns);
+            enum_combo_box(ui, "效果順序：", effect_order, "effect_order");
+            render_simple_vec(
+                ui,
+                "前置技能：",
+                requires,
+                "skill_requires",
+                |ui, name, _| {
+                    ui.text_edit_singleline(name);
+                },
+            );
+            if ui.button("+ 新增前置技能").clicked() {
+                requires.push(SkillName::new());
+            }
+            ui.add_space(SPACING_SMALL);
+            ui.separator();
             ui.heading("目標設定");
             render_target(ui, target);
             ui.add_space(SPACING_SMALL);
@@ -421,6 +590,12 @@ fn render_skill_tags(ui: &mut egui::Ui, skill: &mut SkillType) {
 
 fn render_target(ui: &mut egui::Ui, target: &mut Target) {
     pair_drag_value(ui, "射程：", &mut target.range);
+    enum_combo_box(
+        ui,
+        "射程度量：",
+        &mut target.range_metric,
+        "target_range_metric",
+    );
 
     enum_combo_box(ui, "選擇方式：", &mut target.selection, "target_selection");
     enum_combo_box(
@@ -452,11 +627,31 @@ fn render_area(ui: &mut egui::Ui, area: &mut Area, id_salt: &str) {
         Area::Diamond { radius } => {
             drag_value(ui, "  半徑：", radius);
         }
+        Area::Circle { radius, metric } => {
+            drag_value(ui, "  半徑：", radius);
+            enum_combo_box(ui, "  度量：", metric, &format!("{id_salt}_metric"));
+        }
         Area::Cross { length } => {
             drag_value(ui, "  長度：", length);
         }
-        Area::Line { length } => {
+        Area::Line {
+            length,
+            snap_to_facing,
+            pierce,
+        } => {
             drag_value(ui, "  長度：", length);
+            ui.horizontal(|ui| {
+                ui.label("  吸附 8 方位：");
+                ui.checkbox(snap_to_facing, "");
+            });
+            ui.horizontal(|ui| {
+                ui.label("  穿透阻擋：");
+                ui.checkbox(pierce, "");
+            });
+        }
+        Area::Rectangle { width, depth } => {
+            drag_value(ui, "  寬度：", width);
+            drag_value(ui, "  深度：", depth);
         }
     }
 }
@@ -614,6 +809,26 @@ fn render_continuous_effect(ui: &mut egui::Ui, effect: &mut ContinuousEffect, id
         ContinuousEffect::Blinded => {
             ui.label("（無額外欄位）");
         }
+        ContinuousEffect::DeathNova { value } => {
+            drag_value(ui, "HP 變化：", value);
+        }
+        ContinuousEffect::Regen { amount } => {
+            drag_value(ui, "每回合回復 HP：", amount);
+        }
+        ContinuousEffect::DamageResistance {
+            damage_type,
+            percent,
+        } => {
+            enum_combo_box(ui, "傷害屬性：", damage_type, &format!("{id_salt}_dtype"));
+            drag_value(ui, "抗性%（負值為弱點）：", percent);
+        }
+        ContinuousEffect::Aura { range, effects } => {
+            drag_value(ui, "範圍：", range);
+            ui.label("範圍內友軍獲得：");
+            ui.indent(format!("{id_salt}_aura"), |ui| {
+                render_continuous_effect_list(ui, effects, &format!("{id_salt}_aura"));
+            });
+        }
     }
 }
 
@@ -708,14 +923,44 @@ fn render_effect(
     ui.add_space(SPACING_SMALL);
 
     match effect {
-        Effect::HpEffect { scaling } => {
+        Effect::HpEffect {
+            scaling,
+            damage_type,
+        } => {
             render_scaling(ui, scaling, &format!("{id_salt}_scaling"));
+            enum_combo_box(ui, "傷害屬性：", damage_type, &format!("{id_salt}_dtype"));
         }
         Effect::MpEffect { value } => {
             drag_value(ui, "MP 值：", value);
         }
         Effect::ApplyBuff { buff } => {
-            render_buff_type(ui, buff, &format!("{id_salt}_buff"), ui_state);
+            render_buff_type(
+                ui,
+                Arc::make_mut(buff),
+                &format!("{id_salt}_buff"),
+                ui_state,
+            );
+        }
+        Effect::Dispel { beneficial } => {
+            ui.horizontal(|ui| {
+                ui.label("驅散增益（取消勾選為驅散減益）：");
+                ui.checkbox(beneficial, "");
+            });
+        }
+        Effect::Chain {
+            scaling,
+            damage_type,
+            filter,
+            max_jumps,
+            jump_range,
+            falloff_percent,
+        } => {
+            render_scaling(ui, scaling, &format!("{id_salt}_scaling"));
+            enum_combo_box(ui, "傷害屬性：", damage_type, &format!("{id_salt}_dtype"));
+            enum_combo_box(ui, "跳躍過濾：", filter, &format!("{id_salt}_filter"));
+            drag_value(ui, "跳躍次數：", max_jumps);
+            drag_value(ui, "跳躍範圍：", jump_range);
+            drag_value(ui, "每跳衰減 %：", falloff_percent);
         }
         Effect::ForcedMove {
             direction,
@@ -724,6 +969,19 @@ fn render_effect(
             enum_combo_box(ui, "方向：", direction, &format!("{id_salt}_dir"));
             drag_value(ui, "距離：", distance);
         }
+        Effect::Teleport { max_distance } => {
+            drag_value(ui, "最大距離：", max_distance);
+        }
+        Effect::Pull { distance } => {
+            drag_value(ui, "距離：", distance);
+        }
+        Effect::Summon {
+            unit_type,
+            duration,
+        } => {
+            render_unit_type_selector(ui, unit_type, &ui_state.available_units, id_salt);
+            drag_value(ui, "持續輪數：", duration);
+        }
         Effect::Trample { distance, scaling } => {
             drag_value(ui, "距離：", distance);
             render_scaling(ui, scaling, &format!("{id_salt}_scaling"));
@@ -760,12 +1018,86 @@ fn render_effect(
                 );
             });
         }
+        Effect::Shield { amount, duration } => {
+            drag_value(ui, "護盾量：", amount);
+            drag_value(ui, "持續輪數：", duration);
+        }
+        Effect::If { condition, then } => {
+            enum_combo_box(ui, "條件：", condition, &format!("{id_salt}_cond"));
+            match condition {
+                TargetCondition::TargetBelowHp { percent } => {
+                    drag_value(ui, "目標 HP 低於（%）：", percent);
+                }
+                TargetCondition::TargetHasBuff { buff } => {
+                    ui.horizontal(|ui| {
+                        ui.label("Buff 名稱：");
+                        ui.text_edit_singleline(buff);
+                    });
+                }
+            }
+            ui.label("成立時：");
+            ui.indent(format!("{id_salt}_then"), |ui| {
+                render_effect(ui, then, &format!("{id_salt}_then"), ui_state);
+            });
+        }
+        Effect::Random { options } => {
+            let mut to_remove = None;
+            for (idx, option) in options.iter_mut().enumerate() {
+                let option_salt = format!("{id_salt}_option_{idx}");
+                ui.horizontal(|ui| {
+                    if delete_button(ui) {
+                        to_remove = Some(idx);
+                    }
+                    drag_value(ui, "權重：", &mut option.weight);
+                });
+                ui.indent(&option_salt, |ui| {
+                    render_effect(ui, &mut option.effect, &option_salt, ui_state);
+                });
+            }
+            if let Some(idx) = to_remove {
+                options.remove(idx);
+            }
+            if ui.button("+ 新增隨機選項").clicked() {
+                options.push(WeightedEffect {
+                    weight: 1,
+                    effect: Effect::default(),
+                });
+            }
+        }
         Effect::AllowRemainingMovement | Effect::SwapPosition => {
             ui.label("（無額外欄位）");
         }
     }
 }
 
+/// 渲染單位類型選擇器
+fn render_unit_type_selector(
+    ui: &mut egui::Ui,
+    unit_type: &mut TypeName,
+    available_units: &[TypeName],
+    id_salt: &str,
+) {
+    if available_units.is_empty() {
+        ui.label("（尚未定義任何單位，請先到「單位」tab 創建單位）");
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("單位類型：");
+        egui::ComboBox::from_id_salt(format!("{id_salt}_unit_type"))
+            .selected_text(if unit_type.is_empty() {
+                "選擇單位"
+            } else {
+                unit_type.as_str()
+            })
+            .show_ui(ui, |ui| {
+                for name in available_units {
+                    ui.selectable_value(unit_type, name.clone(), name);
+                }
+            });
+    });
+}
+
 /// 渲染物件類型選擇器（含搜尋）
 fn render_object_type_selector(
     ui: &mut egui::Ui,
@@ -827,9 +1159,15 @@ fn render_buff_type(
     ui_state: &mut SkillTabUIState,
 ) {
     ui.horizontal(|ui| {
-        ui.label("可疊加：");
-        ui.checkbox(&mut buff.stackable, "");
+        ui.label("增益：");
+        ui.checkbox(&mut buff.beneficial, "");
     });
+    enum_combo_box(
+        ui,
+        "疊加規則：",
+        &mut buff.stacking,
+        &format!("{id_salt}_stacking"),
+    );
 
     ui.add_space(SPACING_SMALL);
     ui.label("持續效果：");
@@ -864,6 +1202,9 @@ fn render_end_condition(ui: &mut egui::Ui, condition: &mut EndCondition, id_salt
     match condition {
         EndCondition::Duration(duration) => {
             drag_value(ui, "Duration：", duration);
+            if *duration == 0 {
+                ui.label("（永久）");
+            }
         }
         EndCondition::CasterUsesSkillWithoutTag(tag) => {
             ui.label("CasterUsesSkillWithoutTag：");
@@ -907,19 +1248,19 @@ fn render_effect_condition(ui: &mut egui::Ui, condition: &mut EffectCondition, i
 
 // ==================== 通用輔助函數 ====================
 
-/// 通用 enum 下拉選單，使用 Display 比較 discriminant
+/// 通用 enum 下拉選單，以在地化名稱顯示並比較 discriminant
 fn enum_combo_box<E>(ui: &mut egui::Ui, label: &str, current: &mut E, id_salt: &str)
 where
     E: IntoEnumIterator + Default + Display + Clone,
 {
     ui.horizontal(|ui| {
         ui.label(label);
-        let current_label = current.to_string();
+        let current_label = i18n::label(current);
         egui::ComboBox::from_id_salt(id_salt)
             .selected_text(&current_label)
             .show_ui(ui, |ui| {
                 for variant in E::iter() {
-                    let variant_label = variant.to_string();
+                    let variant_label = i18n::label(&variant);
                     let selected = variant_label == current_label;
                     if ui.selectable_label(selected, &variant_label).clicked() {
                         *current = variant;
@@ -938,7 +1279,7 @@ where
         .num_columns(columns)
         .show(ui, |ui| {
             for (idx, variant) in E::iter().enumerate() {
-                if ui.button(format!("+ {}", variant)).clicked() {
+                if ui.button(format!("+ {}", i18n::label(&variant))).clicked() {
                     items.push(variant);
                 }
                 if (idx + 1) % columns == 0 {
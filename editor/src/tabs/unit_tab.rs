@@ -1,16 +1,22 @@
 //! 單位編輯器 tab
 
-use crate::constants::{SPACING_MEDIUM, SPACING_SMALL};
-use crate::editor_item::EditorItem;
+use crate::constants::{DRAG_VALUE_SPEED, SPACING_MEDIUM, SPACING_SMALL};
+use crate::editor_item::{EditorItem, validate_name};
 use crate::generic_editor::MessageState;
+use crate::utils::i18n::label;
 use crate::utils::search::{filter_by_search, render_search_input};
 use board::domain::alias::SkillName;
+use board::domain::core_types::{SkillTag, SkillType};
 use board::loader_schema::UnitType;
+use board::logic::skill::skill_category::{is_category_tag, validate_unit_skills};
+use strum::IntoEnumIterator;
 
 /// 單位編輯器的 UI 狀態
 #[derive(Debug, Default)]
 pub struct UnitTabUIState {
     pub available_skills: Vec<SkillName>,
+    /// 完整技能定義（驗證技能類別用）
+    pub skills: Vec<SkillType>,
 
     pub skill_search_query: SkillName,
 }
@@ -32,6 +38,16 @@ impl EditorItem for UnitType {
         "單位"
     }
 
+    fn validate(
+        &self,
+        all_items: &[Self],
+        editing_index: Option<usize>,
+        ui_state: &Self::UIState,
+    ) -> Result<(), String> {
+        validate_name(self, all_items, editing_index)?;
+        validate_unit_skills(self, &ui_state.skills).map_err(|errors| errors.join("\n"))
+    }
+
     fn after_confirm(&mut self, ui_state: &Self::UIState) {
         // 依技能列表的順序重建已選技能，使儲存順序與列表一致
         self.skills = ui_state
@@ -62,6 +78,10 @@ pub fn render_form(
         ui.text_edit_singleline(&mut unit.name);
     });
 
+    ui.add_space(SPACING_SMALL);
+    render_allowed_categories(ui, unit);
+    render_max_skills(ui, unit);
+
     ui.add_space(SPACING_SMALL);
     ui.separator();
     ui.heading("技能選擇");
@@ -99,7 +119,14 @@ pub fn render_form(
     ui.separator();
 
     // 依儲存順序列出技能總數與已選技能名稱，方便快速檢視
-    ui.label(format!("已選擇：{} 個技能", unit.skills.len()));
+    match unit.max_skills {
+        Some(max_skills) => ui.label(format!(
+            "已選擇：{} / {} 個技能",
+            unit.skills.len(),
+            max_skills
+        )),
+        None => ui.label(format!("已選擇：{} 個技能", unit.skills.len())),
+    };
     ui.horizontal_wrapped(|ui| {
         for skill_name in &unit.skills {
             ui.label(skill_name);
@@ -107,3 +134,50 @@ pub fn render_form(
         }
     });
 }
+
+/// 渲染可學技能類別（全不勾表示不限制）
+fn render_allowed_categories(ui: &mut egui::Ui, unit: &mut UnitType) {
+    ui.horizontal(|ui| {
+        ui.label("可學類別：");
+        for category in SkillTag::iter().filter(is_category_tag) {
+            let mut selected = unit.allowed_categories.contains(&category);
+            if ui.checkbox(&mut selected, label(&category)).changed() {
+                if selected {
+                    unit.allowed_categories.push(category);
+                } else {
+                    unit.allowed_categories.retain(|c| *c != category);
+                }
+            }
+        }
+        if unit.allowed_categories.is_empty() {
+            ui.label("（不限制）");
+        }
+    });
+}
+
+/// 渲染技能欄位上限（不勾選表示不限制）
+fn render_max_skills(ui: &mut egui::Ui, unit: &mut UnitType) {
+    ui.horizontal(|ui| {
+        ui.label("技能欄位上限：");
+        let mut limited = unit.max_skills.is_some();
+        if ui.checkbox(&mut limited, "限制").changed() {
+            unit.max_skills = if limited {
+                Some(unit.skills.len())
+            } else {
+                None
+            };
+        }
+        match &mut unit.max_skills {
+            Some(max_skills) => {
+                ui.add(
+                    egui::DragValue::new(max_skills)
+                        .speed(DRAG_VALUE_SPEED)
+                        .range(0..=usize::MAX),
+                );
+            }
+            None => {
+                ui.label("（不限制）");
+            }
+        }
+    });
+}
@@ -0,0 +1,162 @@
+use crate::constants::{BATTLEFIELD_CELL_SIZE, BATTLEFIELD_GRID_SPACING};
+use crate::tabs::level_tab::battlefield::{
+    RulerAxis, calculate_grid_dimensions, calculate_visible_range, center_scroll_offset,
+    fit_scroll_offset, switch_level_view, visible_tick_positions,
+};
+use board::ecs_types::components::Position;
+use board::ecs_types::resources::Board;
+use std::collections::HashMap;
+
+const CELL_STRIDE: f32 = BATTLEFIELD_CELL_SIZE + BATTLEFIELD_GRID_SPACING;
+
+#[test]
+fn test_fit_small_board_shows_every_cell() {
+    let board = Board {
+        width: 5,
+        height: 4,
+    };
+    let viewport = calculate_grid_dimensions(board) + egui::vec2(100.0, 50.0);
+
+    let offset = fit_scroll_offset(viewport, board);
+
+    assert_eq!(offset, egui::Vec2::ZERO);
+    let visible = calculate_visible_range(offset, viewport, board);
+    assert_eq!(visible.min, Position { x: 0, y: 0 });
+    assert_eq!(
+        visible.max,
+        Position {
+            x: board.width,
+            y: board.height
+        }
+    );
+}
+
+#[test]
+fn test_fit_large_board_centers_on_middle() {
+    let board = Board {
+        width: 40,
+        height: 30,
+    };
+    let viewport = egui::vec2(10.0 * CELL_STRIDE, 8.0 * CELL_STRIDE);
+
+    let offset = fit_scroll_offset(viewport, board);
+
+    let visible = calculate_visible_range(offset, viewport, board);
+    let middle = Position { x: 20, y: 15 };
+    assert!(visible.min.x <= middle.x && middle.x < visible.max.x);
+    assert!(visible.min.y <= middle.y && middle.y < visible.max.y);
+}
+
+#[test]
+fn test_center_on_clamps_to_board_edges() {
+    let board = Board {
+        width: 40,
+        height: 30,
+    };
+    let viewport = egui::vec2(10.0 * CELL_STRIDE, 8.0 * CELL_STRIDE);
+    let max_offset = calculate_grid_dimensions(board) - viewport;
+
+    let test_data = [
+        ("左上角", Position { x: 0, y: 0 }, egui::Vec2::ZERO),
+        ("右下角", Position { x: 39, y: 29 }, max_offset),
+    ];
+
+    for (label, pos, expected) in test_data {
+        let offset = center_scroll_offset(pos, viewport, board);
+        assert_eq!(offset, expected, "{label}");
+    }
+}
+
+#[test]
+fn test_center_on_interior_unit_puts_it_mid_viewport() {
+    let board = Board {
+        width: 40,
+        height: 30,
+    };
+    let viewport = egui::vec2(10.0 * CELL_STRIDE, 8.0 * CELL_STRIDE);
+    let pos = Position { x: 20, y: 15 };
+
+    let offset = center_scroll_offset(pos, viewport, board);
+
+    let cell_center_x = pos.x as f32 * CELL_STRIDE + BATTLEFIELD_CELL_SIZE / 2.0;
+    let cell_center_y = pos.y as f32 * CELL_STRIDE + BATTLEFIELD_CELL_SIZE / 2.0;
+    assert_eq!(cell_center_x - offset.x, viewport.x / 2.0);
+    assert_eq!(cell_center_y - offset.y, viewport.y / 2.0);
+}
+
+#[test]
+fn test_switch_level_view_restores_per_level_offset() {
+    let mut saved = HashMap::new();
+    let offset_a = egui::vec2(120.0, 40.0);
+    let offset_b = egui::vec2(300.0, 0.0);
+
+    // 首次檢視 A：沒有來源關卡，沿用目前偏移
+    let current = switch_level_view(&mut saved, "", "A", offset_a);
+    assert_eq!(current, offset_a);
+    assert!(saved.is_empty());
+
+    // A → B：保存 A，B 未記錄過則沿用目前偏移
+    let current = switch_level_view(&mut saved, "A", "B", offset_a);
+    assert_eq!(current, offset_a);
+
+    // 在 B 捲動後回到 A：還原 A 的偏移並保存 B
+    let current = switch_level_view(&mut saved, "B", "A", offset_b);
+    assert_eq!(current, offset_a);
+
+    // 再回到 B：還原 B 的偏移
+    let current = switch_level_view(&mut saved, "A", "B", current);
+    assert_eq!(current, offset_b);
+    assert_eq!(saved.len(), 2);
+}
+
+#[test]
+fn test_visible_tick_positions() {
+    let board = Board {
+        width: 20,
+        height: 10,
+    };
+    let viewport = egui::vec2(4.0 * CELL_STRIDE, 3.0 * CELL_STRIDE);
+    let half_cell = BATTLEFIELD_CELL_SIZE / 2.0;
+
+    let test_data = [
+        (
+            "原點欄",
+            RulerAxis::Column,
+            egui::Vec2::ZERO,
+            vec![0, 1, 2, 3],
+        ),
+        ("原點列", RulerAxis::Row, egui::Vec2::ZERO, vec![0, 1, 2]),
+        (
+            "欄捲動 5 格",
+            RulerAxis::Column,
+            egui::vec2(5.0 * CELL_STRIDE, 0.0),
+            vec![5, 6, 7, 8],
+        ),
+        (
+            "捲動半格時第一格中心已出界",
+            RulerAxis::Column,
+            egui::vec2(CELL_STRIDE - half_cell + 1.0, 0.0),
+            vec![1, 2, 3, 4],
+        ),
+        (
+            "捲到底不超出棋盤",
+            RulerAxis::Row,
+            egui::vec2(0.0, 8.0 * CELL_STRIDE),
+            vec![8, 9],
+        ),
+    ];
+
+    for (label, axis, offset, expected) in test_data {
+        let ticks = visible_tick_positions(axis, offset, viewport, board);
+        let indices: Vec<_> = ticks.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indices, expected, "{label}");
+        for (index, pos) in ticks {
+            let center = index as f32 * CELL_STRIDE + half_cell;
+            let expected_pos = match axis {
+                RulerAxis::Column => egui::pos2(center - offset.x, 0.0),
+                RulerAxis::Row => egui::pos2(0.0, center - offset.y),
+            };
+            assert_eq!(pos, expected_pos, "{label}：刻度 {index}");
+        }
+    }
+}
@@ -1,10 +1,18 @@
 use crate::editor_item::EditorItem;
-use crate::generic_editor::{GenericEditorState, MessageState};
-use crate::generic_io::{load_file, save_file};
+use crate::generic_editor::{
+    EditMode, GenericEditorState, MessageState, ReloadDecision, autosave_is_newer, reload_decision,
+    should_autosave,
+};
+use crate::generic_io::{
+    FileIoError, autosave_file, autosave_path_for, backup_path_for, load_file,
+    merge_reloaded_items, read_items, recover_autosave, reload_file, resolve_pending_reload,
+    save_file, temp_path_for, write_items,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 struct TestItem {
@@ -28,6 +36,14 @@ impl EditorItem for TestItem {
     }
 }
 
+/// 最新一則訊息是否為錯誤
+fn is_error(message_state: &MessageState) -> bool {
+    message_state
+        .recent(1)
+        .next()
+        .is_some_and(|message| message.is_error)
+}
+
 fn temp_file_path(name: &str) -> std::path::PathBuf {
     let unique = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -61,6 +77,7 @@ impl Drop for TempFileGuard {
 #[test]
 fn save_file_rewrites_toml_without_legacy_fields() {
     let path = TempFileGuard::new(temp_file_path("legacy_cleanup"));
+    let _backup = TempFileGuard::new(backup_path_for(path.path(), 1));
 
     let legacy_content = r#"
 [[skills]]
@@ -90,3 +107,309 @@ removed_field = "legacy"
     assert!(saved.contains("value = 7"));
     assert!(!saved.contains("removed_field"));
 }
+
+#[test]
+fn read_items_parse_error_mentions_path_and_line() {
+    let path = TempFileGuard::new(temp_file_path("malformed"));
+    let malformed_content = r#"
+[[skills]]
+name = "Slash"
+value = = 7
+"#;
+    fs::write(path.path(), malformed_content).expect("should write malformed toml");
+
+    let err = read_items::<TestItem>(path.path(), "skills").expect_err("should fail to parse");
+    assert!(matches!(err, FileIoError::Parse { .. }));
+    let message = err.to_string();
+    assert!(message.contains(&path.path().display().to_string()));
+    assert!(message.contains(":4:"));
+}
+
+#[test]
+fn load_file_missing_key_reports_path() {
+    let path = TempFileGuard::new(temp_file_path("missing_key"));
+    fs::write(path.path(), "[[objects]]\nname = \"Rock\"\nvalue = 1\n").expect("should write toml");
+
+    let mut state = GenericEditorState::<TestItem>::default();
+    load_file(&mut state, path.path(), "skills");
+    assert!(is_error(&state.message_state));
+    assert!(
+        state
+            .message_state
+            .recent(1)
+            .next()
+            .expect("應有訊息")
+            .text
+            .contains(&path.path().display().to_string())
+    );
+}
+
+#[test]
+fn save_file_failure_keeps_original_intact() {
+    let path = TempFileGuard::new(temp_file_path("atomic"));
+    let original_content = "[[skills]]\nname = \"Slash\"\nvalue = 7\n";
+    fs::write(path.path(), original_content).expect("should write original toml");
+
+    // 在暫存檔位置放一個目錄，讓寫入暫存檔必定失敗
+    let temp_path = temp_path_for(path.path());
+    fs::create_dir(&temp_path).expect("should create blocking directory");
+
+    let mut state = GenericEditorState::<TestItem> {
+        items: vec![TestItem {
+            name: "Fireball".to_string(),
+            value: 9,
+        }],
+        ..Default::default()
+    };
+    save_file(&mut state, path.path(), "skills");
+    fs::remove_dir(&temp_path).expect("should remove blocking directory");
+
+    assert!(is_error(&state.message_state));
+    let content = fs::read_to_string(path.path()).expect("should read original toml");
+    assert_eq!(content, original_content);
+}
+
+#[test]
+fn save_file_leaves_no_temp_file() {
+    let path = TempFileGuard::new(temp_file_path("no_temp"));
+    let mut state = GenericEditorState::<TestItem> {
+        items: vec![TestItem {
+            name: "Slash".to_string(),
+            value: 7,
+        }],
+        ..Default::default()
+    };
+    save_file(&mut state, path.path(), "skills");
+
+    assert!(!is_error(&state.message_state));
+    assert!(path.path().exists());
+    assert!(!temp_path_for(path.path()).exists());
+}
+
+#[test]
+fn write_items_rotates_backups() {
+    let path = TempFileGuard::new(temp_file_path("backup"));
+    let backups: Vec<TempFileGuard> = (1..=3)
+        .map(|index| TempFileGuard::new(backup_path_for(path.path(), index)))
+        .collect();
+    let backup_count = 2;
+
+    for value in 1..=3 {
+        let items = vec![TestItem {
+            name: "Slash".to_string(),
+            value,
+        }];
+        write_items(path.path(), "skills", items, backup_count).expect("should save");
+    }
+
+    let read_value =
+        |p: &Path| read_items::<TestItem>(p, "skills").expect("should read saved toml")[0].value;
+    assert_eq!(read_value(path.path()), 3);
+    assert_eq!(read_value(backups[0].path()), 2);
+    assert_eq!(read_value(backups[1].path()), 1);
+    assert!(!backups[2].path().exists());
+}
+
+fn item(name: &str, value: i32) -> TestItem {
+    TestItem {
+        name: name.to_string(),
+        value,
+    }
+}
+
+/// 在已載入的 state 上把指定項目改成新值（走 confirm_edit，讓 dirty 追蹤生效）
+fn edit_value(state: &mut GenericEditorState<TestItem>, index: usize, value: i32) {
+    let mut edited = state.items[index].clone();
+    edited.value = value;
+    state.edit_mode = EditMode::Editing(index, edited);
+    state.confirm_edit();
+}
+
+#[test]
+fn reload_decision_by_modification_source() {
+    let test_data = [
+        ("都沒改", false, false, ReloadDecision::TakeDisk),
+        ("只有磁碟改", false, true, ReloadDecision::TakeDisk),
+        ("只有本地改", true, false, ReloadDecision::KeepLocal),
+        ("兩邊都改", true, true, ReloadDecision::Conflict),
+    ];
+    for (label, local, disk, expected) in test_data {
+        assert_eq!(reload_decision(local, disk), expected, "{label}");
+    }
+}
+
+#[test]
+fn merge_reloaded_items_keeps_only_listed_local_versions() {
+    let local = vec![item("Slash", 1), item("Local", 2)];
+    let disk = vec![item("Slash", 10), item("Fireball", 20)];
+    let keep: HashSet<String> = ["Local".to_string(), "Fireball".to_string()].into();
+
+    let merged = merge_reloaded_items(&local, disk, &keep);
+
+    // Slash 取磁碟；Fireball 本地已刪除故移除；Local 只在本地，附加於後
+    assert_eq!(merged, vec![item("Slash", 10), item("Local", 2)]);
+}
+
+#[test]
+fn reload_file_disk_only_change_takes_disk() {
+    let path = TempFileGuard::new(temp_file_path("reload_disk_only"));
+    write_items(
+        path.path(),
+        "skills",
+        vec![item("Slash", 1), item("Fireball", 2)],
+        0,
+    )
+    .expect("should write toml");
+    let mut state = GenericEditorState::<TestItem>::default();
+    load_file(&mut state, path.path(), "skills");
+
+    write_items(
+        path.path(),
+        "skills",
+        vec![item("Slash", 5), item("Fireball", 2)],
+        0,
+    )
+    .expect("should write toml");
+    reload_file(&mut state, path.path(), "skills");
+
+    assert_eq!(state.items, vec![item("Slash", 5), item("Fireball", 2)]);
+    assert!(state.pending_reload.is_none());
+    assert!(state.dirty_items.is_empty());
+}
+
+#[test]
+fn reload_file_local_only_change_keeps_local() {
+    let path = TempFileGuard::new(temp_file_path("reload_local_only"));
+    write_items(
+        path.path(),
+        "skills",
+        vec![item("Slash", 1), item("Fireball", 2)],
+        0,
+    )
+    .expect("should write toml");
+    let mut state = GenericEditorState::<TestItem>::default();
+    load_file(&mut state, path.path(), "skills");
+    edit_value(&mut state, 0, 9);
+
+    // 磁碟上只有 Fireball 被外部修改
+    write_items(
+        path.path(),
+        "skills",
+        vec![item("Slash", 1), item("Fireball", 7)],
+        0,
+    )
+    .expect("should write toml");
+    reload_file(&mut state, path.path(), "skills");
+
+    assert_eq!(state.items, vec![item("Slash", 9), item("Fireball", 7)]);
+    assert!(state.pending_reload.is_none());
+    assert_eq!(state.dirty_items, HashSet::from(["Slash".to_string()]));
+}
+
+#[test]
+fn reload_file_conflict_waits_for_choice() {
+    let path = TempFileGuard::new(temp_file_path("reload_conflict"));
+
+    for (keep_local, expected_value) in [(true, 9), (false, 5)] {
+        let mut state = GenericEditorState::<TestItem>::default();
+        write_items(path.path(), "skills", vec![item("Slash", 1)], 0).expect("should write toml");
+        load_file(&mut state, path.path(), "skills");
+        edit_value(&mut state, 0, 9);
+
+        write_items(path.path(), "skills", vec![item("Slash", 5)], 0).expect("should write toml");
+        reload_file(&mut state, path.path(), "skills");
+
+        assert!(is_error(&state.message_state), "衝突時應警告");
+        assert_eq!(state.items, vec![item("Slash", 9)], "使用者選擇前不應覆蓋");
+        let pending = state.pending_reload.as_ref().expect("應暫存衝突");
+        assert_eq!(pending.conflicts, vec!["Slash".to_string()]);
+
+        resolve_pending_reload(&mut state, keep_local);
+        assert_eq!(state.items, vec![item("Slash", expected_value)]);
+        assert!(state.pending_reload.is_none());
+        assert_eq!(state.dirty_items.is_empty(), !keep_local);
+    }
+}
+
+#[test]
+fn should_autosave_requires_changes_and_elapsed_interval() {
+    let interval = Duration::from_secs(60);
+    let test_data = [
+        ("沒有修改", false, Duration::from_secs(120), false),
+        ("有修改但未滿間隔", true, Duration::from_secs(59), false),
+        ("有修改且剛好滿間隔", true, Duration::from_secs(60), true),
+        ("有修改且超過間隔", true, Duration::from_secs(120), true),
+    ];
+    for (label, dirty, elapsed, expected) in test_data {
+        assert_eq!(
+            should_autosave(dirty, elapsed, interval),
+            expected,
+            "{label}"
+        );
+    }
+}
+
+#[test]
+fn autosave_is_newer_by_modified_time() {
+    let older = UNIX_EPOCH + Duration::from_secs(100);
+    let newer = UNIX_EPOCH + Duration::from_secs(200);
+    let test_data = [
+        ("沒有自動存檔", Some(newer), None, false),
+        ("沒有主檔", None, Some(older), true),
+        ("自動存檔較新", Some(older), Some(newer), true),
+        ("主檔較新", Some(newer), Some(older), false),
+        ("同時間", Some(older), Some(older), false),
+    ];
+    for (label, main, autosave, expected) in test_data {
+        assert_eq!(autosave_is_newer(main, autosave), expected, "{label}");
+    }
+}
+
+#[test]
+fn autosave_path_sits_next_to_main_file() {
+    assert_eq!(
+        autosave_path_for(Path::new("ignore-data/levels.toml")),
+        PathBuf::from("ignore-data/levels.autosave.toml")
+    );
+}
+
+#[test]
+fn autosave_keeps_main_file_and_recovery_restores_dirty_items() {
+    let path = TempFileGuard::new(temp_file_path("autosave"));
+    write_items(
+        path.path(),
+        "skills",
+        vec![item("Slash", 1), item("Fireball", 2)],
+        0,
+    )
+    .expect("should write toml");
+    let autosave = TempFileGuard::new(autosave_path_for(path.path()));
+    let mut state = GenericEditorState::<TestItem>::default();
+    load_file(&mut state, path.path(), "skills");
+    edit_value(&mut state, 0, 9);
+    state.delete_item(1);
+
+    autosave_file(&mut state, path.path(), "skills");
+    assert!(!is_error(&state.message_state));
+    assert_eq!(
+        read_items::<TestItem>(path.path(), "skills").expect("should read main file"),
+        vec![item("Slash", 1), item("Fireball", 2)],
+        "自動存檔不應動到主檔"
+    );
+    assert_eq!(state.dirty_items.len(), 2, "自動存檔不應清除未儲存標記");
+
+    // 模擬重新啟動：重新載入主檔後從自動存檔還原
+    let mut restarted = GenericEditorState::<TestItem>::default();
+    load_file(&mut restarted, path.path(), "skills");
+    restarted.pending_recovery = true;
+    recover_autosave(&mut restarted, path.path(), "skills");
+
+    assert_eq!(restarted.items, vec![item("Slash", 9)]);
+    assert_eq!(
+        restarted.dirty_items,
+        HashSet::from(["Slash".to_string(), "Fireball".to_string()]),
+        "與主檔不同或已刪除的項目應標記為未儲存"
+    );
+    assert!(!restarted.pending_recovery);
+    assert!(autosave.path().exists());
+}
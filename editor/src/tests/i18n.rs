@@ -0,0 +1,42 @@
+use crate::constants::LOCALE_ZH_TW;
+use crate::utils::i18n::{Locale, label, label_in};
+use board::domain::core_types::{SkillTag, TargetFilter, TargetSelection, TileTrigger};
+use strum::IntoEnumIterator;
+
+const TEST_LOCALE: Locale = &[
+    ("SkillTag::Melee", "Nahkampf"),
+    ("TargetFilter::Enemy", "Feind"),
+];
+
+#[test]
+fn test_label_in_resolves_localized_name() {
+    assert_eq!(label_in(TEST_LOCALE, &SkillTag::Melee), "Nahkampf");
+    assert_eq!(label_in(TEST_LOCALE, &TargetFilter::Enemy), "Feind");
+}
+
+#[test]
+fn test_label_in_falls_back_to_strum_name() {
+    // 語系缺少的變體
+    assert_eq!(label_in(TEST_LOCALE, &SkillTag::Ranged), "Ranged");
+    // 同名變體屬於不同型別時不可誤用
+    assert_eq!(
+        label_in(&[("SkillTag::Enemy", "x")], &TargetFilter::Enemy),
+        "Enemy"
+    );
+    // 帶資料的變體以變體名稱查詢
+    assert_eq!(label_in(&[], &TileTrigger::Damage { amount: 5 }), "Damage");
+}
+
+#[test]
+fn test_default_locale_covers_ui_enums() {
+    let missing: Vec<String> = SkillTag::iter()
+        .map(|v| (v.to_string(), label(&v)))
+        .chain(TargetFilter::iter().map(|v| (v.to_string(), label(&v))))
+        .chain(TargetSelection::iter().map(|v| (v.to_string(), label(&v))))
+        .chain(TileTrigger::iter().map(|v| (v.to_string(), label(&v))))
+        .filter(|(name, text)| name == text)
+        .map(|(name, _)| name)
+        .collect();
+    assert!(missing.is_empty(), "預設語系缺少翻譯：{missing:?}");
+    assert!(!LOCALE_ZH_TW.is_empty());
+}
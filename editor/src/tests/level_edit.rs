@@ -0,0 +1,93 @@
+use crate::constants::LARGE_BOARD_CONFIRM_AREA;
+use crate::tabs::level_tab::edit::{apply_drag_update, board_resize_needs_confirm};
+use crate::tabs::level_tab::{DragState, DraggedObject};
+use board::domain::alias::Coord;
+use board::ecs_types::components::Position;
+use board::ecs_types::resources::Board;
+use board::loader_schema::{LevelType, ObjectPlacement};
+
+const BOARD: Board = Board {
+    width: 3,
+    height: 3,
+};
+
+fn level_with_object(position: Position) -> LevelType {
+    LevelType {
+        board_width: BOARD.width,
+        board_height: BOARD.height,
+        object_placements: vec![ObjectPlacement {
+            object_type_name: "wall".to_string(),
+            position,
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_drag_update_moves_object() {
+    let mut level = level_with_object(Position { x: 0, y: 0 });
+    let state = DragState {
+        object: DraggedObject::Object(0),
+    };
+
+    apply_drag_update(&mut level, state, Position { x: 2, y: 1 }, BOARD).expect("拖曳應成功");
+
+    assert_eq!(level.object_placements[0].position, Position { x: 2, y: 1 });
+}
+
+#[test]
+fn test_drag_update_out_of_board_returns_error() {
+    let origin = Position { x: 0, y: 0 };
+    let mut level = level_with_object(origin);
+    let state = DragState {
+        object: DraggedObject::Object(0),
+    };
+
+    let err = apply_drag_update(&mut level, state, Position { x: 5, y: 1 }, BOARD)
+        .expect_err("超出棋盤應回傳錯誤");
+
+    assert!(err.contains("(5, 1)"), "錯誤應包含出錯位置，實際為 {err}");
+    assert_eq!(
+        level.object_placements[0].position, origin,
+        "關卡不應被改動"
+    );
+}
+
+#[test]
+fn test_drag_update_stale_index_returns_error() {
+    let origin = Position { x: 0, y: 0 };
+    let mut level = level_with_object(origin);
+    let state = DragState {
+        object: DraggedObject::Unit(0),
+    };
+
+    apply_drag_update(&mut level, state, Position { x: 1, y: 1 }, BOARD)
+        .expect_err("項目已不存在時應回傳錯誤");
+
+    assert_eq!(
+        level.object_placements[0].position, origin,
+        "關卡不應被改動"
+    );
+}
+
+#[test]
+fn test_board_resize_needs_confirm() {
+    let large_side = LARGE_BOARD_CONFIRM_AREA / 10 + 1;
+    // (說明, 目前尺寸, 新尺寸, 預期)
+    let test_data = [
+        ("放大但未超過門檻", (10, 10), (20, 20), false),
+        ("放大到超過門檻", (10, 10), (large_side, 10), true),
+        (
+            "已超過門檻時再放大",
+            (large_side, 10),
+            (large_side, 11),
+            true,
+        ),
+        ("從超過門檻縮小", (large_side, 11), (large_side, 10), false),
+        ("尺寸不變", (large_side, 10), (large_side, 10), false),
+        ("面積溢位視為超過門檻", (10, 10), (Coord::MAX, 2), true),
+    ];
+    for (desc, current, new, expected) in test_data {
+        assert_eq!(board_resize_needs_confirm(current, new), expected, "{desc}");
+    }
+}
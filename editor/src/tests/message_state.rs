@@ -0,0 +1,68 @@
+use crate::constants::{MESSAGE_CAPACITY, MESSAGE_TTL};
+use crate::generic_editor::MessageState;
+use std::time::{Duration, Instant};
+
+fn texts(state: &MessageState, count: usize) -> Vec<&str> {
+    state
+        .recent(count)
+        .map(|message| message.text.as_str())
+        .collect()
+}
+
+#[test]
+fn push_keeps_every_message_newest_first() {
+    let now = Instant::now();
+    let mut state = MessageState::default();
+    state.push_at("載入成功", false, now);
+    state.push_at("儲存失敗", true, now);
+    state.push_at("儲存成功", false, now);
+
+    assert_eq!(texts(&state, 2), vec!["儲存成功", "儲存失敗"]);
+    assert_eq!(
+        texts(&state, 10),
+        vec!["儲存成功", "儲存失敗", "載入成功"],
+        "連續操作的訊息不應互相覆蓋"
+    );
+    let latest = state.recent(1).next().expect("應有訊息");
+    assert!(!latest.is_error);
+    assert!(state.message_visible, "新訊息應讓訊息區顯示");
+}
+
+#[test]
+fn push_beyond_capacity_drops_oldest() {
+    let now = Instant::now();
+    let mut state = MessageState::default();
+    for index in 0..MESSAGE_CAPACITY + 2 {
+        state.push_at(format!("訊息 {index}"), false, now);
+    }
+
+    let all = texts(&state, usize::MAX);
+    assert_eq!(all.len(), MESSAGE_CAPACITY);
+    assert_eq!(
+        all.first().copied(),
+        Some(format!("訊息 {}", MESSAGE_CAPACITY + 1).as_str())
+    );
+    assert_eq!(all.last().copied(), Some("訊息 2"), "最舊的兩則應被捨棄");
+}
+
+#[test]
+fn expire_removes_only_messages_older_than_ttl() {
+    let start = Instant::now();
+    let mut state = MessageState::default();
+    state.push_at("舊訊息", false, start);
+    state.push_at("新訊息", true, start + Duration::from_secs(1));
+
+    state.expire(start + MESSAGE_TTL - Duration::from_millis(1));
+    assert_eq!(
+        texts(&state, 10),
+        vec!["新訊息", "舊訊息"],
+        "未滿 TTL 不應過期"
+    );
+
+    state.expire(start + MESSAGE_TTL);
+    assert_eq!(texts(&state, 10), vec!["新訊息"], "滿 TTL 的訊息應過期");
+
+    state.expire(start + Duration::from_secs(1) + MESSAGE_TTL);
+    assert!(state.is_empty());
+    assert!(state.recent(1).next().is_none());
+}
@@ -1 +1,5 @@
+mod battlefield_view;
 mod generic_io;
+mod i18n;
+mod level_edit;
+mod message_state;
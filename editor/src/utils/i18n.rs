@@ -0,0 +1,30 @@
+//! 列舉顯示名稱的在地化查詢
+
+use crate::constants::LOCALE_ZH_TW;
+use std::any::type_name;
+use std::fmt::Display;
+
+/// 語系表：key 為「型別名稱::變體名稱」，value 為顯示文字
+pub type Locale = &'static [(&'static str, &'static str)];
+
+/// 取得列舉值在編輯器語系下的顯示名稱
+pub fn label<E: Display>(value: &E) -> String {
+    label_in(LOCALE_ZH_TW, value)
+}
+
+/// 取得列舉值在指定語系下的顯示名稱，查無對應時退回 strum 名稱
+pub fn label_in<E: Display>(locale: Locale, value: &E) -> String {
+    let variant = value.to_string();
+    let key = format!("{}::{}", enum_name::<E>(), variant);
+    locale
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, text)| text.to_string())
+        .unwrap_or(variant)
+}
+
+/// 去除模組路徑的型別名稱
+fn enum_name<E>() -> &'static str {
+    let full_name = type_name::<E>();
+    full_name.rsplit("::").next().unwrap_or(full_name)
+}
@@ -1,4 +1,5 @@
 //! 編輯器通用工具
 
 pub mod dnd;
+pub mod i18n;
 pub mod search;